@@ -2,6 +2,7 @@ use anyhow::Result;
 use chrono::{Timelike, Utc};
 use clap::{Parser, Subcommand};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{info, warn};
 
@@ -69,8 +70,11 @@ async fn main() -> Result<()> {
 async fn run_publisher(args: Args) -> Result<()> {
     info!("🕐 Starting MoQ clock publisher");
 
-    // Create tracks to publish
-    let tracks = vec![TrackDefinition::data(args.track.clone(), 0)];
+    // Create tracks to publish. Each minute is one group (like the original
+    // moq-clock), so a 60-second TTL lets relays drop a minute as soon as the next
+    // one starts.
+    let tracks =
+        vec![TrackDefinition::data(args.track.clone(), 0).with_group_ttl(Duration::from_secs(60))];
 
     // Create publisher session with tracks and specified catalog type
     let session =
@@ -154,6 +158,8 @@ async fn run_subscriber(args: Args) -> Result<()> {
         name: args.track.clone(),
         priority: 0,
         track_type: TrackType::Data,
+        codec: None,
+        group_ttl_ms: None,
     };
 
     session
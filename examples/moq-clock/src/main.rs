@@ -1,22 +1,165 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
 use url::Url;
 
 use anyhow::Context;
+use bytes::Bytes;
 use clap::Parser;
 
 mod clock;
+mod media;
 use moq_lite::*;
 
+/// Coalesces duplicate upstream subscriptions keyed by `(broadcast_path, track_name)`:
+/// while a subscription from an earlier request is still alive, a later request for the
+/// same key gets a clone of the cached [`TrackConsumer`] instead of opening a second one
+/// via `subscribe_track`. Once the last clone is dropped the `Weak` expires and the next
+/// request re-subscribes. Same dedup problem as `crate::broker::TrackBroker` in the
+/// wrapper crate (which fans a relay session's upstream subscriptions out to local
+/// consumers), just tracked with a `Weak` handle here rather than an `Arc<()>` refcount,
+/// since there's no separate handle type to hand callers - the `Arc<TrackConsumer>`
+/// itself doubles as that handle.
+#[derive(Default)]
+struct TrackCache {
+	entries: HashMap<(String, String), Weak<TrackConsumer>>,
+}
+
+impl TrackCache {
+	/// Subscribes to `track` in `broadcast`, reusing a still-live subscription for
+	/// `(broadcast_path, track.name)` instead of opening a duplicate one.
+	fn subscribe_track(
+		&mut self,
+		broadcast_path: &str,
+		broadcast: &BroadcastConsumer,
+		track: &Track,
+	) -> Arc<TrackConsumer> {
+		let key = (broadcast_path.to_string(), track.name.clone());
+		if let Some(existing) = self.entries.get(&key).and_then(Weak::upgrade) {
+			tracing::debug!(broadcast = %broadcast_path, track = %track.name, "reusing cached subscription");
+			return existing;
+		}
+
+		let consumer = Arc::new(broadcast.subscribe_track(track));
+		self.entries.insert(key, Arc::downgrade(&consumer));
+		consumer
+	}
+}
+
+/// A running [`clock::Subscriber`] task; aborted on drop so a broadcast going offline
+/// (or being re-announced) tears down exactly its own subscribers.
+struct ActiveSubscriber {
+	handle: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for ActiveSubscriber {
+	fn drop(&mut self) {
+		self.handle.abort();
+	}
+}
+
+/// How long a `--origin-api` lookup is trusted before [`OriginResolver::resolve`]
+/// queries it again for the same broadcast.
+const ORIGIN_API_TTL: Duration = Duration::from_secs(30);
+
+#[derive(serde::Deserialize)]
+struct OriginApiResponse {
+	url: Url,
+}
+
+/// Resolves which relay node currently hosts a broadcast by querying `--origin-api`,
+/// caching each answer for [`ORIGIN_API_TTL`] so a fast-moving announce loop doesn't
+/// hammer the API. Assumes a `GET {api}?broadcast=<path>` endpoint returning
+/// `{"url": "https://..."}` - there's no origin-resolution API elsewhere in this tree
+/// to confirm the real contract against, so this is the simplest shape that satisfies
+/// the request and should be adjusted to match the actual API if it differs.
+struct OriginResolver {
+	api: Url,
+	client: reqwest::Client,
+	cache: HashMap<String, (Url, Instant)>,
+}
+
+impl OriginResolver {
+	fn new(api: Url) -> Self {
+		Self {
+			api,
+			client: reqwest::Client::new(),
+			cache: HashMap::new(),
+		}
+	}
+
+	/// Returns the node URL currently hosting `broadcast_path`, from cache if it was
+	/// resolved less than [`ORIGIN_API_TTL`] ago, otherwise by querying `--origin-api`.
+	async fn resolve(&mut self, broadcast_path: &str) -> anyhow::Result<Url> {
+		if let Some((url, resolved_at)) = self.cache.get(broadcast_path) {
+			if resolved_at.elapsed() < ORIGIN_API_TTL {
+				return Ok(url.clone());
+			}
+		}
+
+		let mut request_url = self.api.clone();
+		request_url.query_pairs_mut().append_pair("broadcast", broadcast_path);
+
+		let response: OriginApiResponse = self
+			.client
+			.get(request_url)
+			.send()
+			.await?
+			.error_for_status()?
+			.json()
+			.await?;
+
+		self.cache
+			.insert(broadcast_path.to_string(), (response.url.clone(), Instant::now()));
+		Ok(response.url)
+	}
+
+	/// Forces the next [`Self::resolve`] call for `broadcast_path` to re-query instead
+	/// of serving a cached entry, since the broadcast just went offline on the node we
+	/// last resolved it to.
+	fn invalidate(&mut self, broadcast_path: &str) {
+		self.cache.remove(broadcast_path);
+	}
+}
+
+/// One `--track-spec name:priority` argument. Lower priority numbers win under congestion,
+/// matching `Track::priority`'s own convention.
+#[derive(Clone)]
+struct TrackArg {
+	name: String,
+	priority: u8,
+}
+
+impl std::str::FromStr for TrackArg {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (name, priority) = s
+			.split_once(':')
+			.ok_or_else(|| format!("expected `name:priority`, got `{s}`"))?;
+		let priority = priority
+			.parse()
+			.map_err(|_| format!("invalid priority `{priority}` in `{s}`"))?;
+		Ok(Self {
+			name: name.to_string(),
+			priority,
+		})
+	}
+}
+
 #[derive(Parser, Clone)]
 pub struct Config {
-	/// Connect to the given URL starting with https://
+	/// Connect to the given URL starting with https://. Required for every role.
 	#[arg(long)]
-	pub url: Url,
+	pub url: Option<Url>,
 
-	/// The name of the broadcast to publish or subscribe to.
-	#[arg(long)]
-	pub broadcast: String,
+	/// The name of a broadcast to publish or subscribe to. Repeat for more than one.
+	#[arg(long = "broadcast", required = true)]
+	pub broadcasts: Vec<String>,
 
-	/// The MoQ client configuration.
+	/// The MoQ client configuration, used by every role.
 	#[command(flatten)]
 	pub client: moq_native::ClientConfig,
 
@@ -24,11 +167,23 @@ pub struct Config {
 	#[arg(long, default_value = "seconds")]
 	pub track: String,
 
+	/// A `name:priority` track to publish or subscribe to on every `--broadcast`.
+	/// Repeat for more than one; defaults to `video:1` and `audio:0` if none are given.
+	#[arg(long = "track-spec")]
+	pub tracks: Vec<TrackArg>,
+
+	/// Origin-resolution API used by `subscribe` to find which relay node currently
+	/// hosts a broadcast, so it can be followed across a cluster instead of only being
+	/// visible when it happens to be local to `--url`. See [`OriginResolver`].
+	#[arg(long)]
+	pub origin_api: Option<Url>,
+
 	/// The log configuration.
 	#[command(flatten)]
 	pub log: moq_native::Log,
 
-	/// Whether to publish the clock or consume it.
+	/// Whether to publish the clock, consume it, or publish a media file. There is no
+	/// role that accepts or relays other sessions.
 	#[command(subcommand)]
 	pub role: Command,
 }
@@ -37,6 +192,11 @@ pub struct Config {
 pub enum Command {
 	Publish,
 	Subscribe,
+	/// Publish a fragmented MP4 read from disk instead of the fake clock data -
+	/// init segment (ftyp+moov) as a header group, then one group per moof+mdat
+	/// fragment, paced by each fragment's decode timestamp so the broadcast plays
+	/// out in real time.
+	PublishMedia { path: PathBuf },
 }
 
 #[tokio::main]
@@ -46,76 +206,310 @@ async fn main() -> anyhow::Result<()> {
 
 	let client = config.client.init()?;
 
-	tracing::info!(url = ?config.url, "connecting to server");
+	let url = config.url.clone().context("--url is required for this role")?;
+	tracing::info!(url = %url, "connecting to server");
 
-	let session = client.connect(config.url).await?;
+	let session = client.connect(url).await?;
 
-	let track1 = Track {
-		name: "video".to_string(),
-		priority: 0,
-	};
-
-	let track2 = Track {
-		name: "audio".to_string(),
-		priority: 0,
+	let tracks = if config.tracks.is_empty() {
+		vec![
+			TrackArg {
+				name: "video".to_string(),
+				priority: 1,
+			},
+			TrackArg {
+				name: "audio".to_string(),
+				priority: 0,
+			},
+		]
+	} else {
+		config.tracks.clone()
 	};
 
 	match config.role {
 		Command::Publish => {
-			let mut broadcast = moq_lite::Broadcast::produce();
-			let track = broadcast.producer.create_track(track1);
-			let clock = clock::Publisher::new(track);
-
 			let origin = moq_lite::Origin::produce();
-			origin.producer.publish_broadcast(&config.broadcast, broadcast.consumer);
+
+			let mut publishers = Vec::new();
+			for broadcast_name in &config.broadcasts {
+				let mut broadcast = moq_lite::Broadcast::produce();
+				for track_arg in &tracks {
+					let producer = broadcast.producer.create_track(Track {
+						name: track_arg.name.clone(),
+						priority: track_arg.priority,
+					});
+					publishers.push(clock::Publisher::new(producer));
+				}
+				origin.producer.publish_broadcast(broadcast_name, broadcast.consumer);
+			}
 
 			let session = moq_lite::Session::connect(session, origin.consumer, None).await?;
 
+			let publish = async {
+				let tasks: Vec<_> = publishers.into_iter().map(|clock| tokio::spawn(clock.run())).collect();
+				for task in tasks {
+					let _ = task.await;
+				}
+			};
+
 			tokio::select! {
 				res = session.closed() => res.map_err(Into::into),
-				_ = clock.run() => Ok(()),
+				_ = publish => Ok(()),
+			}
+		}
+		Command::Subscribe if config.origin_api.is_some() => {
+			// Each broadcast may live on a different node, so unlike the fixed-`--url`
+			// path below, every broadcast gets its own connection, followed
+			// independently as `OriginResolver` redirects it across the cluster. The
+			// already-connected `session` above isn't reused for any of them.
+			drop(session);
+
+			let origin_api = config.origin_api.clone().unwrap();
+			let resolver = Arc::new(tokio::sync::Mutex::new(OriginResolver::new(origin_api)));
+
+			let tasks: Vec<_> = config
+				.broadcasts
+				.iter()
+				.map(|name| {
+					tokio::spawn(follow_broadcast(
+						config.client.clone(),
+						resolver.clone(),
+						url.clone(),
+						name.clone(),
+						tracks.clone(),
+					))
+				})
+				.collect();
+
+			for task in tasks {
+				let _ = task.await;
 			}
+
+			Ok(())
 		}
 		Command::Subscribe => {
 			let origin = moq_lite::Origin::produce();
 			let session = moq_lite::Session::connect(session, None, Some(origin.producer)).await?;
 
-			// NOTE: We could just call `session.consume_broadcast(&config.broadcast)` instead,
-			// However that won't work with IETF MoQ and the current OriginConsumer API the moment.
-			// So instead we do the cooler thing and loop while the broadcast is announced.
+			// NOTE: We could just call `session.consume_broadcast(name)` for each broadcast
+			// instead, however that won't work with IETF MoQ and the current OriginConsumer
+			// API the moment. So instead we do the cooler thing and loop while broadcasts
+			// are announced.
 
-			tracing::info!(broadcast = %config.broadcast, "waiting for broadcast to be online");
+			tracing::info!(broadcasts = ?config.broadcasts, "waiting for broadcasts to be online");
 
-			let path: moq_lite::Path<'_> = config.broadcast.into();
+			let paths: Vec<moq_lite::Path<'_>> = config.broadcasts.iter().map(|name| name.as_str().into()).collect();
 			let mut origin = origin
 				.consumer
-				.consume_only(&[path])
+				.consume_only(&paths)
 				.context("not allowed to consume broadcast")?;
 
-			// The current subscriber if any, dropped after each announce.
-			let mut video: Option<clock::Subscriber> = None;
-			let mut audio: Option<clock::Subscriber> = None;
+			// Coalesces repeated announces of the same broadcast/track pair into a single
+			// upstream subscription instead of resubscribing on every announce.
+			let mut track_cache = TrackCache::default();
+
+			// The subscribers currently running, keyed by broadcast path and then by track
+			// name, so a broadcast going offline (or being re-announced) only tears down
+			// its own subscribers rather than every broadcast's.
+			let mut subscribers: HashMap<String, HashMap<String, ActiveSubscriber>> = HashMap::new();
 
 			loop {
 				tokio::select! {
 					Some(announce) = origin.announced() => match announce {
 						(path, Some(broadcast)) => {
-							tracing::info!(broadcast = %path, "broadcast is online, subscribing to track");
-							let track = broadcast.subscribe_track(&track1);
-							video = Some(clock::Subscriber::new(track));
-
-							let track = broadcast.subscribe_track(&track2);
-							audio = Some(clock::Subscriber::new(track));
+							tracing::info!(broadcast = %path, "broadcast is online, subscribing to tracks");
+							let key = path.to_string();
+							let per_track = subscribers.entry(key.clone()).or_default();
+							for track_arg in &tracks {
+								let track = Track {
+									name: track_arg.name.clone(),
+									priority: track_arg.priority,
+								};
+								let consumer = track_cache.subscribe_track(&key, &broadcast, &track);
+								let subscriber = clock::Subscriber::new((*consumer).clone());
+								// Spawned rather than raced inline via `tokio::select!` (as a
+								// single subscriber was before) since there's now a dynamic,
+								// per-broadcast set of them; errors are logged here instead of
+								// propagated to the caller.
+								let handle = tokio::spawn(async move {
+									// Keeps `track_cache`'s entry (and the upstream subscription
+									// it caches) alive for as long as this task runs.
+									let _consumer = consumer;
+									if let Err(err) = subscriber.run().await {
+										tracing::warn!(?err, "clock subscriber error");
+									}
+								});
+								per_track.insert(track_arg.name.clone(), ActiveSubscriber { handle });
+							}
 						}
 						(path, None) => {
 							tracing::warn!(broadcast = %path, "broadcast is offline, waiting...");
+							subscribers.remove(&path.to_string());
 						}
 					},
 					res = session.closed() => return res.context("session closed"),
-					// NOTE: This drops clock when a new announce arrives, canceling it.
-					Some(res) = async { Some(video.take()?.run().await) } => res.context("clock error")?,
 				}
 			}
 		}
+		Command::PublishMedia { path } => {
+			let media = media::Media::open(&path)?;
+			tracing::info!(
+				path = %path.display(),
+				tracks = media.tracks.len(),
+				fragments = media.fragments.len(),
+				"parsed media file",
+			);
+
+			let mut broadcast = moq_lite::Broadcast::produce();
+			let mut track_producers = HashMap::new();
+			for track in &media.tracks {
+				// Audio is higher priority (lower number) than video, so a relay under
+				// contended bandwidth drops video groups before it drops audio ones.
+				let (name, priority) = if track.is_audio {
+					("audio", 0)
+				} else {
+					("video", 1)
+				};
+				let producer = broadcast.producer.create_track(Track {
+					name: name.to_string(),
+					priority,
+				});
+				track_producers.insert(track.track_id, producer);
+			}
+
+			let origin = moq_lite::Origin::produce();
+			// `--broadcast` may be repeated for `publish`/`subscribe`, but a media file
+			// only has one track set to publish, so only the first name is used here.
+			let broadcast_name = &config.broadcasts[0];
+			origin.producer.publish_broadcast(broadcast_name, broadcast.consumer);
+
+			let session = moq_lite::Session::connect(session, origin.consumer, None).await?;
+
+			let publish = async {
+				for track in &media.tracks {
+					let Some(producer) = track_producers.get_mut(&track.track_id) else {
+						continue;
+					};
+					let Some(mut group) = producer.create_group(0) else {
+						continue;
+					};
+					group.write_frame(Bytes::from(track.init_segment.clone()));
+					group.close();
+				}
+
+				let mut anchors: HashMap<u32, Option<(Instant, f64)>> = HashMap::new();
+				for fragment in &media.fragments {
+					let anchor = anchors.entry(fragment.track_id).or_insert(None);
+					media::pace(anchor, fragment).await;
+
+					let Some(producer) = track_producers.get_mut(&fragment.track_id) else {
+						continue;
+					};
+					let Some(mut group) = producer.create_group(fragment.sequence.into()) else {
+						continue;
+					};
+					group.write_frame(Bytes::from(fragment.data.clone()));
+					group.close();
+				}
+			};
+
+			tokio::select! {
+				res = session.closed() => res.map_err(Into::into),
+				_ = publish => Ok(()),
+			}
+		}
+	}
+}
+
+/// Subscribes to `broadcast_name`'s tracks, following it across relay nodes via
+/// `resolver` instead of assuming it stays on `default_url` forever: resolves which
+/// node currently hosts it (falling back to `default_url` if resolution fails), connects
+/// and subscribes there, and - once the broadcast goes offline or the session drops -
+/// invalidates the cached mapping and re-resolves before reconnecting, so a node
+/// migration is followed rather than silently going dark. Runs until cancelled; logs
+/// rather than returns errors, since it's spawned as one of several independent
+/// per-broadcast tasks by `Command::Subscribe`.
+async fn follow_broadcast(
+	client_config: moq_native::ClientConfig,
+	resolver: Arc<tokio::sync::Mutex<OriginResolver>>,
+	default_url: Url,
+	broadcast_name: String,
+	tracks: Vec<TrackArg>,
+) {
+	let mut current_url = match resolver.lock().await.resolve(&broadcast_name).await {
+		Ok(url) => url,
+		Err(err) => {
+			tracing::warn!(?err, broadcast = %broadcast_name, "origin-api resolution failed, using --url");
+			default_url
+		}
+	};
+
+	// Coalesces repeated announces of this broadcast's tracks into a single upstream
+	// subscription instead of resubscribing on every announce; see `TrackCache`.
+	let mut track_cache = TrackCache::default();
+
+	loop {
+		tracing::info!(broadcast = %broadcast_name, url = %current_url, "connecting to server");
+		let connect_result: anyhow::Result<()> = async {
+			let client = client_config.init()?;
+			let session = client.connect(current_url.clone()).await?;
+
+			let origin = moq_lite::Origin::produce();
+			let session = moq_lite::Session::connect(session, None, Some(origin.producer)).await?;
+
+			let path: moq_lite::Path<'_> = broadcast_name.as_str().into();
+			let mut origin = origin
+				.consumer
+				.consume_only(&[path])
+				.context("not allowed to consume broadcast")?;
+
+			let mut subscribers: HashMap<String, ActiveSubscriber> = HashMap::new();
+
+			loop {
+				tokio::select! {
+					Some(announce) = origin.announced() => match announce {
+						(path, Some(broadcast)) => {
+							tracing::info!(broadcast = %path, "broadcast is online, subscribing to tracks");
+							let key = path.to_string();
+							for track_arg in &tracks {
+								let track = Track {
+									name: track_arg.name.clone(),
+									priority: track_arg.priority,
+								};
+								let consumer = track_cache.subscribe_track(&key, &broadcast, &track);
+								let subscriber = clock::Subscriber::new((*consumer).clone());
+								let handle = tokio::spawn(async move {
+									let _consumer = consumer;
+									if let Err(err) = subscriber.run().await {
+										tracing::warn!(?err, "clock subscriber error");
+									}
+								});
+								subscribers.insert(track_arg.name.clone(), ActiveSubscriber { handle });
+							}
+						}
+						(path, None) => {
+							tracing::warn!(broadcast = %path, "broadcast is offline, re-resolving origin");
+							subscribers.clear();
+							return Ok(());
+						}
+					},
+					res = session.closed() => {
+						subscribers.clear();
+						return res.map_err(Into::into);
+					}
+				}
+			}
+		}
+		.await;
+
+		if let Err(err) = connect_result {
+			tracing::warn!(?err, broadcast = %broadcast_name, "lost connection, re-resolving origin");
+		}
+
+		let mut resolver = resolver.lock().await;
+		resolver.invalidate(&broadcast_name);
+		if let Ok(resolved) = resolver.resolve(&broadcast_name).await {
+			current_url = resolved;
+		}
 	}
 }
\ No newline at end of file
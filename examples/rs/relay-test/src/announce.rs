@@ -0,0 +1,81 @@
+//! Listens for ANNOUNCE/UNANNOUNCE events on an `OriginConsumer` and reports newly
+//! (un)announced broadcast namespaces, for `--discover` mode - turning this tool from a
+//! static single-broadcast subscriber into a dynamic relay explorer that finds and
+//! subscribes to broadcasts as the relay advertises them.
+
+use std::sync::Arc;
+
+use moq_lite::OriginConsumer;
+use tokio::sync::{broadcast, mpsc, Mutex};
+
+/// One ANNOUNCE/UNANNOUNCE event for a broadcast namespace.
+pub enum NamespaceEvent {
+    Announced(String),
+    Unannounced(String),
+}
+
+/// Spawns a task that repeatedly awaits `origin_consumer.announced()` and forwards each
+/// (un)announce as a [`NamespaceEvent`], until `shutdown_rx` fires or the origin
+/// closes. `origin_consumer` is shared behind a `Mutex` rather than moved outright, so
+/// [`moq_lite::OriginConsumer::consume_broadcast`] can still be called on it elsewhere
+/// (e.g. to consume a namespace this loop just reported) while the loop holds it
+/// between awaits.
+pub fn spawn_listener(
+    origin_consumer: Arc<Mutex<OriginConsumer>>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> (
+    mpsc::UnboundedReceiver<NamespaceEvent>,
+    tokio::task::JoinHandle<()>,
+) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let handle = tokio::spawn(async move {
+        loop {
+            let mut consumer = origin_consumer.lock().await;
+            let event = tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                result = consumer.announced() => result,
+            };
+            drop(consumer);
+
+            let event = match event {
+                Some((path, Some(_))) => NamespaceEvent::Announced(path.to_string()),
+                Some((path, None)) => NamespaceEvent::Unannounced(path.to_string()),
+                None => break,
+            };
+            if tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+    (rx, handle)
+}
+
+/// Minimal `*`-wildcard glob match - no glob crate is vendored in this tree, and
+/// matching a namespace prefix doesn't need a full glob engine. Splits `pattern` on
+/// `*` and checks each piece occurs in `text` in order, anchoring the first/last piece
+/// to the start/end of `text` unless `pattern` itself starts/ends with `*`.
+pub fn matches_glob(pattern: &str, text: &str) -> bool {
+    if !pattern.contains('*') {
+        return pattern == text;
+    }
+
+    let starts_wild = pattern.starts_with('*');
+    let ends_wild = pattern.ends_with('*');
+    let parts: Vec<&str> = pattern.split('*').filter(|p| !p.is_empty()).collect();
+
+    let last = parts.len().saturating_sub(1);
+    let mut rest = text;
+    for (i, part) in parts.iter().enumerate() {
+        if i == last && !ends_wild {
+            return rest.ends_with(part);
+        }
+        let Some(pos) = rest.find(part) else {
+            return false;
+        };
+        if i == 0 && !starts_wild && pos != 0 {
+            return false;
+        }
+        rest = &rest[pos + part.len()..];
+    }
+    ends_wild || rest.is_empty()
+}
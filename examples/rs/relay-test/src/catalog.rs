@@ -0,0 +1,117 @@
+//! Discovers subscribable tracks from a broadcast's `catalog.json` track instead of
+//! requiring them to be hardcoded. Mirrors only the slice of the Hang catalog JSON
+//! schema needed to list tracks (see `crate::catalog` in the wrapper crate for the
+//! authoritative schema - this binary has no dependency on it, the way
+//! [`crate::fmp4_reader`] mirrors [`crate::mp4_writer`]'s box layout instead of
+//! importing it).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use moq_lite::{BroadcastConsumer, Track};
+use serde::Deserialize;
+use tokio::time::timeout;
+
+/// Name a broadcast's catalog track is published under, per the Hang catalog format.
+pub const CATALOG_TRACK_NAME: &str = "catalog.json";
+
+/// How long to wait for the catalog's first group before giving up and falling back
+/// to `--tracks`.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// One track a broadcast's catalog (or, lacking a catalog, `--tracks`) advertises.
+#[derive(Clone, Debug)]
+pub struct DiscoveredTrack {
+    pub name: String,
+    pub track_type: String,
+    pub priority: u32,
+    pub codec: Option<String>,
+    pub bitrate: Option<u64>,
+}
+
+impl DiscoveredTrack {
+    /// A [`DiscoveredTrack`] for a name that came from `--tracks` rather than a
+    /// catalog, so there's nothing to show but the name.
+    pub fn from_config_name(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            track_type: "configured".to_string(),
+            priority: 0,
+            codec: None,
+            bitrate: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawCatalog {
+    #[serde(default)]
+    video: Option<RawRenditionGroup>,
+    #[serde(default)]
+    audio: Option<RawRenditionGroup>,
+}
+
+#[derive(Deserialize)]
+struct RawRenditionGroup {
+    renditions: HashMap<String, RawRendition>,
+    #[serde(default)]
+    priority: u32,
+}
+
+#[derive(Deserialize, Default)]
+struct RawRendition {
+    #[serde(default)]
+    codec: Option<String>,
+    #[serde(default)]
+    bitrate: Option<u64>,
+}
+
+/// Subscribes to `broadcast`'s catalog track and flattens its video/audio renditions
+/// into a discovered-track list. Returns `Ok(vec![])` - not an error - when the
+/// broadcast has no catalog track or it doesn't produce a group within
+/// [`DISCOVERY_TIMEOUT`], so callers can fall back to `--tracks` instead of failing
+/// the whole connect.
+pub async fn discover_tracks(broadcast: &BroadcastConsumer) -> Result<Vec<DiscoveredTrack>> {
+    let track = Track {
+        name: CATALOG_TRACK_NAME.to_string(),
+        priority: 0,
+    };
+    let mut consumer = broadcast.subscribe_track(&track);
+
+    let Ok(Ok(Some(mut group))) = timeout(DISCOVERY_TIMEOUT, consumer.next_group()).await else {
+        return Ok(Vec::new());
+    };
+    let Some(data) = group.read_frame().await? else {
+        return Ok(Vec::new());
+    };
+
+    parse(&data)
+}
+
+/// Parses one catalog JSON frame into its flattened track list.
+fn parse(data: &[u8]) -> Result<Vec<DiscoveredTrack>> {
+    let catalog: RawCatalog =
+        serde_json::from_slice(data).context("failed to parse catalog.json")?;
+
+    let mut tracks = Vec::new();
+    if let Some(video) = catalog.video {
+        flatten(&mut tracks, "video", video);
+    }
+    if let Some(audio) = catalog.audio {
+        flatten(&mut tracks, "audio", audio);
+    }
+    Ok(tracks)
+}
+
+fn flatten(tracks: &mut Vec<DiscoveredTrack>, track_type: &str, group: RawRenditionGroup) {
+    for (name, rendition) in group.renditions {
+        tracks.push(DiscoveredTrack {
+            name,
+            track_type: track_type.to_string(),
+            priority: group.priority,
+            codec: rendition.codec,
+            bitrate: rendition.bitrate,
+        });
+    }
+}
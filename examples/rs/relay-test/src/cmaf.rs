@@ -0,0 +1,171 @@
+//! Derives Sesame-protocol codec headers and a `catalog.json` payload from
+//! [`media::Media`]'s demuxed tracks, so `--publish` can describe what it's
+//! publishing (codec, resolution, sample rate) without the caller naming it on the
+//! command line. `start_publish` already infers each track's name from its `track_id`
+//! (`"{video,audio}-{track_id}"`); this fills in the part a subscriber actually needs
+//! to decode and discover what shows up, the way [`crate::catalog::discover_tracks`]
+//! expects a broadcast to advertise on its `catalog.json` track.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::media::{find_box, read_box_header, MediaTrack};
+use crate::sesame_protocol::v1::{CodecType, HeaderCodecData};
+
+/// Codec and geometry detail for one track, read from its standalone init segment's
+/// `moov/trak/mdia/minf/stbl/stsd` sample entry. Video-only fields stay zero for an
+/// audio track and vice versa, rather than being guessed.
+#[derive(Clone, Copy)]
+pub struct TrackCodec {
+    pub codec_type: CodecType,
+    pub width: u16,
+    pub height: u16,
+    pub sample_rate: u32,
+    pub channels: u8,
+}
+
+impl TrackCodec {
+    /// Locates `track`'s sample entry and maps its fourcc to a [`CodecType`]. `None`
+    /// if any box along the way is missing, or the fourcc isn't one this protocol has
+    /// a [`CodecType`] for.
+    pub fn detect(track: &MediaTrack) -> Option<Self> {
+        let moov = find_box(&track.init_segment, b"moov")?;
+        let trak = find_box(moov, b"trak")?;
+        let mdia = find_box(trak, b"mdia")?;
+        let minf = find_box(mdia, b"minf")?;
+        let stbl = find_box(minf, b"stbl")?;
+        let stsd = find_box(stbl, b"stsd")?;
+        let (fourcc, entry) = read_box_header(stsd.get(8..)?)?;
+        let codec_type = codec_type_for(&fourcc)?;
+
+        Some(if track.is_audio {
+            let channels = entry
+                .get(16..18)
+                .map(|b| u16::from_be_bytes(b.try_into().unwrap()) as u8)
+                .unwrap_or(0);
+            // `samplerate` is a 16.16 fixed-point value; the integer Hz sits in the
+            // high 16 bits.
+            let sample_rate = entry
+                .get(24..28)
+                .map(|b| u32::from_be_bytes(b.try_into().unwrap()) >> 16)
+                .unwrap_or(0);
+            Self {
+                codec_type,
+                width: 0,
+                height: 0,
+                sample_rate,
+                channels,
+            }
+        } else {
+            let width = entry
+                .get(24..26)
+                .map(|b| u16::from_be_bytes(b.try_into().unwrap()))
+                .unwrap_or(0);
+            let height = entry
+                .get(26..28)
+                .map(|b| u16::from_be_bytes(b.try_into().unwrap()))
+                .unwrap_or(0);
+            Self {
+                codec_type,
+                width,
+                height,
+                sample_rate: 0,
+                channels: 0,
+            }
+        })
+    }
+
+    /// Builds the [`HeaderCodecData`] [`crate::sesame_protocol::builder::PacketBuilder`]
+    /// attaches to a track's init-segment packet, so a subscriber has codec/geometry
+    /// detail before the first keyframe arrives.
+    pub fn to_header(&self, timescale: u32) -> HeaderCodecData {
+        HeaderCodecData {
+            sample_rate: self.sample_rate,
+            timebase_num: 1,
+            timebase_den: timescale,
+            codec_profile: 0,
+            codec_level: 0,
+            width: self.width,
+            height: self.height,
+            codec_type: self.codec_type as u8,
+            channels: self.channels,
+            bit_depth: 8,
+            reserved: 0,
+        }
+    }
+}
+
+fn codec_type_for(fourcc: &[u8; 4]) -> Option<CodecType> {
+    match fourcc {
+        b"avc1" | b"avc3" => Some(CodecType::VideoAvc),
+        b"hev1" | b"hvc1" => Some(CodecType::VideoHevc),
+        b"av01" => Some(CodecType::VideoAv1),
+        b"vp08" => Some(CodecType::VideoVp8),
+        b"vp09" => Some(CodecType::VideoVp9),
+        b"Opus" => Some(CodecType::AudioOpus),
+        b"mp4a" => Some(CodecType::AudioAac),
+        _ => None,
+    }
+}
+
+fn codec_name(codec_type: CodecType) -> &'static str {
+    match codec_type {
+        CodecType::VideoVp8 => "vp8",
+        CodecType::VideoVp9 => "vp9",
+        CodecType::VideoAvc => "avc1",
+        CodecType::VideoHevc => "hev1",
+        CodecType::VideoAv1 => "av01",
+        CodecType::AudioOpus => "opus",
+        CodecType::AudioAac => "mp4a",
+        CodecType::AudioPcm => "pcm",
+    }
+}
+
+#[derive(Serialize, Default)]
+struct CatalogDoc {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    video: Option<RenditionGroup>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio: Option<RenditionGroup>,
+}
+
+#[derive(Serialize)]
+struct RenditionGroup {
+    renditions: HashMap<String, Rendition>,
+    priority: u32,
+}
+
+#[derive(Serialize, Default)]
+struct Rendition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    codec: Option<String>,
+}
+
+/// Builds the `catalog.json` payload [`crate::catalog::discover_tracks`] parses, one
+/// rendition per `(name, is_audio, codec)` entry - `codec` is omitted when
+/// [`TrackCodec::detect`] didn't recognize the track's sample entry, the same way a
+/// hand-written `--tracks` entry carries no codec detail either.
+pub fn catalog_json(tracks: &[(String, bool, Option<TrackCodec>)]) -> serde_json::Result<String> {
+    let mut doc = CatalogDoc::default();
+    for (name, is_audio, codec) in tracks {
+        let group = if *is_audio {
+            doc.audio.get_or_insert_with(|| RenditionGroup {
+                renditions: HashMap::new(),
+                priority: 0,
+            })
+        } else {
+            doc.video.get_or_insert_with(|| RenditionGroup {
+                renditions: HashMap::new(),
+                priority: 0,
+            })
+        };
+        group.renditions.insert(
+            name.clone(),
+            Rendition {
+                codec: codec.as_ref().map(|c| codec_name(c.codec_type).to_string()),
+            },
+        );
+    }
+    serde_json::to_string(&doc)
+}
@@ -0,0 +1,112 @@
+//! Minimal reader for the init-segment/fragment layout [`crate::mp4_writer`] produces,
+//! used by `--play` to recover a track's audio format and raw samples from the CMAF
+//! bytes carried over MoQ groups. Only reads the boxes that layout actually contains -
+//! not a general ISOBMFF parser.
+
+use anyhow::{bail, Context, Result};
+
+/// Audio format recovered from a track's `moov` init segment.
+pub struct AudioInfo {
+    pub codec_fourcc: [u8; 4],
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// One sample recovered from a `moof`+`mdat` fragment: `pts` from `tfdt`, raw bytes
+/// from `mdat`.
+pub struct Sample {
+    pub pts: u64,
+    pub data: Vec<u8>,
+}
+
+/// Parses the first track's audio sample entry out of a `moov` init segment.
+pub fn parse_init_segment(data: &[u8]) -> Result<AudioInfo> {
+    let moov = find_box(data, b"moov").context("no moov box in init segment")?;
+    let trak = find_box(moov, b"trak").context("no trak box in moov")?;
+    let mdia = find_box(trak, b"mdia").context("no mdia box in trak")?;
+    let minf = find_box(mdia, b"minf").context("no minf box in mdia")?;
+    let stbl = find_box(minf, b"stbl").context("no stbl box in minf")?;
+    let stsd = find_box(stbl, b"stsd").context("no stsd box in stbl")?;
+
+    // stsd is a full box: version(1) + flags(3) + entry_count(4), then the sample entry.
+    if stsd.len() < 8 {
+        bail!("stsd box too short");
+    }
+    let (fourcc, entry) = read_box_header(&stsd[8..]).context("no sample entry in stsd")?;
+
+    // Audio sample entry body: sample_entry_header(8) + reserved(8) + channelcount(2)
+    // + samplesize(2) + pre_defined(2) + reserved(2) + samplerate(4, 16.16 fixed-point).
+    if entry.len() < 28 {
+        bail!("audio sample entry too short");
+    }
+    let channels = u16::from_be_bytes(entry[16..18].try_into().unwrap());
+    let sample_rate = u32::from_be_bytes(entry[24..28].try_into().unwrap()) >> 16;
+
+    Ok(AudioInfo {
+        codec_fourcc: fourcc,
+        sample_rate,
+        channels,
+    })
+}
+
+/// Parses one `moof`+`mdat` fragment into its presentation timestamp and sample bytes.
+pub fn parse_fragment(data: &[u8]) -> Result<Sample> {
+    let moof = find_box(data, b"moof").context("no moof box in fragment")?;
+    let traf = find_box(moof, b"traf").context("no traf box in moof")?;
+    let tfdt = find_box(traf, b"tfdt").context("no tfdt box in traf")?;
+
+    if tfdt.is_empty() {
+        bail!("tfdt box too short");
+    }
+    let version = tfdt[0];
+    let pts = if version == 1 {
+        u64::from_be_bytes(
+            tfdt.get(4..12)
+                .context("truncated 64-bit tfdt")?
+                .try_into()
+                .unwrap(),
+        )
+    } else {
+        u32::from_be_bytes(
+            tfdt.get(4..8)
+                .context("truncated 32-bit tfdt")?
+                .try_into()
+                .unwrap(),
+        ) as u64
+    };
+
+    let mdat = find_box(data, b"mdat").context("no mdat box in fragment")?;
+    Ok(Sample {
+        pts,
+        data: mdat.to_vec(),
+    })
+}
+
+/// Reads one box off the front of `data`: `(fourcc, payload)`, where `payload` is
+/// everything after the 8-byte size+fourcc header. `None` if `data` doesn't start with
+/// a complete box (e.g. too short, or a truncated size).
+fn read_box_header(data: &[u8]) -> Option<([u8; 4], &[u8])> {
+    if data.len() < 8 {
+        return None;
+    }
+    let size = u32::from_be_bytes(data[0..4].try_into().ok()?) as usize;
+    if size < 8 || size > data.len() {
+        return None;
+    }
+    let mut fourcc = [0u8; 4];
+    fourcc.copy_from_slice(&data[4..8]);
+    Some((fourcc, &data[8..size]))
+}
+
+/// Scans `data`'s top-level boxes (not recursively) for the first one matching `kind`.
+fn find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let (fourcc, payload) = read_box_header(&data[offset..])?;
+        if &fourcc == kind {
+            return Some(payload);
+        }
+        offset += 8 + payload.len();
+    }
+    None
+}
@@ -0,0 +1,189 @@
+//! PTS-ordered de-jitter buffer sitting between a track's raw data callback and its
+//! [`TrackDataHandler`](crate::TrackDataHandler): frames are held in a per-track
+//! min-heap keyed by `pts` and released in ascending order once each has sat for
+//! `target_latency`. `pts` is assumed to be in microseconds; wall-clock alignment is an
+//! EMA-smoothed `local_receive_instant - pts` offset, refined on every frame so clock
+//! drift between sender and receiver doesn't widen the window over time.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::sesame_protocol::{BinaryProtocol, FragmentReassembler, ParsedPacket};
+use crate::TrackDataHandler;
+
+struct BufferedFrame {
+    pts: u64,
+    release_at: Instant,
+    data: Vec<u8>,
+}
+
+impl PartialEq for BufferedFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.release_at == other.release_at && self.pts == other.pts
+    }
+}
+impl Eq for BufferedFrame {}
+impl PartialOrd for BufferedFrame {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for BufferedFrame {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.pts
+            .cmp(&other.pts)
+            .then(self.release_at.cmp(&other.release_at))
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    heap: BinaryHeap<Reverse<BufferedFrame>>,
+    delta_micros: Option<f64>,
+    max_seen_pts: Option<u64>,
+}
+
+/// Buffers one track's frames for `target_latency` before forwarding them, in
+/// ascending-PTS order, to a [`TrackDataHandler`].
+pub struct JitterBuffer {
+    target_latency: Duration,
+    start: Instant,
+    inner: Mutex<Inner>,
+    reassembler: Mutex<FragmentReassembler>,
+    handler: Arc<TrackDataHandler>,
+    version_override: Option<u16>,
+    last_released_pts: AtomicU64,
+    late_frames: AtomicU64,
+    reordered_frames: AtomicU64,
+    buffered_frames: AtomicU64,
+}
+
+impl JitterBuffer {
+    /// Creates the buffer and spawns its background release loop. `version_override` is
+    /// forwarded to [`BinaryProtocol::parse_data`] as-is.
+    pub fn new(
+        target_latency: Duration,
+        handler: Arc<TrackDataHandler>,
+        version_override: Option<u16>,
+    ) -> Arc<Self> {
+        let this = Arc::new(Self {
+            target_latency,
+            start: Instant::now(),
+            inner: Mutex::new(Inner::default()),
+            reassembler: Mutex::new(FragmentReassembler::new()),
+            handler,
+            version_override,
+            last_released_pts: AtomicU64::new(0),
+            late_frames: AtomicU64::new(0),
+            reordered_frames: AtomicU64::new(0),
+            buffered_frames: AtomicU64::new(0),
+        });
+        tokio::spawn(this.clone().release_loop());
+        this
+    }
+
+    /// Reassembles `data` if it's one of several fragments of a larger packet (see
+    /// [`FragmentReassembler`]), then parses the (possibly now-complete) packet's PTS
+    /// and either buffers it for in-order release, or - if it doesn't parse as a
+    /// Sesame packet at all - forwards it immediately, since there's no PTS to order
+    /// it by. A fragment that's part of a still-incomplete set is held back entirely.
+    pub fn push(&self, data: &[u8]) {
+        let Some(data) = self
+            .reassembler
+            .lock()
+            .unwrap()
+            .push(data, self.version_override)
+        else {
+            return;
+        };
+        let data = data.as_slice();
+
+        let parsed = match BinaryProtocol::parse_data(data, self.version_override) {
+            Ok(parsed) => parsed,
+            Err(_) => {
+                self.handler.handle_data(data);
+                return;
+            }
+        };
+        let pts = parsed.pts();
+
+        let now = Instant::now();
+        let now_micros = now.saturating_duration_since(self.start).as_micros() as f64;
+        let observed_delta = now_micros - pts as f64;
+
+        let mut inner = self.inner.lock().unwrap();
+
+        let delta = match inner.delta_micros {
+            None => observed_delta,
+            Some(prev) => prev * 0.9 + observed_delta * 0.1,
+        };
+        inner.delta_micros = Some(delta);
+
+        let last_released = self.last_released_pts.load(Ordering::Relaxed);
+        if pts < last_released {
+            self.late_frames.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        if inner.max_seen_pts.is_some_and(|max_seen| pts < max_seen) {
+            self.reordered_frames.fetch_add(1, Ordering::Relaxed);
+        }
+        inner.max_seen_pts = Some(inner.max_seen_pts.map_or(pts, |max_seen| max_seen.max(pts)));
+
+        let release_micros = (pts as f64 + delta).max(0.0) as u64;
+        let release_at = self.start + Duration::from_micros(release_micros) + self.target_latency;
+
+        inner.heap.push(Reverse(BufferedFrame {
+            pts,
+            release_at,
+            data: data.to_vec(),
+        }));
+        self.buffered_frames
+            .store(inner.heap.len() as u64, Ordering::Relaxed);
+    }
+
+    async fn release_loop(self: Arc<Self>) {
+        let tick =
+            (self.target_latency / 4).clamp(Duration::from_millis(5), Duration::from_millis(50));
+        loop {
+            tokio::time::sleep(tick).await;
+            let now = Instant::now();
+            let mut last_released = None;
+            loop {
+                let frame = {
+                    let mut inner = self.inner.lock().unwrap();
+                    match inner.heap.peek() {
+                        Some(Reverse(frame)) if frame.release_at <= now => {
+                            let frame = inner.heap.pop().unwrap().0;
+                            self.buffered_frames
+                                .store(inner.heap.len() as u64, Ordering::Relaxed);
+                            Some(frame)
+                        }
+                        _ => None,
+                    }
+                };
+                let Some(frame) = frame else { break };
+                last_released = Some(frame.pts);
+                self.handler.handle_data(&frame.data);
+            }
+            if let Some(pts) = last_released {
+                self.last_released_pts.store(pts, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn late_count(&self) -> u64 {
+        self.late_frames.load(Ordering::Relaxed)
+    }
+
+    pub fn reordered_count(&self) -> u64 {
+        self.reordered_frames.load(Ordering::Relaxed)
+    }
+
+    pub fn buffered_count(&self) -> u64 {
+        self.buffered_frames.load(Ordering::Relaxed)
+    }
+}
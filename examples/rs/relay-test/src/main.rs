@@ -1,17 +1,44 @@
+mod announce;
+mod catalog;
+mod cmaf;
+mod fmp4_reader;
+mod media;
+mod metrics;
+mod mp4_box;
+mod playback;
+mod sesame_protocol;
+mod stream_ext;
+mod track_params;
+
 use std::collections::HashMap;
-use std::time::Duration;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use bytes::Bytes;
+use clap::{CommandFactory, Parser};
+use clap_complete::Shell;
 use crossterm::{
     event::{self, Event, KeyCode, KeyModifiers},
     terminal::{disable_raw_mode, enable_raw_mode},
 };
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::timeout;
+
+use sesame_protocol::{builder::PacketBuilder, v1::PacketType};
 use url::Url;
 
+use announce::NamespaceEvent;
+use catalog::DiscoveredTrack;
+use futures::{pin_mut, StreamExt};
+use metrics::Metrics;
 use moq_lite::*;
+use playback::{MediaTaskBuilder, PcmMediaTaskBuilder, PlaybackFragment};
+use rand::Rng;
+use stream_ext::TrackConsumerExt;
+use track_params::TrackParams;
 
 #[derive(Parser, Clone)]
 pub struct Config {
@@ -27,21 +54,180 @@ pub struct Config {
     #[arg(long, default_value = "video,audio")]
     pub tracks: String,
 
+    /// Listen for ANNOUNCE/UNANNOUNCE events from the relay and auto-subscribe to
+    /// every advertised namespace's catalog tracks, alongside the fixed --broadcast.
+    /// Turns this tool into a dynamic relay explorer instead of a static subscriber.
+    #[arg(long)]
+    pub discover: bool,
+
+    /// Only auto-subscribe to --discover namespaces whose path matches this `*`-glob
+    /// (e.g. "alice/*"). Every namespace is auto-subscribed when unset.
+    #[arg(long)]
+    pub namespace_prefix: Option<String>,
+
     /// The MoQ client configuration.
     #[command(flatten)]
     pub client: moq_native::ClientConfig,
 
+    /// Forward every subscribed track's frames onward as a new broadcast on a second
+    /// relay, turning this into a thin fan-out relay. Requires --forward-broadcast.
+    #[arg(long)]
+    pub forward_url: Option<Url>,
+
+    /// Broadcast name to publish the forwarded tracks under on --forward-url.
+    #[arg(long)]
+    pub forward_broadcast: Option<String>,
+
+    /// Publish a fragmented-MP4 (CMAF) file's tracks as a broadcast on --url, instead
+    /// of only ever subscribing to one. Requires --publish-broadcast. Paces groups by
+    /// each fragment's decode timestamp to simulate a live stream.
+    #[arg(long)]
+    pub publish: Option<PathBuf>,
+
+    /// Broadcast name to announce --publish's tracks under.
+    #[arg(long)]
+    pub publish_broadcast: Option<String>,
+
+    /// Decode subscribed audio tracks' CMAF fragments and write raw PCM samples to
+    /// `<dir>/<track>.pcm`, instead of only tallying bytes received.
+    #[arg(long)]
+    pub play: Option<PathBuf>,
+
+    /// Record per-track throughput/frame-rate/group-arrival metrics, pushed to
+    /// --metrics-pushgateway on --metrics-interval-secs.
+    #[arg(long)]
+    pub metrics: bool,
+
+    /// Prometheus Pushgateway base URL to push --metrics to, e.g. http://localhost:9091.
+    /// Metrics are recorded locally even without this set, just never pushed anywhere.
+    #[arg(long)]
+    pub metrics_pushgateway: Option<Url>,
+
+    /// Job label to push --metrics under.
+    #[arg(long, default_value = "moq-relay-test")]
+    pub metrics_job: String,
+
+    /// How often to push --metrics to --metrics-pushgateway.
+    #[arg(long, default_value = "10")]
+    pub metrics_interval_secs: u64,
+
+    /// Base delay before the first reconnect retry; doubles on each further failed
+    /// attempt (capped at --backoff-max-secs), with +/-10% jitter.
+    #[arg(long, default_value = "1")]
+    pub backoff_base_secs: u64,
+
+    /// Cap on the reconnect backoff delay, however many attempts have failed.
+    #[arg(long, default_value = "30")]
+    pub backoff_max_secs: u64,
+
+    /// Treat the session as dead and reconnect if no subscribed track receives a new
+    /// group for this many seconds, instead of relying solely on the session close
+    /// signal.
+    #[arg(long, default_value = "30")]
+    pub idle_timeout_secs: u64,
+
     /// The log configuration.
     #[command(flatten)]
     pub log: moq_native::Log,
+
+    /// Write a roff man page for this tool to <DIR>/relay-test.1, then exit. Hidden -
+    /// it's a packaging step (run once when cutting a release), not a day-to-day flag.
+    #[arg(long, hide = true, value_name = "DIR")]
+    pub generate_man: Option<PathBuf>,
+
+    /// Write a shell completion script for the given shell to stdout, then exit.
+    /// Hidden for the same reason as --generate-man.
+    #[arg(long, hide = true, value_enum)]
+    pub generate_completions: Option<Shell>,
+}
+
+/// Broker modeled on the broker pattern from moq-rs: holds each outbound broadcast's
+/// [`BroadcastProducer`] by name, so inbound tracks can be forwarded into it.
+/// [`Self::announce`] both registers the producer here and publishes its matching
+/// consumer on the outbound session's origin, so the upstream relay starts serving it.
+struct Broker {
+    broadcasts: HashMap<String, BroadcastProducer>,
+}
+
+impl Broker {
+    fn new() -> Self {
+        Self {
+            broadcasts: HashMap::new(),
+        }
+    }
+
+    /// Creates broadcast `name`, publishes it on `origin_producer`, and keeps its
+    /// producer so [`Self::create_track`] can wire inbound tracks into it.
+    fn announce(&mut self, name: &str, origin_producer: &OriginProducer) {
+        let broadcast = Broadcast::produce();
+        origin_producer.publish_broadcast(name, broadcast.consumer);
+        self.broadcasts.insert(name.to_string(), broadcast.producer);
+    }
+
+    /// Creates a [`TrackProducer`] for `track` in the broadcast `name`, for a caller to
+    /// forward inbound frames into. `None` if `name` hasn't been [`Self::announce`]d.
+    fn create_track(&mut self, name: &str, track: Track) -> Option<TrackProducer> {
+        self.broadcasts
+            .get_mut(name)
+            .map(|producer| producer.create_track(track))
+    }
+}
+
+/// Tracks the most recent moment any subscribed track received a new group, so
+/// `monitor_session` can notice a session that's silently stalled - still open, but
+/// nothing arriving - instead of reacting only to a hard close.
+struct Liveness {
+    last_group_at: std::sync::Mutex<Instant>,
+}
+
+impl Liveness {
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            last_group_at: std::sync::Mutex::new(Instant::now()),
+        })
+    }
+
+    fn record_group(&self) {
+        *self.last_group_at.lock().unwrap() = Instant::now();
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_group_at.lock().unwrap().elapsed()
+    }
+}
+
+/// Computes the delay before reconnect attempt `attempt` (1-based): `base * 2^(attempt
+/// - 1)`, capped at `max`, with +/-10% jitter - the same backoff shape as
+/// `compute_reconnect_delay`'s `ExponentialBackoff` case in the wrapper crate's
+/// `session.rs`.
+fn compute_backoff_delay(base: Duration, max: Duration, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1) as i32;
+    let scaled = base.as_secs_f64() * 2f64.powi(exponent);
+    let capped = scaled.min(max.as_secs_f64());
+    let jittered = capped * rand::thread_rng().gen_range(0.9..=1.1);
+    Duration::from_secs_f64(jittered.max(0.0))
 }
 
 /// Track subscriber that handles receiving data from a specific track
 struct TrackSubscriber {
     track_name: String,
-    track_consumer: TrackConsumer,
+    /// Taken by `run()` on its first (only) call and turned into a [`stream_ext`]
+    /// group stream, so the read loop can be written as straight-line `StreamExt`
+    /// combinators instead of a hand-rolled `next_group`/`read_frame` select loop.
+    track_consumer: Option<TrackConsumer>,
     bytes_received: u64,
     shutdown_rx: broadcast::Receiver<()>,
+    /// Outbound track to re-publish each received frame to, mirroring inbound group
+    /// boundaries, when `--forward-url`/`--forward-broadcast` are configured.
+    forward: Option<TrackProducer>,
+    forward_bytes: u64,
+    /// Feeds this track's groups to its decoder task, when `--play` is configured.
+    playback_tx: Option<mpsc::Sender<PlaybackFragment>>,
+    /// Records this track's throughput/group-arrival counters, when `--metrics` is set.
+    metrics: Option<Arc<Metrics>>,
+    /// Shared with [`RelayTestApp`], so every track's group arrivals feed its
+    /// idle-timeout liveness check.
+    liveness: Arc<Liveness>,
 }
 
 impl TrackSubscriber {
@@ -49,100 +235,158 @@ impl TrackSubscriber {
         track_name: String,
         track_consumer: TrackConsumer,
         shutdown_rx: broadcast::Receiver<()>,
+        forward: Option<TrackProducer>,
+        playback_tx: Option<mpsc::Sender<PlaybackFragment>>,
+        metrics: Option<Arc<Metrics>>,
+        liveness: Arc<Liveness>,
     ) -> Self {
         Self {
             track_name,
-            track_consumer,
+            track_consumer: Some(track_consumer),
             bytes_received: 0,
             shutdown_rx,
+            forward,
+            forward_bytes: 0,
+            playback_tx,
+            metrics,
+            liveness,
         }
     }
 
-    async fn run(&mut self) -> Result<u64> {
+    /// Returns `(bytes_received, forward_bytes)`.
+    async fn run(&mut self) -> Result<(u64, u64)> {
         println!("Starting subscriber thread for track: {}", self.track_name);
+
+        let track_consumer = self
+            .track_consumer
+            .take()
+            .expect("TrackSubscriber::run must only be called once");
+        let groups = track_consumer.groups();
+        pin_mut!(groups);
+
         let mut group_count = 0u64;
 
         loop {
-            tokio::select! {
+            let next_group = tokio::select! {
                 _ = self.shutdown_rx.recv() => {
                     println!("Track {} subscriber shutting down", self.track_name);
                     break;
                 }
-                group_result = timeout(Duration::from_millis(200), self.track_consumer.next_group()) => {
-                    match group_result {
-                        Ok(Ok(Some(mut group))) => {
-                            group_count += 1;
-                            let mut group_bytes = 0u64;
-                            let mut frame_count = 0;
-
-                            // Read all frames in the group
-                            loop {
-                                tokio::select! {
-                                    _ = self.shutdown_rx.recv() => {
-                                        println!("Track {} cancelled during frame reading", self.track_name);
-                                        return Ok(self.bytes_received);
-                                    }
-                                    frame_result = timeout(Duration::from_millis(100), group.read_frame()) => {
-                                        match frame_result {
-                                            Ok(Ok(Some(frame_data))) => {
-                                                let frame_size = frame_data.len() as u64;
-                                                group_bytes += frame_size;
-                                                frame_count += 1;
-                                                self.bytes_received += frame_size;
-                                            }
-                                            Ok(Ok(None)) => {
-                                                // No more frames in this group
-                                                break;
-                                            }
-                                            Ok(Err(e)) => {
-                                                tracing::error!("Error reading frame from track {}: {:?}", self.track_name, e);
-                                                break;
-                                            }
-                                            Err(_) => {
-                                                // Timeout - check for shutdown
-                                                continue;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                group = groups.next() => group,
+            };
+
+            let Some(group) = next_group else {
+                println!(
+                    "Track {}: No more groups available (received {} groups total)",
+                    self.track_name, group_count
+                );
+                break;
+            };
+
+            let group = match group {
+                Ok(group) => group,
+                Err(e) => {
+                    tracing::error!(
+                        "Error getting next group for track {}: {:?}",
+                        self.track_name,
+                        e
+                    );
+                    if group_count == 0 {
+                        // If we haven't received any data, assume no data available
+                        println!("Track {}: No data available", self.track_name);
+                        break;
+                    }
+                    // Sleep a bit before retrying
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
 
-                            println!(
-                                "Track {}: Group {} - {} frames, {} bytes (total: {} bytes)",
-                                self.track_name, group_count, frame_count, group_bytes, self.bytes_received
-                            );
-                        }
-                        Ok(Ok(None)) => {
-                            println!(
-                                "Track {}: No more groups available (received {} groups total)",
-                                self.track_name, group_count
-                            );
-                            break;
-                        }
-                        Ok(Err(e)) => {
-                            tracing::error!("Error getting next group for track {}: {:?}", self.track_name, e);
-                            if group_count == 0 {
-                                // If we haven't received any data, assume no data available
-                                println!("Track {}: No data available", self.track_name);
-                                break;
-                            }
-                            // Sleep a bit before retrying
-                            tokio::time::sleep(Duration::from_secs(1)).await;
-                        }
-                        Err(_) => {
-                            // Timeout - check for shutdown and continue
-                            continue;
+            group_count += 1;
+            self.liveness.record_group();
+            if let Some(metrics) = &self.metrics {
+                metrics.record_group(&self.track_name);
+            }
+            let mut group_bytes = 0u64;
+            let mut frame_count = 0;
+            let mut forward_group = self
+                .forward
+                .as_mut()
+                .and_then(|producer| producer.create_group(group_count.into()));
+
+            let frames = group.frames();
+            pin_mut!(frames);
+
+            loop {
+                let next_frame = tokio::select! {
+                    _ = self.shutdown_rx.recv() => {
+                        println!("Track {} cancelled during frame reading", self.track_name);
+                        if let Some(forward_group) = forward_group {
+                            forward_group.close();
                         }
+                        return Ok((self.bytes_received, self.forward_bytes));
+                    }
+                    frame = frames.next() => frame,
+                };
+
+                let Some(frame) = next_frame else {
+                    // No more frames in this group
+                    break;
+                };
+
+                let frame_data = match frame {
+                    Ok(data) => data,
+                    Err(e) => {
+                        tracing::error!(
+                            "Error reading frame from track {}: {:?}",
+                            self.track_name,
+                            e
+                        );
+                        break;
+                    }
+                };
+
+                let frame_size = frame_data.len() as u64;
+                group_bytes += frame_size;
+                frame_count += 1;
+                self.bytes_received += frame_size;
+
+                if let Some(metrics) = &self.metrics {
+                    metrics.record_frame(&self.track_name, frame_size);
+                }
+
+                if let Some(forward_group) = forward_group.as_mut() {
+                    forward_group.write_frame(frame_data.clone());
+                    self.forward_bytes += frame_size;
+                }
+
+                if let Some(playback_tx) = &self.playback_tx {
+                    let fragment = PlaybackFragment {
+                        is_init: group_count == 1,
+                        data: frame_data,
+                    };
+                    if playback_tx.send(fragment).await.is_err() {
+                        // Decoder task exited; stop feeding it.
+                        self.playback_tx = None;
                     }
                 }
             }
+
+            if let Some(forward_group) = forward_group {
+                forward_group.close();
+            }
+
+            println!(
+                "Track {}: Group {} - {} frames, {} bytes (total: {} bytes)",
+                self.track_name, group_count, frame_count, group_bytes, self.bytes_received
+            );
         }
 
         println!(
-            "Track {} subscriber finished. Groups: {}, Total bytes: {}",
-            self.track_name, group_count, self.bytes_received
+            "Track {} subscriber finished. Groups: {}, Total bytes: {} in, {} out",
+            self.track_name, group_count, self.bytes_received, self.forward_bytes
         );
-        Ok(self.bytes_received)
+        Ok((self.bytes_received, self.forward_bytes))
     }
 }
 
@@ -152,28 +396,104 @@ struct RelayTestApp {
     client: Option<moq_native::Client>,
     session: Option<Session<moq_native::web_transport_quinn::Session>>,
     broadcast_consumer: Option<BroadcastConsumer>,
-    active_subscribers: HashMap<String, tokio::task::JoinHandle<Result<u64>>>,
+    /// Tracks discovered from the broadcast's `catalog.json`, or (when it has none)
+    /// mirrored from `--tracks`; indexes into this list drive the numeric subscribe
+    /// keybinding. Refreshed on every [`Self::connect_to_relay`], including reconnects.
+    discovered_tracks: Vec<DiscoveredTrack>,
+    /// Second relay connection that forwarded tracks are re-published on, when
+    /// `--forward-url`/`--forward-broadcast` are set. `None` means forwarding is off.
+    forward_session: Option<Session<moq_native::web_transport_quinn::Session>>,
+    forward_origin: Option<OriginProducer>,
+    broker: Broker,
+    /// Builds each audio track's decoder sink; `None` unless `--play` is set.
+    playback_builder: Option<Arc<dyn MediaTaskBuilder>>,
+    playback_tasks: HashMap<String, tokio::task::JoinHandle<Result<()>>>,
+    /// Per-track throughput/group-arrival registry; `None` unless `--metrics` is set.
+    metrics: Option<Arc<Metrics>>,
+    metrics_task: Option<tokio::task::JoinHandle<()>>,
+    active_subscribers: HashMap<String, tokio::task::JoinHandle<Result<(u64, u64)>>>,
     track_stats: HashMap<String, u64>, // Track name -> bytes received
+    forward_stats: HashMap<String, u64>, // Track name -> bytes forwarded
     subscribed_tracks: Vec<String>,    // List of tracks we should be subscribed to
+    /// `;key=value` parameters parsed from each `--tracks` entry, keyed by track name.
+    /// Looked up by [`Self::start_track_subscriber`] to set [`Track::priority`] and
+    /// to show what else was requested, since `moq_lite::Track` carries nothing else.
+    track_params: HashMap<String, TrackParams>,
     shutdown_tx: broadcast::Sender<()>,
     is_connected: bool,
     auto_reconnect: bool,
+    /// Shared with every [`TrackSubscriber`]; feeds `monitor_session`'s idle-timeout
+    /// check.
+    liveness: Arc<Liveness>,
+    /// Number of consecutive failed reconnect attempts since the last successful
+    /// session, driving [`compute_backoff_delay`]. Reset to 0 on success.
+    reconnect_attempt: u32,
+    /// The backoff delay `attempt_reconnect` last slept for (or is about to), shown in
+    /// `show_status`. `None` once reconnected.
+    next_backoff: Option<Duration>,
+    /// Kept around (shared with the `--discover` listener task) so
+    /// [`OriginConsumer::consume_broadcast`] can be called both here and from that
+    /// task on the same origin. `None` until [`Self::connect_to_relay`] succeeds.
+    origin_consumer: Option<Arc<tokio::sync::Mutex<OriginConsumer>>>,
+    /// Namespaces auto-subscribed by `--discover`, in announce order; their paths are
+    /// listed alongside `discovered_tracks` in `show_status`.
+    discovered_namespaces: Vec<DiscoveredNamespace>,
+    namespace_events_rx: Option<mpsc::UnboundedReceiver<NamespaceEvent>>,
+    announce_task: Option<tokio::task::JoinHandle<()>>,
+    /// Connection dedicated to `--publish`, separate from the subscribe session since
+    /// publishing is a producer role. `None` unless `--publish` is set.
+    publish_session: Option<Session<moq_native::web_transport_quinn::Session>>,
+    publish_origin: Option<OriginProducer>,
+    publish_task: Option<tokio::task::JoinHandle<()>>,
+}
+
+/// One namespace (broadcast) `--discover` has consumed after seeing it ANNOUNCEd.
+struct DiscoveredNamespace {
+    path: String,
 }
 
 impl RelayTestApp {
     fn new(config: Config) -> Self {
         let (shutdown_tx, _) = broadcast::channel(16);
+        let playback_builder = config.play.clone().map(|output_dir| {
+            Arc::new(PcmMediaTaskBuilder { output_dir }) as Arc<dyn MediaTaskBuilder>
+        });
+        let metrics = config.metrics.then(Metrics::new);
+        let track_params = parse_tracks(&config.tracks)
+            .into_iter()
+            .map(|spec| (spec.name, spec.params))
+            .collect();
         Self {
             config,
             client: None,
             session: None,
             broadcast_consumer: None,
+            discovered_tracks: Vec::new(),
+            forward_session: None,
+            forward_origin: None,
+            broker: Broker::new(),
+            playback_builder,
+            playback_tasks: HashMap::new(),
+            metrics,
+            metrics_task: None,
             active_subscribers: HashMap::new(),
             track_stats: HashMap::new(),
+            forward_stats: HashMap::new(),
             subscribed_tracks: Vec::new(),
+            track_params,
             shutdown_tx,
             is_connected: false,
             auto_reconnect: true,
+            liveness: Liveness::new(),
+            reconnect_attempt: 0,
+            next_backoff: None,
+            origin_consumer: None,
+            discovered_namespaces: Vec::new(),
+            namespace_events_rx: None,
+            announce_task: None,
+            publish_session: None,
+            publish_origin: None,
+            publish_task: None,
         }
     }
 
@@ -189,6 +509,122 @@ impl RelayTestApp {
         let client = client_config.init()?;
         self.client = Some(client);
         println!("MOQ library initialized successfully");
+
+        if let Some(metrics) = self.metrics.clone() {
+            if let Some(pushgateway_url) = self.config.metrics_pushgateway.clone() {
+                let shutdown_rx = self.shutdown_tx.subscribe();
+                self.metrics_task = Some(metrics::spawn_flush_task(
+                    metrics,
+                    pushgateway_url,
+                    self.config.metrics_job.clone(),
+                    Duration::from_secs(self.config.metrics_interval_secs),
+                    shutdown_rx,
+                ));
+            } else {
+                println!("--metrics set without --metrics-pushgateway; recording locally only");
+            }
+        }
+
+        self.start_publish().await?;
+
+        Ok(())
+    }
+
+    /// Parses `--publish`, connects a dedicated session to `--url`, announces
+    /// `--publish-broadcast`, and spawns a background task that streams the file's
+    /// tracks as paced groups. No-op if `--publish` isn't set.
+    async fn start_publish(&mut self) -> Result<()> {
+        let Some(path) = self.config.publish.clone() else {
+            return Ok(());
+        };
+        let broadcast_name = self
+            .config
+            .publish_broadcast
+            .clone()
+            .context("--publish-broadcast is required when --publish is set")?;
+
+        let media = media::Media::open(&path)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        println!(
+            "Parsed {}: {} track(s), {} fragment(s)",
+            path.display(),
+            media.tracks.len(),
+            media.fragments.len()
+        );
+
+        let client = self
+            .client
+            .as_ref()
+            .context("Client not initialized")?
+            .clone();
+        println!("Connecting to publish relay: {}", self.config.url);
+        let session = client.connect(self.config.url.clone()).await?;
+
+        let Origin {
+            producer: origin_producer,
+            consumer: origin_consumer,
+        } = Origin::produce();
+        self.broker.announce(&broadcast_name, &origin_producer);
+
+        let mut track_producers = HashMap::new();
+        let mut codecs = HashMap::new();
+        let mut catalog_entries = Vec::new();
+        for track in &media.tracks {
+            let name = format!(
+                "{}-{}",
+                if track.is_audio { "audio" } else { "video" },
+                track.track_id
+            );
+            let producer = self
+                .broker
+                .create_track(
+                    &broadcast_name,
+                    Track {
+                        name: name.clone(),
+                        priority: 0,
+                    },
+                )
+                .context("broadcast not announced")?;
+            track_producers.insert(track.track_id, producer);
+
+            let codec = cmaf::TrackCodec::detect(track);
+            catalog_entries.push((name, track.is_audio, codec));
+            if let Some(codec) = codec {
+                codecs.insert(track.track_id, codec);
+            }
+        }
+
+        if let Ok(catalog_json) = cmaf::catalog_json(&catalog_entries) {
+            if let Some(mut producer) = self.broker.create_track(
+                &broadcast_name,
+                Track {
+                    name: catalog::CATALOG_TRACK_NAME.to_string(),
+                    priority: 0,
+                },
+            ) {
+                if let Some(mut group) = producer.create_group(0) {
+                    group.write_frame(Bytes::from(catalog_json.into_bytes()));
+                    group.close();
+                }
+            }
+        }
+
+        let session = Session::connect(session, Some(origin_consumer), None).await?;
+        self.publish_session = Some(session);
+        self.publish_origin = Some(origin_producer);
+
+        println!(
+            "Publishing {} as broadcast {}",
+            path.display(),
+            broadcast_name
+        );
+        let shutdown_rx = self.shutdown_tx.subscribe();
+        self.publish_task = Some(tokio::spawn(async move {
+            if let Err(e) = publish_media(media, track_producers, codecs, shutdown_rx).await {
+                tracing::error!("Publish task failed: {:?}", e);
+            }
+        }));
+
         Ok(())
     }
 
@@ -230,13 +666,20 @@ impl RelayTestApp {
         let origin = Origin::produce();
         let session = Session::connect(session, None, Some(origin.producer)).await?;
 
+        let origin_consumer = Arc::new(tokio::sync::Mutex::new(origin.consumer));
+        self.origin_consumer = Some(origin_consumer.clone());
+
         // Give some time for the broadcast to be available
         println!("Waiting for broadcast to be available...");
         tokio::time::sleep(Duration::from_secs(2)).await;
 
         // Consume the broadcast
         println!("Consuming broadcast: {}", self.config.broadcast);
-        let broadcast_consumer = match origin.consumer.consume_broadcast(&self.config.broadcast) {
+        let broadcast_consumer = match origin_consumer
+            .lock()
+            .await
+            .consume_broadcast(&self.config.broadcast)
+        {
             Some(bc) => bc,
             None => {
                 println!("Failed to consume broadcast (maybe no publisher available?)");
@@ -246,9 +689,170 @@ impl RelayTestApp {
 
         println!("Successfully consuming broadcast!");
 
+        self.discovered_tracks = match catalog::discover_tracks(&broadcast_consumer).await {
+            Ok(tracks) if !tracks.is_empty() => {
+                println!("Discovered {} track(s) from catalog.json", tracks.len());
+                tracks
+            }
+            Ok(_) => {
+                println!("No catalog track found; falling back to --tracks");
+                parse_tracks(&self.config.tracks)
+                    .into_iter()
+                    .map(|spec| DiscoveredTrack::from_config_name(spec.name))
+                    .collect()
+            }
+            Err(e) => {
+                tracing::error!("Failed to read catalog.json: {:?}", e);
+                parse_tracks(&self.config.tracks)
+                    .into_iter()
+                    .map(|spec| DiscoveredTrack::from_config_name(spec.name))
+                    .collect()
+            }
+        };
+
         self.session = Some(session);
         self.broadcast_consumer = Some(broadcast_consumer);
         self.is_connected = true;
+        self.liveness.record_group();
+        self.reconnect_attempt = 0;
+        self.next_backoff = None;
+
+        if self.config.discover {
+            println!("Discover mode: listening for ANNOUNCE events...");
+            let (rx, handle) =
+                announce::spawn_listener(origin_consumer, self.shutdown_tx.subscribe());
+            self.namespace_events_rx = Some(rx);
+            self.announce_task = Some(handle);
+        }
+
+        self.connect_forward().await?;
+
+        Ok(())
+    }
+
+    /// Drains pending [`NamespaceEvent`]s from the `--discover` listener: for each newly
+    /// announced namespace that matches `--namespace-prefix` (or always, if unset),
+    /// consumes its broadcast and auto-subscribes every track its catalog advertises;
+    /// for each unannounced namespace, tears down the subscriptions it started.
+    async fn process_namespace_events(&mut self) -> Result<()> {
+        let Some(rx) = &mut self.namespace_events_rx else {
+            return Ok(());
+        };
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+
+        for event in events {
+            match event {
+                NamespaceEvent::Announced(path) => {
+                    if let Some(prefix) = &self.config.namespace_prefix {
+                        if !announce::matches_glob(prefix, &path) {
+                            continue;
+                        }
+                    }
+                    if self.discovered_namespaces.iter().any(|n| n.path == path) {
+                        continue;
+                    }
+
+                    let Some(origin_consumer) = &self.origin_consumer else {
+                        continue;
+                    };
+                    let Some(broadcast_consumer) =
+                        origin_consumer.lock().await.consume_broadcast(&path)
+                    else {
+                        tracing::warn!(
+                            "Announced namespace {path} vanished before it could be consumed"
+                        );
+                        continue;
+                    };
+
+                    println!("Discovered namespace: {path}");
+                    let tracks = catalog::discover_tracks(&broadcast_consumer)
+                        .await
+                        .unwrap_or_default();
+                    for track in &tracks {
+                        let key = format!("{path}:{}", track.name);
+                        if let Err(e) = self
+                            .start_track_subscriber(key, &track.name, &broadcast_consumer)
+                            .await
+                        {
+                            tracing::error!(
+                                "Failed to auto-subscribe to {path}:{}: {:?}",
+                                track.name,
+                                e
+                            );
+                        }
+                    }
+
+                    self.discovered_namespaces
+                        .push(DiscoveredNamespace { path });
+                }
+                NamespaceEvent::Unannounced(path) => {
+                    let Some(index) = self
+                        .discovered_namespaces
+                        .iter()
+                        .position(|n| n.path == path)
+                    else {
+                        continue;
+                    };
+                    self.discovered_namespaces.remove(index);
+
+                    println!("Namespace unannounced: {path}");
+                    let keys: Vec<String> = self
+                        .active_subscribers
+                        .keys()
+                        .filter(|key| key.starts_with(&format!("{path}:")))
+                        .cloned()
+                        .collect();
+                    for key in keys {
+                        self.unsubscribe_from_track(&key).await?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Connects to `--forward-url` and announces `--forward-broadcast` on it, so
+    /// subsequent [`Self::subscribe_to_track`] calls can mirror inbound tracks onward.
+    /// No-op if `--forward-url` isn't set.
+    async fn connect_forward(&mut self) -> Result<()> {
+        let Some(forward_url) = self.config.forward_url.clone() else {
+            return Ok(());
+        };
+        let forward_broadcast = self
+            .config
+            .forward_broadcast
+            .clone()
+            .context("--forward-broadcast is required when --forward-url is set")?;
+
+        let client = self
+            .client
+            .as_ref()
+            .context("Client not initialized")?
+            .clone();
+
+        println!("Connecting to forward relay: {}", forward_url);
+        let session = client.connect(forward_url).await?;
+
+        let Origin {
+            producer: origin_producer,
+            consumer: origin_consumer,
+        } = Origin::produce();
+        self.broker.announce(&forward_broadcast, &origin_producer);
+
+        let session = Session::connect(session, Some(origin_consumer), None).await?;
+
+        println!(
+            "Forwarding subscribed tracks as broadcast: {}",
+            forward_broadcast
+        );
+
+        self.forward_session = Some(session);
+        self.forward_origin = Some(origin_producer);
         Ok(())
     }
 
@@ -266,8 +870,17 @@ impl RelayTestApp {
         // Close session and reset state
         self.session = None;
         self.broadcast_consumer = None;
+        self.forward_session = None;
+        self.forward_origin = None;
         self.is_connected = false;
 
+        if let Some(handle) = self.announce_task.take() {
+            handle.abort();
+        }
+        self.origin_consumer = None;
+        self.namespace_events_rx = None;
+        self.discovered_namespaces.clear();
+
         println!("Disconnected from relay");
         Ok(())
     }
@@ -300,6 +913,38 @@ impl RelayTestApp {
         Ok(())
     }
 
+    /// Reconnects with backoff: sleeps [`compute_backoff_delay`] for the current
+    /// `reconnect_attempt` (no sleep on the first attempt after a success, since
+    /// `reconnect_attempt` is 0 then), then calls [`Self::reconnect_with_subscriptions`].
+    /// Resets `reconnect_attempt` to 0 on success; otherwise increments it so the next
+    /// call backs off further.
+    async fn attempt_reconnect(&mut self) {
+        if self.reconnect_attempt > 0 {
+            let delay = compute_backoff_delay(
+                Duration::from_secs(self.config.backoff_base_secs),
+                Duration::from_secs(self.config.backoff_max_secs),
+                self.reconnect_attempt,
+            );
+            self.next_backoff = Some(delay);
+            println!(
+                "Reconnect attempt {}: backing off for {:?}",
+                self.reconnect_attempt, delay
+            );
+            tokio::time::sleep(delay).await;
+        }
+
+        if let Err(e) = self.reconnect_with_subscriptions().await {
+            tracing::error!("Reconnect failed: {:?}", e);
+        }
+
+        if self.is_connected {
+            self.reconnect_attempt = 0;
+            self.next_backoff = None;
+        } else {
+            self.reconnect_attempt += 1;
+        }
+    }
+
     async fn monitor_session(&mut self) -> Result<()> {
         if let Some(session) = &self.session {
             let session_closed = session.closed();
@@ -317,7 +962,7 @@ impl RelayTestApp {
 
                     if self.auto_reconnect && !self.subscribed_tracks.is_empty() {
                         println!("Auto-reconnecting...");
-                        self.reconnect_with_subscriptions().await?;
+                        self.attempt_reconnect().await;
                     } else {
                         self.is_connected = false;
                         self.session = None;
@@ -326,7 +971,23 @@ impl RelayTestApp {
                     }
                 }
                 _ = tokio::time::sleep(Duration::from_millis(100)) => {
-                    // Continue monitoring
+                    self.process_namespace_events().await?;
+
+                    let idle_timeout = Duration::from_secs(self.config.idle_timeout_secs);
+                    if !self.active_subscribers.is_empty() && self.liveness.idle_for() >= idle_timeout {
+                        println!(
+                            "No new groups on any subscribed track for {:?}; treating session as dead",
+                            self.liveness.idle_for()
+                        );
+                        if self.auto_reconnect {
+                            self.attempt_reconnect().await;
+                        } else {
+                            self.is_connected = false;
+                            self.session = None;
+                            self.broadcast_consumer = None;
+                            println!("Session idle - use 'c' to reconnect");
+                        }
+                    }
                 }
             }
         }
@@ -342,43 +1003,104 @@ impl RelayTestApp {
             return Ok(());
         }
 
-        if self.active_subscribers.contains_key(track_name) {
-            println!("Already subscribed to track: {}", track_name);
-            return Ok(());
-        }
-
         let broadcast_consumer = self
             .broadcast_consumer
-            .as_ref()
+            .clone()
             .context("Broadcast consumer not available")?;
 
-        println!("Subscribing to track: {}", track_name);
+        if !self.subscribed_tracks.contains(&track_name.to_string()) {
+            self.subscribed_tracks.push(track_name.to_string());
+        }
+
+        self.start_track_subscriber(track_name.to_string(), track_name, &broadcast_consumer)
+            .await
+    }
+
+    /// Subscribes to `track_name` on `broadcast_consumer`, stored under `key` in
+    /// `active_subscribers`/`track_stats`/`forward_stats`. [`Self::subscribe_to_track`]
+    /// calls this with `key == track_name` for the single configured `--broadcast`;
+    /// [`Self::process_namespace_events`] calls it with a namespace-qualified `key` so
+    /// --discover can auto-subscribe the same track name from several namespaces at
+    /// once without colliding.
+    async fn start_track_subscriber(
+        &mut self,
+        key: String,
+        track_name: &str,
+        broadcast_consumer: &BroadcastConsumer,
+    ) -> Result<()> {
+        if self.active_subscribers.contains_key(&key) {
+            println!("Already subscribed to track: {}", key);
+            return Ok(());
+        }
+
+        println!("Subscribing to track: {}", key);
+
+        let params = self
+            .track_params
+            .get(track_name)
+            .cloned()
+            .unwrap_or_default();
+        let priority = params
+            .get("priority")
+            .and_then(|p| p.parse::<u32>().ok())
+            .unwrap_or(0);
+        if !params.is_empty() {
+            let encoded = track_params::encode_params(&params);
+            println!(
+                "Track {} parameters: {:?} ({} bytes encoded)",
+                key,
+                params,
+                encoded.len()
+            );
+        }
 
         let track = Track {
             name: track_name.to_string(),
-            priority: 0,
+            priority,
         };
 
         let track_consumer = broadcast_consumer.subscribe_track(&track);
-        println!("Successfully subscribed to track: {}", track_name);
+        println!("Successfully subscribed to track: {}", key);
 
-        // Add to subscribed tracks list for auto-reconnect
-        if !self.subscribed_tracks.contains(&track_name.to_string()) {
-            self.subscribed_tracks.push(track_name.to_string());
-        }
+        // If forwarding is configured, create a matching outbound track to mirror into
+        let forward_track = self
+            .config
+            .forward_broadcast
+            .as_ref()
+            .and_then(|name| self.broker.create_track(name, track));
+
+        // If --play is set and this looks like an audio track, spin up its decoder task
+        let playback_tx = if track_name.contains("audio") {
+            self.playback_builder.clone().map(|builder| {
+                let (tx, rx) = playback::channel();
+                let shutdown_rx = self.shutdown_tx.subscribe();
+                let handle = playback::spawn_decoder_task(key.clone(), builder, rx, shutdown_rx);
+                self.playback_tasks.insert(key.clone(), handle);
+                tx
+            })
+        } else {
+            None
+        };
 
         // Create subscriber for this track
         let shutdown_rx = self.shutdown_tx.subscribe();
-        let mut subscriber =
-            TrackSubscriber::new(track_name.to_string(), track_consumer, shutdown_rx);
+        let mut subscriber = TrackSubscriber::new(
+            key.clone(),
+            track_consumer,
+            shutdown_rx,
+            forward_track,
+            playback_tx,
+            self.metrics.clone(),
+            self.liveness.clone(),
+        );
 
         // Start subscriber task
         let handle = tokio::spawn(async move { subscriber.run().await });
 
         // Store handle
-        self.active_subscribers
-            .insert(track_name.to_string(), handle);
-        self.track_stats.insert(track_name.to_string(), 0);
+        self.active_subscribers.insert(key.clone(), handle);
+        self.track_stats.insert(key.clone(), 0);
+        self.forward_stats.insert(key, 0);
 
         Ok(())
     }
@@ -395,11 +1117,12 @@ impl RelayTestApp {
 
             // Wait for task to complete and get final stats
             match handle.await {
-                Ok(Ok(bytes)) => {
-                    self.track_stats.insert(track_name.to_string(), bytes);
+                Ok(Ok((bytes_in, bytes_out))) => {
+                    self.track_stats.insert(track_name.to_string(), bytes_in);
+                    self.forward_stats.insert(track_name.to_string(), bytes_out);
                     println!(
-                        "Unsubscribed from track: {} (final: {} bytes)",
-                        track_name, bytes
+                        "Unsubscribed from track: {} (final: {} bytes in, {} bytes out)",
+                        track_name, bytes_in, bytes_out
                     );
                 }
                 Ok(Err(e)) => {
@@ -411,6 +1134,13 @@ impl RelayTestApp {
             }
 
             self.track_stats.remove(track_name);
+            self.forward_stats.remove(track_name);
+
+            if let Some(handle) = self.playback_tasks.remove(track_name) {
+                if let Ok(Err(e)) = handle.await {
+                    tracing::error!("Playback error for {}: {:?}", track_name, e);
+                }
+            }
         } else {
             println!("Not subscribed to track: {}", track_name);
         }
@@ -434,8 +1164,11 @@ impl RelayTestApp {
         let handles: Vec<_> = self.active_subscribers.drain().collect();
         for (track_name, handle) in handles {
             match handle.await {
-                Ok(Ok(bytes)) => {
-                    println!("Track {} finished with {} bytes", track_name, bytes);
+                Ok(Ok((bytes_in, bytes_out))) => {
+                    println!(
+                        "Track {} finished with {} bytes in, {} bytes out",
+                        track_name, bytes_in, bytes_out
+                    );
                 }
                 Ok(Err(e)) => {
                     tracing::error!("Error in subscriber for {}: {:?}", track_name, e);
@@ -447,6 +1180,15 @@ impl RelayTestApp {
         }
 
         self.track_stats.clear();
+        self.forward_stats.clear();
+
+        let playback_handles: Vec<_> = self.playback_tasks.drain().collect();
+        for (track_name, handle) in playback_handles {
+            if let Ok(Err(e)) = handle.await {
+                tracing::error!("Playback error for {}: {:?}", track_name, e);
+            }
+        }
+
         println!("Unsubscribed from all tracks");
         Ok(())
     }
@@ -461,21 +1203,151 @@ impl RelayTestApp {
             "Auto-reconnect: {}",
             if self.auto_reconnect { "ON" } else { "OFF" }
         );
+        println!(
+            "Reconnect attempts: {}{}",
+            self.reconnect_attempt,
+            self.next_backoff
+                .map(|delay| format!(" (next backoff {:?})", delay))
+                .unwrap_or_default()
+        );
         if self.is_connected {
             println!("URL: {}", self.config.url);
             println!("Broadcast: {}", self.config.broadcast);
+            println!(
+                "Idle for: {:?} (reconnects after {}s)",
+                self.liveness.idle_for(),
+                self.config.idle_timeout_secs
+            );
+        }
+        if let Some(publish_broadcast) = &self.config.publish_broadcast {
+            println!(
+                "Publishing: {} as broadcast {}",
+                self.config
+                    .publish
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default(),
+                publish_broadcast
+            );
+        }
+        if let Some(forward_broadcast) = &self.config.forward_broadcast {
+            println!(
+                "Forwarding to: {} as broadcast {}",
+                self.config
+                    .forward_url
+                    .as_ref()
+                    .map(|url| url.to_string())
+                    .unwrap_or_default(),
+                forward_broadcast
+            );
         }
         println!("Active subscriptions: {}", self.active_subscribers.len());
         for track_name in self.active_subscribers.keys() {
-            let bytes = self.track_stats.get(track_name).unwrap_or(&0);
-            println!("  - {}: {} bytes", track_name, bytes);
+            let bytes_in = self.track_stats.get(track_name).unwrap_or(&0);
+            let bytes_out = self.forward_stats.get(track_name).unwrap_or(&0);
+            println!(
+                "  - {}: {} bytes in, {} bytes out",
+                track_name, bytes_in, bytes_out
+            );
         }
         if !self.subscribed_tracks.is_empty() {
             println!("Configured tracks: {}", self.subscribed_tracks.join(", "));
         }
+        if self.config.discover {
+            println!(
+                "Discovered namespaces ({}): {}",
+                self.discovered_namespaces.len(),
+                self.discovered_namespaces
+                    .iter()
+                    .map(|n| n.path.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        if !self.discovered_tracks.is_empty() {
+            println!("Discovered tracks:");
+            for (i, track) in self.discovered_tracks.iter().enumerate() {
+                println!(
+                    "  [{}] {} ({}, priority {}{}{})",
+                    i,
+                    track.name,
+                    track.track_type,
+                    track.priority,
+                    track
+                        .codec
+                        .as_ref()
+                        .map(|c| format!(", codec {c}"))
+                        .unwrap_or_default(),
+                    track
+                        .bitrate
+                        .map(|b| format!(", {b} bps"))
+                        .unwrap_or_default()
+                );
+            }
+        }
+        if let Some(metrics) = &self.metrics {
+            let snapshot = metrics.snapshot();
+            println!("Metrics:");
+            for (track_name, track_metrics) in snapshot {
+                println!(
+                    "  - {}: {:.0} bps ({:.0} peak), {:.1} fps ({:.1} peak)",
+                    track_name,
+                    track_metrics.current_bitrate_bps,
+                    track_metrics.peak_bitrate_bps,
+                    track_metrics.current_fps,
+                    track_metrics.peak_fps
+                );
+            }
+        }
         println!("=============\n");
     }
 
+    /// Runs one `:`-prefixed command-mode line: `add <trackspec>` (parsed the same as
+    /// a `--tracks` entry, so it can carry `;key=value` parameters), `drop <name>`,
+    /// or `list` (an alias for [`Self::show_status`]). Unrecognized input is reported,
+    /// not silently ignored, since a typo here shouldn't look like a no-op.
+    async fn process_command(&mut self, line: &str) -> Result<()> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+
+        let (cmd, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        match cmd {
+            "add" => {
+                if rest.is_empty() {
+                    println!("Usage: add <track>[;key=value...]");
+                    return Ok(());
+                }
+                let spec = track_params::parse_spec(rest);
+                if spec.name.is_empty() {
+                    println!("Usage: add <track>[;key=value...]");
+                    return Ok(());
+                }
+                if !spec.params.is_empty() {
+                    self.track_params.insert(spec.name.clone(), spec.params);
+                }
+                self.subscribe_to_track(&spec.name).await?;
+            }
+            "drop" => {
+                if rest.is_empty() {
+                    println!("Usage: drop <track>");
+                    return Ok(());
+                }
+                self.unsubscribe_from_track(rest).await?;
+            }
+            "list" => {
+                self.show_status();
+            }
+            _ => {
+                println!("Unknown command: {cmd} (expected add/drop/list)");
+            }
+        }
+        Ok(())
+    }
+
     fn show_help(&self) {
         println!("\n=== Keyboard Controls ===");
         println!("c - Connect to relay");
@@ -489,7 +1361,14 @@ impl RelayTestApp {
         println!("a - Subscribe to audio track");
         println!("V - Unsubscribe from video track");
         println!("A - Unsubscribe from audio track");
+        println!("0-9 - Subscribe to discovered track by index (see 's' for the list)");
+        if self.config.discover {
+            println!(
+                "(--discover is on: announced namespaces auto-subscribe; see 's' for the list)"
+            );
+        }
         println!("u - Unsubscribe from all tracks");
+        println!(": - Enter command mode (add <track>[;key=value...] / drop <track> / list)");
         println!("s - Show status");
         println!("h - Show this help");
         println!("q - Quit application");
@@ -504,6 +1383,10 @@ impl RelayTestApp {
         enable_raw_mode()?;
 
         let mut running = true;
+        // `:`-triggered line editor for `process_command`; raw mode doesn't echo
+        // keystrokes, so entering/editing this buffer prints each character by hand.
+        let mut command_mode = false;
+        let mut command_buf = String::new();
         while running {
             tokio::select! {
                 // Monitor session for disconnects
@@ -534,7 +1417,45 @@ impl RelayTestApp {
                                 continue;
                             }
 
+                            if command_mode {
+                                match key_event.code {
+                                    KeyCode::Enter => {
+                                        println!();
+                                        let line = command_buf.clone();
+                                        command_buf.clear();
+                                        command_mode = false;
+                                        if let Err(e) = self.process_command(&line).await {
+                                            println!("Command failed: {e:?}");
+                                        }
+                                    }
+                                    KeyCode::Esc => {
+                                        println!("\nCommand cancelled");
+                                        command_buf.clear();
+                                        command_mode = false;
+                                    }
+                                    KeyCode::Backspace => {
+                                        if command_buf.pop().is_some() {
+                                            print!("\u{8} \u{8}");
+                                            let _ = std::io::stdout().flush();
+                                        }
+                                    }
+                                    KeyCode::Char(c) => {
+                                        command_buf.push(c);
+                                        print!("{c}");
+                                        let _ = std::io::stdout().flush();
+                                    }
+                                    _ => {}
+                                }
+                                continue;
+                            }
+
                             match key_event.code {
+                                KeyCode::Char(':') => {
+                                    command_mode = true;
+                                    command_buf.clear();
+                                    print!("\n: ");
+                                    let _ = std::io::stdout().flush();
+                                }
                                 KeyCode::Char('c') | KeyCode::Char('C') => {
                                     if let Err(e) = self.connect_to_relay().await {
                                         tracing::error!("Failed to connect: {:?}", e);
@@ -546,9 +1467,7 @@ impl RelayTestApp {
                                     }
                                 }
                                 KeyCode::Char('r') | KeyCode::Char('R') => {
-                                    if let Err(e) = self.reconnect_with_subscriptions().await {
-                                        tracing::error!("Failed to reconnect: {:?}", e);
-                                    }
+                                    self.attempt_reconnect().await;
                                 }
                                 KeyCode::Char('t') | KeyCode::Char('T') => {
                                     self.auto_reconnect = !self.auto_reconnect;
@@ -574,6 +1493,19 @@ impl RelayTestApp {
                                         tracing::error!("Failed to unsubscribe from audio: {:?}", e);
                                     }
                                 }
+                                KeyCode::Char(c @ '0'..='9') => {
+                                    let index = c.to_digit(10).unwrap() as usize;
+                                    match self.discovered_tracks.get(index).cloned() {
+                                        Some(track) => {
+                                            if let Err(e) = self.subscribe_to_track(&track.name).await {
+                                                tracing::error!("Failed to subscribe to discovered track {}: {:?}", track.name, e);
+                                            }
+                                        }
+                                        None => {
+                                            println!("No discovered track at index {}", index);
+                                        }
+                                    }
+                                }
                                 KeyCode::Char('u') | KeyCode::Char('U') => {
                                     if let Err(e) = self.unsubscribe_from_all_tracks().await {
                                         tracing::error!("Failed to unsubscribe from all tracks: {:?}", e);
@@ -625,11 +1557,93 @@ impl RelayTestApp {
 
         // Cleanup
         self.disconnect_from_relay().await?;
+        if let Some(handle) = self.publish_task.take() {
+            handle.abort();
+        }
 
         result
     }
 }
 
+/// Streams `media`'s tracks as paced groups: each track's init segment becomes group
+/// 0, then every fragment in file order becomes its own group on its track's
+/// producer, until the file is exhausted or `shutdown_rx` fires. Each group's frame is
+/// Sesame-framed via [`PacketBuilder`] rather than sent as raw fMP4 bytes, so a
+/// `--parse-protocol` subscriber (see `main_mgr`'s `handle_data`) can decode it.
+/// `codecs`, keyed by `track_id`, attaches [`cmaf::TrackCodec::to_header`] to each
+/// track's init-segment packet so a subscriber has codec/geometry detail before its
+/// first keyframe.
+async fn publish_media(
+    media: media::Media,
+    mut track_producers: HashMap<u32, TrackProducer>,
+    codecs: HashMap<u32, cmaf::TrackCodec>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> Result<()> {
+    let is_audio: HashMap<u32, bool> = media
+        .tracks
+        .iter()
+        .map(|track| (track.track_id, track.is_audio))
+        .collect();
+    let packet_type_for = |track_id: u32| {
+        if is_audio.get(&track_id).copied().unwrap_or(false) {
+            PacketType::AudioFrame
+        } else {
+            PacketType::VideoFrame
+        }
+    };
+
+    let mut sequences: HashMap<u32, u64> = HashMap::new();
+    for track in &media.tracks {
+        if let Some(producer) = track_producers.get_mut(&track.track_id) {
+            if let Some(mut group) = producer.create_group(0) {
+                let mut builder =
+                    PacketBuilder::new(packet_type_for(track.track_id), 0, 0, track.init_segment.clone())
+                        .keyframe(true);
+                if let Some(codec) = codecs.get(&track.track_id) {
+                    builder = builder.codec_data(codec.to_header(track.timescale));
+                }
+                group.write_frame(builder.build());
+                group.close();
+            }
+        }
+        sequences.insert(track.track_id, 1);
+    }
+
+    let mut anchors: HashMap<u32, Option<(Instant, f64)>> = HashMap::new();
+    for fragment in &media.fragments {
+        let anchor = anchors.entry(fragment.track_id).or_insert(None);
+        tokio::select! {
+            _ = shutdown_rx.recv() => {
+                println!("Publish cancelled");
+                return Ok(());
+            }
+            _ = media::pace(anchor, fragment) => {}
+        }
+
+        let Some(producer) = track_producers.get_mut(&fragment.track_id) else {
+            continue;
+        };
+        let sequence = sequences.entry(fragment.track_id).or_insert(1);
+        if let Some(mut group) = producer.create_group((*sequence).into()) {
+            let pts_micros = (fragment.timestamp * 1_000_000.0) as u64;
+            let packet = PacketBuilder::new(
+                packet_type_for(fragment.track_id),
+                pts_micros,
+                fragment.sequence as u64,
+                fragment.data.clone(),
+            )
+            .keyframe(fragment.keyframe)
+            .build();
+            group.write_frame(packet);
+            group.close();
+        }
+        *sequence += 1;
+    }
+
+    println!("Finished publishing");
+    Ok(())
+}
+
 /// Guard to ensure terminal raw mode is disabled on drop
 struct TerminalGuard;
 
@@ -645,18 +1659,53 @@ impl Drop for TerminalGuard {
     }
 }
 
-fn parse_tracks(tracks_str: &str) -> Vec<String> {
+/// Splits `--tracks` on `,` and parses each entry's `;key=value` parameters via
+/// [`track_params::parse_spec`].
+fn parse_tracks(tracks_str: &str) -> Vec<track_params::TrackSpec> {
     tracks_str
         .split(',')
-        .map(|s| s.trim().to_string())
+        .map(|s| s.trim())
         .filter(|s| !s.is_empty())
+        .map(track_params::parse_spec)
+        .filter(|spec| !spec.name.is_empty())
         .collect()
 }
 
+/// Writes a roff man page for this tool's CLI to `<dir>/relay-test.1`, via
+/// `clap_mangen` - mirrors how the moq-pub CLI ships a man page alongside its binary
+/// instead of hand-written docs.
+fn generate_man_page(dir: &std::path::Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let path = dir.join("relay-test.1");
+    let mut file = std::fs::File::create(&path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    clap_mangen::Man::new(Config::command())
+        .render(&mut file)
+        .with_context(|| format!("failed to render man page to {}", path.display()))?;
+    println!("Wrote man page to {}", path.display());
+    Ok(())
+}
+
+/// Writes a `shell` completion script for this tool's CLI to stdout, via
+/// `clap_complete`.
+fn generate_completions(shell: Shell) {
+    let mut cmd = Config::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let config = Config::parse();
 
+    if let Some(dir) = &config.generate_man {
+        return generate_man_page(dir);
+    }
+    if let Some(shell) = config.generate_completions {
+        generate_completions(shell);
+        return Ok(());
+    }
+
     // Validate inputs
     if config.broadcast.is_empty() {
         anyhow::bail!("Broadcast name cannot be empty");
@@ -676,7 +1725,18 @@ async fn main() -> Result<()> {
         if i > 0 {
             print!(", ");
         }
-        print!("{}", track);
+        print!("{}", track.name);
+        if !track.params.is_empty() {
+            print!(
+                " ({})",
+                track
+                    .params
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
     }
     println!("\n");
 
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::sync::{Arc, atomic::{AtomicU64, Ordering}};
+use std::path::PathBuf;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
@@ -11,8 +15,15 @@ use crossterm::{
 };
 use url::Url;
 
-use moq_mgr::{Session, SessionConfig, SessionMode, SubscriptionConfig};
+use moq_mgr::{
+    BroadcastConfig, ReconnectPolicy, Session, SessionConfig, SessionMode, StartPosition,
+    SubscriptionConfig,
+};
 
+mod jitter_buffer;
+mod mp4_box;
+mod mp4_source;
+mod mp4_writer;
 mod sesame_protocol;
 
 #[derive(Parser, Clone)]
@@ -37,6 +48,27 @@ pub struct Config {
     #[arg(long)]
     pub parse_protocol: bool,
 
+    /// Publish a fragmented MP4 file (or `-` for stdin) instead of subscribing. The first
+    /// entry of --tracks is used as the published track name.
+    #[arg(long)]
+    pub publish: Option<String>,
+
+    /// Record each subscribed track to `<dir>/<track>.mp4` as fragmented MP4. Implies
+    /// --parse-protocol, since the init segment is synthesized from Sesame codec data.
+    #[arg(long)]
+    pub record: Option<PathBuf>,
+
+    /// De-jitter buffer window, in milliseconds: each track's frames are reordered by
+    /// PTS and held this long before release, to absorb out-of-order group delivery.
+    #[arg(long = "target-latency", default_value_t = 100)]
+    pub target_latency_ms: u64,
+
+    /// Override the Sesame Binary Protocol version used to parse every packet, for
+    /// servers that omit the header's version field (or predate it). Normally the
+    /// version is read from each packet.
+    #[arg(long = "protocol-version")]
+    pub protocol_version: Option<u16>,
+
     /// Include logs from moq-lite/moq-native libraries (verbose)
     #[arg(long)]
     pub verbose_logging: bool,
@@ -54,87 +86,132 @@ struct TrackDataHandler {
     keyframes_received: AtomicU64,
     start_time: Instant,
     parse_protocol: bool,
+    protocol_version: Option<u16>,
+    record_dir: Option<PathBuf>,
+    recorder: Mutex<Option<mp4_writer::Mp4Writer>>,
 }
 
 impl TrackDataHandler {
-    fn new(track_name: String, parse_protocol: bool) -> Self {
+    fn new(
+        track_name: String,
+        parse_protocol: bool,
+        protocol_version: Option<u16>,
+        record_dir: Option<PathBuf>,
+    ) -> Self {
         Self {
             track_name,
             bytes_received: AtomicU64::new(0),
             groups_received: AtomicU64::new(0),
             keyframes_received: AtomicU64::new(0),
             start_time: Instant::now(),
-            parse_protocol,
+            // The init segment comes from a parsed Sesame packet's codec info, so
+            // recording needs the packet parsed regardless of --parse-protocol.
+            parse_protocol: parse_protocol || record_dir.is_some(),
+            protocol_version,
+            record_dir,
+            recorder: Mutex::new(None),
+        }
+    }
+
+    /// Feeds one parsed packet to this track's recorder, creating the MP4 file
+    /// (synthesizing its init segment from the packet's codec info) on the first packet
+    /// that carries codec data. No-op when --record wasn't passed.
+    fn record_packet(&self, parsed: &dyn sesame_protocol::ParsedPacket, is_keyframe: bool) {
+        let Some(record_dir) = &self.record_dir else {
+            return;
+        };
+        let mut recorder = self.recorder.lock().unwrap();
+        if recorder.is_none() {
+            let Some(codec) = parsed.codec_info() else {
+                return;
+            };
+            let timescale = if codec.timebase_den > 0 {
+                codec.timebase_den
+            } else {
+                90_000
+            };
+            let path = record_dir.join(format!("{}.mp4", self.track_name));
+            match mp4_writer::Mp4Writer::create(&path, &codec, timescale) {
+                Ok(writer) => {
+                    println!("Recording track {} to {}", self.track_name, path.display());
+                    *recorder = Some(writer);
+                }
+                Err(e) => {
+                    eprintln!("Failed to start recording track {}: {}", self.track_name, e);
+                    return;
+                }
+            }
+        }
+        if let Some(writer) = recorder.as_mut() {
+            if let Err(e) = writer.write_fragment(parsed.payload(), parsed.pts(), is_keyframe) {
+                eprintln!(
+                    "Failed to write fragment for track {}: {}",
+                    self.track_name, e
+                );
+            }
         }
     }
 
     fn handle_data(&self, data: &[u8]) {
         let size = data.len();
-        self.bytes_received.fetch_add(size as u64, Ordering::Relaxed);
+        self.bytes_received
+            .fetch_add(size as u64, Ordering::Relaxed);
         self.groups_received.fetch_add(1, Ordering::Relaxed);
 
         let packet_info = if self.parse_protocol {
-            // Parse packet using Sesame Binary Protocol
-            let parsed = sesame_protocol::BinaryProtocol::parse_data(data);
-            
-            if parsed.valid {
-                let is_keyframe = (parsed.header.flags & sesame_protocol::FLAG_IS_KEYFRAME) != 0;
-                
-                if is_keyframe {
-                    self.keyframes_received.fetch_add(1, Ordering::Relaxed);
-                }
-                
-                // Build detailed packet info
-                let mut info = String::new();
-                info.push_str(" [");
-                
-                // Packet type
-                let packet_type = sesame_protocol::PacketType::from(parsed.header.packet_type);
-                info.push_str(&packet_type.to_string());
-                
-                // Keyframe status
-                if is_keyframe {
-                    info.push_str(", key");
-                }
-                
-                // PTS - copy field to avoid unaligned access
-                let pts = parsed.header.pts;
-                info.push_str(&format!(", PTS:{}", pts));
-                
-                // Codec info if available
-                if let Some(codec_data) = &parsed.codec_data {
-                    info.push_str(", ");
-                    let codec_type = sesame_protocol::CodecType::from(codec_data.codec_type);
-                    info.push_str(&codec_type.to_string());
-                    
-                    // Add resolution for video - copy fields to avoid unaligned access
-                    if matches!(packet_type, sesame_protocol::PacketType::VideoFrame) {
-                        let width = codec_data.width;
-                        let height = codec_data.height;
-                        info.push_str(&format!(" {}x{}", width, height));
+            // Parse packet using the (possibly version-overridden) Sesame Binary Protocol
+            match sesame_protocol::BinaryProtocol::parse_data(data, self.protocol_version) {
+                Ok(parsed) => {
+                    let is_keyframe = parsed.is_keyframe();
+
+                    if is_keyframe {
+                        self.keyframes_received.fetch_add(1, Ordering::Relaxed);
                     }
-                    
-                    // Add sample rate for audio - copy field to avoid unaligned access
-                    if matches!(packet_type, sesame_protocol::PacketType::AudioFrame) {
-                        let sample_rate = codec_data.sample_rate;
-                        info.push_str(&format!(" {} hz", sample_rate));
+
+                    self.record_packet(parsed.as_ref(), is_keyframe);
+
+                    // Build detailed packet info
+                    let mut info = String::new();
+                    info.push_str(" [");
+
+                    // Packet type
+                    info.push_str(&parsed.packet_type_name());
+
+                    // Keyframe status
+                    if is_keyframe {
+                        info.push_str(", key");
                     }
-                }
-                
-                // Payload info with first and last bytes
-                info.push_str(&format!(", payload:{}", parsed.payload.len()));
-                if !parsed.payload.is_empty() {
-                    info.push_str(&format!(" [0x{:02x}", parsed.payload[0]));
-                    if parsed.payload.len() > 1 {
-                        info.push_str(&format!("...0x{:02x}", parsed.payload[parsed.payload.len() - 1]));
+
+                    // PTS
+                    info.push_str(&format!(", PTS:{}", parsed.pts()));
+
+                    // Codec info if available
+                    if let Some(codec) = parsed.codec_info() {
+                        info.push_str(", ");
+                        info.push_str(&codec.codec_name);
+
+                        if codec.is_audio {
+                            info.push_str(&format!(" {} hz", codec.sample_rate));
+                        } else {
+                            info.push_str(&format!(" {}x{}", codec.width, codec.height));
+                        }
                     }
+
+                    // Payload info with first and last bytes
+                    let payload = parsed.payload();
+                    info.push_str(&format!(", payload:{}", payload.len()));
+                    if !payload.is_empty() {
+                        info.push_str(&format!(" [0x{:02x}", payload[0]));
+                        if payload.len() > 1 {
+                            info.push_str(&format!("...0x{:02x}", payload[payload.len() - 1]));
+                        }
+                        info.push_str("]");
+                    }
+
                     info.push_str("]");
+                    info
                 }
-                
-                info.push_str("]");
-                info
-            } else {
-                " [INVALID PACKET]".to_string()
+                Err(e) => format!(" [{}]", e),
             }
         } else {
             // Simple raw data logging when protocol parsing is disabled
@@ -148,21 +225,28 @@ impl TrackDataHandler {
             info.push_str("]");
             info
         };
-        
+
         // Log packet information
-        println!("Track {}: Size {} bytes{}", self.track_name, size, packet_info);
-        
+        println!(
+            "Track {}: Size {} bytes{}",
+            self.track_name, size, packet_info
+        );
+
         let groups = self.groups_received.load(Ordering::Relaxed);
         let bytes = self.bytes_received.load(Ordering::Relaxed);
-        
+
         // Log every 100 groups or 1MB of data
         if groups % 100 == 0 || bytes % (1024 * 1024) == 0 {
             let duration = self.start_time.elapsed().as_secs().max(1);
             let keyframes = self.keyframes_received.load(Ordering::Relaxed);
-            
+
             println!(
                 "Track {}: {} groups, {} keyframes, {} bytes (avg {} B/s)",
-                self.track_name, groups, keyframes, bytes, bytes / duration
+                self.track_name,
+                groups,
+                keyframes,
+                bytes,
+                bytes / duration
             );
         }
     }
@@ -185,6 +269,7 @@ struct RelayTestApp {
     config: Config,
     session: Option<Session>,
     track_handlers: Arc<HashMap<String, Arc<TrackDataHandler>>>,
+    jitter_buffers: Arc<HashMap<String, Arc<jitter_buffer::JitterBuffer>>>,
     is_connected: bool,
 }
 
@@ -194,6 +279,7 @@ impl RelayTestApp {
             config,
             session: None,
             track_handlers: Arc::new(HashMap::new()),
+            jitter_buffers: Arc::new(HashMap::new()),
             is_connected: false,
         }
     }
@@ -218,6 +304,11 @@ impl RelayTestApp {
 
         println!("Tracks: {}", track_names.join(", "));
 
+        if let Some(record_dir) = &self.config.record {
+            std::fs::create_dir_all(record_dir)?;
+            println!("Recording tracks to: {}", record_dir.display());
+        }
+
         // Create session config
         let mut client_config = moq_native::ClientConfig::default();
         client_config.bind = self.config.bind;
@@ -225,8 +316,11 @@ impl RelayTestApp {
         let session_config = SessionConfig {
             moq_server_url: self.config.url.clone(),
             moq_namespace: self.config.broadcast.clone(),
+            subscribe_namespace: None,
             reconnect_on_failure: true,
+            reconnect_policy: ReconnectPolicy::default(),
             client_config,
+            subscription_grace: None,
         };
 
         let session = Session::new(session_config, SessionMode::SubscribeOnly);
@@ -244,22 +338,46 @@ impl RelayTestApp {
         let mut handlers_map = HashMap::new();
         for track_name in &track_names {
             handlers_map.insert(
-                track_name.clone(), 
-                Arc::new(TrackDataHandler::new(track_name.clone(), self.config.parse_protocol))
+                track_name.clone(),
+                Arc::new(TrackDataHandler::new(
+                    track_name.clone(),
+                    self.config.parse_protocol,
+                    self.config.protocol_version,
+                    self.config.record.clone(),
+                )),
             );
         }
         self.track_handlers = Arc::new(handlers_map);
 
-        // Add subscriptions with data callbacks
+        // Create a jitter buffer per track, sitting between the subscription's data
+        // callback and its handler so out-of-order group delivery gets reordered by PTS.
+        let target_latency = Duration::from_millis(self.config.target_latency_ms);
+        let mut jitter_buffers_map = HashMap::new();
         for track_name in &track_names {
             let handler = self.track_handlers.get(track_name).unwrap().clone();
-            
+            jitter_buffers_map.insert(
+                track_name.clone(),
+                jitter_buffer::JitterBuffer::new(
+                    target_latency,
+                    handler,
+                    self.config.protocol_version,
+                ),
+            );
+        }
+        self.jitter_buffers = Arc::new(jitter_buffers_map);
+
+        // Add subscriptions with data callbacks
+        for track_name in &track_names {
+            let jitter = self.jitter_buffers.get(track_name).unwrap().clone();
+
             let subscription = SubscriptionConfig {
                 moq_track_name: track_name.clone(),
                 data_callback: Arc::new(move |data: &[u8]| {
-                    handler.handle_data(data);
+                    jitter.push(data);
                 }),
                 reconnect_callback: None, // The session will provide its own reconnect callback for track consumers
+                start_position: StartPosition::default(),
+                priority: None,
             };
 
             session.add_subscription(subscription);
@@ -274,7 +392,71 @@ impl RelayTestApp {
 
         println!("Session started successfully");
         println!("Note: Subscriptions will activate when tracks appear in catalog");
-        
+
+        Ok(())
+    }
+
+    /// Ingests `path` (an fMP4 file, or `-` for stdin) and publishes it as the first
+    /// track in --tracks, pacing groups by each fragment's decode timestamp to simulate
+    /// a live stream. Runs until the file is exhausted.
+    async fn publish_file(&mut self, path: &str) -> Result<()> {
+        let track_name = self
+            .config
+            .tracks
+            .split(',')
+            .map(|s| s.trim())
+            .find(|s| !s.is_empty())
+            .unwrap_or("video")
+            .to_string();
+
+        println!("Publishing {} as track '{}'", path, track_name);
+        let source = mp4_source::Mp4Source::open(path)?;
+        println!("Parsed {} fragments", source.fragments().len());
+
+        let mut client_config = moq_native::ClientConfig::default();
+        client_config.bind = self.config.bind;
+
+        let session_config = SessionConfig {
+            moq_server_url: self.config.url.clone(),
+            moq_namespace: self.config.broadcast.clone(),
+            subscribe_namespace: None,
+            reconnect_on_failure: false,
+            reconnect_policy: ReconnectPolicy::default(),
+            client_config,
+            subscription_grace: None,
+        };
+
+        let session = Session::new(session_config, SessionMode::PublishOnly);
+
+        session.set_error_callback(|error| {
+            eprintln!("Session error: {}", error);
+        });
+
+        session.set_status_callback(|status| {
+            println!("Session status: {}", status);
+        });
+
+        session.add_broadcast(BroadcastConfig {
+            moq_track_name: track_name.clone(),
+            priority: 128,
+        });
+
+        session.start().await?;
+        self.is_connected = true;
+
+        let producer = session
+            .producer(&track_name)
+            .ok_or_else(|| anyhow::anyhow!("producer not created for track '{}'", track_name))?;
+        producer.write_object(&source.init_segment)?;
+
+        let mut anchor = None;
+        for fragment in source.fragments() {
+            mp4_source::pace(&mut anchor, fragment).await;
+            producer.write_object(&fragment.data)?;
+        }
+
+        println!("Finished publishing {}", path);
+        self.session = Some(session);
         Ok(())
     }
 
@@ -285,7 +467,7 @@ impl RelayTestApp {
         }
 
         println!("Disconnecting...");
-        
+
         if let Some(session) = self.session.take() {
             session.stop();
         }
@@ -300,12 +482,12 @@ impl RelayTestApp {
         if self.is_connected {
             println!("URL: {}", self.config.url);
             println!("Broadcast: {}", self.config.broadcast);
-            
+
             if let Some(session) = &self.session {
                 println!("Session Running: {}", session.is_running());
             }
         }
-        
+
         println!("Track Statistics:");
         for (track_name, handler) in self.track_handlers.iter() {
             println!(
@@ -315,6 +497,14 @@ impl RelayTestApp {
                 handler.get_keyframes_received(),
                 handler.get_bytes_received()
             );
+            if let Some(jitter) = self.jitter_buffers.get(track_name) {
+                println!(
+                    "      jitter buffer: {} buffered, {} late, {} reordered",
+                    jitter.buffered_count(),
+                    jitter.late_count(),
+                    jitter.reordered_count()
+                );
+            }
         }
         println!("=============\n");
     }
@@ -326,14 +516,18 @@ impl RelayTestApp {
         println!("s - Show status");
         println!("h - Show this help");
         println!("q - Quit application");
-        println!("\nNote: With MOQ Manager, tracks are subscribed only when they appear in the catalog.");
-        
-        let track_names: Vec<String> = self.config.tracks
+        println!(
+            "\nNote: With MOQ Manager, tracks are subscribed only when they appear in the catalog."
+        );
+
+        let track_names: Vec<String> = self
+            .config
+            .tracks
             .split(',')
             .map(|s| s.trim().to_string())
             .filter(|s| !s.is_empty())
             .collect();
-        
+
         println!("Requested track subscriptions: {}", track_names.join(", "));
         println!("========================\n");
     }
@@ -393,7 +587,7 @@ impl RelayTestApp {
 #[tokio::main]
 async fn main() -> Result<()> {
     let config = Config::parse();
-    
+
     // Initialize logging based on verbose flag
     if config.verbose_logging {
         // More verbose logging that includes moq-lite/moq-native logs
@@ -407,10 +601,33 @@ async fn main() -> Result<()> {
     println!("Broadcast: {}", config.broadcast);
     println!("Tracks: {}", config.tracks);
     println!("Bind Address: {}", config.bind);
-    println!("Protocol Parsing: {}", if config.parse_protocol { "ENABLED" } else { "DISABLED" });
-    println!("Verbose Logging: {}", if config.verbose_logging { "ENABLED" } else { "DISABLED" });
+    println!(
+        "Protocol Parsing: {}",
+        if config.parse_protocol {
+            "ENABLED"
+        } else {
+            "DISABLED"
+        }
+    );
+    println!(
+        "Verbose Logging: {}",
+        if config.verbose_logging {
+            "ENABLED"
+        } else {
+            "DISABLED"
+        }
+    );
+    if let Some(record_dir) = &config.record {
+        println!("Recording to: {}", record_dir.display());
+    }
     println!();
 
+    let publish_path = config.publish.clone();
     let mut app = RelayTestApp::new(config);
+
+    if let Some(path) = publish_path {
+        return app.publish_file(&path).await;
+    }
+
     app.run().await
 }
@@ -0,0 +1,428 @@
+//! Parses a (possibly multi-track) fragmented-MP4 (CMAF) file for `--publish`: one
+//! standalone `ftyp`+`moov` init segment per `trak`, and a sequence of `moof`+`mdat`
+//! fragments split per `traf` so each becomes a self-contained object for one track,
+//! keyed by that track's id and the fragment's `moof` sequence number. Generalizes
+//! `mp4_source` (the single-track reader `main_mgr`'s `--publish` uses) to real files
+//! that mux several tracks - shares its small box-writing helpers with `mp4_writer`
+//! via `mp4_box`, the same way both binaries already share `sesame_protocol`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::mp4_box::{find_subslice, full_box, make_box, patch_trun_data_offset};
+
+/// One track's standalone init segment, extracted from the file's `moov`.
+pub struct MediaTrack {
+    pub track_id: u32,
+    pub is_audio: bool,
+    /// `mdhd`'s timescale, used to convert a fragment's `tfdt` into seconds.
+    pub timescale: u32,
+    /// Self-contained `ftyp`+`moov` init segment for this track only.
+    pub init_segment: Vec<u8>,
+}
+
+/// One track's slice of a `moof`+`mdat` fragment, self-contained and ready to publish
+/// as a single group.
+pub struct MediaFragment {
+    pub track_id: u32,
+    /// The fragment's `moof` sequence number (`mfhd.sequence_number`) - monotonic
+    /// across every track, since all tracks in a muxed file share one `moof` sequence.
+    pub sequence: u32,
+    pub keyframe: bool,
+    /// Decode timestamp in seconds, derived from `tfdt` and the track's timescale.
+    /// Used to pace publishing to simulate a live stream.
+    pub timestamp: f64,
+    /// `moof`+`mdat` bytes for this track's samples only.
+    pub data: Vec<u8>,
+}
+
+/// A parsed file's tracks and fragments, in file order.
+pub struct Media {
+    pub tracks: Vec<MediaTrack>,
+    pub fragments: Vec<MediaFragment>,
+}
+
+impl Media {
+    /// Reads and parses `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let data =
+            std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Self::parse(&data)
+    }
+
+    fn parse(data: &[u8]) -> Result<Self> {
+        let top = top_level_boxes(data);
+        let ftyp_end = top
+            .iter()
+            .find(|(kind, _, _)| kind == b"ftyp")
+            .map(|&(_, _, end)| end)
+            .context("no ftyp box found")?;
+        let (_, moov_start, moov_end) = *top
+            .iter()
+            .find(|(kind, _, _)| kind == b"moov")
+            .context("no moov box found")?;
+        let ftyp = &data[0..ftyp_end];
+        let moov = &data[moov_start + 8..moov_end];
+
+        let tracks = parse_tracks(ftyp, moov)?;
+        if tracks.is_empty() {
+            bail!("moov has no trak boxes");
+        }
+        let timescales: HashMap<u32, u32> =
+            tracks.iter().map(|t| (t.track_id, t.timescale)).collect();
+
+        let mut fragments = Vec::new();
+        let mut i = 0;
+        while i < top.len() {
+            let (kind, moof_start, moof_end) = top[i];
+            if &kind != b"moof" {
+                i += 1;
+                continue;
+            }
+            let Some(&(_, mdat_start, mdat_end)) =
+                top.get(i + 1).filter(|(kind, _, _)| kind == b"mdat")
+            else {
+                i += 1;
+                continue;
+            };
+
+            let moof_payload = &data[moof_start + 8..moof_end];
+            let mdat_payload = &data[mdat_start + 8..mdat_end];
+            let sequence = mfhd_sequence(moof_payload).unwrap_or(0);
+            fragments.extend(split_fragment(
+                sequence,
+                moof_payload,
+                mdat_payload,
+                &timescales,
+            )?);
+
+            i += 2;
+        }
+
+        if fragments.is_empty() {
+            bail!("no moof/mdat fragments found after the init segment");
+        }
+
+        Ok(Self { tracks, fragments })
+    }
+}
+
+/// Sleeps until `fragment.timestamp` has elapsed relative to `anchor`, the
+/// `(wall-clock, timestamp)` pair recorded for this track's first fragment - the same
+/// pacing `mp4_source::pace` does, kept per-track since tracks don't share a clock.
+pub async fn pace(anchor: &mut Option<(Instant, f64)>, fragment: &MediaFragment) {
+    let (anchor_wall, anchor_ts) = *anchor.get_or_insert((Instant::now(), fragment.timestamp));
+    let target = anchor_wall + Duration::from_secs_f64((fragment.timestamp - anchor_ts).max(0.0));
+    let now = Instant::now();
+    if target > now {
+        tokio::time::sleep(target - now).await;
+    }
+}
+
+fn parse_tracks(ftyp: &[u8], moov: &[u8]) -> Result<Vec<MediaTrack>> {
+    let mvhd = find_box(moov, b"mvhd").context("no mvhd box in moov")?;
+    let trex_boxes = find_box(moov, b"mvex")
+        .map(|mvex| find_all_boxes(mvex, b"trex"))
+        .unwrap_or_default();
+
+    let mut tracks = Vec::new();
+    for trak in find_all_boxes(moov, b"trak") {
+        let tkhd = find_box(trak, b"tkhd").context("no tkhd box in trak")?;
+        let track_id = tkhd_track_id(tkhd).context("malformed tkhd")?;
+        let mdia = find_box(trak, b"mdia").context("no mdia box in trak")?;
+        let is_audio = find_box(mdia, b"hdlr").map(hdlr_is_audio).unwrap_or(false);
+        let timescale = find_box(mdia, b"mdhd")
+            .and_then(mdhd_timescale)
+            .unwrap_or(1000);
+
+        let trex = trex_boxes
+            .iter()
+            .find(|trex| trex_track_id(trex) == Some(track_id))
+            .map(|body| make_box(b"trex", body))
+            .unwrap_or_else(|| build_default_trex(track_id));
+
+        let track_moov = make_box(
+            b"moov",
+            &[
+                make_box(b"mvhd", mvhd),
+                make_box(b"trak", trak),
+                make_box(b"mvex", &trex),
+            ]
+            .concat(),
+        );
+
+        tracks.push(MediaTrack {
+            track_id,
+            is_audio,
+            timescale,
+            init_segment: [ftyp, &track_moov[..]].concat(),
+        });
+    }
+    Ok(tracks)
+}
+
+/// Splits one `moof`+`mdat` pair into a per-track [`MediaFragment`] for each `traf` it
+/// contains. The common case - one `traf` per fragment, as every writer in this tree
+/// (and most CMAF muxers) produces - just rewraps the original bytes unchanged. A
+/// muxed fragment with several `traf`s is split by slicing `mdat` in `traf` order,
+/// using each `trun`'s per-sample sizes to find the boundaries; this assumes samples
+/// are stored in the same order as their `traf`s list them, which isn't guaranteed by
+/// the spec but holds for any muxer that writes tracks sequentially per fragment.
+fn split_fragment(
+    sequence: u32,
+    moof_payload: &[u8],
+    mdat_payload: &[u8],
+    timescales: &HashMap<u32, u32>,
+) -> Result<Vec<MediaFragment>> {
+    let trafs = find_all_boxes(moof_payload, b"traf");
+    if trafs.is_empty() {
+        bail!("moof has no traf boxes");
+    }
+
+    let timestamp_of = |track_id: u32, traf: &[u8]| {
+        tfdt_time(traf)
+            .map(|t| t as f64 / *timescales.get(&track_id).unwrap_or(&1000) as f64)
+            .unwrap_or(0.0)
+    };
+
+    if trafs.len() == 1 {
+        let traf = trafs[0];
+        let track_id = tfhd_track_id(traf).context("malformed tfhd")?;
+        return Ok(vec![MediaFragment {
+            track_id,
+            sequence,
+            keyframe: trun_is_sync_sample(traf),
+            timestamp: timestamp_of(track_id, traf),
+            data: [
+                make_box(b"moof", moof_payload),
+                make_box(b"mdat", mdat_payload),
+            ]
+            .concat(),
+        }]);
+    }
+
+    let mut fragments = Vec::new();
+    let mut mdat_offset = 0;
+    for traf in trafs {
+        let track_id = tfhd_track_id(traf).context("malformed tfhd")?;
+        let sample_bytes = trun_total_sample_bytes(traf).with_context(|| {
+            format!(
+                "track {track_id}'s fragment has no per-sample sizes; \
+                 can't split a multi-track moof without them"
+            )
+        })?;
+        let slice = mdat_payload
+            .get(mdat_offset..mdat_offset + sample_bytes)
+            .with_context(|| format!("track {track_id}'s fragment runs past the end of mdat"))?;
+        mdat_offset += sample_bytes;
+
+        let mfhd = full_box(b"mfhd", 0, 0, &sequence.to_be_bytes());
+        let mut moof = make_box(b"moof", &[mfhd, make_box(b"traf", traf)].concat());
+        let data_offset = (moof.len() + 8) as i32;
+        patch_trun_data_offset(&mut moof, data_offset);
+        moof.extend_from_slice(&make_box(b"mdat", slice));
+
+        fragments.push(MediaFragment {
+            track_id,
+            sequence,
+            keyframe: trun_is_sync_sample(traf),
+            timestamp: timestamp_of(track_id, traf),
+            data: moof,
+        });
+    }
+
+    Ok(fragments)
+}
+
+fn build_default_trex(track_id: u32) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&track_id.to_be_bytes());
+    body.extend_from_slice(&1u32.to_be_bytes()); // default_sample_description_index
+    body.extend_from_slice(&0u32.to_be_bytes()); // default_sample_duration
+    body.extend_from_slice(&0u32.to_be_bytes()); // default_sample_size
+    body.extend_from_slice(&0u32.to_be_bytes()); // default_sample_flags
+    full_box(b"trex", 0, 0, &body)
+}
+
+/// Reads one box off the front of `data`: `(fourcc, payload)`, where `payload` is
+/// everything after the 8-byte size+fourcc header. `None` if `data` doesn't start with
+/// a complete box.
+pub(crate) fn read_box_header(data: &[u8]) -> Option<([u8; 4], &[u8])> {
+    if data.len() < 8 {
+        return None;
+    }
+    let size = u32::from_be_bytes(data[0..4].try_into().ok()?) as usize;
+    if size < 8 || size > data.len() {
+        return None;
+    }
+    let mut fourcc = [0u8; 4];
+    fourcc.copy_from_slice(&data[4..8]);
+    Some((fourcc, &data[8..size]))
+}
+
+/// Scans `data`'s top-level boxes (not recursively) for every one matching `kind`.
+pub(crate) fn find_all_boxes<'a>(data: &'a [u8], kind: &[u8; 4]) -> Vec<&'a [u8]> {
+    let mut out = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let Some((fourcc, payload)) = read_box_header(&data[offset..]) else {
+            break;
+        };
+        if &fourcc == kind {
+            out.push(payload);
+        }
+        offset += 8 + payload.len();
+    }
+    out
+}
+
+pub(crate) fn find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    find_all_boxes(data, kind).into_iter().next()
+}
+
+/// Like [`find_all_boxes`], but keeping each box's absolute `(start, end)` offsets
+/// into `data` instead of just its payload - needed at the top level to slice out
+/// `ftyp`/`moov` verbatim and to locate each `moof`/`mdat` pair.
+fn top_level_boxes(data: &[u8]) -> Vec<([u8; 4], usize, usize)> {
+    let mut boxes = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        if size < 8 || offset + size > data.len() {
+            break;
+        }
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[offset + 4..offset + 8]);
+        boxes.push((kind, offset, offset + size));
+        offset += size;
+    }
+    boxes
+}
+
+fn tkhd_track_id(tkhd: &[u8]) -> Option<u32> {
+    let version = *tkhd.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = tkhd.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn hdlr_is_audio(hdlr: &[u8]) -> bool {
+    hdlr.get(8..12) == Some(b"soun".as_slice())
+}
+
+fn mdhd_timescale(mdhd: &[u8]) -> Option<u32> {
+    let version = *mdhd.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = mdhd.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn mfhd_sequence(moof_payload: &[u8]) -> Option<u32> {
+    let mfhd = find_box(moof_payload, b"mfhd")?;
+    let bytes = mfhd.get(4..8)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn tfhd_track_id(traf: &[u8]) -> Option<u32> {
+    let tfhd = find_box(traf, b"tfhd")?;
+    let bytes = tfhd.get(4..8)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn tfdt_time(traf: &[u8]) -> Option<u64> {
+    let tfdt = find_box(traf, b"tfdt")?;
+    let version = *tfdt.first()?;
+    if version == 1 {
+        let bytes = tfdt.get(4..12)?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    } else {
+        let bytes = tfdt.get(4..8)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?) as u64)
+    }
+}
+
+/// Whether `traf`'s first sample is a sync sample, i.e. `sample_is_difference_sample`
+/// (bit 16 of the sample flags) is clear. Only `first_sample_flags` is read: when
+/// `first-sample-flags-present` is not set the fragment counts as sync, whether or not
+/// it carries per-sample flags.
+fn trun_is_sync_sample(traf: &[u8]) -> bool {
+    let Some(trun) = find_box(traf, b"trun") else {
+        return true;
+    };
+    let Some(flag_bytes) = trun.get(1..4) else {
+        return true;
+    };
+    let flags = u32::from_be_bytes([0, flag_bytes[0], flag_bytes[1], flag_bytes[2]]);
+
+    let first_sample_flags_present = flags & 0x000004 != 0;
+    let mut offset = 8;
+    if flags & 0x000001 != 0 {
+        offset += 4; // data_offset
+    }
+    let sample_flags = if first_sample_flags_present {
+        offset += 4;
+        trun.get(offset - 4..offset)
+    } else {
+        None
+    };
+
+    match sample_flags
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_be_bytes)
+    {
+        Some(sample_flags) => sample_flags & 0x00010000 == 0,
+        None => true,
+    }
+}
+
+/// Sums `trun`'s per-sample sizes, the byte length of this `traf`'s slice of `mdat`.
+/// `None` if `trun` doesn't carry per-sample sizes (`sample_size_present` unset) - then
+/// the size can only be recovered from `trex`/`tfhd` defaults, which callers here treat
+/// as unsupported rather than guessing.
+fn trun_total_sample_bytes(traf: &[u8]) -> Option<usize> {
+    let trun = find_box(traf, b"trun")?;
+    let flags = u32::from_be_bytes([0, trun[1], trun[2], trun[3]]);
+    const SAMPLE_SIZE_PRESENT: u32 = 0x000200;
+    if flags & SAMPLE_SIZE_PRESENT == 0 {
+        return None;
+    }
+    let sample_count = u32::from_be_bytes(trun.get(4..8)?.try_into().ok()?) as usize;
+
+    let mut offset = 8;
+    if flags & 0x000001 != 0 {
+        offset += 4; // data_offset
+    }
+    if flags & 0x000004 != 0 {
+        offset += 4; // first_sample_flags
+    }
+
+    // Per-sample optional fields, in their fixed on-wire order.
+    let fields: &[(u32, usize)] = &[
+        (0x000100, 4), // sample_duration
+        (SAMPLE_SIZE_PRESENT, 4),
+        (0x000400, 4), // sample_flags
+        (0x000800, 4), // sample_composition_time_offset
+    ];
+    let entry_size: usize = fields
+        .iter()
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, size)| size)
+        .sum();
+    let size_offset_in_entry: usize = fields
+        .iter()
+        .take_while(|(bit, _)| *bit != SAMPLE_SIZE_PRESENT)
+        .filter(|(bit, _)| flags & bit != 0)
+        .map(|(_, size)| size)
+        .sum();
+
+    let mut total = 0usize;
+    for i in 0..sample_count {
+        let entry_start = offset + i * entry_size + size_offset_in_entry;
+        let bytes = trun.get(entry_start..entry_start + 4)?;
+        total += u32::from_be_bytes(bytes.try_into().ok()?) as usize;
+    }
+    Some(total)
+}
@@ -0,0 +1,254 @@
+//! Per-track metrics behind `--metrics`: throughput, frame rate, and group-arrival
+//! delay, recorded from `TrackSubscriber`'s read loop and periodically pushed to a
+//! Prometheus Pushgateway. Mirrors the `BitrateTracker`/registry split in
+//! `crate::metrics` (the wrapper crate's `MoqSession` telemetry, a different `crate` -
+//! this binary has no dependency on it), adapted to what this tool observes: a
+//! receive-only path with no sessions/RTT to track.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+use url::Url;
+
+/// How long a rate sample window is accumulated before folding into the EWMA.
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+/// Weight given to the newest sample when smoothing the rate EWMA.
+const RATE_EWMA_ALPHA: f64 = 0.3;
+
+/// Smooths a per-second counter into a current/peak rate estimate, using an
+/// exponentially-weighted moving average over `RATE_WINDOW`-sized samples. `scale`
+/// converts the raw per-window total into the reported unit (8.0 for bytes -> bits,
+/// 1.0 for a plain per-second count).
+struct RateTracker {
+    scale: f64,
+    window_start: Instant,
+    window_total: f64,
+    current: f64,
+    peak: f64,
+}
+
+impl RateTracker {
+    fn new(scale: f64) -> Self {
+        Self {
+            scale,
+            window_start: Instant::now(),
+            window_total: 0.0,
+            current: 0.0,
+            peak: 0.0,
+        }
+    }
+
+    fn record(&mut self, amount: u64) {
+        self.window_total += amount as f64;
+
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= RATE_WINDOW {
+            let sample = self.window_total * self.scale / elapsed.as_secs_f64();
+            self.current = if self.current == 0.0 {
+                sample
+            } else {
+                RATE_EWMA_ALPHA * sample + (1.0 - RATE_EWMA_ALPHA) * self.current
+            };
+            self.peak = self.peak.max(self.current);
+            self.window_total = 0.0;
+            self.window_start = Instant::now();
+        }
+    }
+}
+
+/// A point-in-time snapshot of one track's counters, returned by [`Metrics::snapshot`].
+#[derive(Clone, Debug, Default)]
+pub struct TrackMetrics {
+    pub bytes_received: u64,
+    pub frame_count: u64,
+    pub group_count: u64,
+    pub current_bitrate_bps: f64,
+    pub peak_bitrate_bps: f64,
+    pub current_fps: f64,
+    pub peak_fps: f64,
+    pub avg_group_interval: Option<Duration>,
+}
+
+/// Live, mutable state backing a [`TrackMetrics`] snapshot for one track.
+#[derive(Default)]
+struct TrackState {
+    metrics: TrackMetrics,
+    bitrate: Option<RateTracker>,
+    frame_rate: Option<RateTracker>,
+    last_group_at: Option<Instant>,
+    group_interval_sum: Duration,
+    group_interval_samples: u32,
+}
+
+/// Shared per-track metrics registry, handed into every `TrackSubscriber::new` behind
+/// an `Arc` so the read loop and the background flush task both see live counters.
+#[derive(Default)]
+pub struct Metrics {
+    tracks: Mutex<HashMap<String, TrackState>>,
+}
+
+impl Metrics {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Records one received frame: updates the byte/frame counters and the bitrate/fps
+    /// EWMAs for `track_name`.
+    pub fn record_frame(&self, track_name: &str, bytes: u64) {
+        let mut tracks = self.tracks.lock().unwrap();
+        let state = tracks.entry(track_name.to_string()).or_default();
+
+        state.metrics.bytes_received += bytes;
+        state.metrics.frame_count += 1;
+
+        let bitrate = state.bitrate.get_or_insert_with(|| RateTracker::new(8.0));
+        bitrate.record(bytes);
+        state.metrics.current_bitrate_bps = bitrate.current;
+        state.metrics.peak_bitrate_bps = bitrate.peak;
+
+        let frame_rate = state
+            .frame_rate
+            .get_or_insert_with(|| RateTracker::new(1.0));
+        frame_rate.record(1);
+        state.metrics.current_fps = frame_rate.current;
+        state.metrics.peak_fps = frame_rate.peak;
+    }
+
+    /// Records one group's arrival: updates the group count and the running average of
+    /// inter-group arrival delay for `track_name`.
+    pub fn record_group(&self, track_name: &str) {
+        let mut tracks = self.tracks.lock().unwrap();
+        let state = tracks.entry(track_name.to_string()).or_default();
+
+        state.metrics.group_count += 1;
+
+        let now = Instant::now();
+        if let Some(last) = state.last_group_at.replace(now) {
+            state.group_interval_sum += now - last;
+            state.group_interval_samples += 1;
+            state.metrics.avg_group_interval =
+                Some(state.group_interval_sum / state.group_interval_samples);
+        }
+    }
+
+    pub fn snapshot(&self) -> HashMap<String, TrackMetrics> {
+        self.tracks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, state)| (name.clone(), state.metrics.clone()))
+            .collect()
+    }
+}
+
+/// Renders `snapshot` as Prometheus text exposition format, one `moq_relay_test_*`
+/// metric family per counter, labeled by `track`.
+fn render_prometheus(snapshot: &HashMap<String, TrackMetrics>) -> String {
+    let mut out = String::new();
+    let gauges: &[(&str, fn(&TrackMetrics) -> f64)] = &[
+        ("moq_relay_test_bytes_received_total", |m| {
+            m.bytes_received as f64
+        }),
+        ("moq_relay_test_frames_received_total", |m| {
+            m.frame_count as f64
+        }),
+        ("moq_relay_test_groups_received_total", |m| {
+            m.group_count as f64
+        }),
+        ("moq_relay_test_bitrate_bps", |m| m.current_bitrate_bps),
+        ("moq_relay_test_bitrate_peak_bps", |m| m.peak_bitrate_bps),
+        ("moq_relay_test_fps", |m| m.current_fps),
+        ("moq_relay_test_fps_peak", |m| m.peak_fps),
+        ("moq_relay_test_group_interval_seconds", |m| {
+            m.avg_group_interval.map(|d| d.as_secs_f64()).unwrap_or(0.0)
+        }),
+    ];
+
+    for (name, value_of) in gauges {
+        out.push_str(&format!("# TYPE {name} gauge\n"));
+        for (track, metrics) in snapshot {
+            out.push_str(&format!(
+                "{name}{{track=\"{}\"}} {}\n",
+                escape_label(track),
+                value_of(metrics)
+            ));
+        }
+    }
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Pushes `body` (Prometheus text exposition format) to a Pushgateway's
+/// `/metrics/job/<job>` endpoint. No HTTP client crate is vendored in this tree and
+/// a Pushgateway POST is simple enough not to need one, so this writes the request
+/// directly over a `TcpStream`; the response is not read back, since a failed push
+/// shouldn't block (or be blocked by) the relay test's own read loop.
+async fn push(pushgateway_url: &Url, job: &str, body: &str) -> Result<()> {
+    let host = pushgateway_url
+        .host_str()
+        .context("pushgateway URL has no host")?;
+    let port = pushgateway_url.port_or_known_default().unwrap_or(9091);
+    let path = format!(
+        "{}/metrics/job/{job}",
+        pushgateway_url.path().trim_end_matches('/')
+    );
+
+    let mut stream = TcpStream::connect((host, port))
+        .await
+        .with_context(|| format!("failed to connect to pushgateway at {host}:{port}"))?;
+
+    let request = format!(
+        "POST {path} HTTP/1.1\r\n\
+         Host: {host}\r\n\
+         Content-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n\
+         {body}",
+        body.len()
+    );
+
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .context("failed to write pushgateway request")?;
+    stream
+        .flush()
+        .await
+        .context("failed to flush pushgateway request")
+}
+
+/// Spawns the background task that renders a [`Metrics::snapshot`] and pushes it to
+/// `pushgateway_url` every `interval`, until `shutdown_rx` fires. Push failures are
+/// logged, not propagated - metrics delivery shouldn't take down the relay test.
+pub fn spawn_flush_task(
+    metrics: Arc<Metrics>,
+    pushgateway_url: Url,
+    job: String,
+    interval: Duration,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(interval);
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                _ = tick.tick() => {
+                    let snapshot = metrics.snapshot();
+                    let body = render_prometheus(&snapshot);
+                    if let Err(e) = push(&pushgateway_url, &job, &body).await {
+                        tracing::error!("Failed to push metrics: {:?}", e);
+                    }
+                }
+            }
+        }
+    })
+}
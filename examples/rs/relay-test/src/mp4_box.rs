@@ -0,0 +1,37 @@
+//! Tiny ISOBMFF box-writing helpers shared by [`crate::mp4_writer`] (the `--record`
+//! `.mp4` writer) and [`crate::media`] (the fMP4 fragment parser's re-muxing path).
+//! Kept in one place so the `trun.data_offset` patching trick in particular - it
+//! locates the box by searching for its fourcc rather than tracking the offset
+//! structurally - only needs fixing in one spot if it turns out to be wrong.
+
+pub(crate) fn make_box(kind: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + payload.len());
+    out.extend_from_slice(&((payload.len() + 8) as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(payload);
+    out
+}
+
+pub(crate) fn full_box(kind: &[u8; 4], version: u8, flags: u32, body: &[u8]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(4 + body.len());
+    payload.push(version);
+    payload.extend_from_slice(&flags.to_be_bytes()[1..]);
+    payload.extend_from_slice(body);
+    make_box(kind, &payload)
+}
+
+/// `trun`'s `data_offset` field sits right after its 12-byte full-box header +
+/// `sample_count`, i.e. 16 bytes into the `trun` box (8 into its payload); patches it in place by
+/// locating the `trun` fourcc since its offset depends on sibling box sizes above it.
+pub(crate) fn patch_trun_data_offset(moof: &mut [u8], data_offset: i32) {
+    if let Some(pos) = find_subslice(moof, b"trun") {
+        let offset_field = pos + 4 + 8; // fourcc + (version/flags + sample_count)
+        moof[offset_field..offset_field + 4].copy_from_slice(&data_offset.to_be_bytes());
+    }
+}
+
+pub(crate) fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
@@ -0,0 +1,212 @@
+//! Small fragmented-MP4 (CMAF) reader for `--publish`: parses a file (or stdin) into
+//! an init segment plus a sequence of [`Fragment`]s, so the publish loop in `main_mgr`
+//! doesn't need to know anything about MP4 box layout - just pace and forward.
+
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+/// One `moof`+`mdat` fragment ready to publish as a group.
+pub struct Fragment {
+    /// Raw bytes of the fragment (`moof` through the end of its `mdat`).
+    pub data: Vec<u8>,
+    /// Whether this fragment's first sample is a sync sample. Every fragment still
+    /// becomes its own group when published (CMAF fragments are produced GOP-aligned,
+    /// same assumption `moq_mgr::Fmp4Ingestor` makes) - this is only informational;
+    /// `main_mgr`'s publish loop does not read it.
+    pub keyframe: bool,
+    /// Decode timestamp in seconds, derived from the fragment's `tfdt` and the track's
+    /// `mdhd` timescale. Used to pace publishing to simulate a live stream.
+    pub timestamp: f64,
+}
+
+/// Parses a fragmented-MP4 file into an init segment and its `Fragment`s.
+pub struct Mp4Source {
+    /// The `ftyp`+`moov` init segment, published once before any fragment.
+    pub init_segment: Vec<u8>,
+    fragments: Vec<Fragment>,
+}
+
+impl Mp4Source {
+    /// Read and parse `path`, or stdin if `path == "-"`.
+    pub fn open(path: &str) -> Result<Self> {
+        let data = if path == "-" {
+            let mut buf = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut buf)
+                .context("failed to read fMP4 data from stdin")?;
+            buf
+        } else {
+            std::fs::read(path).with_context(|| format!("failed to read {path}"))?
+        };
+        Self::parse(data)
+    }
+
+    fn parse(data: Vec<u8>) -> Result<Self> {
+        let top_boxes = top_level_boxes(&data);
+        let moov_range = top_boxes
+            .iter()
+            .find(|(kind, _, _)| kind == b"moov")
+            .copied()
+            .context("no moov box found")?;
+        let init_segment = data[0..moov_range.2].to_vec();
+
+        let timescale = find_box(&data[moov_range.1 + 8..moov_range.2], b"trak")
+            .and_then(|trak| find_box(trak, b"mdia"))
+            .and_then(|mdia| find_box(mdia, b"mdhd"))
+            .and_then(mdhd_timescale)
+            .unwrap_or(1000);
+
+        let mut fragments = Vec::new();
+        let mut first = true;
+        let mut i = 0;
+        while i < top_boxes.len() {
+            let (kind, moof_start, moof_end) = top_boxes[i];
+            if &kind != b"moof" {
+                i += 1;
+                continue;
+            }
+
+            let mut j = i + 1;
+            let mut fragment_end = None;
+            while j < top_boxes.len() {
+                let (next_kind, _, next_end) = top_boxes[j];
+                if &next_kind == b"mdat" {
+                    fragment_end = Some(next_end);
+                    break;
+                }
+                if &next_kind == b"moof" {
+                    break;
+                }
+                j += 1;
+            }
+            let Some(fragment_end) = fragment_end else {
+                i += 1;
+                continue;
+            };
+
+            let moof_payload = &data[moof_start + 8..moof_end];
+            let decode_time = tfdt_time(moof_payload);
+            let timestamp = decode_time
+                .map(|t| t as f64 / timescale as f64)
+                .unwrap_or(0.0);
+
+            fragments.push(Fragment {
+                data: data[moof_start..fragment_end].to_vec(),
+                keyframe: first || trun_is_sync_sample(moof_payload),
+                timestamp,
+            });
+            first = false;
+
+            i = j + 1;
+        }
+
+        if fragments.is_empty() {
+            anyhow::bail!("no moof/mdat fragments found after the init segment");
+        }
+
+        Ok(Self {
+            init_segment,
+            fragments,
+        })
+    }
+
+    pub fn fragments(&self) -> &[Fragment] {
+        &self.fragments
+    }
+}
+
+/// Sleeps until `fragment.timestamp` has elapsed relative to `anchor`, the
+/// `(wall-clock, timestamp)` pair recorded for the first fragment, so playback is
+/// paced to simulate a live stream instead of bursting the whole file at once.
+pub async fn pace(anchor: &mut Option<(Instant, f64)>, fragment: &Fragment) {
+    let (anchor_wall, anchor_ts) = *anchor.get_or_insert((Instant::now(), fragment.timestamp));
+    let target = anchor_wall + Duration::from_secs_f64((fragment.timestamp - anchor_ts).max(0.0));
+    let now = Instant::now();
+    if target > now {
+        tokio::time::sleep(target - now).await;
+    }
+}
+
+fn top_level_boxes(data: &[u8]) -> Vec<([u8; 4], usize, usize)> {
+    let mut boxes = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        if size < 8 || offset + size > data.len() {
+            break;
+        }
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[offset + 4..offset + 8]);
+        boxes.push((kind, offset, offset + size));
+        offset += size;
+    }
+    boxes
+}
+
+fn find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    top_level_boxes(data)
+        .into_iter()
+        .find(|(box_kind, _, _)| box_kind == kind)
+        .map(|(_, start, end)| &data[start + 8..end])
+}
+
+fn tfdt_time(moof_payload: &[u8]) -> Option<u64> {
+    let traf = find_box(moof_payload, b"traf")?;
+    let tfdt = find_box(traf, b"tfdt")?;
+    let version = *tfdt.first()?;
+    if version == 1 {
+        let bytes = tfdt.get(4..12)?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    } else {
+        let bytes = tfdt.get(4..8)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?) as u64)
+    }
+}
+
+/// Whether `trun`'s first sample is a sync sample, i.e. `sample_is_difference_sample`
+/// (bit 16 of the sample flags) is clear. Only `first_sample_flags` is read: when
+/// `first-sample-flags-present` is not set the fragment counts as sync, whether or not
+/// it carries per-sample flags.
+fn trun_is_sync_sample(moof_payload: &[u8]) -> bool {
+    let traf = match find_box(moof_payload, b"traf") {
+        Some(traf) => traf,
+        None => return true,
+    };
+    let trun = match find_box(traf, b"trun") {
+        Some(trun) => trun,
+        None => return true,
+    };
+    let Some(flag_bytes) = trun.get(1..4) else {
+        return true;
+    };
+    let flags = u32::from_be_bytes([0, flag_bytes[0], flag_bytes[1], flag_bytes[2]]);
+
+    let first_sample_flags_present = flags & 0x000004 != 0;
+    let mut offset = 8; // version+flags (4) + sample_count (4)
+    if flags & 0x000001 != 0 {
+        offset += 4; // data_offset
+    }
+    let sample_flags = if first_sample_flags_present {
+        offset += 4;
+        trun.get(offset - 4..offset)
+    } else {
+        None
+    };
+
+    match sample_flags
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u32::from_be_bytes)
+    {
+        Some(sample_flags) => sample_flags & 0x00010000 == 0,
+        None => true,
+    }
+}
+
+fn mdhd_timescale(mdhd_payload: &[u8]) -> Option<u32> {
+    let version = *mdhd_payload.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = mdhd_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
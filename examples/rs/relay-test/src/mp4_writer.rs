@@ -0,0 +1,302 @@
+//! Minimal fragmented-MP4 (CMAF) writer used by `--record`: builds a one-track
+//! `ftyp`+`moov` init segment from a Sesame [`CodecInfo`], then appends each received
+//! packet as its own `moof`+`mdat` fragment. The Sesame protocol carries no decoder
+//! config (SPS/PPS, `AudioSpecificConfig`, ...), so sample entries describe the codec
+//! and its dimensions/sample rate only - good enough for offline inspection with a tool
+//! that tolerates a bare sample entry, not guaranteed to decode in every player.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::mp4_box::{find_subslice, full_box, make_box, patch_trun_data_offset};
+use crate::sesame_protocol::CodecInfo;
+
+const TRACK_ID: u32 = 1;
+
+/// Writes one track's init segment then a stream of single-sample fragments to `path`.
+pub struct Mp4Writer {
+    file: File,
+    timescale: u32,
+    sequence: u32,
+}
+
+impl Mp4Writer {
+    /// Creates `path` and writes its `ftyp`+`moov` init segment, sized for one track
+    /// described by `codec`. `timescale` should match the unit `pts` is expressed in.
+    pub fn create(path: &Path, codec: &CodecInfo, timescale: u32) -> Result<Self> {
+        let mut file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        file.write_all(&build_init_segment(codec, timescale))
+            .context("failed to write init segment")?;
+        Ok(Self {
+            file,
+            timescale,
+            sequence: 0,
+        })
+    }
+
+    /// Appends one fragment (`moof`+`mdat`) containing `payload` as a single sample.
+    /// `pts` is in the same timescale passed to [`Self::create`] and becomes the
+    /// fragment's `baseMediaDecodeTime`; `keyframe` marks the sample as a sync sample.
+    pub fn write_fragment(&mut self, payload: &[u8], pts: u64, keyframe: bool) -> Result<()> {
+        self.sequence += 1;
+        let fragment = build_fragment(self.sequence, pts, payload, keyframe);
+        self.file
+            .write_all(&fragment)
+            .context("failed to write fragment")
+    }
+
+    pub fn timescale(&self) -> u32 {
+        self.timescale
+    }
+}
+
+/// Maps a [`CodecInfo::codec_name`] (one of the `Display` strings each protocol
+/// version's `CodecType` produces) to its ISOBMFF sample-entry fourcc.
+fn sample_entry_fourcc(codec_name: &str) -> [u8; 4] {
+    match codec_name {
+        "VP8" => *b"vp08",
+        "VP9" => *b"vp09",
+        "AVC" => *b"avc1",
+        "HEVC" => *b"hvc1",
+        "AV1" => *b"av01",
+        "OPUS" => *b"Opus",
+        "AAC" => *b"mp4a",
+        "PCM" => *b"twos",
+        _ => *b"mp4v", // Unknown codec: generic fourcc rather than failing the recording
+    }
+}
+
+fn build_init_segment(codec: &CodecInfo, timescale: u32) -> Vec<u8> {
+    let ftyp = make_box(
+        b"ftyp",
+        &[
+            b"isom".as_slice(),
+            &0u32.to_be_bytes(),
+            b"isom",
+            b"iso5",
+            b"mp41",
+        ]
+        .concat(),
+    );
+
+    let mvhd = build_mvhd(timescale);
+    let trak = build_trak(codec, timescale);
+    let mvex = make_box(b"mvex", &build_trex());
+    let moov_body = [mvhd, trak, mvex].concat();
+    let moov = make_box(b"moov", &moov_body);
+
+    [ftyp, moov].concat()
+}
+
+fn build_mvhd(timescale: u32) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&0u32.to_be_bytes()); // creation_time
+    body.extend_from_slice(&0u32.to_be_bytes()); // modification_time
+    body.extend_from_slice(&timescale.to_be_bytes());
+    body.extend_from_slice(&0u32.to_be_bytes()); // duration (unknown, fragmented)
+    body.extend_from_slice(&0x00010000u32.to_be_bytes()); // rate 1.0
+    body.extend_from_slice(&0x0100u16.to_be_bytes()); // volume 1.0
+    body.extend_from_slice(&[0u8; 10]); // reserved
+    body.extend_from_slice(&identity_matrix());
+    body.extend_from_slice(&[0u8; 24]); // pre_defined
+    body.extend_from_slice(&(TRACK_ID + 1).to_be_bytes()); // next_track_ID
+    full_box(b"mvhd", 0, 0, &body)
+}
+
+fn identity_matrix() -> [u8; 36] {
+    let mut m = [0u8; 36];
+    m[0..4].copy_from_slice(&0x00010000u32.to_be_bytes());
+    m[16..20].copy_from_slice(&0x00010000u32.to_be_bytes());
+    m[32..36].copy_from_slice(&0x40000000u32.to_be_bytes());
+    m
+}
+
+fn build_trak(codec: &CodecInfo, timescale: u32) -> Vec<u8> {
+    let tkhd = build_tkhd(codec);
+    let mdia = build_mdia(codec, timescale);
+    make_box(b"trak", &[tkhd, mdia].concat())
+}
+
+fn build_tkhd(codec: &CodecInfo) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&0u32.to_be_bytes()); // creation_time
+    body.extend_from_slice(&0u32.to_be_bytes()); // modification_time
+    body.extend_from_slice(&TRACK_ID.to_be_bytes());
+    body.extend_from_slice(&0u32.to_be_bytes()); // reserved
+    body.extend_from_slice(&0u32.to_be_bytes()); // duration
+    body.extend_from_slice(&[0u8; 8]); // reserved
+    body.extend_from_slice(&0u16.to_be_bytes()); // layer
+    body.extend_from_slice(&0u16.to_be_bytes()); // alternate_group
+    let volume: u16 = if codec.is_audio { 0x0100 } else { 0 };
+    body.extend_from_slice(&volume.to_be_bytes());
+    body.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    body.extend_from_slice(&identity_matrix());
+    body.extend_from_slice(&((codec.width as u32) << 16).to_be_bytes());
+    body.extend_from_slice(&((codec.height as u32) << 16).to_be_bytes());
+    full_box(b"tkhd", 0, 0x000007, &body) // track_enabled | track_in_movie | track_in_preview
+}
+
+fn build_mdia(codec: &CodecInfo, timescale: u32) -> Vec<u8> {
+    let mdhd = build_mdhd(timescale);
+    let hdlr = build_hdlr(codec);
+    let minf = build_minf(codec);
+    make_box(b"mdia", &[mdhd, hdlr, minf].concat())
+}
+
+fn build_mdhd(timescale: u32) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&0u32.to_be_bytes()); // creation_time
+    body.extend_from_slice(&0u32.to_be_bytes()); // modification_time
+    body.extend_from_slice(&timescale.to_be_bytes());
+    body.extend_from_slice(&0u32.to_be_bytes()); // duration
+    body.extend_from_slice(&0x55c4u16.to_be_bytes()); // language "und"
+    body.extend_from_slice(&0u16.to_be_bytes()); // pre_defined
+    full_box(b"mdhd", 0, 0, &body)
+}
+
+fn build_hdlr(codec: &CodecInfo) -> Vec<u8> {
+    let (handler, name): (&[u8; 4], &str) = if codec.is_audio {
+        (b"soun", "SoundHandler")
+    } else {
+        (b"vide", "VideoHandler")
+    };
+    let mut body = Vec::new();
+    body.extend_from_slice(&0u32.to_be_bytes()); // pre_defined
+    body.extend_from_slice(handler);
+    body.extend_from_slice(&[0u8; 12]); // reserved
+    body.extend_from_slice(name.as_bytes());
+    body.push(0); // null terminator
+    full_box(b"hdlr", 0, 0, &body)
+}
+
+fn build_minf(codec: &CodecInfo) -> Vec<u8> {
+    let media_header = if codec.is_audio {
+        full_box(b"smhd", 0, 0, &[0u8; 4])
+    } else {
+        full_box(b"vmhd", 0, 1, &[0u8; 8])
+    };
+    let dinf = make_box(
+        b"dinf",
+        &make_box(b"dref", &{
+            let mut body = Vec::new();
+            body.extend_from_slice(&0u8.to_be_bytes());
+            body.extend_from_slice(&0u32.to_be_bytes()[1..]);
+            body.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+            body.extend_from_slice(&full_box(b"url ", 0, 1, &[]));
+            body
+        }),
+    );
+    let stbl = build_stbl(codec);
+    make_box(b"minf", &[media_header, dinf, stbl].concat())
+}
+
+fn build_stbl(codec: &CodecInfo) -> Vec<u8> {
+    let stsd = build_stsd(codec);
+    let empty_table_boxes = [
+        full_box(b"stts", 0, 0, &0u32.to_be_bytes()),
+        full_box(b"stsc", 0, 0, &0u32.to_be_bytes()),
+        full_box(b"stsz", 0, 0, &[0u8; 8]),
+        full_box(b"stco", 0, 0, &0u32.to_be_bytes()),
+    ]
+    .concat();
+    make_box(b"stbl", &[stsd, empty_table_boxes].concat())
+}
+
+fn build_stsd(codec: &CodecInfo) -> Vec<u8> {
+    let sample_entry = if codec.is_audio {
+        build_audio_sample_entry(codec)
+    } else {
+        build_video_sample_entry(codec)
+    };
+    let mut body = Vec::new();
+    body.extend_from_slice(&1u32.to_be_bytes()); // entry_count
+    body.extend_from_slice(&sample_entry);
+    full_box(b"stsd", 0, 0, &body)
+}
+
+fn sample_entry_header(data_reference_index: u16) -> [u8; 8] {
+    let mut header = [0u8; 8];
+    header[6..8].copy_from_slice(&data_reference_index.to_be_bytes());
+    header
+}
+
+fn build_video_sample_entry(codec: &CodecInfo) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&sample_entry_header(1));
+    body.extend_from_slice(&0u16.to_be_bytes()); // pre_defined
+    body.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    body.extend_from_slice(&[0u8; 12]); // pre_defined
+    body.extend_from_slice(&codec.width.to_be_bytes());
+    body.extend_from_slice(&codec.height.to_be_bytes());
+    body.extend_from_slice(&0x00480000u32.to_be_bytes()); // horizresolution 72dpi
+    body.extend_from_slice(&0x00480000u32.to_be_bytes()); // vertresolution 72dpi
+    body.extend_from_slice(&0u32.to_be_bytes()); // reserved
+    body.extend_from_slice(&1u16.to_be_bytes()); // frame_count
+    body.extend_from_slice(&[0u8; 32]); // compressorname
+    body.extend_from_slice(&0x0018u16.to_be_bytes()); // depth
+    body.extend_from_slice(&0xFFFFu16.to_be_bytes()); // pre_defined
+    make_box(&sample_entry_fourcc(&codec.codec_name), &body)
+}
+
+fn build_audio_sample_entry(codec: &CodecInfo) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&sample_entry_header(1));
+    body.extend_from_slice(&[0u8; 8]); // reserved
+    body.extend_from_slice(&(codec.channels.max(1) as u16).to_be_bytes());
+    body.extend_from_slice(&16u16.to_be_bytes()); // samplesize
+    body.extend_from_slice(&0u16.to_be_bytes()); // pre_defined
+    body.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    body.extend_from_slice(&(codec.sample_rate << 16).to_be_bytes());
+    make_box(&sample_entry_fourcc(&codec.codec_name), &body)
+}
+
+fn build_trex() -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&TRACK_ID.to_be_bytes());
+    body.extend_from_slice(&1u32.to_be_bytes()); // default_sample_description_index
+    body.extend_from_slice(&0u32.to_be_bytes()); // default_sample_duration
+    body.extend_from_slice(&0u32.to_be_bytes()); // default_sample_size
+    body.extend_from_slice(&0u32.to_be_bytes()); // default_sample_flags
+    full_box(b"trex", 0, 0, &body)
+}
+
+fn build_fragment(
+    sequence: u32,
+    base_media_decode_time: u64,
+    payload: &[u8],
+    keyframe: bool,
+) -> Vec<u8> {
+    let mfhd = full_box(b"mfhd", 0, 0, &sequence.to_be_bytes());
+
+    let tfhd_flags = 0x020000; // default-base-is-moof
+    let tfhd = full_box(b"tfhd", 0, tfhd_flags, &TRACK_ID.to_be_bytes());
+
+    let tfdt = full_box(b"tfdt", 1, 0, &base_media_decode_time.to_be_bytes());
+
+    // sample_duration_present | sample_size_present | sample_flags_present | data_offset_present
+    let trun_flags = 0x000001 | 0x000100 | 0x000200 | 0x000400;
+    let sample_flags: u32 = if keyframe { 0x02000000 } else { 0x01010000 };
+    let mut trun_body = Vec::new();
+    trun_body.extend_from_slice(&1u32.to_be_bytes()); // sample_count
+    trun_body.extend_from_slice(&0i32.to_be_bytes()); // data_offset placeholder, patched below
+    trun_body.extend_from_slice(&0u32.to_be_bytes()); // sample_duration (unknown per-sample)
+    trun_body.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    trun_body.extend_from_slice(&sample_flags.to_be_bytes());
+    let trun = full_box(b"trun", 0, trun_flags, &trun_body);
+
+    let traf = make_box(b"traf", &[tfhd, tfdt, trun].concat());
+    let mut moof = make_box(b"moof", &[mfhd, traf].concat());
+
+    // Patch trun's data_offset now that moof's total size is known: offset is measured
+    // from the start of moof to the start of mdat's payload (moof.len() + mdat's 8-byte header).
+    let data_offset = (moof.len() + 8) as i32;
+    patch_trun_data_offset(&mut moof, data_offset);
+
+    let mdat = make_box(b"mdat", payload);
+    moof.extend_from_slice(&mdat);
+    moof
+}
@@ -0,0 +1,132 @@
+//! Decode pipeline for `--play`: treats each track's MoQ groups as CMAF fragments (the
+//! first group as the track's `moov` init segment, every later one as a `moof`+`mdat`
+//! fragment - the inverse of what [`crate::mp4_writer`] produces for `--record`) and
+//! routes the samples they contain to a [`MediaSink`]. Network reads happen in
+//! `TrackSubscriber::run`; decoding happens in its own task connected by a bounded
+//! channel, so a slow sink applies backpressure to the subscriber rather than letting
+//! undelivered fragments pile up in memory.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::fmp4_reader::{self, AudioInfo};
+
+const PLAYBACK_CHANNEL_CAPACITY: usize = 32;
+
+/// One MoQ group handed from the network loop to a track's decoder task.
+pub struct PlaybackFragment {
+    /// `true` for a track's first group (its `moov` init segment); `false` for every
+    /// later group (a `moof`+`mdat` fragment).
+    pub is_init: bool,
+    pub data: Bytes,
+}
+
+/// Builds a [`MediaSink`] once a track's init segment has been parsed. Splits the
+/// per-track setup (where output goes) from the hot per-sample write path, the way
+/// A2DP media playback splits its sink-task builder from the sink itself.
+pub trait MediaTaskBuilder: Send + Sync {
+    fn build(&self, track_name: &str, audio: &AudioInfo) -> Result<Box<dyn MediaSink>>;
+}
+
+/// Receives one track's decoded samples in presentation order.
+pub trait MediaSink: Send {
+    fn write_sample(&mut self, pts: u64, data: &[u8]) -> Result<()>;
+}
+
+/// Writes each track's raw PCM samples to `<output_dir>/<track>.pcm`, playable with
+/// e.g. `ffplay -f s16le -ar <rate> -ac <channels> <file>`. A real-time speaker sink
+/// would need a platform audio backend, which isn't vendored in this tree - this is the
+/// audio-only PCM output path to start from.
+pub struct PcmMediaTaskBuilder {
+    pub output_dir: PathBuf,
+}
+
+impl MediaTaskBuilder for PcmMediaTaskBuilder {
+    fn build(&self, track_name: &str, audio: &AudioInfo) -> Result<Box<dyn MediaSink>> {
+        std::fs::create_dir_all(&self.output_dir)
+            .with_context(|| format!("failed to create {}", self.output_dir.display()))?;
+        let file_name: String = track_name
+            .chars()
+            .map(|c| if c == '/' { '_' } else { c })
+            .collect();
+        let path = self.output_dir.join(format!("{file_name}.pcm"));
+        println!(
+            "Playback: track {} is {} Hz / {} ch, writing PCM to {}",
+            track_name,
+            audio.sample_rate,
+            audio.channels,
+            path.display()
+        );
+        let file =
+            File::create(&path).with_context(|| format!("failed to create {}", path.display()))?;
+        Ok(Box::new(PcmFileSink { file }))
+    }
+}
+
+struct PcmFileSink {
+    file: File,
+}
+
+impl MediaSink for PcmFileSink {
+    fn write_sample(&mut self, _pts: u64, data: &[u8]) -> Result<()> {
+        self.file
+            .write_all(data)
+            .context("failed to write PCM sample")
+    }
+}
+
+/// Creates the bounded channel a [`TrackSubscriber`](crate::TrackSubscriber) feeds
+/// fragments into and a decoder task reads from.
+pub fn channel() -> (
+    mpsc::Sender<PlaybackFragment>,
+    mpsc::Receiver<PlaybackFragment>,
+) {
+    mpsc::channel(PLAYBACK_CHANNEL_CAPACITY)
+}
+
+/// Spawns a track's decoder task: parses the init segment out of the first fragment it
+/// sees, builds a sink via `builder`, then parses and writes every later fragment until
+/// the channel closes or `shutdown_rx` fires. For PCM, "decoding" a sample is a
+/// pass-through - the codec's payload already is PCM.
+pub fn spawn_decoder_task(
+    track_name: String,
+    builder: Arc<dyn MediaTaskBuilder>,
+    mut fragment_rx: mpsc::Receiver<PlaybackFragment>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<Result<()>> {
+    tokio::spawn(async move {
+        let mut sink: Option<Box<dyn MediaSink>> = None;
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => {
+                    break;
+                }
+                fragment = fragment_rx.recv() => {
+                    let Some(fragment) = fragment else { break };
+
+                    if fragment.is_init {
+                        let audio = fmp4_reader::parse_init_segment(&fragment.data)
+                            .context("failed to parse playback init segment")?;
+                        sink = Some(builder.build(&track_name, &audio)?);
+                        continue;
+                    }
+
+                    let Some(sink) = sink.as_mut() else {
+                        // Fragment arrived before its init segment was parsed; drop it.
+                        continue;
+                    };
+                    let sample = fmp4_reader::parse_fragment(&fragment.data)
+                        .context("failed to parse playback fragment")?;
+                    sink.write_sample(sample.pts, &sample.data)?;
+                }
+            }
+        }
+        Ok(())
+    })
+}
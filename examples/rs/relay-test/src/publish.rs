@@ -0,0 +1,265 @@
+//! Companion publisher CLI: connects to a relay, announces a broadcast, and streams a
+//! file into it framed with the Sesame Binary Protocol, for soak-testing a relay/
+//! subscriber without needing a live camera feed. Doesn't share a `lib.rs` with
+//! `main`/`main_mgr`, so it redeclares the modules it needs by path rather than
+//! importing them from another binary.
+//!
+//! The `file` argument is parsed as fragmented MP4 (CMAF) when it has `ftyp` and `moov`
+//! boxes and at least one `moof`+`mdat` fragment; anything else
+//! is treated as a raw elementary stream and sliced into fixed-size chunks (see
+//! `--chunk-size`) instead, since a raw stream has no self-describing frame boundary to
+//! split on without also knowing its codec.
+
+mod catalog;
+mod cmaf;
+mod media;
+mod sesame_protocol;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use clap::Parser;
+use moq_lite::*;
+use url::Url;
+
+use sesame_protocol::{builder::PacketBuilder, v1::PacketType};
+
+/// Default pacing interval between raw-mode chunks, chosen to look like a ~25fps video
+/// stream - there's no embedded timestamp to pace against in that mode.
+const RAW_CHUNK_INTERVAL: Duration = Duration::from_millis(40);
+
+#[derive(Parser, Clone)]
+pub struct Config {
+    /// Connect to the given URL starting with https://
+    #[arg(long, default_value = "https://relay1.moq.sesame-streams.com:4433")]
+    pub url: Url,
+
+    /// Broadcast name to announce the published tracks under.
+    #[arg(long, default_value = "publisher")]
+    pub broadcast: String,
+
+    /// File to publish: a fragmented MP4, or (if it doesn't parse as one) a raw
+    /// elementary stream.
+    pub file: PathBuf,
+
+    /// Replay `file` continuously instead of publishing it once, for soak testing.
+    #[arg(long)]
+    pub r#loop: bool,
+
+    /// Chunk size, in bytes, used to split a raw (non-fMP4) `file` into packets.
+    #[arg(long, default_value = "65536")]
+    pub chunk_size: usize,
+
+    /// The MoQ client configuration.
+    #[command(flatten)]
+    pub client: moq_native::ClientConfig,
+
+    /// The log configuration.
+    #[command(flatten)]
+    pub log: moq_native::Log,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let config = Config::parse();
+    config.log.init();
+
+    if config.broadcast.is_empty() {
+        anyhow::bail!("Broadcast name cannot be empty");
+    }
+    if config.chunk_size == 0 {
+        anyhow::bail!("--chunk-size must be greater than 0");
+    }
+
+    let mut client_config = config.client.clone();
+    if client_config.bind.to_string() == "[::]:0" {
+        client_config.bind = "0.0.0.0:0".parse().unwrap();
+    }
+    let client = client_config.init()?;
+
+    println!("Connecting to {}", config.url);
+    let session = client.connect(config.url.clone()).await?;
+
+    let Origin {
+        producer: origin_producer,
+        consumer: origin_consumer,
+    } = Origin::produce();
+    let broadcast = Broadcast::produce();
+    origin_producer.publish_broadcast(&config.broadcast, broadcast.consumer);
+    let mut broadcast_producer = broadcast.producer;
+
+    let _session = Session::connect(session, Some(origin_consumer), None).await?;
+    println!(
+        "Publishing {} as broadcast {}",
+        config.file.display(),
+        config.broadcast
+    );
+
+    loop {
+        match media::Media::open(&config.file) {
+            Ok(media) => publish_media(&mut broadcast_producer, &config.file, media).await?,
+            Err(_) => publish_raw(&mut broadcast_producer, &config.file, config.chunk_size).await?,
+        }
+
+        if !config.r#loop {
+            break;
+        }
+        println!("Reached end of {}, looping", config.file.display());
+    }
+
+    println!("Finished publishing");
+    Ok(())
+}
+
+/// Publishes a parsed fMP4's tracks: one init-segment group per track (keyframe, `id`
+/// 0), then one group per fragment numbered from 1 per track, paced by each
+/// fragment's decode timestamp. Mirrors `main`'s `--publish`, duplicated rather than
+/// shared since this binary has no `lib.rs` to share it from.
+async fn publish_media(
+    broadcast_producer: &mut BroadcastProducer,
+    path: &std::path::Path,
+    media: media::Media,
+) -> Result<()> {
+    println!(
+        "Parsed {}: {} track(s), {} fragment(s)",
+        path.display(),
+        media.tracks.len(),
+        media.fragments.len()
+    );
+
+    let is_audio: HashMap<u32, bool> = media
+        .tracks
+        .iter()
+        .map(|track| (track.track_id, track.is_audio))
+        .collect();
+    let packet_type_for = |track_id: u32| {
+        if is_audio.get(&track_id).copied().unwrap_or(false) {
+            PacketType::AudioFrame
+        } else {
+            PacketType::VideoFrame
+        }
+    };
+
+    let mut track_producers = HashMap::new();
+    let mut codecs = HashMap::new();
+    let mut catalog_entries = Vec::new();
+    for track in &media.tracks {
+        let name = format!(
+            "{}-{}",
+            if track.is_audio { "audio" } else { "video" },
+            track.track_id
+        );
+        let producer = broadcast_producer.create_track(Track {
+            name: name.clone(),
+            priority: 0,
+        });
+
+        let codec = cmaf::TrackCodec::detect(track);
+        catalog_entries.push((name, track.is_audio, codec));
+        if let Some(codec) = codec {
+            codecs.insert(track.track_id, codec);
+        }
+        track_producers.insert(track.track_id, producer);
+    }
+
+    if let Ok(catalog_json) = cmaf::catalog_json(&catalog_entries) {
+        let mut producer = broadcast_producer.create_track(Track {
+            name: catalog::CATALOG_TRACK_NAME.to_string(),
+            priority: 0,
+        });
+        if let Some(mut group) = producer.create_group(0) {
+            group.write_frame(Bytes::from(catalog_json.into_bytes()));
+            group.close();
+        }
+    }
+
+    for track in &media.tracks {
+        let Some(producer) = track_producers.get_mut(&track.track_id) else {
+            continue;
+        };
+        let Some(mut group) = producer.create_group(0) else {
+            continue;
+        };
+        let mut builder = PacketBuilder::new(
+            packet_type_for(track.track_id),
+            0,
+            0,
+            track.init_segment.clone(),
+        )
+        .keyframe(true);
+        if let Some(codec) = codecs.get(&track.track_id) {
+            builder = builder.codec_data(codec.to_header(track.timescale));
+        }
+        group.write_frame(builder.build());
+        group.close();
+    }
+
+    let mut sequences: HashMap<u32, u64> = media.tracks.iter().map(|t| (t.track_id, 1)).collect();
+    let mut anchors: HashMap<u32, Option<(Instant, f64)>> = HashMap::new();
+    for fragment in &media.fragments {
+        let anchor = anchors.entry(fragment.track_id).or_insert(None);
+        media::pace(anchor, fragment).await;
+
+        let Some(producer) = track_producers.get_mut(&fragment.track_id) else {
+            continue;
+        };
+        let sequence = sequences.entry(fragment.track_id).or_insert(1);
+        if let Some(mut group) = producer.create_group((*sequence).into()) {
+            let pts_micros = (fragment.timestamp * 1_000_000.0) as u64;
+            let packet = PacketBuilder::new(
+                packet_type_for(fragment.track_id),
+                pts_micros,
+                fragment.sequence as u64,
+                fragment.data.clone(),
+            )
+            .keyframe(fragment.keyframe)
+            .build();
+            group.write_frame(packet);
+            group.close();
+        }
+        *sequence += 1;
+    }
+
+    Ok(())
+}
+
+/// Publishes `path` as a single "raw" video track, sliced into `chunk_size`-byte
+/// packets since there's no parseable frame boundary. Every chunk is treated as its own
+/// keyframe (and group) - a raw stream carries no GOP structure this tool can read.
+async fn publish_raw(
+    broadcast_producer: &mut BroadcastProducer,
+    path: &std::path::Path,
+    chunk_size: usize,
+) -> Result<()> {
+    let data = std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    println!(
+        "Publishing {} as a raw elementary stream ({} bytes, {} byte chunks)",
+        path.display(),
+        data.len(),
+        chunk_size
+    );
+
+    let mut producer = broadcast_producer.create_track(Track {
+        name: "video".to_string(),
+        priority: 0,
+    });
+
+    for (id, chunk) in data.chunks(chunk_size).enumerate() {
+        tokio::time::sleep(RAW_CHUNK_INTERVAL).await;
+
+        let Some(mut group) = producer.create_group(id as u64) else {
+            continue;
+        };
+        let pts_micros = id as u64 * RAW_CHUNK_INTERVAL.as_micros() as u64;
+        let packet = PacketBuilder::new(PacketType::VideoFrame, pts_micros, id as u64, chunk.to_vec())
+            .keyframe(true)
+            .build();
+        group.write_frame(packet);
+        group.close();
+    }
+
+    Ok(())
+}
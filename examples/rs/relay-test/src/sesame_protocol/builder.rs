@@ -0,0 +1,206 @@
+//! Constructs outgoing Sesame Binary Protocol v1 packets - the write-side counterpart
+//! to [`super::v1::parse`]. Mirrors moq-pub's `media.rs`, which assembles init/segment
+//! fragments before handing them to the transport: callers build a packet here instead
+//! of hand-rolling the header/codec-data/metadata/payload byte layout themselves.
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+
+use super::v1::{
+    calculate_header_size, HeaderCodecData, HeaderData, HeaderStructuredMetadata, PacketType,
+    FLAG_HAS_CODEC_DATA, FLAG_HAS_METADATA, FLAG_HAS_STRUCTURED_METADATA, FLAG_IS_FRAGMENTED,
+    FLAG_IS_KEYFRAME, PROTOCOL_MAGIC, PROTOCOL_VERSION,
+};
+use super::Encodable;
+
+/// Builds a single v1 packet. `pts`/`id`/`payload` are required; codec data, the fixed
+/// routing-string metadata, the variable-length structured metadata, and the keyframe
+/// flag are optional and only contribute header bytes (via [`calculate_header_size`])
+/// when set.
+pub struct PacketBuilder {
+    packet_type: PacketType,
+    pts: u64,
+    id: u64,
+    codec_data: Option<HeaderCodecData>,
+    metadata: Option<String>,
+    structured_metadata: Option<HeaderStructuredMetadata>,
+    is_keyframe: bool,
+    payload: Bytes,
+}
+
+impl PacketBuilder {
+    pub fn new(packet_type: PacketType, pts: u64, id: u64, payload: impl Into<Bytes>) -> Self {
+        Self {
+            packet_type,
+            pts,
+            id,
+            codec_data: None,
+            metadata: None,
+            structured_metadata: None,
+            is_keyframe: false,
+            payload: payload.into(),
+        }
+    }
+
+    pub fn codec_data(mut self, codec_data: HeaderCodecData) -> Self {
+        self.codec_data = Some(codec_data);
+        self
+    }
+
+    pub fn metadata(mut self, metadata: impl Into<String>) -> Self {
+        self.metadata = Some(metadata.into());
+        self
+    }
+
+    /// Sets the variable-length key/value attribute block (see
+    /// [`super::v1::FLAG_HAS_STRUCTURED_METADATA`]), alongside (not instead of)
+    /// [`Self::metadata`]'s fixed routing string.
+    pub fn structured_metadata(mut self, attributes: HashMap<String, String>) -> Self {
+        self.structured_metadata = Some(HeaderStructuredMetadata { attributes });
+        self
+    }
+
+    pub fn keyframe(mut self, is_keyframe: bool) -> Self {
+        self.is_keyframe = is_keyframe;
+        self
+    }
+
+    /// Encodes the packet: magic/version, then whichever of `HeaderData`,
+    /// [`super::v1::HeaderMetadata`], and `HeaderCodecData` are present, in wire order,
+    /// followed by the payload.
+    pub fn build(self) -> Bytes {
+        self.encode_one(
+            &self.payload,
+            None,
+            self.codec_data.as_ref(),
+            self.metadata.as_deref(),
+            self.structured_metadata.as_ref(),
+        )
+    }
+
+    /// Like [`Self::build`], but splits the payload into multiple packets if it
+    /// exceeds `mtu` bytes, each carrying the same `id`/`pts`/`packet_type`/keyframe
+    /// flag and a `fragment_index`/`fragment_count` pair (see
+    /// [`super::v1::FLAG_IS_FRAGMENTED`]). Codec data and both metadata blocks, if set,
+    /// travel only on fragment 0 rather than being repeated on every fragment -
+    /// [`super::reassembly::FragmentReassembler`] restores them onto the reassembled
+    /// packet. `fragment_count` is a single byte, so payloads needing more than 255
+    /// fragments at this `mtu` are capped at 255 chunks (the last one oversized)
+    /// rather than silently dropped.
+    pub fn build_fragmented(self, mtu: usize) -> Vec<Bytes> {
+        if mtu == 0 || self.payload.len() <= mtu {
+            return vec![self.build()];
+        }
+
+        let needed = self.payload.len().div_ceil(mtu);
+        let count = needed.min(255) as u8;
+
+        let mut fragments = Vec::with_capacity(count as usize);
+        let mut offset = 0;
+        for index in 0..count {
+            // The last fragment absorbs whatever remains, including any overflow past
+            // `mtu` once `count` has been capped at 255.
+            let chunk_len = if index as usize == count as usize - 1 {
+                self.payload.len() - offset
+            } else {
+                mtu
+            };
+            let chunk = &self.payload[offset..offset + chunk_len];
+
+            let codec_data = (index == 0).then(|| self.codec_data.as_ref()).flatten();
+            let metadata = (index == 0).then(|| self.metadata.as_deref()).flatten();
+            let structured_metadata = (index == 0)
+                .then(|| self.structured_metadata.as_ref())
+                .flatten();
+            fragments.push(self.encode_one(
+                chunk,
+                Some((index, count)),
+                codec_data,
+                metadata,
+                structured_metadata,
+            ));
+
+            offset += chunk_len;
+        }
+
+        fragments
+    }
+
+    /// Encodes one packet: magic/version, then whichever of `HeaderData`,
+    /// [`super::v1::HeaderMetadata`], [`super::v1::HeaderStructuredMetadata`], and
+    /// `HeaderCodecData` are present, in wire order, followed by `payload`. `fragment`
+    /// sets `FLAG_IS_FRAGMENTED` and packs `(index, count)` into `reserved` when
+    /// present.
+    fn encode_one(
+        &self,
+        payload: &[u8],
+        fragment: Option<(u8, u8)>,
+        codec_data: Option<&HeaderCodecData>,
+        metadata: Option<&str>,
+        structured_metadata: Option<&HeaderStructuredMetadata>,
+    ) -> Bytes {
+        let mut flags = 0u32;
+        if metadata.is_some() {
+            flags |= FLAG_HAS_METADATA;
+        }
+        if structured_metadata.is_some() {
+            flags |= FLAG_HAS_STRUCTURED_METADATA;
+        }
+        if codec_data.is_some() {
+            flags |= FLAG_HAS_CODEC_DATA;
+        }
+        if self.is_keyframe {
+            flags |= FLAG_IS_KEYFRAME;
+        }
+        if fragment.is_some() {
+            flags |= FLAG_IS_FRAGMENTED;
+        }
+
+        let structured_metadata_size = structured_metadata.map_or(0, |m| m.encoded_size());
+        let header_size = calculate_header_size(flags, structured_metadata_size);
+        let reserved = match fragment {
+            Some((index, count)) => (index as u16) | ((count as u16) << 8),
+            None => 0,
+        };
+
+        let header = HeaderData {
+            magic: PROTOCOL_MAGIC,
+            flags,
+            pts: self.pts,
+            id: self.id,
+            version: PROTOCOL_VERSION,
+            header_size,
+            packet_type: self.packet_type as u16,
+            reserved,
+        };
+
+        let mut out = Vec::with_capacity(header_size as usize + payload.len());
+        header.encode(&mut out);
+
+        if let Some(metadata) = metadata {
+            out.extend_from_slice(&Self::metadata_bytes(metadata));
+        }
+
+        if let Some(structured_metadata) = structured_metadata {
+            structured_metadata.encode(&mut out);
+        }
+
+        if let Some(codec_data) = codec_data {
+            codec_data.encode(&mut out);
+        }
+
+        out.extend_from_slice(payload);
+        Bytes::from(out)
+    }
+
+    /// Packs `metadata` into the fixed 64-byte, NUL-terminated field `HeaderMetadata`
+    /// carries; truncated (with room kept for the terminator) if it doesn't fit.
+    fn metadata_bytes(metadata: &str) -> [u8; super::v1::HEADER_METADATA_SIZE] {
+        let mut bytes = [0u8; super::v1::HEADER_METADATA_SIZE];
+        let src = metadata.as_bytes();
+        let len = src.len().min(bytes.len() - 1);
+        bytes[..len].copy_from_slice(&src[..len]);
+        bytes
+    }
+}
@@ -0,0 +1,138 @@
+//! Reassembles packets split by [`super::builder::PacketBuilder::build_fragmented`]
+//! back into one logical packet, keyed by the `id` every fragment of a split payload
+//! shares.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::builder::PacketBuilder;
+use super::v1::{self, HeaderCodecData, HeaderMetadata};
+use super::{Cursor, Decodable};
+
+/// How long an incomplete fragment set is kept before being dropped. There's no
+/// group-boundary signal plumbed down to [`FragmentReassembler::push`] (a
+/// [`crate::jitter_buffer::JitterBuffer`] only sees individual frames, not the group
+/// they came from), so staleness is used as a practical stand-in for "the sender moved
+/// on to a new group without finishing this one".
+const STALE_AFTER: Duration = Duration::from_secs(2);
+
+struct PendingSet {
+    packet_type: v1::PacketType,
+    pts: u64,
+    is_keyframe: bool,
+    codec_data: Option<HeaderCodecData>,
+    metadata: Option<String>,
+    structured_metadata: Option<HashMap<String, String>>,
+    parts: Vec<Option<Vec<u8>>>,
+    received: usize,
+    first_seen: Instant,
+}
+
+/// Feeds packets through fragment reassembly, one at a time, keeping per-`id` state
+/// for fragments still waiting on the rest of their set.
+#[derive(Default)]
+pub struct FragmentReassembler {
+    pending: HashMap<u64, PendingSet>,
+}
+
+impl FragmentReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds one received packet through reassembly. Returns the bytes of a complete
+    /// logical packet - `data` unchanged if it wasn't fragmented, or the freshly
+    /// rebuilt packet once every fragment sharing its `id` has arrived - or `None`
+    /// while a fragment set is still incomplete. Packets that don't parse at all (or
+    /// parse as a version other than v1, which is the only version fragmentation is
+    /// implemented for) pass through unchanged, same as an unfragmented packet.
+    pub fn push(&mut self, data: &[u8], version_override: Option<u16>) -> Option<Vec<u8>> {
+        self.evict_stale();
+
+        let mut cursor = Cursor::new(data);
+        let Ok(header) = v1::HeaderData::decode(&mut cursor) else {
+            return Some(data.to_vec());
+        };
+
+        let version = version_override.unwrap_or(header.version);
+        if version != v1::PROTOCOL_VERSION || header.flags & v1::FLAG_IS_FRAGMENTED == 0 {
+            return Some(data.to_vec());
+        }
+
+        let Ok(parsed) = v1::parse(data, header, &mut cursor) else {
+            return Some(data.to_vec());
+        };
+
+        let index = parsed.header.fragment_index() as usize;
+        let count = parsed.header.fragment_count() as usize;
+        if count == 0 || index >= count {
+            return Some(data.to_vec());
+        }
+
+        let id = parsed.header.id;
+        let set = self.pending.entry(id).or_insert_with(|| PendingSet {
+            packet_type: parsed.packet_type,
+            pts: parsed.header.pts,
+            is_keyframe: parsed.header.flags & v1::FLAG_IS_KEYFRAME != 0,
+            codec_data: None,
+            metadata: None,
+            structured_metadata: None,
+            parts: vec![None; count],
+            received: 0,
+            first_seen: Instant::now(),
+        });
+
+        if index == 0 {
+            set.codec_data = parsed.codec_data.clone();
+            set.metadata = parsed.metadata.as_ref().map(Self::metadata_to_string);
+            set.structured_metadata = parsed
+                .structured_metadata
+                .as_ref()
+                .map(|m| m.attributes.clone());
+        }
+
+        if set.parts[index].is_none() {
+            set.parts[index] = Some(parsed.payload.to_vec());
+            set.received += 1;
+        }
+
+        if set.received < count {
+            return None;
+        }
+
+        let set = self.pending.remove(&id)?;
+        let mut payload = Vec::new();
+        for part in set.parts.into_iter().flatten() {
+            payload.extend(part);
+        }
+
+        let mut builder =
+            PacketBuilder::new(set.packet_type, set.pts, id, payload).keyframe(set.is_keyframe);
+        if let Some(codec_data) = set.codec_data {
+            builder = builder.codec_data(codec_data);
+        }
+        if let Some(metadata) = set.metadata {
+            builder = builder.metadata(metadata);
+        }
+        if let Some(structured_metadata) = set.structured_metadata {
+            builder = builder.structured_metadata(structured_metadata);
+        }
+
+        Some(builder.build().to_vec())
+    }
+
+    fn evict_stale(&mut self) {
+        let now = Instant::now();
+        self.pending
+            .retain(|_, set| now.duration_since(set.first_seen) < STALE_AFTER);
+    }
+
+    fn metadata_to_string(metadata: &HeaderMetadata) -> String {
+        let nul = metadata
+            .metadata
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(metadata.metadata.len());
+        String::from_utf8_lossy(&metadata.metadata[..nul]).into_owned()
+    }
+}
@@ -0,0 +1,554 @@
+//! Sesame Binary Protocol v1: the original fixed header layout. The base layout
+//! (everything not gated by a flag bit) is frozen as of protocol version 1 - a
+//! genuinely new wire format belongs in a new version module, not here - but new
+//! *optional*, flag-gated blocks (see [`FLAG_IS_FRAGMENTED`],
+//! [`FLAG_HAS_STRUCTURED_METADATA`]) are still added here rather than forked into a
+//! new version, since a packet that doesn't set the flag round-trips identically to
+//! before it existed.
+
+use std::collections::HashMap;
+
+use super::{CodecInfo, Cursor, Decodable, Encodable, ParsedPacket, ProtocolError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum PacketType {
+    VideoFrame = 1,
+    AudioFrame = 2,
+    Rpc = 3,
+    MuxedData = 4,
+    DecoderData = 5,
+}
+
+impl TryFrom<u16> for PacketType {
+    type Error = ProtocolError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(PacketType::VideoFrame),
+            2 => Ok(PacketType::AudioFrame),
+            3 => Ok(PacketType::Rpc),
+            4 => Ok(PacketType::MuxedData),
+            5 => Ok(PacketType::DecoderData),
+            other => Err(ProtocolError::UnknownPacketType(other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CodecType {
+    VideoVp8 = 1,
+    VideoVp9 = 2,
+    VideoAvc = 3,
+    VideoHevc = 4,
+    VideoAv1 = 5,
+    AudioOpus = 64,
+    AudioAac = 65,
+    AudioPcm = 66,
+}
+
+impl TryFrom<u8> for CodecType {
+    type Error = ProtocolError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(CodecType::VideoVp8),
+            2 => Ok(CodecType::VideoVp9),
+            3 => Ok(CodecType::VideoAvc),
+            4 => Ok(CodecType::VideoHevc),
+            5 => Ok(CodecType::VideoAv1),
+            64 => Ok(CodecType::AudioOpus),
+            65 => Ok(CodecType::AudioAac),
+            66 => Ok(CodecType::AudioPcm),
+            other => Err(ProtocolError::UnknownCodec(other)),
+        }
+    }
+}
+
+// Flag constants
+pub const FLAG_HAS_CODEC_DATA: u32 = 1 << 0;
+pub const FLAG_HAS_METADATA: u32 = 1 << 1;
+pub const FLAG_IS_KEYFRAME: u32 = 1 << 2;
+/// `reserved` carries `fragment_index` in its low byte and `fragment_count` in its
+/// high byte instead of being all-zero. Set by [`super::builder::PacketBuilder`] when a
+/// payload is split across multiple packets sharing one `id`/`pts`; see
+/// [`HeaderData::fragment_index`]/[`HeaderData::fragment_count`].
+pub const FLAG_IS_FRAGMENTED: u32 = 1 << 3;
+/// Gates an optional [`HeaderStructuredMetadata`] block, alongside (not instead of)
+/// [`FLAG_HAS_METADATA`]'s fixed 64-byte routing string - this carries arbitrary
+/// key/value attributes a routing string can't express (e.g. per-rendition ABR hints),
+/// without breaking readers that only understand the old field.
+pub const FLAG_HAS_STRUCTURED_METADATA: u32 = 1 << 4;
+
+// Protocol constants
+pub const PROTOCOL_MAGIC: u32 = 0x4D534553; // 'SESM'
+pub const PROTOCOL_VERSION: u16 = 1;
+
+#[derive(Debug, Clone)]
+pub struct HeaderData {
+    pub magic: u32,       // 0x4D534553 ('SESM') - 4 bytes (offset 0)
+    pub flags: u32,       // Feature flags - 4 bytes (offset 4)
+    pub pts: u64,         // Presentation timestamp - 8 bytes (offset 8)
+    pub id: u64,          // Packet identifier - 8 bytes (offset 16)
+    pub version: u16,     // Protocol version - 2 bytes (offset 24)
+    pub header_size: u16, // Total size of all headers (excluding payload) - 2 bytes (offset 26)
+    pub packet_type: u16, // Type of packet - 2 bytes (offset 28)
+    pub reserved: u16,    // Reserved - 2 bytes (offset 30), or fragment index/count (see FLAG_IS_FRAGMENTED)
+}
+
+impl HeaderData {
+    /// This packet's position among the fragments sharing its `id`. Only meaningful
+    /// when `flags & FLAG_IS_FRAGMENTED != 0`.
+    pub fn fragment_index(&self) -> u8 {
+        (self.reserved & 0x00FF) as u8
+    }
+
+    /// How many fragments share this packet's `id`. Only meaningful when
+    /// `flags & FLAG_IS_FRAGMENTED != 0`.
+    pub fn fragment_count(&self) -> u8 {
+        (self.reserved >> 8) as u8
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HeaderCodecData {
+    pub sample_rate: u32,   // Audio sample rate - 4 bytes (offset 0)
+    pub timebase_num: u32,  // Timebase numerator - 4 bytes (offset 4)
+    pub timebase_den: u32,  // Timebase denominator - 4 bytes (offset 8)
+    pub codec_profile: u16, // Codec profile - 2 bytes (offset 12)
+    pub codec_level: u16,   // Codec level - 2 bytes (offset 14)
+    pub width: u16,         // Frame width (video only) - 2 bytes (offset 16)
+    pub height: u16,        // Frame height (video only) - 2 bytes (offset 18)
+    pub codec_type: u8,     // Codec identifier - 1 byte (offset 20)
+    pub channels: u8,       // Audio channels - 1 byte (offset 21)
+    pub bit_depth: u8,      // Bit depth (8, 10, 12, 16) - 1 byte (offset 22)
+    pub reserved: u8,       // Reserved - 1 byte (offset 23)
+}
+
+#[derive(Debug, Clone)]
+pub struct HeaderMetadata {
+    pub metadata: [u8; 64], // Null-terminated metadata string for routing
+}
+
+// Header size constants - these are wire sizes, not `mem::size_of`, since the structs
+// above no longer carry a `#[repr(C, packed)]` layout guarantee now that they're read
+// field-by-field through `Decodable` instead of transmuted out of raw bytes.
+pub const HEADER_DATA_SIZE: usize = 4 + 4 + 8 + 8 + 2 + 2 + 2 + 2;
+pub const HEADER_CODEC_DATA_SIZE: usize = 4 + 4 + 4 + 2 + 2 + 2 + 2 + 1 + 1 + 1 + 1;
+pub const HEADER_METADATA_SIZE: usize = 64;
+
+/// `structured_metadata_size` is the caller's pre-computed
+/// [`HeaderStructuredMetadata::encoded_size`] when `flags & FLAG_HAS_STRUCTURED_METADATA
+/// != 0`, and is ignored otherwise - unlike the other blocks it has no fixed size, so it
+/// can't be accounted for from `flags` alone.
+pub fn calculate_header_size(flags: u32, structured_metadata_size: usize) -> u16 {
+    let mut size = HEADER_DATA_SIZE;
+
+    if flags & FLAG_HAS_METADATA != 0 {
+        size += HEADER_METADATA_SIZE;
+    }
+
+    if flags & FLAG_HAS_STRUCTURED_METADATA != 0 {
+        size += structured_metadata_size;
+    }
+
+    if flags & FLAG_HAS_CODEC_DATA != 0 {
+        size += HEADER_CODEC_DATA_SIZE;
+    }
+
+    size as u16
+}
+
+impl Decodable for HeaderData {
+    fn decode(cursor: &mut Cursor) -> Result<Self, ProtocolError> {
+        let magic = cursor.read_u32()?;
+        if magic != PROTOCOL_MAGIC {
+            return Err(ProtocolError::BadMagic);
+        }
+
+        Ok(Self {
+            magic,
+            flags: cursor.read_u32()?,
+            pts: cursor.read_u64()?,
+            id: cursor.read_u64()?,
+            version: cursor.read_u16()?,
+            header_size: cursor.read_u16()?,
+            packet_type: cursor.read_u16()?,
+            reserved: cursor.read_u16()?,
+        })
+    }
+}
+
+impl Encodable for HeaderData {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.magic.to_le_bytes());
+        out.extend_from_slice(&self.flags.to_le_bytes());
+        out.extend_from_slice(&self.pts.to_le_bytes());
+        out.extend_from_slice(&self.id.to_le_bytes());
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.header_size.to_le_bytes());
+        out.extend_from_slice(&self.packet_type.to_le_bytes());
+        out.extend_from_slice(&self.reserved.to_le_bytes());
+    }
+}
+
+impl Decodable for HeaderCodecData {
+    fn decode(cursor: &mut Cursor) -> Result<Self, ProtocolError> {
+        Ok(Self {
+            sample_rate: cursor.read_u32()?,
+            timebase_num: cursor.read_u32()?,
+            timebase_den: cursor.read_u32()?,
+            codec_profile: cursor.read_u16()?,
+            codec_level: cursor.read_u16()?,
+            width: cursor.read_u16()?,
+            height: cursor.read_u16()?,
+            codec_type: cursor.read_u8()?,
+            channels: cursor.read_u8()?,
+            bit_depth: cursor.read_u8()?,
+            reserved: cursor.read_u8()?,
+        })
+    }
+}
+
+impl Encodable for HeaderCodecData {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.sample_rate.to_le_bytes());
+        out.extend_from_slice(&self.timebase_num.to_le_bytes());
+        out.extend_from_slice(&self.timebase_den.to_le_bytes());
+        out.extend_from_slice(&self.codec_profile.to_le_bytes());
+        out.extend_from_slice(&self.codec_level.to_le_bytes());
+        out.extend_from_slice(&self.width.to_le_bytes());
+        out.extend_from_slice(&self.height.to_le_bytes());
+        out.push(self.codec_type);
+        out.push(self.channels);
+        out.push(self.bit_depth);
+        out.push(self.reserved);
+    }
+}
+
+impl Decodable for HeaderMetadata {
+    fn decode(cursor: &mut Cursor) -> Result<Self, ProtocolError> {
+        let mut metadata = [0u8; HEADER_METADATA_SIZE];
+        metadata.copy_from_slice(cursor.read_bytes(HEADER_METADATA_SIZE)?);
+        Ok(Self { metadata })
+    }
+}
+
+impl Encodable for HeaderMetadata {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.metadata);
+    }
+}
+
+/// Alternate, variable-length metadata block gated by
+/// [`FLAG_HAS_STRUCTURED_METADATA`]: arbitrary UTF-8 key/value attributes, unlike
+/// [`HeaderMetadata`]'s single fixed-size routing string. Wire format is a `u16` entry
+/// count, then per entry a `u16 key_len`/`u16 val_len` pair followed by that many UTF-8
+/// key then value bytes.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderStructuredMetadata {
+    pub attributes: HashMap<String, String>,
+}
+
+impl HeaderStructuredMetadata {
+    /// Wire size of this block, for [`calculate_header_size`] - not `mem::size_of`,
+    /// since the block has no fixed layout.
+    pub fn encoded_size(&self) -> usize {
+        2 + self
+            .attributes
+            .iter()
+            .map(|(k, v)| 4 + k.len() + v.len())
+            .sum::<usize>()
+    }
+}
+
+impl Decodable for HeaderStructuredMetadata {
+    fn decode(cursor: &mut Cursor) -> Result<Self, ProtocolError> {
+        let count = cursor.read_u16()?;
+        let mut attributes = HashMap::with_capacity(count as usize);
+        for _ in 0..count {
+            let key_len = cursor.read_u16()? as usize;
+            let val_len = cursor.read_u16()? as usize;
+            let key = String::from_utf8_lossy(cursor.read_bytes(key_len)?).into_owned();
+            let val = String::from_utf8_lossy(cursor.read_bytes(val_len)?).into_owned();
+            attributes.insert(key, val);
+        }
+        Ok(Self { attributes })
+    }
+}
+
+impl Encodable for HeaderStructuredMetadata {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&(self.attributes.len() as u16).to_le_bytes());
+        for (key, val) in &self.attributes {
+            out.extend_from_slice(&(key.len() as u16).to_le_bytes());
+            out.extend_from_slice(&(val.len() as u16).to_le_bytes());
+            out.extend_from_slice(key.as_bytes());
+            out.extend_from_slice(val.as_bytes());
+        }
+    }
+}
+
+/// A packet parsed against the v1 layout. Still exposes the raw header/codec-data
+/// structs for callers that want v1-specific detail; [`ParsedPacket`] covers what the
+/// rest of the app needs without caring which version produced it.
+pub struct V1Packet<'a> {
+    pub header: HeaderData,
+    pub packet_type: PacketType,
+    pub metadata: Option<HeaderMetadata>,
+    pub structured_metadata: Option<HeaderStructuredMetadata>,
+    pub codec_data: Option<HeaderCodecData>,
+    pub payload: &'a [u8],
+}
+
+/// Parses `data` against the v1 layout, given `header` (already decoded and
+/// magic-checked by [`super::BinaryProtocol::parse_data`]) and `cursor` (positioned
+/// right after it). Only the v1-specific metadata/codec-data/payload layout is handled
+/// here.
+pub fn parse<'a>(
+    data: &'a [u8],
+    header: HeaderData,
+    cursor: &mut Cursor<'a>,
+) -> Result<V1Packet<'a>, ProtocolError> {
+    if data.len() < header.header_size as usize {
+        return Err(ProtocolError::Truncated {
+            needed: header.header_size as usize,
+            got: data.len(),
+        });
+    }
+
+    let packet_type = PacketType::try_from(header.packet_type)?;
+
+    let mut metadata = None;
+    let mut structured_metadata = None;
+    let mut codec_data = None;
+
+    if header.flags & FLAG_HAS_METADATA != 0 {
+        metadata = Some(HeaderMetadata::decode(cursor)?);
+    }
+
+    if header.flags & FLAG_HAS_STRUCTURED_METADATA != 0 {
+        structured_metadata = Some(HeaderStructuredMetadata::decode(cursor)?);
+    }
+
+    if header.flags & FLAG_HAS_CODEC_DATA != 0 {
+        let decoded = HeaderCodecData::decode(cursor)?;
+        CodecType::try_from(decoded.codec_type)?;
+        codec_data = Some(decoded);
+    }
+
+    let payload_start = header.header_size as usize;
+    let payload = if payload_start <= data.len() {
+        &data[payload_start..]
+    } else {
+        &[]
+    };
+
+    Ok(V1Packet {
+        header,
+        packet_type,
+        metadata,
+        structured_metadata,
+        codec_data,
+        payload,
+    })
+}
+
+impl<'a> ParsedPacket for V1Packet<'a> {
+    fn pts(&self) -> u64 {
+        self.header.pts
+    }
+
+    fn is_keyframe(&self) -> bool {
+        self.header.flags & FLAG_IS_KEYFRAME != 0
+    }
+
+    fn packet_type_name(&self) -> String {
+        self.packet_type.to_string()
+    }
+
+    fn payload(&self) -> &[u8] {
+        self.payload
+    }
+
+    fn codec_info(&self) -> Option<CodecInfo> {
+        let codec_data = self.codec_data.as_ref()?;
+        let codec_type = CodecType::try_from(codec_data.codec_type).ok()?;
+        Some(CodecInfo {
+            codec_name: codec_type.to_string(),
+            is_audio: self.packet_type == PacketType::AudioFrame,
+            width: codec_data.width,
+            height: codec_data.height,
+            sample_rate: codec_data.sample_rate,
+            channels: codec_data.channels,
+            timebase_den: codec_data.timebase_den,
+        })
+    }
+
+    fn structured_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.structured_metadata.as_ref().map(|m| &m.attributes)
+    }
+}
+
+impl std::fmt::Display for PacketType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PacketType::VideoFrame => write!(f, "VIDEO"),
+            PacketType::AudioFrame => write!(f, "AUDIO"),
+            PacketType::Rpc => write!(f, "RPC"),
+            PacketType::MuxedData => write!(f, "MUXED"),
+            PacketType::DecoderData => write!(f, "DECODER"),
+        }
+    }
+}
+
+impl std::fmt::Display for CodecType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodecType::VideoVp8 => write!(f, "VP8"),
+            CodecType::VideoVp9 => write!(f, "VP9"),
+            CodecType::VideoAvc => write!(f, "AVC"),
+            CodecType::VideoHevc => write!(f, "HEVC"),
+            CodecType::VideoAv1 => write!(f, "AV1"),
+            CodecType::AudioOpus => write!(f, "OPUS"),
+            CodecType::AudioAac => write!(f, "AAC"),
+            CodecType::AudioPcm => write!(f, "PCM"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(flags: u32, header_size: u16) -> HeaderData {
+        HeaderData {
+            magic: PROTOCOL_MAGIC,
+            flags,
+            pts: 42,
+            id: 7,
+            version: PROTOCOL_VERSION,
+            header_size,
+            packet_type: PacketType::VideoFrame as u16,
+            reserved: 0,
+        }
+    }
+
+    #[test]
+    fn header_data_round_trips_through_encode_decode() {
+        let original = header(FLAG_IS_KEYFRAME, HEADER_DATA_SIZE as u16);
+
+        let mut bytes = Vec::new();
+        original.encode(&mut bytes);
+
+        let mut cursor = Cursor::new(&bytes);
+        let decoded = HeaderData::decode(&mut cursor).unwrap();
+
+        assert_eq!(decoded.magic, original.magic);
+        assert_eq!(decoded.flags, original.flags);
+        assert_eq!(decoded.pts, original.pts);
+        assert_eq!(decoded.id, original.id);
+        assert_eq!(decoded.version, original.version);
+        assert_eq!(decoded.header_size, original.header_size);
+        assert_eq!(decoded.packet_type, original.packet_type);
+    }
+
+    #[test]
+    fn header_data_decode_rejects_bad_magic() {
+        let mut bad = header(0, HEADER_DATA_SIZE as u16);
+        bad.magic = 0xdead_beef;
+        let mut bytes = Vec::new();
+        bad.encode(&mut bytes);
+
+        let mut cursor = Cursor::new(&bytes);
+        assert!(matches!(
+            HeaderData::decode(&mut cursor),
+            Err(ProtocolError::BadMagic)
+        ));
+    }
+
+    #[test]
+    fn header_data_decode_reports_truncation() {
+        let mut bytes = Vec::new();
+        header(0, HEADER_DATA_SIZE as u16).encode(&mut bytes);
+        bytes.truncate(HEADER_DATA_SIZE - 1);
+
+        let mut cursor = Cursor::new(&bytes);
+        assert!(matches!(
+            HeaderData::decode(&mut cursor),
+            Err(ProtocolError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn packet_type_try_from_rejects_unknown_values() {
+        assert!(matches!(
+            PacketType::try_from(99),
+            Err(ProtocolError::UnknownPacketType(99))
+        ));
+    }
+
+    #[test]
+    fn codec_type_try_from_rejects_unknown_values() {
+        assert!(matches!(
+            CodecType::try_from(200),
+            Err(ProtocolError::UnknownCodec(200))
+        ));
+    }
+
+    #[test]
+    fn parse_reads_a_minimal_video_frame_packet() {
+        let header_size = HEADER_DATA_SIZE as u16;
+        let head = header(FLAG_IS_KEYFRAME, header_size);
+        let mut data = Vec::new();
+        head.encode(&mut data);
+        data.extend_from_slice(&[1, 2, 3, 4]);
+
+        let mut cursor = Cursor::new(&data);
+        let decoded_header = HeaderData::decode(&mut cursor).unwrap();
+        let packet = parse(&data, decoded_header, &mut cursor).unwrap();
+
+        assert_eq!(packet.pts(), 42);
+        assert!(packet.is_keyframe());
+        assert_eq!(packet.payload(), &[1, 2, 3, 4]);
+        assert!(packet.codec_info().is_none());
+    }
+
+    #[test]
+    fn parse_reads_structured_metadata_when_flagged() {
+        let mut metadata = HeaderStructuredMetadata::default();
+        metadata
+            .attributes
+            .insert("rendition".to_string(), "720p".to_string());
+        let header_size = HEADER_DATA_SIZE as u16 + metadata.encoded_size() as u16;
+        let head = header(FLAG_HAS_STRUCTURED_METADATA, header_size);
+
+        let mut data = Vec::new();
+        head.encode(&mut data);
+        metadata.encode(&mut data);
+
+        let mut cursor = Cursor::new(&data);
+        let decoded_header = HeaderData::decode(&mut cursor).unwrap();
+        let packet = parse(&data, decoded_header, &mut cursor).unwrap();
+
+        assert_eq!(
+            packet.structured_metadata().unwrap().get("rendition"),
+            Some(&"720p".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_rejects_a_header_size_longer_than_the_data() {
+        let head = header(0, HEADER_DATA_SIZE as u16 + 100);
+        let mut data = Vec::new();
+        head.encode(&mut data);
+
+        let mut cursor = Cursor::new(&data);
+        let decoded_header = HeaderData::decode(&mut cursor).unwrap();
+        assert!(matches!(
+            parse(&data, decoded_header, &mut cursor),
+            Err(ProtocolError::Truncated { .. })
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+//! Sesame Binary Protocol v2: widens `width`/`height` to `u32` (v1's `u16` tops out at
+//! 65535px, which 8K+ capture already exceeds) and adds an HDR transfer-characteristics
+//! field to the codec data, signaled by [`FLAG_HAS_HDR_DATA`]. Everything else - the
+//! leading [`HeaderData`], the optional [`HeaderMetadata`] - is unchanged from v1, so v2
+//! reuses those types directly rather than redeclaring them.
+
+use super::{CodecInfo, Cursor, Decodable, Encodable, ParsedPacket, ProtocolError};
+use crate::sesame_protocol::v1::{HeaderData, HeaderMetadata};
+
+pub const PROTOCOL_VERSION: u16 = 2;
+
+pub const FLAG_HAS_CODEC_DATA: u32 = 1 << 0;
+pub const FLAG_HAS_METADATA: u32 = 1 << 1;
+pub const FLAG_IS_KEYFRAME: u32 = 1 << 2;
+/// Codec data carries a meaningful `transfer_characteristics` (HDR10/HLG/etc.) rather
+/// than the SDR default. v2-only - v1 has no such field.
+pub const FLAG_HAS_HDR_DATA: u32 = 1 << 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum PacketType {
+    VideoFrame = 1,
+    AudioFrame = 2,
+    Rpc = 3,
+    MuxedData = 4,
+    DecoderData = 5,
+}
+
+impl TryFrom<u16> for PacketType {
+    type Error = ProtocolError;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(PacketType::VideoFrame),
+            2 => Ok(PacketType::AudioFrame),
+            3 => Ok(PacketType::Rpc),
+            4 => Ok(PacketType::MuxedData),
+            5 => Ok(PacketType::DecoderData),
+            other => Err(ProtocolError::UnknownPacketType(other)),
+        }
+    }
+}
+
+impl std::fmt::Display for PacketType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PacketType::VideoFrame => write!(f, "VIDEO"),
+            PacketType::AudioFrame => write!(f, "AUDIO"),
+            PacketType::Rpc => write!(f, "RPC"),
+            PacketType::MuxedData => write!(f, "MUXED"),
+            PacketType::DecoderData => write!(f, "DECODER"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CodecType {
+    VideoVp8 = 1,
+    VideoVp9 = 2,
+    VideoAvc = 3,
+    VideoHevc = 4,
+    VideoAv1 = 5,
+    AudioOpus = 64,
+    AudioAac = 65,
+    AudioPcm = 66,
+}
+
+impl TryFrom<u8> for CodecType {
+    type Error = ProtocolError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(CodecType::VideoVp8),
+            2 => Ok(CodecType::VideoVp9),
+            3 => Ok(CodecType::VideoAvc),
+            4 => Ok(CodecType::VideoHevc),
+            5 => Ok(CodecType::VideoAv1),
+            64 => Ok(CodecType::AudioOpus),
+            65 => Ok(CodecType::AudioAac),
+            66 => Ok(CodecType::AudioPcm),
+            other => Err(ProtocolError::UnknownCodec(other)),
+        }
+    }
+}
+
+impl std::fmt::Display for CodecType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodecType::VideoVp8 => write!(f, "VP8"),
+            CodecType::VideoVp9 => write!(f, "VP9"),
+            CodecType::VideoAvc => write!(f, "AVC"),
+            CodecType::VideoHevc => write!(f, "HEVC"),
+            CodecType::VideoAv1 => write!(f, "AV1"),
+            CodecType::AudioOpus => write!(f, "OPUS"),
+            CodecType::AudioAac => write!(f, "AAC"),
+            CodecType::AudioPcm => write!(f, "PCM"),
+        }
+    }
+}
+
+/// v2's codec data: identical to [`super::v1::HeaderCodecData`] except `width`/`height`
+/// are `u32` and a trailing `transfer_characteristics` replaces v1's final reserved byte.
+#[derive(Debug, Clone)]
+pub struct HeaderCodecData {
+    pub sample_rate: u32,
+    pub timebase_num: u32,
+    pub timebase_den: u32,
+    pub codec_profile: u16,
+    pub codec_level: u16,
+    pub width: u32,
+    pub height: u32,
+    pub codec_type: u8,
+    pub channels: u8,
+    pub bit_depth: u8,
+    /// ISO/IEC 23091-2 transfer characteristics code point; meaningful only when
+    /// `FLAG_HAS_HDR_DATA` is set. 0 (SDR/unspecified) otherwise.
+    pub transfer_characteristics: u8,
+}
+
+impl Decodable for HeaderCodecData {
+    fn decode(cursor: &mut Cursor) -> Result<Self, ProtocolError> {
+        Ok(Self {
+            sample_rate: cursor.read_u32()?,
+            timebase_num: cursor.read_u32()?,
+            timebase_den: cursor.read_u32()?,
+            codec_profile: cursor.read_u16()?,
+            codec_level: cursor.read_u16()?,
+            width: cursor.read_u32()?,
+            height: cursor.read_u32()?,
+            codec_type: cursor.read_u8()?,
+            channels: cursor.read_u8()?,
+            bit_depth: cursor.read_u8()?,
+            transfer_characteristics: cursor.read_u8()?,
+        })
+    }
+}
+
+impl Encodable for HeaderCodecData {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.sample_rate.to_le_bytes());
+        out.extend_from_slice(&self.timebase_num.to_le_bytes());
+        out.extend_from_slice(&self.timebase_den.to_le_bytes());
+        out.extend_from_slice(&self.codec_profile.to_le_bytes());
+        out.extend_from_slice(&self.codec_level.to_le_bytes());
+        out.extend_from_slice(&self.width.to_le_bytes());
+        out.extend_from_slice(&self.height.to_le_bytes());
+        out.push(self.codec_type);
+        out.push(self.channels);
+        out.push(self.bit_depth);
+        out.push(self.transfer_characteristics);
+    }
+}
+
+pub struct V2Packet<'a> {
+    pub header: HeaderData,
+    pub packet_type: PacketType,
+    pub metadata: Option<HeaderMetadata>,
+    pub codec_data: Option<HeaderCodecData>,
+    pub payload: &'a [u8],
+}
+
+/// Parses `data` against the v2 layout, given `header` (already decoded and
+/// magic-checked by [`super::BinaryProtocol::parse_data`]) and `cursor` (positioned
+/// right after it). Like [`super::v1::parse`], only the version-specific layout is
+/// handled here.
+pub fn parse<'a>(
+    data: &'a [u8],
+    header: HeaderData,
+    cursor: &mut Cursor<'a>,
+) -> Result<V2Packet<'a>, ProtocolError> {
+    if data.len() < header.header_size as usize {
+        return Err(ProtocolError::Truncated {
+            needed: header.header_size as usize,
+            got: data.len(),
+        });
+    }
+
+    let packet_type = PacketType::try_from(header.packet_type)?;
+
+    let mut metadata = None;
+    let mut codec_data = None;
+
+    if header.flags & FLAG_HAS_METADATA != 0 {
+        metadata = Some(HeaderMetadata::decode(cursor)?);
+    }
+
+    if header.flags & FLAG_HAS_CODEC_DATA != 0 {
+        let decoded = HeaderCodecData::decode(cursor)?;
+        CodecType::try_from(decoded.codec_type)?;
+        codec_data = Some(decoded);
+    }
+
+    let payload_start = header.header_size as usize;
+    let payload = if payload_start <= data.len() {
+        &data[payload_start..]
+    } else {
+        &[]
+    };
+
+    Ok(V2Packet {
+        header,
+        packet_type,
+        metadata,
+        codec_data,
+        payload,
+    })
+}
+
+impl<'a> ParsedPacket for V2Packet<'a> {
+    fn pts(&self) -> u64 {
+        self.header.pts
+    }
+
+    fn is_keyframe(&self) -> bool {
+        self.header.flags & FLAG_IS_KEYFRAME != 0
+    }
+
+    fn packet_type_name(&self) -> String {
+        self.packet_type.to_string()
+    }
+
+    fn payload(&self) -> &[u8] {
+        self.payload
+    }
+
+    fn codec_info(&self) -> Option<CodecInfo> {
+        let codec_data = self.codec_data.as_ref()?;
+        let codec_type = CodecType::try_from(codec_data.codec_type).ok()?;
+        let width = codec_data.width;
+        let height = codec_data.height;
+        Some(CodecInfo {
+            codec_name: codec_type.to_string(),
+            is_audio: self.packet_type == PacketType::AudioFrame,
+            width: width.min(u16::MAX as u32) as u16,
+            height: height.min(u16::MAX as u32) as u16,
+            sample_rate: codec_data.sample_rate,
+            channels: codec_data.channels,
+            timebase_den: codec_data.timebase_den,
+        })
+    }
+}
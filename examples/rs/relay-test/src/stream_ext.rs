@@ -0,0 +1,75 @@
+//! `futures::Stream` adapters over `moq_lite`'s `TrackConsumer`/`GroupConsumer`, so
+//! callers can compose `StreamExt` combinators (`take_while`, `timeout`, `throttle`,
+//! ...) over a track's groups and frames instead of hand-rolling the
+//! `next_group`/`read_frame` loop inside a `tokio::select!`.
+
+use anyhow::Result;
+use async_stream::try_stream;
+use bytes::Bytes;
+use futures::Stream;
+use moq_lite::{GroupConsumer, TrackConsumer};
+
+/// One frame read from a track, tagged with the sequence of the group it came from -
+/// a caller that mirrors groups onward (e.g. a forwarder) needs this to open a matching
+/// outbound group.
+pub struct Frame {
+    pub group_sequence: u64,
+    pub data: Bytes,
+}
+
+pub trait TrackConsumerExt {
+    /// Every frame across every group on this track, in arrival order.
+    fn frames(self) -> impl Stream<Item = Result<Frame>>;
+
+    /// Every group on this track, each as its own frame stream.
+    fn groups(self) -> impl Stream<Item = Result<GroupStream>>;
+}
+
+impl TrackConsumerExt for TrackConsumer {
+    fn frames(mut self) -> impl Stream<Item = Result<Frame>> {
+        try_stream! {
+            while let Some(mut group) = self.next_group().await? {
+                let group_sequence = group.sequence;
+                while let Some(data) = group.read_frame().await? {
+                    yield Frame { group_sequence, data };
+                }
+            }
+        }
+    }
+
+    fn groups(mut self) -> impl Stream<Item = Result<GroupStream>> {
+        try_stream! {
+            while let Some(group) = self.next_group().await? {
+                yield GroupStream::new(group);
+            }
+        }
+    }
+}
+
+/// One group's frames, in arrival order.
+pub struct GroupStream {
+    sequence: u64,
+    group: GroupConsumer,
+}
+
+impl GroupStream {
+    fn new(group: GroupConsumer) -> Self {
+        Self {
+            sequence: group.sequence,
+            group,
+        }
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn frames(self) -> impl Stream<Item = Result<Bytes>> {
+        let mut group = self.group;
+        try_stream! {
+            while let Some(data) = group.read_frame().await? {
+                yield data;
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Per-track subscribe parameters parsed from `--tracks`' `;key=value` suffixes (e.g.
+//! `"video;priority=1;group_order=desc,audio"`), plus QUIC-style varint encode/decode
+//! helpers so a parsed [`TrackParams`] map can be serialized and round-tripped. No
+//! parameters extension exists on `moq_lite::Track` (just `name`/`priority`) and
+//! nothing in this tree reads these bytes off the wire, so this only demonstrates the
+//! client-side encoding - the way [`crate::catalog`] mirrors a schema with no shared
+//! dependency on the real thing.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+/// One `--tracks` entry's parsed name and `;key=value` parameters, sorted by key
+/// (e.g. `"video;priority=1;group_order=desc"` -> `name: "video"`,
+/// `params: {"group_order": "desc", "priority": "1"}`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrackSpec {
+    pub name: String,
+    pub params: TrackParams,
+}
+
+/// A track's parameters, keyed by name. `BTreeMap` so encoding order is stable
+/// regardless of parse order, which keeps round-trips byte-for-byte reproducible.
+pub type TrackParams = BTreeMap<String, String>;
+
+/// Parses one `--tracks` entry (already split on `,`) into its track name and
+/// `;key=value` parameters. A bare `key` with no `=` is stored with an empty value
+/// rather than rejected, so a typo doesn't take down the whole `--tracks` list.
+pub fn parse_spec(entry: &str) -> TrackSpec {
+    let mut parts = entry.split(';');
+    let name = parts.next().unwrap_or("").trim().to_string();
+
+    let mut params = TrackParams::new();
+    for param in parts {
+        let param = param.trim();
+        if param.is_empty() {
+            continue;
+        }
+        match param.split_once('=') {
+            Some((key, value)) => {
+                params.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            None => {
+                params.insert(param.to_string(), String::new());
+            }
+        }
+    }
+
+    TrackSpec { name, params }
+}
+
+/// Encodes `value` as a QUIC variable-length integer: the top two bits of the first
+/// byte select a 1/2/4/8-byte encoding, and the remaining 6/14/30/62 bits hold the
+/// value, big-endian. Mirrors the length-prefix scheme MoQ Transport's own wire
+/// format uses, even though nothing in this tree decodes it off a real connection.
+pub fn encode_varint(value: u64) -> Vec<u8> {
+    if value < (1 << 6) {
+        vec![value as u8]
+    } else if value < (1 << 14) {
+        (value as u16 | 0x4000).to_be_bytes().to_vec()
+    } else if value < (1 << 30) {
+        (value as u32 | 0x8000_0000).to_be_bytes().to_vec()
+    } else if value < (1 << 62) {
+        (value | 0xC000_0000_0000_0000).to_be_bytes().to_vec()
+    } else {
+        panic!("varint value {value} exceeds the 62-bit QUIC varint range");
+    }
+}
+
+/// Decodes one QUIC variable-length integer from the start of `data`, returning the
+/// value and the number of bytes it consumed.
+pub fn decode_varint(data: &[u8]) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    let len = 1usize << (first >> 6);
+    let bytes = data.get(..len)?;
+
+    let mut value = (bytes[0] & 0x3F) as u64;
+    for &byte in &bytes[1..] {
+        value = (value << 8) | byte as u64;
+    }
+    Some((value, len))
+}
+
+/// Encodes `s` as a varint byte-length prefix followed by its raw UTF-8 bytes.
+pub fn encode_string(s: &str) -> Vec<u8> {
+    let mut out = encode_varint(s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+    out
+}
+
+/// Decodes one length-prefixed string from the start of `data`, returning the string
+/// and the number of bytes it consumed.
+pub fn decode_string(data: &[u8]) -> Option<(String, usize)> {
+    let (len, prefix_len) = decode_varint(data)?;
+    let len = len as usize;
+    let bytes = data.get(prefix_len..prefix_len + len)?;
+    let s = String::from_utf8(bytes.to_vec()).ok()?;
+    Some((s, prefix_len + len))
+}
+
+/// Encodes `params` as a varint entry count followed by varint-length-prefixed
+/// key/value string pairs, in `params`' (sorted) iteration order.
+pub fn encode_params(params: &TrackParams) -> Vec<u8> {
+    let mut out = encode_varint(params.len() as u64);
+    for (key, value) in params {
+        out.extend(encode_string(key));
+        out.extend(encode_string(value));
+    }
+    out
+}
+
+/// Decodes a varint-prefixed parameters map previously written by [`encode_params`].
+pub fn decode_params(data: &[u8]) -> Result<(TrackParams, usize)> {
+    let (count, mut offset) = decode_varint(data).context("truncated params: missing count")?;
+
+    let mut params = TrackParams::new();
+    for _ in 0..count {
+        let (key, key_len) =
+            decode_string(&data[offset..]).context("truncated params: missing key")?;
+        offset += key_len;
+        let (value, value_len) =
+            decode_string(&data[offset..]).context("truncated params: missing value")?;
+        offset += value_len;
+        params.insert(key, value);
+    }
+
+    Ok((params, offset))
+}
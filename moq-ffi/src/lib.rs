@@ -1,13 +1,14 @@
 #![allow(clippy::missing_safety_doc)]
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use std::collections::HashMap;
-use std::ffi::CStr;
-use std::os::raw::c_char;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
 use std::ptr;
 use std::sync::{LazyLock, Mutex};
 use std::time::Duration;
 use tokio::runtime::Runtime;
+use tokio::sync::mpsc;
 use url::Url;
 
 // Import MOQ libraries
@@ -32,6 +33,46 @@ static MEMORY_TRACKER: LazyLock<Mutex<HashMap<usize, usize>>> =
 /// ID counter for all handles
 static ID_COUNTER: LazyLock<Mutex<u64>> = LazyLock::new(|| Mutex::new(1));
 
+thread_local! {
+    /// Underlying cause of the most recent non-`Success` `MoqResult` returned from an
+    /// FFI call made on this thread. Set via `set_last_error`, read via
+    /// `moq_get_last_error`; overwritten by the next failing call on the same thread.
+    static LAST_ERROR: std::cell::RefCell<Option<CString>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Record the underlying cause of an FFI call that is about to return a non-`Success`
+/// `MoqResult`, so `moq_get_last_error` can report more than just the result code
+fn set_last_error(error: impl std::fmt::Display) {
+    let message = CString::new(error.to_string())
+        .unwrap_or_else(|_| CString::new("error message contained a NUL byte").unwrap());
+    LAST_ERROR.with(|cell| *cell.borrow_mut() = Some(message));
+}
+
+/// Classify a `moq_client_connect*` failure into a more specific [`MoqResult`] than a
+/// flat `NetworkError`, by scanning the `Display` of `error` and every link in its
+/// `anyhow` source chain for TLS/DNS keywords. Connection-establishment errors in this
+/// stack (QUIC handshake, rustls, hickory-resolver) don't expose a typed "this was a TLS
+/// problem" variant, so this is necessarily a best-effort text match rather than a
+/// downcast - it only ever widens the bucket the caller sees, never narrows correctness.
+fn classify_connect_error(error: &anyhow::Error) -> MoqResult {
+    for cause in error.chain() {
+        let message = cause.to_string().to_ascii_lowercase();
+        if message.contains("certificate")
+            || message.contains("tls")
+            || message.contains("handshake")
+        {
+            return MoqResult::TlsError;
+        }
+        if message.contains("dns")
+            || message.contains("resolve")
+            || message.contains("no such host")
+        {
+            return MoqResult::DnsError;
+        }
+    }
+    MoqResult::NetworkError
+}
+
 /// Storage for all MOQ handles
 struct HandleStorage {
     clients: HashMap<u64, ClientData>,
@@ -42,6 +83,20 @@ struct HandleStorage {
     track_consumers: HashMap<u64, TrackConsumerData>,
     group_producers: HashMap<u64, GroupProducerData>,
     group_consumers: HashMap<u64, GroupConsumerData>,
+    announced: HashMap<u64, AnnouncedHandleData>,
+    relays: HashMap<u64, RelayData>,
+    /// Upstream broadcast consumers shared across relays so that relaying the same
+    /// `(src_session, name)` pair to several downstream sessions only subscribes once.
+    relay_upstream_cache: HashMap<(u64, String), BroadcastConsumer>,
+    /// Background tasks driving `moq_track_consumer_on_group`, keyed by track id
+    track_group_watchers: HashMap<u64, tokio::task::JoinHandle<()>>,
+    /// Background tasks driving `moq_group_consumer_on_frame`, keyed by group id
+    group_frame_watchers: HashMap<u64, tokio::task::JoinHandle<()>>,
+    cancel_tokens: HashMap<u64, CancelTokenData>,
+    /// Running/finished `moq_publish_mp4` ingest tasks
+    publish_handles: HashMap<u64, PublishMp4Data>,
+    /// Background tasks driving `moq_session_subscribe`, keyed by subscription id
+    subscriptions: HashMap<u64, tokio::task::JoinHandle<()>>,
 }
 
 impl HandleStorage {
@@ -55,13 +110,70 @@ impl HandleStorage {
             track_consumers: HashMap::new(),
             group_producers: HashMap::new(),
             group_consumers: HashMap::new(),
+            announced: HashMap::new(),
+            relays: HashMap::new(),
+            relay_upstream_cache: HashMap::new(),
+            track_group_watchers: HashMap::new(),
+            group_frame_watchers: HashMap::new(),
+            cancel_tokens: HashMap::new(),
+            publish_handles: HashMap::new(),
+            subscriptions: HashMap::new(),
         }
     }
 }
 
+/// Control flags shared between `moq_publish_mp4`'s ingest task and the
+/// `moq_publish_pause`/`moq_publish_stop` calls that steer it
+struct PublishMp4Data {
+    paused: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    stopped: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+/// Backing state for a `MoqCancelToken`, shared between the holder of the handle and
+/// whichever `*_ex` read call is currently selecting on it
+#[allow(dead_code)]
+struct CancelTokenData {
+    notify: std::sync::Arc<tokio::sync::Notify>,
+    cancelled: bool,
+}
+
+/// Bookkeeping for a broadcast relayed from one session's `subscribe_origin` onto
+/// another session's `publish_origin` via `moq_relay_broadcast`
+#[allow(dead_code)]
+struct RelayData {
+    src_session_id: u64,
+    dst_session_id: u64,
+    name: String,
+}
+
 /// Client data
 struct ClientData {
     client: moq_native::Client,
+    transport: MoqTransport,
+    /// Decorrelated-jitter backoff policy set via `moq_client_set_reconnect`; sessions
+    /// created via `moq_client_connect` after this is set are supervised for reconnect
+    reconnect: Option<ReconnectConfig>,
+    /// Callback registered via `moq_client_on_state`, fired on connect/reconnect events
+    state_callback: Option<ClientStateCallback>,
+}
+
+/// Decorrelated-jitter backoff parameters for `moq_client_set_reconnect`
+///
+/// `max_retries == 0` means retry forever. Before each attempt, sleep a random
+/// duration in `[base_ms, delay*3]`, then set `delay = min(max_ms, delay*3)`; `delay`
+/// resets to `base_ms` after any successful reconnect.
+#[derive(Clone, Copy)]
+struct ReconnectConfig {
+    max_retries: u32,
+    base_ms: u64,
+    max_ms: u64,
+}
+
+/// Connection-state callback registered via `moq_client_on_state`
+#[derive(Clone, Copy)]
+struct ClientStateCallback {
+    ctx: usize, // stashed as an address so the struct stays Send across the runtime task
+    callback: extern "C" fn(*mut c_void, MoqConnState, u64),
 }
 
 /// Session data
@@ -72,6 +184,35 @@ struct SessionData {
     session: moq_lite::Session<web_transport_quinn::Session>,
     publish_origin: Option<moq_lite::OriginProducer>,
     subscribe_origin: Option<moq_lite::OriginConsumer>,
+    callbacks: Option<SessionCallbacks>,
+    callback_task_spawned: bool,
+    /// Background task spawned by `moq_session_announced`, cancelled on session
+    /// close/free so it doesn't keep firing into a freed `user_data` pointer
+    announced_task: Option<tokio::task::JoinHandle<()>>,
+    /// Background tasks spawned by `moq_session_set_callbacks` to push
+    /// `on_broadcast_announced` and `on_state_change`/`on_error` events, cancelled
+    /// alongside `announced_task` on clear/close/free so they don't keep a live
+    /// `OriginConsumer` clone (or `session.closed()` future) running into a freed
+    /// `ctx` pointer after "teardown"
+    callbacks_announced_task: Option<tokio::task::JoinHandle<()>>,
+    callbacks_state_task: Option<tokio::task::JoinHandle<()>>,
+    /// Broadcasts registered via `moq_session_publish`, kept around so the
+    /// reconnect supervisor spawned for `moq_client_set_reconnect` can re-publish
+    /// them onto a freshly dialed session after the connection drops
+    published_broadcasts: Vec<(String, BroadcastConsumer)>,
+}
+
+/// Event callbacks registered via `moq_session_set_callbacks`
+///
+/// These fire from a task spawned on `RUNTIME`, never from the caller's thread, so
+/// access to `HANDLES` from inside a callback invocation must go through the shared
+/// mutex like everywhere else in this file.
+#[derive(Clone, Copy)]
+struct SessionCallbacks {
+    ctx: usize, // stashed as an address so the struct stays Send across the runtime task
+    on_state_change: extern "C" fn(*mut c_void, bool),
+    on_error: extern "C" fn(*mut c_void, *const c_char),
+    on_broadcast_announced: extern "C" fn(*mut c_void, *const c_char, bool),
 }
 
 /// Broadcast producer data
@@ -98,6 +239,25 @@ struct TrackProducerData {
     priority: u8,
     producer: TrackProducer,
     groups: Vec<u64>, // Group producer IDs
+    fmp4: Option<Fmp4TrackState>,
+}
+
+/// Group boundary policy for `moq_fmp4_track_push_segment`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoqFmp4GroupBoundary {
+    /// Start a new group for every `moof`+`mdat` segment pushed
+    PerSegment = 0,
+    /// Start a new group only at keyframe-bearing segments
+    PerKeyframe = 1,
+}
+
+/// Per-track state for the fMP4/CMAF ingest helper
+struct Fmp4TrackState {
+    #[allow(dead_code)]
+    boundary: MoqFmp4GroupBoundary,
+    next_sequence: u64,
+    current_group: Option<GroupProducer>,
 }
 
 /// Track consumer data
@@ -140,6 +300,24 @@ pub enum MoqResult {
     TlsError = 3,
     DnsError = 4,
     GeneralError = 5,
+    /// A `*_ex` read call was aborted via its `MoqCancelToken`
+    Cancelled = 6,
+    /// A `*_ex` read call's `MoqReadOptions::timeout_ms` elapsed with no data
+    TimedOut = 7,
+}
+
+/// Connection-state values reported via `moq_client_on_state`
+///
+/// `session_id` passed alongside `Connecting`, and alongside a `Failed` initial
+/// `moq_client_connect` dial, is `0` (no session exists yet); otherwise it identifies
+/// the `MoqSession` being (re)established.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoqConnState {
+    Connecting = 0,
+    Connected = 1,
+    Reconnecting = 2,
+    Failed = 3,
 }
 
 /// Session modes for MOQ connections
@@ -157,6 +335,17 @@ pub struct MoqClientConfig {
     pub bind_addr: *const c_char,
     pub tls_disable_verify: bool,
     pub tls_root_cert_path: *const c_char,
+    pub transport: MoqTransport,
+}
+
+/// Selects the wire transport used by `moq_client_connect*`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoqTransport {
+    /// MoQ over WebTransport (the default, interoperable with browsers)
+    WebTransport = 0,
+    /// MoQ directly over QUIC, for peers that don't negotiate WebTransport
+    RawQuic = 1,
 }
 
 /// Opaque handle for the MOQ client
@@ -183,6 +372,28 @@ pub struct MoqBroadcastConsumer {
     pub id: u64,
 }
 
+/// Opaque handle for an active `moq_relay_broadcast` fan-out
+#[repr(C)]
+pub struct MoqRelayHandle {
+    pub id: u64,
+}
+
+/// Opaque handle for a cancellation token, used to abort a blocked `*_ex` read call
+#[repr(C)]
+pub struct MoqCancelToken {
+    pub id: u64,
+}
+
+/// Options for `moq_track_consumer_next_group_ex` / `moq_group_consumer_read_frame_ex`
+///
+/// `timeout_ms == 0` means wait indefinitely (subject only to `cancel_token`, if set).
+/// `cancel_token` may be null to disable cancellation for that call.
+#[repr(C)]
+pub struct MoqReadOptions {
+    pub timeout_ms: u64,
+    pub cancel_token: *mut MoqCancelToken,
+}
+
 /// Opaque handle for a MOQ track producer
 #[repr(C)]
 pub struct MoqTrackProducer {
@@ -207,6 +418,12 @@ pub struct MoqGroupConsumer {
     pub id: u64,
 }
 
+/// Opaque handle for a subscription started by `moq_session_subscribe`
+#[repr(C)]
+pub struct MoqSubscription {
+    pub id: u64,
+}
+
 /// Track information
 #[repr(C)]
 pub struct MoqTrack {
@@ -214,6 +431,35 @@ pub struct MoqTrack {
     pub priority: u8,
 }
 
+/// Opaque handle for an announcement-discovery stream
+#[repr(C)]
+pub struct MoqAnnouncedHandle {
+    pub id: u64,
+}
+
+/// Opaque handle for a `moq_publish_mp4` ingest task
+#[repr(C)]
+pub struct MoqPublishHandle {
+    pub id: u64,
+}
+
+/// Progress/outcome events reported by `moq_publish_mp4`'s callback
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoqMp4Event {
+    /// One `moof`+`mdat` fragment was parsed and published as a group; `message` is null
+    FragmentPublished = 0,
+    /// Ingest reached end of file and all tracks were closed; `message` is null
+    Finished = 1,
+    /// Ingest stopped because of a parse or I/O error; `message` describes it
+    Error = 2,
+}
+
+/// One ANNOUNCE/UNANNOUNCE event buffered for an announcement-discovery handle
+struct AnnouncedHandleData {
+    receiver: mpsc::Receiver<(String, bool)>,
+}
+
 /// Generate a new unique ID
 fn next_id() -> u64 {
     let mut counter = ID_COUNTER.lock().unwrap();
@@ -257,7 +503,10 @@ pub unsafe extern "C" fn moq_client_new(
     } else {
         match CStr::from_ptr(config.bind_addr).to_str() {
             Ok(addr) => addr.to_string(),
-            Err(_) => return MoqResult::InvalidArgument,
+            Err(e) => {
+                set_last_error(e);
+                return MoqResult::InvalidArgument;
+            }
         }
     };
 
@@ -266,7 +515,10 @@ pub unsafe extern "C" fn moq_client_new(
     } else {
         match CStr::from_ptr(config.tls_root_cert_path).to_str() {
             Ok(path) => Some(path.to_string()),
-            Err(_) => return MoqResult::InvalidArgument,
+            Err(e) => {
+                set_last_error(e);
+                return MoqResult::InvalidArgument;
+            }
         }
     };
 
@@ -287,11 +539,19 @@ pub unsafe extern "C" fn moq_client_new(
     // Initialize the client
     let client = match RUNTIME.block_on(async { client_config.init() }) {
         Ok(client) => client,
-        Err(_) => return MoqResult::GeneralError,
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::GeneralError;
+        }
     };
 
     let client_id = next_id();
-    let client_data = ClientData { client };
+    let client_data = ClientData {
+        client,
+        transport: config.transport,
+        reconnect: None,
+        state_callback: None,
+    };
 
     // Store the client data
     {
@@ -306,6 +566,81 @@ pub unsafe extern "C" fn moq_client_new(
     MoqResult::Success
 }
 
+/// Opt a client into automatic reconnect for sessions it creates via `moq_client_connect`
+///
+/// Once set, a session created afterwards is supervised: if the underlying QUIC
+/// connection drops, it is transparently redialed using classic decorrelated-jitter
+/// backoff (see [`ReconnectConfig`]) and any broadcasts published on it via
+/// `moq_session_publish` are re-published on the new connection. Pass `max_retries = 0`
+/// to retry forever. Only affects sessions connected *after* this call; existing
+/// sessions are not retroactively supervised.
+#[no_mangle]
+pub unsafe extern "C" fn moq_client_set_reconnect(
+    client: *mut MoqClient,
+    max_retries: u32,
+    base_ms: u64,
+    max_ms: u64,
+) -> MoqResult {
+    if client.is_null() || base_ms == 0 || max_ms < base_ms {
+        return MoqResult::InvalidArgument;
+    }
+
+    let client = &*client;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.clients.get_mut(&client.id) {
+        Some(client_data) => {
+            client_data.reconnect = Some(ReconnectConfig {
+                max_retries,
+                base_ms,
+                max_ms,
+            });
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Register a callback for connection-state changes (connecting/connected/reconnecting/failed)
+///
+/// Fired for the initial `moq_client_connect` dial as well as for every reconnect
+/// attempt driven by `moq_client_set_reconnect`. Calling this again replaces the
+/// previously registered callback.
+#[no_mangle]
+pub unsafe extern "C" fn moq_client_on_state(
+    client: *mut MoqClient,
+    callback: extern "C" fn(*mut c_void, MoqConnState, u64),
+    user_data: *mut c_void,
+) -> MoqResult {
+    if client.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let client = &*client;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.clients.get_mut(&client.id) {
+        Some(client_data) => {
+            client_data.state_callback = Some(ClientStateCallback {
+                ctx: user_data as usize,
+                callback,
+            });
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Rewrite a relay URL's scheme to match the client's selected transport
+///
+/// `moq_native::Client::connect` itself dispatches on URL scheme, so until this crate
+/// wires in a dedicated raw-QUIC session type, `MoqTransport::RawQuic` is threaded
+/// through by requesting the `moq` scheme instead of `https`/WebTransport's scheme.
+fn apply_transport(mut url: Url, transport: MoqTransport) -> Url {
+    if transport == MoqTransport::RawQuic {
+        let _ = url.set_scheme("moq");
+    }
+    url
+}
+
 /// Connect to a MOQ server
 #[no_mangle]
 pub unsafe extern "C" fn moq_client_connect(
@@ -320,18 +655,29 @@ pub unsafe extern "C" fn moq_client_connect(
     let client = &*client;
     let url_str = match CStr::from_ptr(url).to_str() {
         Ok(url) => url,
-        Err(_) => return MoqResult::InvalidArgument,
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
     };
 
     let url = match Url::parse(url_str) {
         Ok(url) => url,
-        Err(_) => return MoqResult::InvalidArgument,
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
     };
 
     // Get the client and establish connection
-    let (session_data, session_created) = {
+    let (session_data, session_created, state_cb) = {
         let mut handles = HANDLES.lock().unwrap();
         if let Some(client_data) = handles.clients.get_mut(&client.id) {
+            let url = apply_transport(url.clone(), client_data.transport);
+            let state_cb = client_data.state_callback;
+            if let Some(cb) = state_cb {
+                (cb.callback)(cb.ctx as *mut c_void, MoqConnState::Connecting, 0);
+            }
             match RUNTIME.block_on(async {
                 let connection = client_data.client.connect(url.clone()).await?;
 
@@ -350,10 +696,23 @@ pub unsafe extern "C" fn moq_client_connect(
                         session,
                         publish_origin: Some(publish_origin),
                         subscribe_origin: None,
+                        callbacks: None,
+                        callback_task_spawned: false,
+                        announced_task: None,
+                        callbacks_announced_task: None,
+                        callbacks_state_task: None,
+                        published_broadcasts: Vec::new(),
                     };
-                    (session_data, true)
+                    (session_data, true, state_cb)
+                }
+                Err(e) => {
+                    if let Some(cb) = state_cb {
+                        (cb.callback)(cb.ctx as *mut c_void, MoqConnState::Failed, 0);
+                    }
+                    let result = classify_connect_error(&e);
+                    set_last_error(e);
+                    return result;
                 }
-                Err(_) => return MoqResult::NetworkError,
             }
         } else {
             return MoqResult::InvalidArgument;
@@ -362,12 +721,25 @@ pub unsafe extern "C" fn moq_client_connect(
 
     let session_id = next_id();
 
-    // Store the session data
-    {
+    // Store the session data, then wire up the reconnect supervisor if the client
+    // opted in via moq_client_set_reconnect before this call
+    let reconnect_enabled = {
         let mut handles = HANDLES.lock().unwrap();
         if session_created {
             handles.sessions.insert(session_id, session_data);
         }
+        handles
+            .clients
+            .get(&client.id)
+            .map(|c| c.reconnect.is_some())
+            .unwrap_or(false)
+    };
+
+    if let Some(cb) = state_cb {
+        (cb.callback)(cb.ctx as *mut c_void, MoqConnState::Connected, session_id);
+    }
+    if reconnect_enabled {
+        spawn_reconnect_supervisor(client.id, session_id);
     }
 
     // Create and return the session handle
@@ -392,18 +764,25 @@ pub unsafe extern "C" fn moq_client_connect_with_mode(
     let client = &*client;
     let url_str = match CStr::from_ptr(url).to_str() {
         Ok(url) => url,
-        Err(_) => return MoqResult::InvalidArgument,
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
     };
 
     let url = match Url::parse(url_str) {
         Ok(url) => url,
-        Err(_) => return MoqResult::InvalidArgument,
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
     };
 
     // Get the client and establish connection
     let (session_data, session_created) = {
         let mut handles = HANDLES.lock().unwrap();
         if let Some(client_data) = handles.clients.get_mut(&client.id) {
+            let url = apply_transport(url.clone(), client_data.transport);
             match RUNTIME.block_on(async {
                 let connection = client_data.client.connect(url.clone()).await?;
 
@@ -455,10 +834,20 @@ pub unsafe extern "C" fn moq_client_connect_with_mode(
                         session,
                         publish_origin,
                         subscribe_origin,
+                        callbacks: None,
+                        callback_task_spawned: false,
+                        announced_task: None,
+                        callbacks_announced_task: None,
+                        callbacks_state_task: None,
+                        published_broadcasts: Vec::new(),
                     };
                     (session_data, true)
                 }
-                Err(_) => return MoqResult::NetworkError,
+                Err(e) => {
+                    let result = classify_connect_error(&e);
+                    set_last_error(e);
+                    return result;
+                }
             }
         } else {
             return MoqResult::InvalidArgument;
@@ -482,6 +871,139 @@ pub unsafe extern "C" fn moq_client_connect_with_mode(
     MoqResult::Success
 }
 
+/// Fire a client's registered `moq_client_on_state` callback, if any
+fn notify_client_state(client_id: u64, state: MoqConnState, session_id: u64) {
+    let handles = HANDLES.lock().unwrap();
+    if let Some(cb) = handles
+        .clients
+        .get(&client_id)
+        .and_then(|c| c.state_callback)
+    {
+        (cb.callback)(cb.ctx as *mut c_void, state, session_id);
+    }
+}
+
+/// Background task backing `moq_client_set_reconnect` for a session created via
+/// `moq_client_connect`
+///
+/// Waits for the session to close, then redials with decorrelated-jitter backoff,
+/// swapping the live `session`/`publish_origin` inside `HANDLES` in place (so the
+/// caller's original `MoqSession` handle id keeps resolving) and re-publishing
+/// whatever broadcasts had been registered via `moq_session_publish`. Stops silently
+/// once the client or session handle is freed, once reconnect is unset, or once
+/// `max_retries` is exhausted (after firing `MoqConnState::Failed`).
+///
+/// Note: `MoqTrackConsumer`/`MoqGroupConsumer` handles obtained from the broken
+/// session are not revived - subscribers must call `moq_session_consume` again after
+/// observing `MoqConnState::Connected`.
+fn spawn_reconnect_supervisor(client_id: u64, session_id: u64) {
+    RUNTIME.spawn(async move {
+        loop {
+            let moq_session = {
+                let handles = HANDLES.lock().unwrap();
+                match handles.sessions.get(&session_id) {
+                    Some(session_data) => session_data.session.clone(),
+                    None => return,
+                }
+            };
+            let _ = moq_session.closed().await;
+
+            let (reconnect, url_str, published) = {
+                let handles = HANDLES.lock().unwrap();
+                let reconnect = match handles.clients.get(&client_id).and_then(|c| c.reconnect) {
+                    Some(reconnect) => reconnect,
+                    None => return,
+                };
+                match handles.sessions.get(&session_id) {
+                    Some(session_data) => (
+                        reconnect,
+                        session_data.url.clone(),
+                        session_data.published_broadcasts.clone(),
+                    ),
+                    None => return,
+                }
+            };
+
+            notify_client_state(client_id, MoqConnState::Reconnecting, session_id);
+
+            let url = match Url::parse(&url_str) {
+                Ok(url) => url,
+                Err(_) => {
+                    notify_client_state(client_id, MoqConnState::Failed, session_id);
+                    return;
+                }
+            };
+
+            let mut delay = reconnect.base_ms;
+            let mut attempt: u32 = 0;
+            let mut reconnected = None;
+            while reconnect.max_retries == 0 || attempt < reconnect.max_retries {
+                attempt += 1;
+
+                let wait_ms = {
+                    use rand::Rng;
+                    rand::thread_rng().gen_range(reconnect.base_ms..=delay * 3)
+                };
+                tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+                delay = (delay * 3).min(reconnect.max_ms);
+
+                let (client, transport) = {
+                    let handles = HANDLES.lock().unwrap();
+                    match handles.clients.get(&client_id) {
+                        Some(client_data) => (client_data.client.clone(), client_data.transport),
+                        None => return, // client was freed; nothing left to supervise
+                    }
+                };
+                let dial_url = apply_transport(url.clone(), transport);
+
+                let dial_result: anyhow::Result<_> = async {
+                    let connection = client.connect(dial_url).await?;
+                    let publish_origin = moq_lite::Origin::produce();
+                    let session =
+                        moq_lite::Session::connect(connection, Some(publish_origin.consumer), None)
+                            .await?;
+                    Ok((session, publish_origin.producer))
+                }
+                .await;
+
+                match dial_result {
+                    Ok(result) => {
+                        reconnected = Some(result);
+                        break;
+                    }
+                    Err(_) => continue,
+                }
+            }
+
+            let (new_session, new_publish_origin) = match reconnected {
+                Some(result) => result,
+                None => {
+                    notify_client_state(client_id, MoqConnState::Failed, session_id);
+                    return;
+                }
+            };
+
+            let mut new_publish_origin = new_publish_origin;
+            for (name, consumer) in &published {
+                new_publish_origin.publish_broadcast(name, consumer.clone());
+            }
+
+            {
+                let mut handles = HANDLES.lock().unwrap();
+                if let Some(session_data) = handles.sessions.get_mut(&session_id) {
+                    session_data.session = new_session;
+                    session_data.publish_origin = Some(new_publish_origin);
+                } else {
+                    // Handle was freed while we were redialing; drop the new session.
+                    return;
+                }
+            }
+
+            notify_client_state(client_id, MoqConnState::Connected, session_id);
+        }
+    });
+}
+
 /// Free a MOQ client handle
 #[no_mangle]
 pub unsafe extern "C" fn moq_client_free(client: *mut MoqClient) {
@@ -502,7 +1024,17 @@ pub unsafe extern "C" fn moq_session_free(session: *mut MoqSession) {
 
         // Remove from storage
         let mut handles = HANDLES.lock().unwrap();
-        handles.sessions.remove(&session.id);
+        if let Some(session_data) = handles.sessions.remove(&session.id) {
+            if let Some(task) = session_data.announced_task {
+                task.abort();
+            }
+            if let Some(task) = session_data.callbacks_announced_task {
+                task.abort();
+            }
+            if let Some(task) = session_data.callbacks_state_task {
+                task.abort();
+            }
+        }
     }
 }
 
@@ -530,13 +1062,92 @@ pub unsafe extern "C" fn moq_session_close(session: *mut MoqSession) -> MoqResul
     let session = &*session;
     let mut handles = HANDLES.lock().unwrap();
 
-    if handles.sessions.remove(&session.id).is_some() {
+    if let Some(session_data) = handles.sessions.remove(&session.id) {
+        if let Some(task) = session_data.announced_task {
+            task.abort();
+        }
+        if let Some(task) = session_data.callbacks_announced_task {
+            task.abort();
+        }
+        if let Some(task) = session_data.callbacks_state_task {
+            task.abort();
+        }
         MoqResult::Success
     } else {
         MoqResult::InvalidArgument
     }
 }
 
+/// Close a MOQ session with an explicit application error code and reason
+///
+/// Unlike `moq_session_close`, this actually drives the MoQ/WebTransport close
+/// handshake: it clones the session, sends `moq_lite::Error::App(code)` to the peer,
+/// and blocks (on `RUNTIME`, with a bounded timeout) until the close is confirmed
+/// before dropping the handle. `reason` is logged locally for diagnostics; the
+/// underlying `Error::App` variant in this version of moq-lite only carries the
+/// numeric code over the wire, so the string itself is not sent to the peer.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_close_with(
+    session: *mut MoqSession,
+    code: u32,
+    reason: *const c_char,
+) -> MoqResult {
+    if session.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+    let reason_str = if reason.is_null() {
+        String::new()
+    } else {
+        match CStr::from_ptr(reason).to_str() {
+            Ok(reason) => reason.to_string(),
+            Err(e) => {
+                set_last_error(e);
+                return MoqResult::InvalidArgument;
+            }
+        }
+    };
+
+    let moq_session = {
+        let handles = HANDLES.lock().unwrap();
+        match handles.sessions.get(&session.id) {
+            Some(session_data) => session_data.session.clone(),
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    tracing::info!(code, reason = %reason_str, "closing session with application error");
+
+    let result = RUNTIME.block_on(async {
+        moq_session.clone().close(moq_lite::Error::App(code));
+        tokio::time::timeout(Duration::from_secs(2), moq_session.closed()).await
+    });
+
+    // Remove the handle regardless of whether the peer confirmed in time; the local
+    // side has already sent its close and has nothing more to do with this session.
+    let mut handles = HANDLES.lock().unwrap();
+    if let Some(session_data) = handles.sessions.remove(&session.id) {
+        if let Some(task) = session_data.announced_task {
+            task.abort();
+        }
+        if let Some(task) = session_data.callbacks_announced_task {
+            task.abort();
+        }
+        if let Some(task) = session_data.callbacks_state_task {
+            task.abort();
+        }
+    }
+
+    match result {
+        Ok(_) => MoqResult::Success,
+        Err(elapsed) => {
+            set_last_error(elapsed);
+            MoqResult::NetworkError
+        }
+    }
+}
+
 /// Check if a MOQ session is still alive (blocking call)
 /// Returns true if session is alive, false if closed/terminated
 /// This is a blocking call that will check the session state
@@ -567,44 +1178,266 @@ pub unsafe extern "C" fn moq_session_is_alive(session: *const MoqSession) -> boo
     }
 }
 
-/// Create a new broadcast producer
+/// Register push-based event callbacks for a session
+///
+/// Spawns two long-lived tasks on `RUNTIME` (once per session): one awaits
+/// `session.closed()`, the other the subscribe origin's announced stream (only when the
+/// session has a subscribe origin). They invoke the stored function pointers as events
+/// happen instead of requiring the caller to poll via `moq_session_is_alive`. Calling
+/// this again before `moq_session_clear_callbacks` just replaces the stored pointers;
+/// it does not spawn more tasks.
 #[no_mangle]
-pub extern "C" fn moq_broadcast_producer_new(
-    producer_out: *mut *mut MoqBroadcastProducer,
+pub unsafe extern "C" fn moq_session_set_callbacks(
+    session: *mut MoqSession,
+    ctx: *mut c_void,
+    on_state_change: extern "C" fn(*mut c_void, bool),
+    on_error: extern "C" fn(*mut c_void, *const c_char),
+    on_broadcast_announced: extern "C" fn(*mut c_void, *const c_char, bool),
 ) -> MoqResult {
-    if producer_out.is_null() {
+    if session.is_null() {
         return MoqResult::InvalidArgument;
     }
 
-    let producer_id = next_id();
-    let broadcast_produce = moq_lite::Broadcast::produce();
-    let producer_data = BroadcastProducerData {
-        name: String::new(),
-        broadcast: broadcast_produce,
-        tracks: Vec::new(),
+    let session = &*session;
+    let callbacks = SessionCallbacks {
+        ctx: ctx as usize,
+        on_state_change,
+        on_error,
+        on_broadcast_announced,
     };
 
-    // Store the producer data
-    {
+    let needs_spawn = {
         let mut handles = HANDLES.lock().unwrap();
-        handles
-            .broadcast_producers
-            .insert(producer_id, producer_data);
-    }
+        match handles.sessions.get_mut(&session.id) {
+            Some(session_data) => {
+                session_data.callbacks = Some(callbacks);
+                let needs_spawn = !session_data.callback_task_spawned;
+                session_data.callback_task_spawned = true;
+                needs_spawn
+            }
+            None => return MoqResult::InvalidArgument,
+        }
+    };
 
-    // Create and return the producer handle
-    let boxed_producer = Box::new(MoqBroadcastProducer { id: producer_id });
-    unsafe {
-        *producer_out = Box::into_raw(boxed_producer);
-    }
+    if needs_spawn {
+        let session_id = session.id;
 
-    MoqResult::Success
-}
+        // Push broadcast announcements to the callback as they arrive.
+        let origin_consumer = {
+            let handles = HANDLES.lock().unwrap();
+            handles
+                .sessions
+                .get(&session_id)
+                .and_then(|session_data| session_data.subscribe_origin.clone())
+        };
+        let callbacks_announced_task = origin_consumer.map(|mut origin_consumer| {
+            RUNTIME.spawn(async move {
+                while let Some((path, broadcast)) = origin_consumer.announced().await {
+                    // Clone the callbacks out and drop the lock before invoking one:
+                    // `HANDLES` isn't reentrant, and a push-based callback calling back
+                    // into another `moq_*` function is the expected use case.
+                    let callbacks = {
+                        let handles = HANDLES.lock().unwrap();
+                        handles
+                            .sessions
+                            .get(&session_id)
+                            .and_then(|session_data| session_data.callbacks)
+                    };
+                    if let Some(callbacks) = callbacks {
+                        if let Ok(name) = CString::new(path.to_string()) {
+                            (callbacks.on_broadcast_announced)(
+                                callbacks.ctx as *mut c_void,
+                                name.as_ptr(),
+                                broadcast.is_some(),
+                            );
+                        }
+                    }
+                }
+            })
+        });
 
-/// Create a track producer within a broadcast
-#[no_mangle]
-pub unsafe extern "C" fn moq_broadcast_producer_create_track(
-    producer: *mut MoqBroadcastProducer,
+        // Notify state-change/error callbacks when the session goes away instead of
+        // making callers poll moq_session_is_alive on a timeout.
+        let moq_session = {
+            let handles = HANDLES.lock().unwrap();
+            handles
+                .sessions
+                .get(&session_id)
+                .map(|sd| sd.session.clone())
+        };
+        let callbacks_state_task = moq_session.map(|moq_session| {
+            RUNTIME.spawn(async move {
+                let result = moq_session.closed().await;
+
+                // Same reentrancy concern as the announced-broadcast task above.
+                let callbacks = {
+                    let handles = HANDLES.lock().unwrap();
+                    handles
+                        .sessions
+                        .get(&session_id)
+                        .and_then(|session_data| session_data.callbacks)
+                };
+                if let Some(callbacks) = callbacks {
+                    (callbacks.on_state_change)(callbacks.ctx as *mut c_void, false);
+                    if let Err(e) = result {
+                        if let Ok(message) = CString::new(e.to_string()) {
+                            (callbacks.on_error)(callbacks.ctx as *mut c_void, message.as_ptr());
+                        }
+                    }
+                }
+            })
+        });
+
+        let mut handles = HANDLES.lock().unwrap();
+        if let Some(session_data) = handles.sessions.get_mut(&session_id) {
+            session_data.callbacks_announced_task = callbacks_announced_task;
+            session_data.callbacks_state_task = callbacks_state_task;
+        }
+    }
+
+    MoqResult::Success
+}
+
+/// Clear previously registered callbacks; call this before `moq_session_free` so the
+/// background callback task stops touching the session once it is torn down.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_clear_callbacks(session: *mut MoqSession) -> MoqResult {
+    if session.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.sessions.get_mut(&session.id) {
+        Some(session_data) => {
+            session_data.callbacks = None;
+            session_data.callback_task_spawned = false;
+            if let Some(task) = session_data.callbacks_announced_task.take() {
+                task.abort();
+            }
+            if let Some(task) = session_data.callbacks_state_task.take() {
+                task.abort();
+            }
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Push every broadcast the session's subscribe origin announces (or un-announces)
+/// through `callback`, for as long as the session stays open
+///
+/// Spawns a single long-lived task on `RUNTIME`, stored on `SessionData` so
+/// `moq_session_close`/`moq_session_free` cancel it automatically; calling this again
+/// first cancels the previous watch. Unlike `moq_session_announced_open` (which scopes
+/// to a path prefix and requires polling via `moq_announced_next`), this covers the
+/// whole session and pushes events as they happen.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_announced(
+    session: *mut MoqSession,
+    callback: extern "C" fn(*const c_char, bool, *mut c_void),
+    user_data: *mut c_void,
+) -> MoqResult {
+    if session.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+    let session_id = session.id;
+    let user_data_addr = user_data as usize;
+
+    let origin_consumer = {
+        let mut handles = HANDLES.lock().unwrap();
+        let session_data = match handles.sessions.get_mut(&session_id) {
+            Some(session_data) => session_data,
+            None => return MoqResult::InvalidArgument,
+        };
+        if let Some(old_task) = session_data.announced_task.take() {
+            old_task.abort();
+        }
+        match session_data.subscribe_origin.clone() {
+            Some(origin_consumer) => origin_consumer,
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let handle = RUNTIME.spawn(async move {
+        let mut origin_consumer = origin_consumer;
+        while let Some((path, broadcast)) = origin_consumer.announced().await {
+            if let Ok(name) = CString::new(path.to_string()) {
+                let user_data_ptr = user_data_addr as *mut c_void;
+                callback(name.as_ptr(), broadcast.is_some(), user_data_ptr);
+            }
+        }
+    });
+
+    let mut handles = HANDLES.lock().unwrap();
+    if let Some(session_data) = handles.sessions.get_mut(&session_id) {
+        session_data.announced_task = Some(handle);
+    }
+
+    MoqResult::Success
+}
+
+/// Stop a watch started by `moq_session_announced`
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_announced_stop(session: *mut MoqSession) -> MoqResult {
+    if session.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.sessions.get_mut(&session.id) {
+        Some(session_data) => match session_data.announced_task.take() {
+            Some(task) => {
+                task.abort();
+                MoqResult::Success
+            }
+            None => MoqResult::InvalidArgument,
+        },
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Create a new broadcast producer
+#[no_mangle]
+pub extern "C" fn moq_broadcast_producer_new(
+    producer_out: *mut *mut MoqBroadcastProducer,
+) -> MoqResult {
+    if producer_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let producer_id = next_id();
+    let broadcast_produce = moq_lite::Broadcast::produce();
+    let producer_data = BroadcastProducerData {
+        name: String::new(),
+        broadcast: broadcast_produce,
+        tracks: Vec::new(),
+    };
+
+    // Store the producer data
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles
+            .broadcast_producers
+            .insert(producer_id, producer_data);
+    }
+
+    // Create and return the producer handle
+    let boxed_producer = Box::new(MoqBroadcastProducer { id: producer_id });
+    unsafe {
+        *producer_out = Box::into_raw(boxed_producer);
+    }
+
+    MoqResult::Success
+}
+
+/// Create a track producer within a broadcast
+#[no_mangle]
+pub unsafe extern "C" fn moq_broadcast_producer_create_track(
+    producer: *mut MoqBroadcastProducer,
     track: *const MoqTrack,
     track_out: *mut *mut MoqTrackProducer,
 ) -> MoqResult {
@@ -620,7 +1453,10 @@ pub unsafe extern "C" fn moq_broadcast_producer_create_track(
     } else {
         match CStr::from_ptr(track_info.name).to_str() {
             Ok(name) => name.to_string(),
-            Err(_) => return MoqResult::InvalidArgument,
+            Err(e) => {
+                set_last_error(e);
+                return MoqResult::InvalidArgument;
+            }
         }
     };
 
@@ -658,7 +1494,10 @@ pub unsafe extern "C" fn moq_broadcast_producer_create_track(
 
             match track_result {
                 Ok(producer) => producer,
-                Err(_) => return MoqResult::InvalidArgument,
+                Err(e) => {
+                    set_last_error(e);
+                    return MoqResult::InvalidArgument;
+                }
             }
         } else {
             return MoqResult::InvalidArgument;
@@ -671,6 +1510,7 @@ pub unsafe extern "C" fn moq_broadcast_producer_create_track(
         priority: track_info.priority,
         producer: track_producer,
         groups: Vec::new(),
+        fmp4: None,
     };
 
     // Store the track data and update the broadcast
@@ -692,6 +1532,438 @@ pub unsafe extern "C" fn moq_broadcast_producer_create_track(
     MoqResult::Success
 }
 
+/// Find the first top-level ISOBMFF box of type `kind` in `data`, returning its
+/// payload (the bytes after the 8-byte size+type header)
+fn fmp4_find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut offset = 0usize;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().ok()?) as usize;
+        let box_type = &data[offset + 4..offset + 8];
+        if size < 8 || offset + size > data.len() {
+            break;
+        }
+        if box_type == kind {
+            return Some(&data[offset + 8..offset + size]);
+        }
+        offset += size;
+    }
+    None
+}
+
+/// Extract the `tfdt` base media decode time out of a `moof` payload, used only to
+/// validate that a pushed segment is a well-formed fragment
+fn fmp4_tfdt_time(moof_payload: &[u8]) -> Option<u64> {
+    let traf = fmp4_find_box(moof_payload, b"traf")?;
+    let tfdt = fmp4_find_box(traf, b"tfdt")?;
+    if tfdt.is_empty() {
+        return None;
+    }
+    if tfdt[0] == 1 {
+        Some(u64::from_be_bytes(tfdt.get(4..12)?.try_into().ok()?))
+    } else {
+        Some(u32::from_be_bytes(tfdt.get(4..8)?.try_into().ok()?) as u64)
+    }
+}
+
+/// Add an fMP4/CMAF-ingesting track: the init segment is stored as the sole frame of
+/// group 0, after which `moq_fmp4_track_push_segment` starts one group per pushed
+/// `moof`+`mdat` segment according to `boundary`, so callers don't have to manage
+/// group sequence numbers by hand for every CMAF chunk
+#[no_mangle]
+pub unsafe extern "C" fn moq_broadcast_producer_add_fmp4_track(
+    producer: *mut MoqBroadcastProducer,
+    track: *const MoqTrack,
+    boundary: MoqFmp4GroupBoundary,
+    init_segment: *const u8,
+    init_len: usize,
+    track_out: *mut *mut MoqTrackProducer,
+) -> MoqResult {
+    if producer.is_null() || track.is_null() || track_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+    if init_segment.is_null() && init_len > 0 {
+        return MoqResult::InvalidArgument;
+    }
+
+    let mut result = MoqResult::GeneralError;
+    let mut new_track_out: *mut MoqTrackProducer = ptr::null_mut();
+    if moq_broadcast_producer_create_track(producer, track, &mut new_track_out)
+        != MoqResult::Success
+    {
+        return MoqResult::InvalidArgument;
+    }
+
+    let init_bytes = if init_len == 0 {
+        Vec::new()
+    } else {
+        std::slice::from_raw_parts(init_segment, init_len).to_vec()
+    };
+
+    {
+        let track = &*new_track_out;
+        let mut handles = HANDLES.lock().unwrap();
+        if let Some(track_data) = handles.track_producers.get_mut(&track.id) {
+            let group_info = moq_lite::Group { sequence: 0 };
+            if let Some(mut group) = track_data.producer.create_group(group_info) {
+                group.write_frame(init_bytes);
+                group.close();
+                track_data.fmp4 = Some(Fmp4TrackState {
+                    boundary,
+                    next_sequence: 1,
+                    current_group: None,
+                });
+                result = MoqResult::Success;
+            }
+        }
+    }
+
+    if result == MoqResult::Success {
+        *track_out = new_track_out;
+    } else {
+        let _ = Box::from_raw(new_track_out);
+    }
+
+    result
+}
+
+/// Push one `moof`+`mdat` CMAF segment onto a track created with
+/// `moq_broadcast_producer_add_fmp4_track`
+///
+/// CMAF segments are produced GOP-aligned in practice, so a new segment boundary is
+/// also a keyframe boundary under both `MoqFmp4GroupBoundary` policies here; the enum
+/// is kept so a future, more precise `trun` sample-flag parse has somewhere to plug in.
+#[no_mangle]
+pub unsafe extern "C" fn moq_fmp4_track_push_segment(
+    track: *mut MoqTrackProducer,
+    data: *const u8,
+    data_len: usize,
+) -> MoqResult {
+    if track.is_null() || (data.is_null() && data_len > 0) {
+        return MoqResult::InvalidArgument;
+    }
+
+    let track = &*track;
+    let segment = std::slice::from_raw_parts(data, data_len);
+
+    let moof = match fmp4_find_box(segment, b"moof") {
+        Some(moof) => moof,
+        None => return MoqResult::InvalidArgument,
+    };
+    if fmp4_find_box(segment, b"mdat").is_none() || fmp4_tfdt_time(moof).is_none() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let mut handles = HANDLES.lock().unwrap();
+    let track_data = match handles.track_producers.get_mut(&track.id) {
+        Some(track_data) => track_data,
+        None => return MoqResult::InvalidArgument,
+    };
+    if track_data.fmp4.is_none() {
+        return MoqResult::InvalidArgument;
+    }
+
+    if let Some(group) = track_data.fmp4.as_mut().unwrap().current_group.take() {
+        group.close();
+    }
+
+    let sequence = track_data.fmp4.as_ref().unwrap().next_sequence;
+    let mut group = match track_data
+        .producer
+        .create_group(moq_lite::Group { sequence })
+    {
+        Some(group) => group,
+        None => return MoqResult::GeneralError,
+    };
+    group.write_frame(segment.to_vec());
+
+    let fmp4 = track_data.fmp4.as_mut().unwrap();
+    fmp4.next_sequence += 1;
+    fmp4.current_group = Some(group);
+
+    MoqResult::Success
+}
+
+/// Walk the top-level boxes of an ISOBMFF buffer, returning `(type, start, end)` with
+/// `start`/`end` absolute offsets into `data` (header included), stopping at the first
+/// malformed box
+fn fmp4_top_level_boxes(data: &[u8]) -> Vec<([u8; 4], usize, usize)> {
+    let mut out = Vec::new();
+    let mut offset = 0usize;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        if size < 8 || offset + size > data.len() {
+            break;
+        }
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[offset + 4..offset + 8]);
+        out.push((kind, offset, offset + size));
+        offset += size;
+    }
+    out
+}
+
+/// Extract the `track_ID` field out of a `tkhd` box payload (byte 0 is version, bytes
+/// 1-3 are flags, then creation/modification times of 4 bytes each in version 0 or 8
+/// bytes each in version 1)
+fn fmp4_track_id(box_payload: &[u8]) -> Option<u32> {
+    if box_payload.is_empty() {
+        return None;
+    }
+    let offset = if box_payload[0] == 1 { 20 } else { 12 };
+    let bytes = box_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Parse a fragmented/CMAF MP4 file and publish one MOQ track per `trak`, one group per
+/// `moof`+`mdat` fragment
+///
+/// Reads the whole file into memory up front (simpler than streaming the parse, at the
+/// cost of holding the full file in RAM); `ftyp`+`moov` become each track's init segment
+/// via `moq_broadcast_producer_add_fmp4_track`; every subsequent `moof`+`mdat` pair is
+/// routed to its track by the `track_ID` in that fragment's `tfhd` and pushed with
+/// `moq_fmp4_track_push_segment`, so a new group begins exactly on each fragment
+/// boundary. Runs on `RUNTIME`; pause/resume with `moq_publish_pause`, abort early with
+/// `moq_publish_stop`. `callback` fires after each fragment, once at the end
+/// (`MoqMp4Event::Finished`), and on any fatal error (`MoqMp4Event::Error`).
+#[no_mangle]
+pub unsafe extern "C" fn moq_publish_mp4(
+    producer: *mut MoqBroadcastProducer,
+    path: *const c_char,
+    callback: extern "C" fn(MoqMp4Event, *const c_char, *mut c_void),
+    user_data: *mut c_void,
+    handle_out: *mut *mut MoqPublishHandle,
+) -> MoqResult {
+    if producer.is_null() || path.is_null() || handle_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let path_str = match CStr::from_ptr(path).to_str() {
+        Ok(path) => path.to_string(),
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
+    };
+
+    let producer_addr = producer as usize;
+    let user_data_addr = user_data as usize;
+    let paused = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let stopped = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+    let handle_id = next_id();
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles.publish_handles.insert(
+            handle_id,
+            PublishMp4Data {
+                paused: paused.clone(),
+                stopped: stopped.clone(),
+            },
+        );
+    }
+
+    RUNTIME.spawn(async move {
+        let producer = producer_addr as *mut MoqBroadcastProducer;
+
+        // Only `usize` (Send) is captured here, not a raw pointer, since this closure
+        // is called from inside a loop that awaits and so must stay Send.
+        let report = |event: MoqMp4Event, message: &str| {
+            let c_message = CString::new(message).unwrap_or_default();
+            let message_ptr = if message.is_empty() {
+                ptr::null()
+            } else {
+                c_message.as_ptr()
+            };
+            callback(event, message_ptr, user_data_addr as *mut c_void);
+        };
+
+        let data = match std::fs::read(&path_str) {
+            Ok(data) => data,
+            Err(e) => {
+                report(
+                    MoqMp4Event::Error,
+                    &format!("failed to read {path_str}: {e}"),
+                );
+                let mut handles = HANDLES.lock().unwrap();
+                handles.publish_handles.remove(&handle_id);
+                return;
+            }
+        };
+
+        let top_boxes = fmp4_top_level_boxes(&data);
+        let (moov_start, moov_end) = match top_boxes.iter().find(|(kind, _, _)| kind == b"moov") {
+            Some((_, start, end)) => (*start, *end),
+            None => {
+                report(MoqMp4Event::Error, "no moov box found");
+                let mut handles = HANDLES.lock().unwrap();
+                handles.publish_handles.remove(&handle_id);
+                return;
+            }
+        };
+        let moov_payload = &data[moov_start + 8..moov_end];
+        let init_segment = &data[0..moov_end];
+
+        let mut tracks: HashMap<u32, *mut MoqTrackProducer> = HashMap::new();
+        for (kind, start, end) in fmp4_top_level_boxes(moov_payload) {
+            if &kind != b"trak" {
+                continue;
+            }
+            let trak_payload = &moov_payload[start + 8..end];
+            let tkhd = match fmp4_find_box(trak_payload, b"tkhd") {
+                Some(tkhd) => tkhd,
+                None => continue,
+            };
+            let track_id = match fmp4_track_id(tkhd) {
+                Some(id) => id,
+                None => continue,
+            };
+
+            let name = CString::new(format!("track{track_id}")).unwrap();
+            let track_desc = MoqTrack {
+                name: name.as_ptr(),
+                priority: 128,
+            };
+            let mut track_out: *mut MoqTrackProducer = ptr::null_mut();
+            let result = moq_broadcast_producer_add_fmp4_track(
+                producer,
+                &track_desc,
+                MoqFmp4GroupBoundary::PerSegment,
+                init_segment.as_ptr(),
+                init_segment.len(),
+                &mut track_out,
+            );
+            if result == MoqResult::Success {
+                tracks.insert(track_id, track_out);
+            }
+        }
+
+        if tracks.is_empty() {
+            report(MoqMp4Event::Error, "no usable tracks found in moov");
+            let mut handles = HANDLES.lock().unwrap();
+            handles.publish_handles.remove(&handle_id);
+            return;
+        }
+
+        let mut i = 0;
+        while i < top_boxes.len() {
+            if stopped.load(std::sync::atomic::Ordering::SeqCst) {
+                break;
+            }
+            while paused.load(std::sync::atomic::Ordering::SeqCst)
+                && !stopped.load(std::sync::atomic::Ordering::SeqCst)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+
+            let (kind, moof_start, moof_end) = top_boxes[i];
+            if &kind != b"moof" {
+                i += 1;
+                continue;
+            }
+            // A moof is normally immediately followed by its mdat; skip anything in
+            // between (e.g. a leading sidx) and bail on this fragment if no mdat shows
+            // up before the next moof.
+            let mut j = i + 1;
+            let mut mdat_end = None;
+            while j < top_boxes.len() {
+                let (next_kind, _, next_end) = top_boxes[j];
+                if &next_kind == b"mdat" {
+                    mdat_end = Some(next_end);
+                    break;
+                }
+                if &next_kind == b"moof" {
+                    break;
+                }
+                j += 1;
+            }
+            let fragment_end = match mdat_end {
+                Some(end) => end,
+                None => {
+                    i += 1;
+                    continue;
+                }
+            };
+
+            let moof_payload = &data[moof_start + 8..moof_end];
+            // `tfhd` puts `track_ID` right after its 4-byte version/flags header.
+            let track_id = fmp4_find_box(moof_payload, b"traf")
+                .and_then(|traf| fmp4_find_box(traf, b"tfhd"))
+                .and_then(|tfhd| tfhd.get(4..8))
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u32::from_be_bytes);
+
+            if let Some(track_id) = track_id {
+                if let Some(track) = tracks.get(&track_id) {
+                    let fragment = &data[moof_start..fragment_end];
+                    let result =
+                        moq_fmp4_track_push_segment(*track, fragment.as_ptr(), fragment.len());
+                    if result == MoqResult::Success {
+                        report(MoqMp4Event::FragmentPublished, "");
+                    }
+                }
+            }
+
+            i = j + 1;
+        }
+
+        report(MoqMp4Event::Finished, "");
+        let mut handles = HANDLES.lock().unwrap();
+        handles.publish_handles.remove(&handle_id);
+    });
+
+    let boxed_handle = Box::new(MoqPublishHandle { id: handle_id });
+    *handle_out = Box::into_raw(boxed_handle);
+
+    MoqResult::Success
+}
+
+/// Pause or resume a `moq_publish_mp4` ingest task; each call flips the paused state
+#[no_mangle]
+pub unsafe extern "C" fn moq_publish_pause(handle: *mut MoqPublishHandle) -> MoqResult {
+    if handle.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let handle = &*handle;
+    let handles = HANDLES.lock().unwrap();
+    match handles.publish_handles.get(&handle.id) {
+        Some(data) => {
+            data.paused
+                .fetch_xor(true, std::sync::atomic::Ordering::SeqCst);
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Stop a `moq_publish_mp4` ingest task after its current fragment
+#[no_mangle]
+pub unsafe extern "C" fn moq_publish_stop(handle: *mut MoqPublishHandle) -> MoqResult {
+    if handle.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let handle = &*handle;
+    let handles = HANDLES.lock().unwrap();
+    match handles.publish_handles.get(&handle.id) {
+        Some(data) => {
+            data.stopped
+                .store(true, std::sync::atomic::Ordering::SeqCst);
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Free a `MoqPublishHandle`; does not stop an in-flight ingest, call
+/// `moq_publish_stop` first if that's desired
+#[no_mangle]
+pub unsafe extern "C" fn moq_publish_free(handle: *mut MoqPublishHandle) {
+    if !handle.is_null() {
+        let _ = Box::from_raw(handle);
+    }
+}
+
 /// Publish a broadcast on a session
 #[no_mangle]
 pub unsafe extern "C" fn moq_session_publish(
@@ -708,107 +1980,370 @@ pub unsafe extern "C" fn moq_session_publish(
 
     let name = match CStr::from_ptr(broadcast_name).to_str() {
         Ok(name) => name.to_string(),
-        Err(_) => return MoqResult::InvalidArgument,
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
     };
 
     // Publish the broadcast on the session
     {
         let mut handles = HANDLES.lock().unwrap();
 
-        // Validate session exists
-        if !handles.sessions.contains_key(&session.id) {
+        // Validate session exists
+        if !handles.sessions.contains_key(&session.id) {
+            return MoqResult::InvalidArgument;
+        }
+
+        // Get the broadcast consumer
+        let consumer = if let Some(broadcast) = handles.broadcast_producers.get_mut(&producer.id) {
+            broadcast.name = name.clone();
+            broadcast.broadcast.consumer.clone()
+        } else {
+            return MoqResult::InvalidArgument;
+        };
+
+        // Clone the data we need and drop the lock to avoid deadlock
+        let session_id = session.id;
+        drop(handles);
+
+        // Use the runtime to ensure we're in the right context for any async operations
+        RUNTIME.block_on(async {
+            let mut handles = HANDLES.lock().unwrap();
+            if let Some(session_data) = handles.sessions.get_mut(&session_id) {
+                // Use the origin producer to publish the broadcast
+                if let Some(ref mut origin_producer) = session_data.publish_origin {
+                    origin_producer.publish_broadcast(&name, consumer.clone());
+                }
+                // Remember this broadcast so moq_client_set_reconnect's supervisor
+                // can re-publish it after a redial.
+                session_data
+                    .published_broadcasts
+                    .retain(|(n, _)| n != &name);
+                session_data
+                    .published_broadcasts
+                    .push((name.clone(), consumer));
+            }
+        });
+    }
+
+    MoqResult::Success
+}
+
+/// Consume a broadcast from a session
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_consume(
+    session: *mut MoqSession,
+    broadcast_name: *const c_char,
+    consumer_out: *mut *mut MoqBroadcastConsumer,
+) -> MoqResult {
+    if session.is_null() || broadcast_name.is_null() || consumer_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+
+    let name = match CStr::from_ptr(broadcast_name).to_str() {
+        Ok(name) => name.to_string(),
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
+    };
+
+    // Block on the async operation directly
+    let consumer = {
+        let mut handles = HANDLES.lock().unwrap();
+        if let Some(session_data) = handles.sessions.get_mut(&session.id) {
+            // Use the origin consumer to consume broadcasts
+            if let Some(ref origin_consumer) = session_data.subscribe_origin {
+                // Wrap in block_on in case consume_broadcast internally uses async operations
+                RUNTIME.block_on(async { origin_consumer.consume_broadcast(&name) })
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    };
+
+    let consumer = match consumer {
+        Some(c) => c,
+        None => return MoqResult::InvalidArgument,
+    };
+
+    let consumer_id = next_id();
+    let consumer_data = BroadcastConsumerData {
+        session_id: session.id,
+        name,
+        consumer,
+        tracks: Vec::new(),
+    };
+
+    // Store the consumer data
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles
+            .broadcast_consumers
+            .insert(consumer_id, consumer_data);
+    }
+
+    // Create and return the consumer handle
+    let boxed_consumer = Box::new(MoqBroadcastConsumer { id: consumer_id });
+    *consumer_out = Box::into_raw(boxed_consumer);
+
+    MoqResult::Success
+}
+
+/// Relay a broadcast from one session's subscribe origin onto another session's
+/// publish origin, without copying frame bytes through C
+///
+/// Resolves `broadcast_name` via `src_session`'s `subscribe_origin.consume_broadcast`
+/// and re-publishes the resulting `BroadcastConsumer` onto `dst_session`'s
+/// `publish_origin.publish_broadcast`. If this broadcast is already being relayed from
+/// `src_session` (by this call or a prior one), the cached upstream consumer is reused
+/// instead of subscribing again, so fanning the same broadcast out to several
+/// downstream sessions only holds one upstream subscription.
+#[no_mangle]
+pub unsafe extern "C" fn moq_relay_broadcast(
+    src_session: *mut MoqSession,
+    broadcast_name: *const c_char,
+    dst_session: *mut MoqSession,
+    relay_out: *mut *mut MoqRelayHandle,
+) -> MoqResult {
+    if src_session.is_null()
+        || broadcast_name.is_null()
+        || dst_session.is_null()
+        || relay_out.is_null()
+    {
+        return MoqResult::InvalidArgument;
+    }
+
+    let src_session = &*src_session;
+    let dst_session = &*dst_session;
+
+    let name = match CStr::from_ptr(broadcast_name).to_str() {
+        Ok(name) => name.to_string(),
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
+    };
+
+    let mut handles = HANDLES.lock().unwrap();
+
+    if !handles.sessions.contains_key(&dst_session.id) {
+        return MoqResult::InvalidArgument;
+    }
+
+    let cache_key = (src_session.id, name.clone());
+    let consumer = if let Some(consumer) = handles.relay_upstream_cache.get(&cache_key) {
+        consumer.clone()
+    } else {
+        let consumer = match handles.sessions.get(&src_session.id) {
+            Some(session_data) => match &session_data.subscribe_origin {
+                Some(origin_consumer) => {
+                    RUNTIME.block_on(async { origin_consumer.consume_broadcast(&name) })
+                }
+                None => None,
+            },
+            None => return MoqResult::InvalidArgument,
+        };
+        let consumer = match consumer {
+            Some(c) => c,
+            None => return MoqResult::InvalidArgument,
+        };
+        handles
+            .relay_upstream_cache
+            .insert(cache_key, consumer.clone());
+        consumer
+    };
+
+    if let Some(session_data) = handles.sessions.get_mut(&dst_session.id) {
+        if let Some(ref mut origin_producer) = session_data.publish_origin {
+            origin_producer.publish_broadcast(&name, consumer);
+        } else {
+            return MoqResult::InvalidArgument;
+        }
+    } else {
+        return MoqResult::InvalidArgument;
+    }
+
+    let relay_id = next_id();
+    handles.relays.insert(
+        relay_id,
+        RelayData {
+            src_session_id: src_session.id,
+            dst_session_id: dst_session.id,
+            name,
+        },
+    );
+
+    let boxed_relay = Box::new(MoqRelayHandle { id: relay_id });
+    *relay_out = Box::into_raw(boxed_relay);
+
+    MoqResult::Success
+}
+
+/// Tear down a relay created by `moq_relay_broadcast`
+///
+/// This drops our bookkeeping handle; it does not currently un-announce the broadcast
+/// from the destination session's publish origin, since that requires a revocation API
+/// this crate does not yet expose a binding for.
+#[no_mangle]
+pub unsafe extern "C" fn moq_relay_free(relay: *mut MoqRelayHandle) {
+    if relay.is_null() {
+        return;
+    }
+
+    let relay = Box::from_raw(relay);
+    let mut handles = HANDLES.lock().unwrap();
+    handles.relays.remove(&relay.id);
+}
+
+/// Open an announcement-discovery stream for broadcasts whose path starts with `prefix`
+///
+/// Drives the session's `subscribe_origin` announced stream on a task spawned on the
+/// shared `RUNTIME` so that polling from C via `moq_announced_next` is a cheap channel
+/// receive rather than a `block_on` over the whole stream. The task tracks which paths
+/// are currently live so it can correctly emit an unannounce event when the origin
+/// retracts a path.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_announced_open(
+    session: *mut MoqSession,
+    prefix: *const c_char,
+    handle_out: *mut *mut MoqAnnouncedHandle,
+) -> MoqResult {
+    if session.is_null() || prefix.is_null() || handle_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+    let prefix = match CStr::from_ptr(prefix).to_str() {
+        Ok(prefix) => prefix.to_string(),
+        Err(e) => {
+            set_last_error(e);
             return MoqResult::InvalidArgument;
         }
+    };
 
-        // Get the broadcast consumer
-        let consumer = if let Some(broadcast) = handles.broadcast_producers.get_mut(&producer.id) {
-            broadcast.name = name.clone();
-            broadcast.broadcast.consumer.clone()
-        } else {
-            return MoqResult::InvalidArgument;
-        };
+    let mut origin_consumer = {
+        let handles = HANDLES.lock().unwrap();
+        match handles.sessions.get(&session.id) {
+            Some(session_data) => match &session_data.subscribe_origin {
+                Some(origin_consumer) => origin_consumer.clone(),
+                None => return MoqResult::InvalidArgument,
+            },
+            None => return MoqResult::InvalidArgument,
+        }
+    };
 
-        // Clone the data we need and drop the lock to avoid deadlock
-        let session_id = session.id;
-        drop(handles);
+    let (tx, rx) = mpsc::channel(128);
 
-        // Use the runtime to ensure we're in the right context for any async operations
-        RUNTIME.block_on(async {
-            let mut handles = HANDLES.lock().unwrap();
-            if let Some(session_data) = handles.sessions.get_mut(&session_id) {
-                // Use the origin producer to publish the broadcast
-                if let Some(ref mut origin_producer) = session_data.publish_origin {
-                    origin_producer.publish_broadcast(&name, consumer);
+    RUNTIME.spawn(async move {
+        let mut live: HashSet<String> = HashSet::new();
+
+        while let Some((path, broadcast)) = origin_consumer.announced().await {
+            let path = path.to_string();
+            if !path.starts_with(&prefix) {
+                continue;
+            }
+
+            match broadcast {
+                Some(_) => {
+                    live.insert(path.clone());
+                    if tx.send((path, true)).await.is_err() {
+                        break;
+                    }
+                }
+                None => {
+                    if live.remove(&path) && tx.send((path, false)).await.is_err() {
+                        break;
+                    }
                 }
             }
-        });
+        }
+    });
+
+    let handle_id = next_id();
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles
+            .announced
+            .insert(handle_id, AnnouncedHandleData { receiver: rx });
     }
 
+    let boxed_handle = Box::new(MoqAnnouncedHandle { id: handle_id });
+    *handle_out = Box::into_raw(boxed_handle);
+
     MoqResult::Success
 }
 
-/// Consume a broadcast from a session
+/// Poll the next buffered ANNOUNCE (`active = true`) or UNANNOUNCE (`active = false`)
+/// event from an announcement-discovery handle opened with `moq_session_announced_open`
+///
+/// If no event is currently buffered, `name_out` is set to null and `Success` is
+/// returned so callers can poll this in a loop instead of blocking.
 #[no_mangle]
-pub unsafe extern "C" fn moq_session_consume(
-    session: *mut MoqSession,
-    broadcast_name: *const c_char,
-    consumer_out: *mut *mut MoqBroadcastConsumer,
+pub unsafe extern "C" fn moq_announced_next(
+    handle: *mut MoqAnnouncedHandle,
+    name_out: *mut *mut c_char,
+    active_out: *mut bool,
 ) -> MoqResult {
-    if session.is_null() || broadcast_name.is_null() || consumer_out.is_null() {
+    if handle.is_null() || name_out.is_null() || active_out.is_null() {
         return MoqResult::InvalidArgument;
     }
 
-    let session = &*session;
-
-    let name = match CStr::from_ptr(broadcast_name).to_str() {
-        Ok(name) => name.to_string(),
-        Err(_) => return MoqResult::InvalidArgument,
-    };
+    let handle = &*handle;
 
-    // Block on the async operation directly
-    let consumer = {
+    let event = {
         let mut handles = HANDLES.lock().unwrap();
-        if let Some(session_data) = handles.sessions.get_mut(&session.id) {
-            // Use the origin consumer to consume broadcasts
-            if let Some(ref origin_consumer) = session_data.subscribe_origin {
-                // Wrap in block_on in case consume_broadcast internally uses async operations
-                RUNTIME.block_on(async { origin_consumer.consume_broadcast(&name) })
-            } else {
-                None
-            }
-        } else {
-            None
+        match handles.announced.get_mut(&handle.id) {
+            Some(data) => data.receiver.try_recv().ok(),
+            None => return MoqResult::InvalidArgument,
         }
     };
 
-    let consumer = match consumer {
-        Some(c) => c,
-        None => return MoqResult::InvalidArgument,
-    };
+    match event {
+        Some((name, active)) => {
+            let mut bytes = name.into_bytes();
+            bytes.push(0);
+            let len = bytes.len();
 
-    let consumer_id = next_id();
-    let consumer_data = BroadcastConsumerData {
-        session_id: session.id,
-        name,
-        consumer,
-        tracks: Vec::new(),
-    };
+            let layout = std::alloc::Layout::from_size_align(len, 1).unwrap();
+            let ptr = std::alloc::alloc(layout);
+            if ptr.is_null() {
+                return MoqResult::GeneralError;
+            }
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, len);
 
-    // Store the consumer data
-    {
-        let mut handles = HANDLES.lock().unwrap();
-        handles
-            .broadcast_consumers
-            .insert(consumer_id, consumer_data);
-    }
+            {
+                let mut tracker = MEMORY_TRACKER.lock().unwrap();
+                tracker.insert(ptr as usize, len);
+            }
 
-    // Create and return the consumer handle
-    let boxed_consumer = Box::new(MoqBroadcastConsumer { id: consumer_id });
-    *consumer_out = Box::into_raw(boxed_consumer);
+            *name_out = ptr as *mut c_char;
+            *active_out = active;
+        }
+        None => {
+            *name_out = ptr::null_mut();
+            *active_out = false;
+        }
+    }
 
     MoqResult::Success
 }
 
+/// Free an announcement-discovery handle opened with `moq_session_announced_open`
+#[no_mangle]
+pub unsafe extern "C" fn moq_announced_free(handle: *mut MoqAnnouncedHandle) {
+    if !handle.is_null() {
+        let handle = Box::from_raw(handle);
+        let mut handles = HANDLES.lock().unwrap();
+        handles.announced.remove(&handle.id);
+    }
+}
+
 /// Subscribe to a track within a broadcast
 #[no_mangle]
 pub unsafe extern "C" fn moq_broadcast_consumer_subscribe_track(
@@ -828,7 +2363,10 @@ pub unsafe extern "C" fn moq_broadcast_consumer_subscribe_track(
     } else {
         match CStr::from_ptr(track_info.name).to_str() {
             Ok(name) => name.to_string(),
-            Err(_) => return MoqResult::InvalidArgument,
+            Err(e) => {
+                set_last_error(e);
+                return MoqResult::InvalidArgument;
+            }
         }
     };
 
@@ -888,6 +2426,137 @@ pub unsafe extern "C" fn moq_broadcast_consumer_subscribe_track(
     MoqResult::Success
 }
 
+/// Subscribe to a track and receive its data through a single callback, without
+/// juggling the intermediate broadcast/track/group handles yourself
+///
+/// Composes `moq_session_consume` + `moq_broadcast_consumer_subscribe_track` +
+/// `moq_track_consumer_on_group` + `moq_group_consumer_on_frame` into one call: resolves
+/// `broadcast_name` on `session`'s `subscribe_origin`, subscribes to `track_name`, and
+/// spawns a background task that drives every group to completion, trampolining each
+/// frame's bytes into `callback(data, len, user_data)`. `callback` fires once more with
+/// `(null, 0)` when the track ends (EOF/error), the same end-of-stream convention
+/// `moq_group_consumer_on_frame` uses; `moq_session_unsubscribe` aborts the task without
+/// that final call. This is
+/// what lets a plain C/C++ caller receive track data without hand-rolling the
+/// group/frame loop that `RelayTestApp` does in Rust.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_subscribe(
+    session: *mut MoqSession,
+    broadcast_name: *const c_char,
+    track_name: *const c_char,
+    callback: extern "C" fn(*const u8, usize, *mut c_void),
+    user_data: *mut c_void,
+    subscription_out: *mut *mut MoqSubscription,
+) -> MoqResult {
+    if session.is_null()
+        || broadcast_name.is_null()
+        || track_name.is_null()
+        || subscription_out.is_null()
+    {
+        return MoqResult::InvalidArgument;
+    }
+
+    let session = &*session;
+
+    let broadcast_name = match CStr::from_ptr(broadcast_name).to_str() {
+        Ok(name) => name.to_string(),
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
+    };
+    let track_name = match CStr::from_ptr(track_name).to_str() {
+        Ok(name) => name.to_string(),
+        Err(e) => {
+            set_last_error(e);
+            return MoqResult::InvalidArgument;
+        }
+    };
+
+    let broadcast_consumer = {
+        let mut handles = HANDLES.lock().unwrap();
+        match handles.sessions.get_mut(&session.id) {
+            Some(session_data) => match &session_data.subscribe_origin {
+                Some(origin_consumer) => {
+                    RUNTIME.block_on(async { origin_consumer.consume_broadcast(&broadcast_name) })
+                }
+                None => None,
+            },
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let broadcast_consumer = match broadcast_consumer {
+        Some(consumer) => consumer,
+        None => {
+            set_last_error(format!("broadcast '{}' not found", broadcast_name));
+            return MoqResult::GeneralError;
+        }
+    };
+
+    let moq_track = Track {
+        name: track_name.clone(),
+        priority: 0,
+    };
+    let mut track_consumer =
+        RUNTIME.block_on(async { broadcast_consumer.subscribe_track(&moq_track) });
+
+    let subscription_id = next_id();
+    let user_data_addr = user_data as usize;
+
+    let handle = RUNTIME.spawn(async move {
+        loop {
+            match track_consumer.next_group().await {
+                Ok(Some(mut group)) => loop {
+                    match group.read_frame().await {
+                        Ok(Some(frame)) => {
+                            let user_data_ptr = user_data_addr as *mut c_void;
+                            callback(frame.as_ptr(), frame.len(), user_data_ptr);
+                        }
+                        Ok(None) => break,
+                        Err(_) => break,
+                    }
+                },
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        let user_data_ptr = user_data_addr as *mut c_void;
+        callback(ptr::null(), 0, user_data_ptr);
+    });
+
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles.subscriptions.insert(subscription_id, handle);
+    }
+
+    let boxed_subscription = Box::new(MoqSubscription {
+        id: subscription_id,
+    });
+    *subscription_out = Box::into_raw(boxed_subscription);
+
+    MoqResult::Success
+}
+
+/// Cancel a subscription started by `moq_session_subscribe`, aborting its background
+/// task and consuming the handle
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_unsubscribe(subscription: *mut MoqSubscription) -> MoqResult {
+    if subscription.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let subscription = Box::from_raw(subscription);
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.subscriptions.remove(&subscription.id) {
+        Some(handle) => {
+            handle.abort();
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
 /// Create a group within a track producer
 #[no_mangle]
 pub unsafe extern "C" fn moq_track_producer_create_group(
@@ -1003,6 +2672,60 @@ pub unsafe extern "C" fn moq_group_producer_finish(group: *mut MoqGroupProducer)
     }
 }
 
+/// Create a cancellation token for use with `MoqReadOptions::cancel_token`
+#[no_mangle]
+pub unsafe extern "C" fn moq_cancel_token_new(token_out: *mut *mut MoqCancelToken) -> MoqResult {
+    if token_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let id = next_id();
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles.cancel_tokens.insert(
+            id,
+            CancelTokenData {
+                notify: std::sync::Arc::new(tokio::sync::Notify::new()),
+                cancelled: false,
+            },
+        );
+    }
+
+    let boxed_token = Box::new(MoqCancelToken { id });
+    *token_out = Box::into_raw(boxed_token);
+
+    MoqResult::Success
+}
+
+/// Trip a cancellation token, aborting any `*_ex` read call currently waiting on it
+#[no_mangle]
+pub unsafe extern "C" fn moq_cancel_token_cancel(token: *mut MoqCancelToken) -> MoqResult {
+    if token.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let token = &*token;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.cancel_tokens.get_mut(&token.id) {
+        Some(data) => {
+            data.cancelled = true;
+            data.notify.notify_waiters();
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Free a cancellation token created by `moq_cancel_token_new`
+#[no_mangle]
+pub unsafe extern "C" fn moq_cancel_token_free(token: *mut MoqCancelToken) {
+    if !token.is_null() {
+        let token = Box::from_raw(token);
+        let mut handles = HANDLES.lock().unwrap();
+        handles.cancel_tokens.remove(&token.id);
+    }
+}
+
 /// Get the next group from a track consumer (blocking simulation)
 #[no_mangle]
 pub unsafe extern "C" fn moq_track_consumer_next_group(
@@ -1073,49 +2796,157 @@ pub unsafe extern "C" fn moq_track_consumer_next_group(
             }
         };
 
-        // Restore the track_data back to the map before returning
-        {
-            let mut handles = HANDLES.lock().unwrap();
-            handles.track_consumers.insert(track_id, track_data);
+        // Restore the track_data back to the map before returning
+        {
+            let mut handles = HANDLES.lock().unwrap();
+            handles.track_consumers.insert(track_id, track_data);
+        }
+
+        result
+    };
+
+    match group_consumer_opt {
+        Some((group_consumer, session_id)) => {
+            let group_id = next_id();
+            let sequence = group_consumer.sequence;
+
+            let group_data = GroupConsumerData {
+                session_id,
+                track_id: track.id,
+                sequence,
+                consumer: group_consumer,
+                current_frame: 0,
+            };
+
+            // Store the group data and update the track
+            {
+                let mut handles = HANDLES.lock().unwrap();
+                handles.group_consumers.insert(group_id, group_data);
+
+                if let Some(track_data) = handles.track_consumers.get_mut(&track.id) {
+                    track_data.groups.push(group_id);
+                }
+            }
+
+            // Create and return the group handle
+            let boxed_group = Box::new(MoqGroupConsumer { id: group_id });
+            *group_out = Box::into_raw(boxed_group);
+
+            MoqResult::Success
+        }
+        None => {
+            // No groups available
+            *group_out = ptr::null_mut();
+            MoqResult::Success
+        }
+    }
+}
+
+/// Get the next group from a track consumer, with a configurable timeout and an
+/// optional cancellation token, instead of the hardcoded 500 ms poll in
+/// `moq_track_consumer_next_group`
+///
+/// Unlike the plain version, `Success` with a null `group_out` means the stream ended;
+/// `MoqResult::TimedOut` means `options.timeout_ms` elapsed with no group yet available,
+/// and `MoqResult::Cancelled` means `options.cancel_token` was tripped.
+#[no_mangle]
+pub unsafe extern "C" fn moq_track_consumer_next_group_ex(
+    track: *mut MoqTrackConsumer,
+    options: *const MoqReadOptions,
+    group_out: *mut *mut MoqGroupConsumer,
+) -> MoqResult {
+    if track.is_null() || options.is_null() || group_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let track = &*track;
+    let options = &*options;
+
+    let timeout = if options.timeout_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(options.timeout_ms))
+    };
+
+    let notify = if options.cancel_token.is_null() {
+        None
+    } else {
+        let cancel_token = &*options.cancel_token;
+        let handles = HANDLES.lock().unwrap();
+        handles
+            .cancel_tokens
+            .get(&cancel_token.id)
+            .map(|data| data.notify.clone())
+    };
+
+    let (mut track_data, session_id) = {
+        let mut handles = HANDLES.lock().unwrap();
+        match handles.track_consumers.remove(&track.id) {
+            Some(track_data) => {
+                let session_id = track_data.session_id;
+                (track_data, session_id)
+            }
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let outcome = RUNTIME.block_on(async {
+        let timeout_fut = async {
+            match timeout {
+                Some(d) => tokio::time::sleep(d).await,
+                None => std::future::pending::<()>().await,
+            }
+        };
+        let cancel_fut = async {
+            match &notify {
+                Some(n) => n.notified().await,
+                None => std::future::pending::<()>().await,
+            }
+        };
+
+        tokio::select! {
+            result = track_data.consumer.next_group() => Ok(result),
+            _ = timeout_fut => Err(MoqResult::TimedOut),
+            _ = cancel_fut => Err(MoqResult::Cancelled),
         }
+    });
 
-        result
-    };
+    // Restore the track_data back to the map before returning
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles.track_consumers.insert(track.id, track_data);
+    }
 
-    match group_consumer_opt {
-        Some((group_consumer, session_id)) => {
+    match outcome {
+        Ok(Ok(Some(group))) => {
             let group_id = next_id();
-            let sequence = 0; // TODO: Get actual sequence from group
-
+            let sequence = group.sequence;
             let group_data = GroupConsumerData {
                 session_id,
                 track_id: track.id,
                 sequence,
-                consumer: group_consumer,
+                consumer: group,
                 current_frame: 0,
             };
-
-            // Store the group data and update the track
             {
                 let mut handles = HANDLES.lock().unwrap();
                 handles.group_consumers.insert(group_id, group_data);
-
                 if let Some(track_data) = handles.track_consumers.get_mut(&track.id) {
                     track_data.groups.push(group_id);
                 }
             }
-
-            // Create and return the group handle
             let boxed_group = Box::new(MoqGroupConsumer { id: group_id });
             *group_out = Box::into_raw(boxed_group);
-
             MoqResult::Success
         }
-        None => {
-            // No groups available
+        Ok(Ok(None)) | Ok(Err(_)) => {
             *group_out = ptr::null_mut();
             MoqResult::Success
         }
+        Err(result) => {
+            *group_out = ptr::null_mut();
+            result
+        }
     }
 }
 
@@ -1225,6 +3056,419 @@ pub unsafe extern "C" fn moq_group_consumer_read_frame(
     }
 }
 
+/// Heap-allocate a copy of `data` and register it with `MEMORY_TRACKER` so it can later
+/// be released with `moq_free`; returns null on allocation failure
+unsafe fn alloc_tracked(data: &[u8]) -> *mut u8 {
+    if data.is_empty() {
+        return ptr::null_mut();
+    }
+
+    let layout = std::alloc::Layout::from_size_align(data.len(), 1).unwrap();
+    let out_ptr = std::alloc::alloc(layout);
+    if out_ptr.is_null() {
+        return ptr::null_mut();
+    }
+
+    std::ptr::copy_nonoverlapping(data.as_ptr(), out_ptr, data.len());
+
+    let mut tracker = MEMORY_TRACKER.lock().unwrap();
+    tracker.insert(out_ptr as usize, data.len());
+
+    out_ptr
+}
+
+/// Read a frame from a group consumer, with a configurable timeout and an optional
+/// cancellation token, instead of the hardcoded 500 ms poll in
+/// `moq_group_consumer_read_frame`
+///
+/// Unlike the plain version, `Success` with a null `data_out` means the stream ended (or
+/// that the frame read was empty);
+/// `MoqResult::TimedOut` means `options.timeout_ms` elapsed with no frame yet available,
+/// and `MoqResult::Cancelled` means `options.cancel_token` was tripped.
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_consumer_read_frame_ex(
+    group: *mut MoqGroupConsumer,
+    options: *const MoqReadOptions,
+    data_out: *mut *mut u8,
+    data_len_out: *mut usize,
+) -> MoqResult {
+    if group.is_null() || options.is_null() || data_out.is_null() || data_len_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let group = &*group;
+    let options = &*options;
+
+    let timeout = if options.timeout_ms == 0 {
+        None
+    } else {
+        Some(Duration::from_millis(options.timeout_ms))
+    };
+
+    let notify = if options.cancel_token.is_null() {
+        None
+    } else {
+        let cancel_token = &*options.cancel_token;
+        let handles = HANDLES.lock().unwrap();
+        handles
+            .cancel_tokens
+            .get(&cancel_token.id)
+            .map(|data| data.notify.clone())
+    };
+
+    let mut consumer_temp = {
+        let mut handles = HANDLES.lock().unwrap();
+        match handles.group_consumers.remove(&group.id) {
+            Some(data) => data,
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let outcome = RUNTIME.block_on(async {
+        let timeout_fut = async {
+            match timeout {
+                Some(d) => tokio::time::sleep(d).await,
+                None => std::future::pending::<()>().await,
+            }
+        };
+        let cancel_fut = async {
+            match &notify {
+                Some(n) => n.notified().await,
+                None => std::future::pending::<()>().await,
+            }
+        };
+
+        tokio::select! {
+            result = consumer_temp.consumer.read_frame() => Ok(result),
+            _ = timeout_fut => Err(MoqResult::TimedOut),
+            _ = cancel_fut => Err(MoqResult::Cancelled),
+        }
+    });
+
+    if let Ok(Ok(Some(_))) = &outcome {
+        consumer_temp.current_frame += 1;
+    }
+
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles.group_consumers.insert(group.id, consumer_temp);
+    }
+
+    match outcome {
+        Ok(Ok(Some(frame))) => {
+            let out_ptr = alloc_tracked(&frame);
+            if frame.is_empty() {
+                *data_out = ptr::null_mut();
+                *data_len_out = 0;
+            } else if out_ptr.is_null() {
+                return MoqResult::GeneralError;
+            } else {
+                *data_out = out_ptr;
+                *data_len_out = frame.len();
+            }
+            MoqResult::Success
+        }
+        Ok(Ok(None)) | Ok(Err(_)) => {
+            *data_out = ptr::null_mut();
+            *data_len_out = 0;
+            MoqResult::Success
+        }
+        Err(result) => {
+            *data_out = ptr::null_mut();
+            *data_len_out = 0;
+            result
+        }
+    }
+}
+
+/// A single frame out of `moq_group_consumer_read_frames`'s batch
+///
+/// All frames returned by one call share a single contiguous allocation - `ptr` is an
+/// offset into it, not an independently freeable buffer. Free the whole batch at once
+/// with `moq_free_frames`.
+#[repr(C)]
+pub struct MoqFrame {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+/// Drain up to `max_frames` frames that are already available on a group consumer,
+/// without blocking for more
+///
+/// Unlike `moq_group_consumer_read_frame`/`_ex`, this never waits: it polls
+/// `read_frame()` just long enough to see whether a frame is immediately ready, and
+/// stops as soon as one isn't (or the stream ends/errors, or `max_frames` is reached).
+/// `frames_out` must point to caller-allocated space for at least `max_frames`
+/// `MoqFrame` slots; `count_out` receives how many were actually filled (0 if none were
+/// ready). All filled frames' bytes live in one `moq_free_frames`-managed allocation
+/// instead of one tracked allocation per frame, to cut per-frame allocation overhead.
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_consumer_read_frames(
+    group: *mut MoqGroupConsumer,
+    max_frames: usize,
+    frames_out: *mut MoqFrame,
+    count_out: *mut usize,
+) -> MoqResult {
+    if group.is_null() || frames_out.is_null() || count_out.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+    if max_frames == 0 {
+        *count_out = 0;
+        return MoqResult::Success;
+    }
+
+    let group = &*group;
+    let group_id = group.id;
+
+    let mut consumer_temp = {
+        let mut handles = HANDLES.lock().unwrap();
+        match handles.group_consumers.remove(&group_id) {
+            Some(data) => data,
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let mut collected = Vec::new();
+    RUNTIME.block_on(async {
+        while collected.len() < max_frames {
+            // A zero-duration timeout polls read_frame() once instead of waiting for
+            // the next frame to arrive, giving us the "already available" semantics.
+            match tokio::time::timeout(
+                Duration::from_millis(0),
+                consumer_temp.consumer.read_frame(),
+            )
+            .await
+            {
+                Ok(Ok(Some(frame))) => {
+                    consumer_temp.current_frame += 1;
+                    collected.push(frame);
+                }
+                // No frame ready right now, the stream ended, or it errored: stop
+                // draining without blocking for more.
+                _ => break,
+            }
+        }
+    });
+
+    {
+        let mut handles = HANDLES.lock().unwrap();
+        handles.group_consumers.insert(group_id, consumer_temp);
+    }
+
+    let total_len: usize = collected.iter().map(|frame| frame.len()).sum();
+    if total_len == 0 {
+        *count_out = 0;
+        return MoqResult::Success;
+    }
+
+    let layout = std::alloc::Layout::from_size_align(total_len, 1).unwrap();
+    let base = std::alloc::alloc(layout);
+    if base.is_null() {
+        return MoqResult::GeneralError;
+    }
+    {
+        let mut tracker = MEMORY_TRACKER.lock().unwrap();
+        tracker.insert(base as usize, total_len);
+    }
+
+    let slots = std::slice::from_raw_parts_mut(frames_out, max_frames);
+    let mut offset = 0usize;
+    for (i, frame) in collected.iter().enumerate() {
+        let len = frame.len();
+        std::ptr::copy_nonoverlapping(frame.as_ptr(), base.add(offset), len);
+        slots[i] = MoqFrame {
+            ptr: base.add(offset),
+            len,
+        };
+        offset += len;
+    }
+
+    *count_out = collected.len();
+    MoqResult::Success
+}
+
+/// Free a batch returned by `moq_group_consumer_read_frames`
+///
+/// `frames`/`count` must be exactly the values that call wrote out. Since all `count`
+/// frames share one allocation, this releases it via `frames[0].ptr` - don't call
+/// `moq_free` on the individual entries yourself.
+#[no_mangle]
+pub unsafe extern "C" fn moq_free_frames(frames: *const MoqFrame, count: usize) {
+    if frames.is_null() || count == 0 {
+        return;
+    }
+    moq_free((*frames).ptr);
+}
+
+/// Drive `moq_track_consumer_next_group` from a background task instead of polling
+///
+/// Takes ownership of the track consumer for the lifetime of the watch: it is removed
+/// from `track_consumers` and moved into a task spawned on `RUNTIME` that loops calling
+/// `next_group()` and invokes `callback` with each new group (registered like
+/// `moq_track_consumer_next_group` does), or with a null group once the stream ends or
+/// errors. Call `moq_track_consumer_on_group_cancel` to stop early; don't call the
+/// blocking `moq_track_consumer_next_group` on the same track concurrently.
+#[no_mangle]
+pub unsafe extern "C" fn moq_track_consumer_on_group(
+    track: *mut MoqTrackConsumer,
+    callback: extern "C" fn(*mut MoqGroupConsumer, *mut c_void),
+    user_data: *mut c_void,
+) -> MoqResult {
+    if track.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let track = &*track;
+    let track_id = track.id;
+    let user_data_addr = user_data as usize;
+
+    let mut track_data = {
+        let mut handles = HANDLES.lock().unwrap();
+        if let Some(old) = handles.track_group_watchers.remove(&track_id) {
+            old.abort();
+        }
+        match handles.track_consumers.remove(&track_id) {
+            Some(data) => data,
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let handle = RUNTIME.spawn(async move {
+        loop {
+            match track_data.consumer.next_group().await {
+                Ok(Some(group)) => {
+                    let sequence = group.sequence;
+                    let group_id = next_id();
+                    let group_data = GroupConsumerData {
+                        session_id: track_data.session_id,
+                        track_id,
+                        sequence,
+                        consumer: group,
+                        current_frame: 0,
+                    };
+                    {
+                        let mut handles = HANDLES.lock().unwrap();
+                        handles.group_consumers.insert(group_id, group_data);
+                    }
+                    let boxed_group = Box::new(MoqGroupConsumer { id: group_id });
+                    let user_data_ptr = user_data_addr as *mut c_void;
+                    callback(Box::into_raw(boxed_group), user_data_ptr);
+                }
+                Ok(None) | Err(_) => {
+                    let user_data_ptr = user_data_addr as *mut c_void;
+                    callback(ptr::null_mut(), user_data_ptr);
+                    break;
+                }
+            }
+        }
+
+        let mut handles = HANDLES.lock().unwrap();
+        handles.track_group_watchers.remove(&track_id);
+    });
+
+    let mut handles = HANDLES.lock().unwrap();
+    handles.track_group_watchers.insert(track_id, handle);
+
+    MoqResult::Success
+}
+
+/// Stop a watch started by `moq_track_consumer_on_group`
+#[no_mangle]
+pub unsafe extern "C" fn moq_track_consumer_on_group_cancel(
+    track: *mut MoqTrackConsumer,
+) -> MoqResult {
+    if track.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let track = &*track;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.track_group_watchers.remove(&track.id) {
+        Some(handle) => {
+            handle.abort();
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
+/// Drive `moq_group_consumer_read_frame` from a background task instead of polling
+///
+/// Takes ownership of the group consumer for the lifetime of the watch, same caveat as
+/// `moq_track_consumer_on_group`: the handle is removed from `group_consumers` and moved
+/// into the spawned task, so don't call the blocking read concurrently. `callback` fires
+/// with each frame's bytes, or with `(null, 0)` once the stream ends or errors.
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_consumer_on_frame(
+    group: *mut MoqGroupConsumer,
+    callback: extern "C" fn(*const u8, usize, *mut c_void),
+    user_data: *mut c_void,
+) -> MoqResult {
+    if group.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let group = &*group;
+    let group_id = group.id;
+    let user_data_addr = user_data as usize;
+
+    let mut group_data = {
+        let mut handles = HANDLES.lock().unwrap();
+        if let Some(old) = handles.group_frame_watchers.remove(&group_id) {
+            old.abort();
+        }
+        match handles.group_consumers.remove(&group_id) {
+            Some(data) => data,
+            None => return MoqResult::InvalidArgument,
+        }
+    };
+
+    let handle = RUNTIME.spawn(async move {
+        loop {
+            match group_data.consumer.read_frame().await {
+                Ok(Some(frame)) => {
+                    group_data.current_frame += 1;
+                    let user_data_ptr = user_data_addr as *mut c_void;
+                    callback(frame.as_ptr(), frame.len(), user_data_ptr);
+                }
+                Ok(None) | Err(_) => {
+                    let user_data_ptr = user_data_addr as *mut c_void;
+                    callback(ptr::null(), 0, user_data_ptr);
+                    break;
+                }
+            }
+        }
+
+        let mut handles = HANDLES.lock().unwrap();
+        handles.group_frame_watchers.remove(&group_id);
+    });
+
+    let mut handles = HANDLES.lock().unwrap();
+    handles.group_frame_watchers.insert(group_id, handle);
+
+    MoqResult::Success
+}
+
+/// Stop a watch started by `moq_group_consumer_on_frame`
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_consumer_on_frame_cancel(
+    group: *mut MoqGroupConsumer,
+) -> MoqResult {
+    if group.is_null() {
+        return MoqResult::InvalidArgument;
+    }
+
+    let group = &*group;
+    let mut handles = HANDLES.lock().unwrap();
+    match handles.group_frame_watchers.remove(&group.id) {
+        Some(handle) => {
+            handle.abort();
+            MoqResult::Success
+        }
+        None => MoqResult::InvalidArgument,
+    }
+}
+
 /// Free memory allocated by the FFI layer
 #[no_mangle]
 pub unsafe extern "C" fn moq_free(ptr: *mut u8) {
@@ -1283,6 +3527,22 @@ pub unsafe extern "C" fn moq_group_producer_free(group: *mut MoqGroupProducer) {
     }
 }
 
+/// The sequence number this group was created with (see `moq_lite::Group`)
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_producer_sequence(group: *mut MoqGroupProducer) -> u64 {
+    if group.is_null() {
+        return 0;
+    }
+
+    let group = &*group;
+    let handles = HANDLES.lock().unwrap();
+    handles
+        .group_producers
+        .get(&group.id)
+        .map(|data| data.sequence)
+        .unwrap_or(0)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn moq_group_consumer_free(group: *mut MoqGroupConsumer) {
     if !group.is_null() {
@@ -1292,22 +3552,65 @@ pub unsafe extern "C" fn moq_group_consumer_free(group: *mut MoqGroupConsumer) {
     }
 }
 
-/// Get the last error message (placeholder)
+/// The sequence number of this group, as assigned by its producer
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_consumer_sequence(group: *mut MoqGroupConsumer) -> u64 {
+    if group.is_null() {
+        return 0;
+    }
+
+    let group = &*group;
+    let handles = HANDLES.lock().unwrap();
+    handles
+        .group_consumers
+        .get(&group.id)
+        .map(|data| data.sequence)
+        .unwrap_or(0)
+}
+
+/// The number of frames already read from this group via `moq_group_consumer_read_frame`
+/// (or its `_ex`/callback equivalents)
+#[no_mangle]
+pub unsafe extern "C" fn moq_group_consumer_frame_index(group: *mut MoqGroupConsumer) -> u64 {
+    if group.is_null() {
+        return 0;
+    }
+
+    let group = &*group;
+    let handles = HANDLES.lock().unwrap();
+    handles
+        .group_consumers
+        .get(&group.id)
+        .map(|data| data.current_frame as u64)
+        .unwrap_or(0)
+}
+
+/// Get the underlying cause of the most recent non-`Success` `MoqResult` returned by
+/// an FFI call on the calling thread, or null if none has occurred (or it's already
+/// been superseded by a later call). The returned pointer is only valid until the next
+/// `moq_*` call on this thread; copy it out before making another call.
 #[no_mangle]
 pub extern "C" fn moq_get_last_error() -> *const c_char {
-    ptr::null()
+    LAST_ERROR.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .map(|message| message.as_ptr())
+            .unwrap_or(ptr::null())
+    })
 }
 
 /// Convert a MoqResult to a human-readable string
 #[no_mangle]
 pub extern "C" fn moq_result_to_string(result: MoqResult) -> *const c_char {
-    let message = match result {
-        MoqResult::Success => "Success",
-        MoqResult::InvalidArgument => "Invalid argument",
-        MoqResult::NetworkError => "Network error",
-        MoqResult::TlsError => "TLS error",
-        MoqResult::DnsError => "DNS error",
-        MoqResult::GeneralError => "General error",
+    let message: &'static [u8] = match result {
+        MoqResult::Success => b"Success\0",
+        MoqResult::InvalidArgument => b"Invalid argument\0",
+        MoqResult::NetworkError => b"Network error\0",
+        MoqResult::TlsError => b"TLS error\0",
+        MoqResult::DnsError => b"DNS error\0",
+        MoqResult::GeneralError => b"General error\0",
+        MoqResult::Cancelled => b"Cancelled\0",
+        MoqResult::TimedOut => b"Timed out\0",
     };
 
     message.as_ptr() as *const c_char
@@ -1341,27 +3644,61 @@ pub extern "C" fn _moq_ffi_keep_symbols() {
         moq_init as *const (),
         moq_client_new as *const (),
         moq_client_connect as *const (),
+        moq_client_set_reconnect as *const (),
+        moq_client_on_state as *const (),
         moq_client_free as *const (),
         moq_session_free as *const (),
         moq_session_is_connected as *const (),
         moq_session_close as *const (),
+        moq_session_close_with as *const (),
+        moq_relay_broadcast as *const (),
+        moq_relay_free as *const (),
+        moq_session_set_callbacks as *const (),
+        moq_session_clear_callbacks as *const (),
+        moq_session_announced as *const (),
+        moq_session_announced_stop as *const (),
         moq_broadcast_producer_new as *const (),
         moq_broadcast_producer_create_track as *const (),
+        moq_broadcast_producer_add_fmp4_track as *const (),
+        moq_fmp4_track_push_segment as *const (),
+        moq_publish_mp4 as *const (),
+        moq_publish_pause as *const (),
+        moq_publish_stop as *const (),
+        moq_publish_free as *const (),
         moq_session_publish as *const (),
         moq_session_consume as *const (),
+        moq_session_announced_open as *const (),
+        moq_announced_next as *const (),
+        moq_announced_free as *const (),
         moq_broadcast_consumer_subscribe_track as *const (),
+        moq_session_subscribe as *const (),
+        moq_session_unsubscribe as *const (),
         moq_track_producer_create_group as *const (),
         moq_group_producer_write_frame as *const (),
         moq_group_producer_finish as *const (),
         moq_track_consumer_next_group as *const (),
+        moq_track_consumer_next_group_ex as *const (),
+        moq_track_consumer_on_group as *const (),
+        moq_track_consumer_on_group_cancel as *const (),
         moq_group_consumer_read_frame as *const (),
+        moq_group_consumer_read_frame_ex as *const (),
+        moq_group_consumer_read_frames as *const (),
+        moq_free_frames as *const (),
+        moq_group_consumer_on_frame as *const (),
+        moq_group_consumer_on_frame_cancel as *const (),
+        moq_cancel_token_new as *const (),
+        moq_cancel_token_cancel as *const (),
+        moq_cancel_token_free as *const (),
         moq_free as *const (),
         moq_broadcast_producer_free as *const (),
         moq_broadcast_consumer_free as *const (),
         moq_track_producer_free as *const (),
         moq_track_consumer_free as *const (),
         moq_group_producer_free as *const (),
+        moq_group_producer_sequence as *const (),
         moq_group_consumer_free as *const (),
+        moq_group_consumer_sequence as *const (),
+        moq_group_consumer_frame_index as *const (),
         moq_get_last_error as *const (),
         moq_result_to_string as *const (),
     ];
@@ -4,18 +4,55 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CatalogTrack {
     #[serde(rename = "trackName")]
     pub track_name: String,
     #[serde(rename = "type")]
     pub track_type: String,
     pub priority: i32,
+    /// ISOBMFF sample entry fourcc (e.g. "avc1", "mp4a"), when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    /// Bits per second, when the catalog carries one (HANG's `HangRendition.bitrate`,
+    /// or this same field in the standard catalog format)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<i64>,
+    /// Media timescale (ticks per second) from the track's `mdhd`, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timescale: Option<u32>,
+    /// Original ISOBMFF track ID from the source file's `tkhd`, when known
+    #[serde(rename = "trackId", skip_serializing_if = "Option::is_none")]
+    pub track_id: Option<u32>,
+}
+
+/// What changed about [`CatalogProcessor`]'s track set between one `process_catalog_data`
+/// call and the next, so a subscriber only has to resubscribe the tracks that actually
+/// appeared, disappeared, or had their priority/codec/bitrate change mid-stream - instead
+/// of treating every update as "tear everything down and start over".
+#[derive(Debug, Clone, PartialEq)]
+pub enum CatalogEvent {
+    TrackAdded(CatalogTrack),
+    TrackRemoved(String),
+    TrackChanged {
+        old: CatalogTrack,
+        new: CatalogTrack,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct StandardCatalog {
-    tracks: Vec<CatalogTrack>,
+pub struct StandardCatalog {
+    pub tracks: Vec<CatalogTrack>,
+}
+
+impl StandardCatalog {
+    pub fn from_tracks(tracks: Vec<CatalogTrack>) -> Self {
+        Self { tracks }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("failed to serialize catalog")
+    }
 }
 
 // HANG catalog format (used by moq-clock and some other examples)
@@ -55,113 +92,128 @@ impl CatalogProcessor {
         }
     }
 
-    pub fn process_catalog_data(&self, data: &[u8]) -> Result<()> {
+    /// Parses `data` as either catalog format, diffs the result against the
+    /// previously known track set, and returns what changed - tracks unaffected by this
+    /// update keep their existing state rather than being cleared and re-inserted.
+    pub fn process_catalog_data(&self, data: &[u8]) -> Result<Vec<CatalogEvent>> {
         let json_str = std::str::from_utf8(data).context("Invalid UTF-8 in catalog")?;
-        
-        // Try to parse as standard catalog first
-        if let Ok(catalog) = serde_json::from_str::<StandardCatalog>(json_str) {
-            self.process_standard_catalog(catalog);
-            return Ok(());
-        }
-        
-        // Try HANG format
-        if let Ok(catalog) = serde_json::from_str::<HangCatalog>(json_str) {
-            self.process_hang_catalog(catalog);
-            return Ok(());
-        }
-        
-        anyhow::bail!("Unknown catalog format");
-    }
 
-    fn process_standard_catalog(&self, catalog: StandardCatalog) {
-        let mut tracks = self.available_tracks.write();
-        tracks.clear();
-        
-        tracing::info!("Processing standard catalog with {} tracks", catalog.tracks.len());
-        
-        for track in catalog.tracks {
+        let new_tracks = if let Ok(catalog) = serde_json::from_str::<StandardCatalog>(json_str) {
             tracing::info!(
-                "Available track: {} (type: {}, priority: {})",
-                track.track_name,
-                track.track_type,
-                track.priority
+                "Processing standard catalog with {} tracks",
+                catalog.tracks.len()
+            );
+            Self::tracks_from_standard(catalog)
+        } else if let Ok(catalog) = serde_json::from_str::<HangCatalog>(json_str) {
+            tracing::info!("Processing HANG catalog format");
+            Self::tracks_from_hang(catalog)
+        } else {
+            anyhow::bail!("Unknown catalog format");
+        };
+
+        Ok(self.apply_tracks(new_tracks))
+    }
+
+    fn tracks_from_standard(catalog: StandardCatalog) -> HashMap<String, CatalogTrack> {
+        catalog
+            .tracks
+            .into_iter()
+            .map(|track| (track.track_name.clone(), track))
+            .collect()
+    }
+
+    fn tracks_from_hang(catalog: HangCatalog) -> HashMap<String, CatalogTrack> {
+        let mut tracks = HashMap::new();
+
+        let groups = catalog
+            .video
+            .iter()
+            .map(|group| ("video", group))
+            .chain(catalog.audio.iter().map(|group| ("audio", group)))
+            .chain(
+                catalog
+                    .extra
+                    .iter()
+                    .map(|(name, group)| (name.as_str(), group)),
             );
-            tracks.insert(track.track_name.clone(), track);
+
+        for (track_type, group) in groups {
+            let Some(renditions) = &group.renditions else {
+                continue;
+            };
+            let priority = group.priority.unwrap_or(50);
+
+            for (track_name, rendition) in renditions {
+                tracks.insert(
+                    track_name.clone(),
+                    CatalogTrack {
+                        track_name: track_name.clone(),
+                        track_type: track_type.to_string(),
+                        priority,
+                        codec: rendition.codec.clone(),
+                        bitrate: rendition.bitrate,
+                        timescale: None,
+                        track_id: None,
+                    },
+                );
+            }
         }
+
+        tracks
     }
 
-    fn process_hang_catalog(&self, catalog: HangCatalog) {
+    /// Replaces `available_tracks` with `new_tracks`, returning the add/remove/change
+    /// events implied by the difference. Logs each event at the same granularity the
+    /// previous clear-and-reinsert code logged whole-catalog processing at.
+    fn apply_tracks(&self, new_tracks: HashMap<String, CatalogTrack>) -> Vec<CatalogEvent> {
         let mut tracks = self.available_tracks.write();
-        tracks.clear();
-        
-        tracing::info!("Processing HANG catalog format");
-        
-        // Process video tracks
-        if let Some(video_group) = &catalog.video {
-            if let Some(renditions) = &video_group.renditions {
-                for (track_name, _rendition) in renditions {
-                    let priority = video_group.priority.unwrap_or(50);
-                    tracing::info!(
-                        "Available video track: {} (priority: {})",
-                        track_name,
-                        priority
-                    );
-                    tracks.insert(
-                        track_name.clone(),
-                        CatalogTrack {
-                            track_name: track_name.clone(),
-                            track_type: "video".to_string(),
-                            priority,
-                        },
-                    );
+        let mut events = Vec::new();
+
+        for (track_name, new_track) in &new_tracks {
+            match tracks.get(track_name) {
+                None => events.push(CatalogEvent::TrackAdded(new_track.clone())),
+                Some(old_track) if old_track != new_track => {
+                    events.push(CatalogEvent::TrackChanged {
+                        old: old_track.clone(),
+                        new: new_track.clone(),
+                    })
                 }
+                Some(_) => {}
             }
         }
-        
-        // Process audio tracks
-        if let Some(audio_group) = &catalog.audio {
-            if let Some(renditions) = &audio_group.renditions {
-                for (track_name, _rendition) in renditions {
-                    let priority = audio_group.priority.unwrap_or(50);
-                    tracing::info!(
-                        "Available audio track: {} (priority: {})",
-                        track_name,
-                        priority
-                    );
-                    tracks.insert(
-                        track_name.clone(),
-                        CatalogTrack {
-                            track_name: track_name.clone(),
-                            track_type: "audio".to_string(),
-                            priority,
-                        },
-                    );
-                }
+        for track_name in tracks.keys() {
+            if !new_tracks.contains_key(track_name) {
+                events.push(CatalogEvent::TrackRemoved(track_name.clone()));
             }
         }
-        
-        // Process other track groups
-        for (group_name, track_group) in &catalog.extra {
-            if let Some(renditions) = &track_group.renditions {
-                for (track_name, _rendition) in renditions {
-                    let priority = track_group.priority.unwrap_or(50);
+
+        for event in &events {
+            match event {
+                CatalogEvent::TrackAdded(track) => {
                     tracing::info!(
-                        "Available {} track: {} (priority: {})",
-                        group_name,
-                        track_name,
-                        priority
+                        "Catalog track added: {} (type: {}, priority: {})",
+                        track.track_name,
+                        track.track_type,
+                        track.priority
                     );
-                    tracks.insert(
-                        track_name.clone(),
-                        CatalogTrack {
-                            track_name: track_name.clone(),
-                            track_type: group_name.clone(),
-                            priority,
-                        },
+                }
+                CatalogEvent::TrackRemoved(track_name) => {
+                    tracing::info!("Catalog track removed: {}", track_name);
+                }
+                CatalogEvent::TrackChanged { new, .. } => {
+                    tracing::info!(
+                        "Catalog track changed: {} (priority: {}, codec: {:?}, bitrate: {:?})",
+                        new.track_name,
+                        new.priority,
+                        new.codec,
+                        new.bitrate
                     );
                 }
             }
         }
+
+        *tracks = new_tracks;
+        events
     }
 
     pub fn get_available_tracks(&self) -> HashMap<String, CatalogTrack> {
@@ -171,6 +223,50 @@ impl CatalogProcessor {
     pub fn is_track_available(&self, track_name: &str) -> bool {
         self.available_tracks.read().contains_key(track_name)
     }
+
+    /// Every rendition currently available for logical track `group_name` (e.g.
+    /// `"video"`/`"audio"`) - a [`CatalogTrack`]'s `track_type` doubles as its
+    /// rendition group, so no separate grouping state is needed beyond the flat
+    /// `available_tracks` map [`Self::process_catalog_data`] already maintains.
+    pub fn renditions(&self, group_name: &str) -> Vec<CatalogTrack> {
+        self.available_tracks
+            .read()
+            .values()
+            .filter(|track| track.track_type == group_name)
+            .cloned()
+            .collect()
+    }
+
+    /// Picks the best rendition of `group_name` for a `target_bitrate_kbps` budget:
+    /// the highest-bitrate rendition at or below it, or - if every rendition exceeds
+    /// the budget - the lowest-bitrate one available, so playback can still start
+    /// rather than selecting nothing. Renditions with no known bitrate are treated as
+    /// fitting any budget, since there's nothing to compare. Assumes
+    /// [`CatalogTrack::bitrate`] is in bits per second, per the HANG catalog
+    /// convention `HangRendition.bitrate` is read from.
+    pub fn select_rendition(
+        &self,
+        group_name: &str,
+        target_bitrate_kbps: i64,
+    ) -> Option<CatalogTrack> {
+        let renditions = self.renditions(group_name);
+
+        renditions
+            .iter()
+            .filter(|track| {
+                track
+                    .bitrate
+                    .map(|bps| bps / 1000 <= target_bitrate_kbps)
+                    .unwrap_or(true)
+            })
+            .max_by_key(|track| track.bitrate.unwrap_or(i64::MIN))
+            .or_else(|| {
+                renditions
+                    .iter()
+                    .min_by_key(|track| track.bitrate.unwrap_or(i64::MAX))
+            })
+            .cloned()
+    }
 }
 
 impl Default for CatalogProcessor {
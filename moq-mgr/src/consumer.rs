@@ -1,16 +1,80 @@
 use anyhow::Result;
+use parking_lot::{Condvar, Mutex};
+use std::collections::VecDeque;
 use std::sync::Arc;
-use parking_lot::Mutex;
+use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
 
-use moq_lite::{BroadcastConsumer, Track, TrackConsumer};
+use moq_lite::{BroadcastConsumer, GroupConsumer, Track, TrackConsumer};
 
 pub type DataCallback = Arc<dyn Fn(&[u8]) + Send + Sync>;
 
+/// Fired by a [`Consumer`] when its track read loop ends abnormally (a transport
+/// error rather than a clean end-of-track), so the owner can trigger a session-level
+/// reconnect instead of leaving the track silently dead.
+pub type ReconnectCallback = Arc<dyn Fn() + Send + Sync>;
+
+/// Where a [`Consumer`] should start reading a track, applied once when it first
+/// subscribes and again every time the consumer-triggered reconnect path
+/// ([`SubscriptionConfig::reconnect_callback`]) causes it to resubscribe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartPosition {
+    /// Skip the older backlog already cached at subscribe time and start from the
+    /// most recent cached group (or, if none is cached, the first group published
+    /// afterward) - the usual choice for a live viewer joining late.
+    Latest,
+    /// Start from the oldest group the broadcast still has cached - the usual choice
+    /// for a recorder that wants a complete, gap-free copy.
+    Earliest,
+    /// Skip groups until reaching the given [`moq_lite::GroupConsumer::sequence`].
+    FromGroup(u64),
+}
+
 #[derive(Clone)]
 pub struct SubscriptionConfig {
     pub moq_track_name: String,
     pub data_callback: DataCallback,
+    /// Invoked once if the track's read loop fails (e.g. the underlying group/frame
+    /// read errors out). Not invoked when the track simply ends cleanly.
+    pub reconnect_callback: Option<ReconnectCallback>,
+    /// Where to start reading the track. Defaults to [`StartPosition::Earliest`],
+    /// matching the behavior before this field existed.
+    pub start_position: StartPosition,
+    /// Hint passed through to the underlying [`Track::priority`] so the session can
+    /// favor some tracks over others when bandwidth is constrained. Lower values are
+    /// higher priority, matching [`Track::priority`]'s own convention.
+    pub priority: Option<u8>,
+}
+
+impl Default for StartPosition {
+    fn default() -> Self {
+        StartPosition::Earliest
+    }
+}
+
+/// A compiled pattern for [`crate::Session::add_subscription_pattern`], matched
+/// against track names as the catalog updates. A trailing `*` makes it a prefix
+/// match (e.g. `"video/*"` matches `"video/720p"`); without one it's an exact match.
+#[derive(Clone)]
+pub enum TrackPattern {
+    Exact(String),
+    Prefix(String),
+}
+
+impl TrackPattern {
+    pub fn parse(pattern: &str) -> Self {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => TrackPattern::Prefix(prefix.to_string()),
+            None => TrackPattern::Exact(pattern.to_string()),
+        }
+    }
+
+    pub fn matches(&self, track_name: &str) -> bool {
+        match self {
+            TrackPattern::Exact(name) => track_name == name,
+            TrackPattern::Prefix(prefix) => track_name.starts_with(prefix.as_str()),
+        }
+    }
 }
 
 pub struct Consumer {
@@ -36,7 +100,7 @@ impl Consumer {
 
         // Automatically start the consumer
         consumer.start_internal()?;
-        
+
         Ok(consumer)
     }
 
@@ -49,13 +113,11 @@ impl Consumer {
 
         let track = Track {
             name: self.config.moq_track_name.clone(),
-            priority: 0,
+            priority: self.config.priority.unwrap_or(0),
         };
 
-        let track_consumer = self
-            .broadcast_consumer
-            .subscribe_track(&track);
-        
+        let track_consumer = self.broadcast_consumer.subscribe_track(&track);
+
         *self.track_consumer.lock() = Some(track_consumer);
 
         // Spawn worker thread
@@ -63,15 +125,25 @@ impl Consumer {
         let running = self.running.clone();
         let callback = self.config.data_callback.clone();
         let track_name = self.config.moq_track_name.clone();
+        let reconnect_callback = self.config.reconnect_callback.clone();
+        let start_position = self.config.start_position;
 
         let handle = tokio::spawn(async move {
-            Self::consumer_loop(track_consumer, running, callback, track_name).await;
+            Self::consumer_loop(
+                track_consumer,
+                running,
+                callback,
+                track_name,
+                reconnect_callback,
+                start_position,
+            )
+            .await;
         });
 
         *self.worker_handle.lock() = Some(handle);
-        
+
         tracing::info!("Consumer started for track: {}", self.config.moq_track_name);
-        
+
         Ok(())
     }
 
@@ -80,15 +152,33 @@ impl Consumer {
         running: Arc<Mutex<bool>>,
         callback: DataCallback,
         track_name: String,
+        reconnect_callback: Option<ReconnectCallback>,
+        start_position: StartPosition,
     ) {
-        while *running.lock() {
+        // Applied once, to the first group fetched; every group after that is
+        // delivered as it arrives regardless of `start_position`.
+        let mut pending_group = None;
+        let mut sought = false;
+
+        'read: while *running.lock() {
             let consumer_opt = {
                 let mut guard = track_consumer.lock();
                 guard.take()
             };
-            
+
             if let Some(mut consumer) = consumer_opt {
-                match consumer.next_group().await {
+                if !sought {
+                    sought = true;
+                    pending_group =
+                        Self::seek_start_position(&mut consumer, start_position, &track_name).await;
+                }
+
+                let next_group = match pending_group.take() {
+                    Some(group) => Ok(Some(group)),
+                    None => consumer.next_group().await,
+                };
+
+                match next_group {
                     Ok(Some(mut group)) => {
                         // Read all frames from this group
                         loop {
@@ -112,11 +202,14 @@ impl Consumer {
                                         track_name,
                                         e
                                     );
-                                    break;
+                                    if let Some(callback) = &reconnect_callback {
+                                        callback();
+                                    }
+                                    break 'read;
                                 }
                             }
                         }
-                        
+
                         // Put the consumer back
                         *track_consumer.lock() = Some(consumer);
                     }
@@ -126,6 +219,9 @@ impl Consumer {
                     }
                     Err(e) => {
                         tracing::error!("Error getting next group for track {}: {}", track_name, e);
+                        if let Some(callback) = &reconnect_callback {
+                            callback();
+                        }
                         break;
                     }
                 }
@@ -133,20 +229,68 @@ impl Consumer {
                 break;
             }
         }
-        
+
         tracing::info!("Consumer loop ended for track: {}", track_name);
     }
 
+    /// Advances `consumer` to honor `position`, returning the first group that
+    /// should actually be delivered (if any was fetched in the process), so the
+    /// caller doesn't re-request a group that was already consumed here.
+    async fn seek_start_position(
+        consumer: &mut TrackConsumer,
+        position: StartPosition,
+        track_name: &str,
+    ) -> Option<GroupConsumer> {
+        match position {
+            StartPosition::Earliest => None,
+            StartPosition::Latest => {
+                // Drain any backlog already cached at subscribe time, keeping only
+                // the most recent group, without blocking on groups that haven't
+                // arrived yet.
+                let mut latest = None;
+                while let Ok(Ok(Some(group))) =
+                    tokio::time::timeout(Duration::from_millis(0), consumer.next_group()).await
+                {
+                    latest = Some(group);
+                }
+                latest
+            }
+            StartPosition::FromGroup(target) => loop {
+                match consumer.next_group().await {
+                    Ok(Some(group)) if group.sequence < target => continue,
+                    Ok(Some(group)) => return Some(group),
+                    Ok(None) => {
+                        tracing::info!(
+                            "Track {} ended before reaching group {}",
+                            track_name,
+                            target
+                        );
+                        return None;
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            "Error seeking track {} to group {}: {}",
+                            track_name,
+                            target,
+                            e
+                        );
+                        return None;
+                    }
+                }
+            },
+        }
+    }
+
     pub fn stop(&self) {
         *self.running.lock() = false;
-        
+
         // Take the handle and abort it
         if let Some(handle) = self.worker_handle.lock().take() {
             handle.abort();
         }
-        
+
         *self.track_consumer.lock() = None;
-        
+
         tracing::info!("Consumer stopped for track: {}", self.config.moq_track_name);
     }
 
@@ -164,3 +308,172 @@ impl Drop for Consumer {
         self.stop();
     }
 }
+
+/// What happens to a [`BufferedSubscription`]'s ring buffer when it's full and a new
+/// frame arrives from the subscription's worker task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Evict the oldest buffered frame to make room for the new one.
+    DropOldest,
+    /// Discard the new frame, keeping everything already buffered.
+    DropNewest,
+}
+
+/// Outcome of [`BufferedSubscription::poll`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollOutcome {
+    /// A frame is available, with no drops since the previous poll.
+    Frame,
+    /// A frame is available, but the ring buffer overflowed at least once since the
+    /// previous poll, so some frames were dropped before this one.
+    FrameOverflowed,
+    /// No frame arrived within the requested timeout.
+    Timeout,
+    /// The buffer was closed by [`BufferedSubscription::close`] (or its drop) and has
+    /// been drained. The end of the track does not close the buffer; polls after the
+    /// track ends return [`Self::Timeout`].
+    Closed,
+}
+
+struct RingBuffer {
+    frames: VecDeque<Vec<u8>>,
+    capacity: usize,
+    policy: OverflowPolicy,
+    overflowed: bool,
+    closed: bool,
+}
+
+impl RingBuffer {
+    fn push(&mut self, frame: Vec<u8>) {
+        if self.frames.len() >= self.capacity {
+            self.overflowed = true;
+            match self.policy {
+                OverflowPolicy::DropOldest => {
+                    self.frames.pop_front();
+                    self.frames.push_back(frame);
+                }
+                OverflowPolicy::DropNewest => {}
+            }
+        } else {
+            self.frames.push_back(frame);
+        }
+    }
+}
+
+/// A pull-based alternative to [`SubscriptionConfig::data_callback`]: incoming frames
+/// are pushed into a bounded ring buffer from the subscription's worker task, and
+/// [`Self::poll`] lets a caller pull them on its own thread and schedule, instead of
+/// being invoked from whatever thread tokio happens to run the callback on.
+pub struct BufferedSubscription {
+    _consumer: Consumer,
+    ring: Arc<(Mutex<RingBuffer>, Condvar)>,
+    current_frame: Mutex<Option<Vec<u8>>>,
+}
+
+impl BufferedSubscription {
+    pub fn new(
+        broadcast_consumer: Arc<BroadcastConsumer>,
+        track_name: &str,
+        capacity: usize,
+        policy: OverflowPolicy,
+    ) -> Result<Self> {
+        let ring = Arc::new((
+            Mutex::new(RingBuffer {
+                frames: VecDeque::with_capacity(capacity),
+                capacity,
+                policy,
+                overflowed: false,
+                closed: false,
+            }),
+            Condvar::new(),
+        ));
+
+        let push_ring = ring.clone();
+        let data_callback: DataCallback = Arc::new(move |data: &[u8]| {
+            let (lock, cvar) = &*push_ring;
+            lock.lock().push(data.to_vec());
+            cvar.notify_one();
+        });
+
+        let consumer = Consumer::new(
+            broadcast_consumer,
+            SubscriptionConfig {
+                moq_track_name: track_name.to_string(),
+                data_callback,
+                reconnect_callback: None,
+                start_position: StartPosition::default(),
+                priority: None,
+            },
+        )?;
+
+        Ok(Self {
+            _consumer: consumer,
+            ring,
+            current_frame: Mutex::new(None),
+        })
+    }
+
+    /// Waits up to `max_wait` for the next frame. On [`PollOutcome::Frame`] or
+    /// [`PollOutcome::FrameOverflowed`] the frame is available via
+    /// [`Self::current_frame_ptr`] until the next call to `poll` or
+    /// [`Self::free_current_frame`].
+    pub fn poll(&self, max_wait: Duration) -> PollOutcome {
+        let (lock, cvar) = &*self.ring;
+        let mut guard = lock.lock();
+        let deadline = Instant::now() + max_wait;
+
+        loop {
+            if let Some(frame) = guard.frames.pop_front() {
+                let overflowed = std::mem::replace(&mut guard.overflowed, false);
+                drop(guard);
+                *self.current_frame.lock() = Some(frame);
+                return if overflowed {
+                    PollOutcome::FrameOverflowed
+                } else {
+                    PollOutcome::Frame
+                };
+            }
+            if guard.closed {
+                return PollOutcome::Closed;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return PollOutcome::Timeout;
+            }
+            cvar.wait_for(&mut guard, remaining);
+        }
+    }
+
+    /// Raw pointer and length of the frame most recently returned by [`Self::poll`],
+    /// or `(null, 0)` if there isn't one (e.g. nothing has been polled yet, or the
+    /// last poll didn't return a frame). Valid until the next `poll()` call or
+    /// [`Self::free_current_frame`] - callers that need the data past that point must
+    /// copy it out first.
+    pub fn current_frame_ptr(&self) -> (*const u8, usize) {
+        match self.current_frame.lock().as_ref() {
+            Some(frame) => (frame.as_ptr(), frame.len()),
+            None => (std::ptr::null(), 0),
+        }
+    }
+
+    /// Releases the frame held for [`Self::current_frame_ptr`] early, instead of
+    /// waiting for it to be replaced by the next `poll()`.
+    pub fn free_current_frame(&self) {
+        *self.current_frame.lock() = None;
+    }
+
+    /// Marks the buffer closed, so a pending or future [`Self::poll`] returns
+    /// [`PollOutcome::Closed`] once everything already buffered has been drained.
+    pub fn close(&self) {
+        let (lock, cvar) = &*self.ring;
+        lock.lock().closed = true;
+        cvar.notify_one();
+    }
+}
+
+impl Drop for BufferedSubscription {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
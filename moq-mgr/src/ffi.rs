@@ -2,7 +2,73 @@ use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_void};
 use std::sync::Arc;
 
-use crate::{Session, SessionConfig, SessionMode, BroadcastConfig, SubscriptionConfig};
+use crate::{
+    BroadcastConfig, BufferedSubscription, ConnectionState, OverflowPolicy, PollOutcome,
+    ReconnectPolicy, Session, SessionConfig, SessionMode, StartPosition, SubscriptionConfig,
+    SubscriptionState,
+};
+
+/// Exponential-backoff-with-full-jitter policy for a session's reconnect loop; see
+/// [`ReconnectPolicy`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct MoqMgrReconnectPolicy {
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub multiplier: f64,
+    /// `0` means retry forever.
+    pub max_retries: u32,
+    pub jitter: i32,
+}
+
+impl From<MoqMgrReconnectPolicy> for ReconnectPolicy {
+    fn from(policy: MoqMgrReconnectPolicy) -> Self {
+        Self {
+            initial_backoff_ms: policy.initial_backoff_ms,
+            max_backoff_ms: policy.max_backoff_ms,
+            multiplier: policy.multiplier,
+            max_retries: policy.max_retries,
+            jitter: policy.jitter != 0,
+        }
+    }
+}
+
+/// Connection lifecycle state delivered to a callback registered via
+/// `moq_mgr_session_set_connection_callback`; see [`ConnectionState`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoqMgrConnectionState {
+    Connecting = 0,
+    Connected = 1,
+    Reconnecting = 2,
+    Disconnected = 3,
+    Failed = 4,
+}
+
+impl From<ConnectionState> for MoqMgrConnectionState {
+    fn from(state: ConnectionState) -> Self {
+        match state {
+            ConnectionState::Connecting => MoqMgrConnectionState::Connecting,
+            ConnectionState::Connected => MoqMgrConnectionState::Connected,
+            ConnectionState::Reconnecting => MoqMgrConnectionState::Reconnecting,
+            ConnectionState::Disconnected => MoqMgrConnectionState::Disconnected,
+            ConnectionState::Failed => MoqMgrConnectionState::Failed,
+        }
+    }
+}
+
+/// Connection callback function type
+/// Parameters: state, reconnect attempt count (0 outside of reconnection), user_data
+pub type MoqMgrConnectionCallback = extern "C" fn(MoqMgrConnectionState, u32, *mut c_void);
+
+/// Opaque handle to a track added after `moq_mgr_session_start` via
+/// `moq_mgr_session_subscribe`/`moq_mgr_session_announce`, passed back to
+/// `moq_mgr_session_unsubscribe`/`moq_mgr_session_unannounce` to cancel it.
+pub type MoqMgrTrackHandle = u64;
+
+/// Sentinel returned by `moq_mgr_session_subscribe`/`moq_mgr_session_announce` on
+/// failure (e.g. the session isn't connected yet); no real handle is ever `0`.
+pub const MOQ_MGR_INVALID_TRACK_HANDLE: MoqMgrTrackHandle = 0;
 
 /// Result codes for FFI functions
 #[repr(C)]
@@ -15,6 +81,37 @@ pub enum MoqMgrResult {
     ErrorInternal = -4,
 }
 
+/// Category of the most recent error recorded via [`moq_mgr_get_last_error`],
+/// letting a C caller disambiguate a `null`/`Error*` return beyond the coarse
+/// [`MoqMgrResult`] code.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoqMgrErrorCategory {
+    /// No error has been recorded on this thread since the last `moq_mgr_clear_last_error`.
+    None = 0,
+    InvalidParameter = 1,
+    InvalidUrl = 2,
+    InvalidBindAddress = 3,
+    InvalidSessionMode = 4,
+    RuntimeInitFailed = 5,
+    ConnectionFailed = 6,
+    Internal = 7,
+}
+
+thread_local! {
+    static LAST_ERROR: std::cell::RefCell<(MoqMgrErrorCategory, Option<CString>)> =
+        std::cell::RefCell::new((MoqMgrErrorCategory::None, None));
+}
+
+/// Records `message` as the calling thread's last error, retrievable via
+/// [`moq_mgr_get_last_error`]/[`moq_mgr_get_last_error_category`] until the next FFI
+/// call on this thread overwrites it or [`moq_mgr_clear_last_error`] is called.
+fn set_last_error(category: MoqMgrErrorCategory, message: impl Into<Vec<u8>>) {
+    if let Ok(c_message) = CString::new(message) {
+        LAST_ERROR.with(|cell| *cell.borrow_mut() = (category, Some(c_message)));
+    }
+}
+
 /// Opaque handle to a MoQ Manager session
 pub struct MoqMgrSession {
     session: Arc<Session>,
@@ -37,8 +134,49 @@ pub type MoqMgrDataCallback = extern "C" fn(*const u8, usize, *mut c_void);
 /// Parameters: level (0=ERROR, 1=WARN, 2=INFO, 3=DEBUG, 4=TRACE), message, user_data
 pub type MoqMgrLogCallback = extern "C" fn(i32, *const c_char, *mut c_void);
 
+/// Structured log callback function type, registered via
+/// `moq_mgr_init_with_structured_logging`.
+///
+/// Parameters: level (0=ERROR, 1=WARN, 2=INFO, 3=DEBUG, 4=TRACE), target (the event's
+/// `tracing` target, e.g. `moq_mgr::session`), message, fields_json (a JSON object of
+/// the event's non-message fields plus a `spans` array naming the active span scope,
+/// outermost first), user_data.
+pub type MoqMgrStructuredLogCallback =
+    extern "C" fn(i32, *const c_char, *const c_char, *const c_char, *mut c_void);
+
+/// Announce callback function type for `SessionMode::DiscoverOnly` sessions
+/// Parameters: broadcast path (null-terminated C string), added (1=appeared, 0=withdrawn), user_data
+pub type MoqMgrAnnounceCallback = extern "C" fn(*const c_char, i32, *mut c_void);
+
+/// State of a track added via `moq_mgr_session_add_subscription`; see
+/// [`SubscriptionState`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoqMgrSubscriptionState {
+    Active = 0,
+    Pending = 1,
+    NotInCatalog = 2,
+}
+
+impl From<SubscriptionState> for MoqMgrSubscriptionState {
+    fn from(state: SubscriptionState) -> Self {
+        match state {
+            SubscriptionState::Active => MoqMgrSubscriptionState::Active,
+            SubscriptionState::Pending => MoqMgrSubscriptionState::Pending,
+            SubscriptionState::NotInCatalog => MoqMgrSubscriptionState::NotInCatalog,
+        }
+    }
+}
+
+/// Subscription status callback function type
+/// Parameters: track name (null-terminated C string), new state, user_data
+pub type MoqMgrSubscriptionStatusCallback =
+    extern "C" fn(*const c_char, MoqMgrSubscriptionState, *mut c_void);
+
 // Global storage for the log callback
 static mut LOG_CALLBACK: Option<(MoqMgrLogCallback, *mut c_void)> = None;
+// Global storage for the structured log callback; see `moq_mgr_init_with_structured_logging`.
+static mut STRUCTURED_LOG_CALLBACK: Option<(MoqMgrStructuredLogCallback, *mut c_void)> = None;
 static LOG_CALLBACK_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
 
 /// Initialize the MoQ Manager library
@@ -52,7 +190,7 @@ pub extern "C" fn moq_mgr_init() -> MoqMgrResult {
 
 /// Initialize the MoQ Manager library with custom log callback
 /// This should be called once at startup if you want to receive log messages
-/// 
+///
 /// Parameters:
 /// - log_callback: Function to receive log messages
 /// - user_data: User data pointer passed to log callback
@@ -64,42 +202,78 @@ pub extern "C" fn moq_mgr_init_with_logging(
     include_moq_libs: i32,
 ) -> MoqMgrResult {
     let _lock = LOG_CALLBACK_LOCK.lock().unwrap();
-    
+
     // Store the callback globally
     unsafe {
         LOG_CALLBACK = Some((log_callback, user_data));
     }
-    
+
     // Initialize tracing with our custom subscriber
     use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-    
+
     let callback_layer = CallbackLayer::new();
-    
+
     // Create filter based on include_moq_libs flag
     let filter = if include_moq_libs != 0 {
         // Include all logs
-        EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| EnvFilter::new("debug"))
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"))
     } else {
         // Only include moq_mgr logs
-        EnvFilter::try_from_default_env()
-            .unwrap_or_else(|_| EnvFilter::new("moq_mgr=debug"))
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("moq_mgr=debug"))
+    };
+
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(callback_layer)
+        .try_init();
+
+    MoqMgrResult::Success
+}
+
+/// Initialize the MoQ Manager library with a structured log callback
+/// This should be called once at startup if you want to receive logs with their
+/// target, fields, and span context intact instead of a pre-flattened message.
+///
+/// Parameters:
+/// - log_callback: Function to receive structured log events; see [`MoqMgrStructuredLogCallback`]
+/// - user_data: User data pointer passed to the log callback
+/// - include_moq_libs: If true, include logs from moq-lite/moq-native; if false, only moq-mgr logs
+#[no_mangle]
+pub extern "C" fn moq_mgr_init_with_structured_logging(
+    log_callback: MoqMgrStructuredLogCallback,
+    user_data: *mut c_void,
+    include_moq_libs: i32,
+) -> MoqMgrResult {
+    let _lock = LOG_CALLBACK_LOCK.lock().unwrap();
+
+    unsafe {
+        STRUCTURED_LOG_CALLBACK = Some((log_callback, user_data));
+    }
+
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+    let callback_layer = CallbackLayer::new();
+
+    let filter = if include_moq_libs != 0 {
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"))
+    } else {
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("moq_mgr=debug"))
     };
-    
+
     let _ = tracing_subscriber::registry()
         .with(filter)
         .with(callback_layer)
         .try_init();
-    
+
     MoqMgrResult::Success
 }
 
 /// Create a new MoQ Manager session
-/// 
+///
 /// Parameters:
 /// - server_url: The MoQ server URL (e.g., "https://relay.moq.example.com:4433")
 /// - namespace: The broadcast namespace to use
-/// - mode: Session mode (0=PublishOnly, 1=SubscribeOnly)
+/// - mode: Session mode (0=PublishOnly, 1=SubscribeOnly, 2=DiscoverOnly)
 /// - reconnect: Whether to automatically reconnect on failure (0=false, 1=true)
 ///
 /// Returns: Pointer to MoqMgrSession or null on error
@@ -113,12 +287,41 @@ pub extern "C" fn moq_mgr_session_create(
     moq_mgr_session_create_with_bind(server_url, namespace, mode, reconnect, std::ptr::null())
 }
 
+/// Create a new MoQ Manager session with an explicit reconnect backoff policy; see
+/// [`MoqMgrReconnectPolicy`]. `policy` may be null to use [`ReconnectPolicy::default`].
+///
+/// Parameters are otherwise identical to `moq_mgr_session_create_with_bind`.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_create_with_reconnect_policy(
+    server_url: *const c_char,
+    namespace: *const c_char,
+    mode: i32,
+    reconnect: i32,
+    bind_addr: *const c_char,
+    policy: *const MoqMgrReconnectPolicy,
+) -> *mut MoqMgrSession {
+    let reconnect_policy = if policy.is_null() {
+        ReconnectPolicy::default()
+    } else {
+        unsafe { *policy }.into()
+    };
+
+    moq_mgr_session_create_with_bind_and_policy(
+        server_url,
+        namespace,
+        mode,
+        reconnect,
+        bind_addr,
+        reconnect_policy,
+    )
+}
+
 /// Create a new MoQ Manager session with custom bind address
-/// 
+///
 /// Parameters:
 /// - server_url: The MoQ server URL (e.g., "https://relay.moq.example.com:4433")
 /// - namespace: The broadcast namespace to use
-/// - mode: Session mode (0=PublishOnly, 1=SubscribeOnly)
+/// - mode: Session mode (0=PublishOnly, 1=SubscribeOnly, 2=DiscoverOnly)
 /// - reconnect: Whether to automatically reconnect on failure (0=false, 1=true)
 /// - bind_addr: Optional bind address (e.g., "0.0.0.0:0" for IPv4, null for default)
 ///
@@ -130,65 +333,133 @@ pub extern "C" fn moq_mgr_session_create_with_bind(
     mode: i32,
     reconnect: i32,
     bind_addr: *const c_char,
+) -> *mut MoqMgrSession {
+    moq_mgr_session_create_with_bind_and_policy(
+        server_url,
+        namespace,
+        mode,
+        reconnect,
+        bind_addr,
+        ReconnectPolicy::default(),
+    )
+}
+
+fn moq_mgr_session_create_with_bind_and_policy(
+    server_url: *const c_char,
+    namespace: *const c_char,
+    mode: i32,
+    reconnect: i32,
+    bind_addr: *const c_char,
+    reconnect_policy: ReconnectPolicy,
 ) -> *mut MoqMgrSession {
     if server_url.is_null() || namespace.is_null() {
+        set_last_error(
+            MoqMgrErrorCategory::InvalidParameter,
+            "server_url and namespace must not be null",
+        );
         return std::ptr::null_mut();
     }
 
     let server_url_str = unsafe {
         match CStr::from_ptr(server_url).to_str() {
             Ok(s) => s,
-            Err(_) => return std::ptr::null_mut(),
+            Err(_) => {
+                set_last_error(
+                    MoqMgrErrorCategory::InvalidParameter,
+                    "server_url is not valid UTF-8",
+                );
+                return std::ptr::null_mut();
+            }
         }
     };
 
     let namespace_str = unsafe {
         match CStr::from_ptr(namespace).to_str() {
             Ok(s) => s,
-            Err(_) => return std::ptr::null_mut(),
+            Err(_) => {
+                set_last_error(
+                    MoqMgrErrorCategory::InvalidParameter,
+                    "namespace is not valid UTF-8",
+                );
+                return std::ptr::null_mut();
+            }
         }
     };
 
     let url = match server_url_str.parse() {
         Ok(u) => u,
-        Err(_) => return std::ptr::null_mut(),
+        Err(e) => {
+            set_last_error(
+                MoqMgrErrorCategory::InvalidUrl,
+                format!("failed to parse server_url '{}': {}", server_url_str, e),
+            );
+            return std::ptr::null_mut();
+        }
     };
 
     let session_mode = match mode {
         0 => SessionMode::PublishOnly,
         1 => SessionMode::SubscribeOnly,
-        _ => return std::ptr::null_mut(),
+        2 => SessionMode::DiscoverOnly,
+        _ => {
+            set_last_error(
+                MoqMgrErrorCategory::InvalidSessionMode,
+                format!("unknown session mode {}", mode),
+            );
+            return std::ptr::null_mut();
+        }
     };
 
     let mut client_config = moq_native::ClientConfig::default();
-    
+
     // Parse bind address if provided
     if !bind_addr.is_null() {
         let bind_addr_str = unsafe {
             match CStr::from_ptr(bind_addr).to_str() {
                 Ok(s) => s,
-                Err(_) => return std::ptr::null_mut(),
+                Err(_) => {
+                    set_last_error(
+                        MoqMgrErrorCategory::InvalidBindAddress,
+                        "bind_addr is not valid UTF-8",
+                    );
+                    return std::ptr::null_mut();
+                }
             }
         };
-        
+
         match bind_addr_str.parse() {
             Ok(addr) => client_config.bind = addr,
-            Err(_) => return std::ptr::null_mut(),
+            Err(e) => {
+                set_last_error(
+                    MoqMgrErrorCategory::InvalidBindAddress,
+                    format!("failed to parse bind_addr '{}': {}", bind_addr_str, e),
+                );
+                return std::ptr::null_mut();
+            }
         }
     }
 
     let config = SessionConfig {
         moq_server_url: url,
         moq_namespace: namespace_str.to_string(),
+        subscribe_namespace: None,
         reconnect_on_failure: reconnect != 0,
+        reconnect_policy,
         client_config,
+        subscription_grace: None,
     };
 
     let session = Session::new(config, session_mode);
-    
+
     let runtime = match tokio::runtime::Runtime::new() {
         Ok(rt) => Arc::new(rt),
-        Err(_) => return std::ptr::null_mut(),
+        Err(e) => {
+            set_last_error(
+                MoqMgrErrorCategory::RuntimeInitFailed,
+                format!("failed to initialize tokio runtime: {}", e),
+            );
+            return std::ptr::null_mut();
+        }
     };
 
     Box::into_raw(Box::new(MoqMgrSession {
@@ -210,7 +481,7 @@ pub extern "C" fn moq_mgr_session_set_error_callback(
 
     let session = unsafe { &*session };
     let user_data_ptr = user_data as usize;
-    
+
     session.session.set_error_callback(move |msg: &str| {
         let c_msg = match CString::new(msg) {
             Ok(s) => s,
@@ -235,7 +506,7 @@ pub extern "C" fn moq_mgr_session_set_status_callback(
 
     let session = unsafe { &*session };
     let user_data_ptr = user_data as usize;
-    
+
     session.session.set_status_callback(move |msg: &str| {
         let c_msg = match CString::new(msg) {
             Ok(s) => s,
@@ -247,6 +518,115 @@ pub extern "C" fn moq_mgr_session_set_status_callback(
     MoqMgrResult::Success
 }
 
+/// Set connection lifecycle callback for the session; see [`MoqMgrConnectionState`].
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_set_connection_callback(
+    session: *mut MoqMgrSession,
+    callback: MoqMgrConnectionCallback,
+    user_data: *mut c_void,
+) -> MoqMgrResult {
+    if session.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+    let user_data_ptr = user_data as usize;
+
+    session
+        .session
+        .set_connection_callback(move |state: ConnectionState, attempt: u32| {
+            callback(state.into(), attempt, user_data_ptr as *mut c_void);
+        });
+
+    MoqMgrResult::Success
+}
+
+/// Set announce callback for the session; only fires for `SessionMode::DiscoverOnly`.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_set_announce_callback(
+    session: *mut MoqMgrSession,
+    callback: MoqMgrAnnounceCallback,
+    user_data: *mut c_void,
+) -> MoqMgrResult {
+    if session.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+    let user_data_ptr = user_data as usize;
+
+    session
+        .session
+        .set_announce_callback(move |path: &str, added: bool| {
+            let c_path = match CString::new(path) {
+                Ok(s) => s,
+                Err(_) => return,
+            };
+            callback(c_path.as_ptr(), added as i32, user_data_ptr as *mut c_void);
+        });
+
+    MoqMgrResult::Success
+}
+
+/// Enable or disable the catalog-activity heartbeat; see [`Session::set_keepalive`].
+/// Passing `0` for either `interval_ms` or `timeout_ms` disables the keepalive.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_set_keepalive(
+    session: *mut MoqMgrSession,
+    interval_ms: u64,
+    timeout_ms: u64,
+) -> MoqMgrResult {
+    if session.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+
+    let (interval, timeout) = if interval_ms == 0 || timeout_ms == 0 {
+        (None, None)
+    } else {
+        (
+            Some(std::time::Duration::from_millis(interval_ms)),
+            Some(std::time::Duration::from_millis(timeout_ms)),
+        )
+    };
+
+    session.session.set_keepalive(interval, timeout);
+
+    MoqMgrResult::Success
+}
+
+/// Set the subscription status callback; see [`Session::set_subscription_status_callback`].
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_set_subscription_status_callback(
+    session: *mut MoqMgrSession,
+    callback: MoqMgrSubscriptionStatusCallback,
+    user_data: *mut c_void,
+) -> MoqMgrResult {
+    if session.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+    let user_data_ptr = user_data as usize;
+
+    session
+        .session
+        .set_subscription_status_callback(move |track_name: &str, state: SubscriptionState| {
+            let c_track_name = match CString::new(track_name) {
+                Ok(s) => s,
+                Err(_) => return,
+            };
+            callback(
+                c_track_name.as_ptr(),
+                state.into(),
+                user_data_ptr as *mut c_void,
+            );
+        });
+
+    MoqMgrResult::Success
+}
+
 /// Add a subscription to the session (for consumer mode)
 /// Must be called before moq_mgr_session_start
 #[no_mangle]
@@ -261,7 +641,7 @@ pub extern "C" fn moq_mgr_session_add_subscription(
     }
 
     let session = unsafe { &*session };
-    
+
     let track_name_str = unsafe {
         match CStr::from_ptr(track_name).to_str() {
             Ok(s) => s.to_string(),
@@ -270,7 +650,7 @@ pub extern "C" fn moq_mgr_session_add_subscription(
     };
 
     let user_data_ptr = user_data as usize;
-    
+
     let data_callback = Arc::new(move |data: &[u8]| {
         callback(data.as_ptr(), data.len(), user_data_ptr as *mut c_void);
     });
@@ -279,12 +659,49 @@ pub extern "C" fn moq_mgr_session_add_subscription(
         moq_track_name: track_name_str,
         data_callback,
         reconnect_callback: None, // FFI layer doesn't provide reconnect callbacks - session handles it
+        start_position: StartPosition::default(),
+        priority: None,
     };
 
     session.session.add_subscription(subscription);
     MoqMgrResult::Success
 }
 
+/// Subscribe to every track matching `pattern` (a trailing `*` makes it a prefix
+/// match, e.g. `"video/*"`); see [`Session::add_subscription_pattern`]. Must be
+/// called before `moq_mgr_session_start`.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_add_subscription_pattern(
+    session: *mut MoqMgrSession,
+    pattern: *const c_char,
+    callback: MoqMgrDataCallback,
+    user_data: *mut c_void,
+) -> MoqMgrResult {
+    if session.is_null() || pattern.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+
+    let pattern_str = unsafe {
+        match CStr::from_ptr(pattern).to_str() {
+            Ok(s) => s,
+            Err(_) => return MoqMgrResult::ErrorInvalidParameter,
+        }
+    };
+
+    let user_data_ptr = user_data as usize;
+
+    let data_callback = Arc::new(move |data: &[u8]| {
+        callback(data.as_ptr(), data.len(), user_data_ptr as *mut c_void);
+    });
+
+    session
+        .session
+        .add_subscription_pattern(pattern_str, data_callback);
+    MoqMgrResult::Success
+}
+
 /// Add a broadcast to the session (for producer mode)
 /// Must be called before moq_mgr_session_start
 #[no_mangle]
@@ -298,7 +715,7 @@ pub extern "C" fn moq_mgr_session_add_broadcast(
     }
 
     let session = unsafe { &*session };
-    
+
     let track_name_str = unsafe {
         match CStr::from_ptr(track_name).to_str() {
             Ok(s) => s.to_string(),
@@ -315,6 +732,255 @@ pub extern "C" fn moq_mgr_session_add_broadcast(
     MoqMgrResult::Success
 }
 
+/// Subscribe to a track on an already-started session (unlike
+/// `moq_mgr_session_add_subscription`, this works after `moq_mgr_session_start`).
+///
+/// Returns a handle identifying the subscription, or `MOQ_MGR_INVALID_TRACK_HANDLE` if
+/// the session isn't connected yet or the track name isn't valid UTF-8.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_subscribe(
+    session: *mut MoqMgrSession,
+    track_name: *const c_char,
+    callback: MoqMgrDataCallback,
+    user_data: *mut c_void,
+) -> MoqMgrTrackHandle {
+    if session.is_null() || track_name.is_null() {
+        return MOQ_MGR_INVALID_TRACK_HANDLE;
+    }
+
+    let session = unsafe { &*session };
+
+    let track_name_str = unsafe {
+        match CStr::from_ptr(track_name).to_str() {
+            Ok(s) => s,
+            Err(_) => return MOQ_MGR_INVALID_TRACK_HANDLE,
+        }
+    };
+
+    let user_data_ptr = user_data as usize;
+    let data_callback = Arc::new(move |data: &[u8]| {
+        callback(data.as_ptr(), data.len(), user_data_ptr as *mut c_void);
+    });
+
+    match session.session.subscribe(track_name_str, data_callback) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::error!("moq_mgr_session_subscribe failed: {}", e);
+            MOQ_MGR_INVALID_TRACK_HANDLE
+        }
+    }
+}
+
+/// Cancel a subscription returned by `moq_mgr_session_subscribe`.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_unsubscribe(
+    session: *mut MoqMgrSession,
+    handle: MoqMgrTrackHandle,
+) -> MoqMgrResult {
+    if session.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+    if session.session.unsubscribe(handle) {
+        MoqMgrResult::Success
+    } else {
+        MoqMgrResult::ErrorInvalidParameter
+    }
+}
+
+/// Publish a new track on an already-started session (unlike
+/// `moq_mgr_session_add_broadcast`, this works after `moq_mgr_session_start`).
+///
+/// Returns a handle identifying the announcement, or `MOQ_MGR_INVALID_TRACK_HANDLE` if
+/// the session isn't connected yet or the track name isn't valid UTF-8.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_announce(
+    session: *mut MoqMgrSession,
+    track_name: *const c_char,
+    priority: u32,
+) -> MoqMgrTrackHandle {
+    if session.is_null() || track_name.is_null() {
+        return MOQ_MGR_INVALID_TRACK_HANDLE;
+    }
+
+    let session = unsafe { &*session };
+
+    let track_name_str = unsafe {
+        match CStr::from_ptr(track_name).to_str() {
+            Ok(s) => s,
+            Err(_) => return MOQ_MGR_INVALID_TRACK_HANDLE,
+        }
+    };
+
+    match session.session.announce(track_name_str, priority) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::error!("moq_mgr_session_announce failed: {}", e);
+            MOQ_MGR_INVALID_TRACK_HANDLE
+        }
+    }
+}
+
+/// End an announcement returned by `moq_mgr_session_announce`.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_unannounce(
+    session: *mut MoqMgrSession,
+    handle: MoqMgrTrackHandle,
+) -> MoqMgrResult {
+    if session.is_null() {
+        return MoqMgrResult::ErrorInvalidParameter;
+    }
+
+    let session = unsafe { &*session };
+    if session.session.unannounce(handle) {
+        MoqMgrResult::Success
+    } else {
+        MoqMgrResult::ErrorInvalidParameter
+    }
+}
+
+/// Opaque handle to a pull-based subscription created by
+/// `moq_mgr_session_subscribe_buffered`.
+pub struct MoqMgrBufferedSubscription {
+    inner: BufferedSubscription,
+}
+
+/// What `moq_mgr_session_subscribe_buffered`'s ring buffer does when full and a new
+/// frame arrives.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoqMgrOverflowPolicy {
+    DropOldest = 0,
+    DropNewest = 1,
+}
+
+impl From<MoqMgrOverflowPolicy> for OverflowPolicy {
+    fn from(policy: MoqMgrOverflowPolicy) -> Self {
+        match policy {
+            MoqMgrOverflowPolicy::DropOldest => OverflowPolicy::DropOldest,
+            MoqMgrOverflowPolicy::DropNewest => OverflowPolicy::DropNewest,
+        }
+    }
+}
+
+/// Result of `moq_mgr_poll`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoqMgrPollResult {
+    /// A frame is available at the pointers written to `out_ptr`/`out_len`.
+    Frame = 0,
+    /// A frame is available, but the buffer overflowed since the previous poll, so
+    /// one or more frames were dropped before this one.
+    FrameOverflowed = 1,
+    /// No frame arrived within `max_wait_ms`.
+    Timeout = 2,
+    /// The subscription's buffer was closed and has been drained. The end of the
+    /// track does not close it; polls after the track ends return `Timeout`.
+    Closed = 3,
+    /// `handle` was null.
+    InvalidHandle = -1,
+}
+
+/// Subscribe to `track_name` on an already-started session, buffering incoming
+/// frames in a bounded ring buffer instead of invoking a callback - see
+/// `moq_mgr_poll` to pull frames from it. Like `moq_mgr_session_subscribe`, this
+/// only works once `moq_mgr_session_start` has connected.
+///
+/// Returns null if the session isn't connected yet, the track name isn't valid
+/// UTF-8, or `capacity` is zero.
+#[no_mangle]
+pub extern "C" fn moq_mgr_session_subscribe_buffered(
+    session: *mut MoqMgrSession,
+    track_name: *const c_char,
+    capacity: usize,
+    policy: MoqMgrOverflowPolicy,
+) -> *mut MoqMgrBufferedSubscription {
+    if session.is_null() || track_name.is_null() || capacity == 0 {
+        return std::ptr::null_mut();
+    }
+
+    let session = unsafe { &*session };
+
+    let track_name_str = unsafe {
+        match CStr::from_ptr(track_name).to_str() {
+            Ok(s) => s,
+            Err(_) => return std::ptr::null_mut(),
+        }
+    };
+
+    let Some(broadcast_consumer) = session.session.broadcast_consumer() else {
+        return std::ptr::null_mut();
+    };
+
+    match BufferedSubscription::new(broadcast_consumer, track_name_str, capacity, policy.into()) {
+        Ok(inner) => Box::into_raw(Box::new(MoqMgrBufferedSubscription { inner })),
+        Err(e) => {
+            tracing::error!("moq_mgr_session_subscribe_buffered failed: {}", e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Wait up to `max_wait_ms` for the next frame on `handle`. On `Frame`/
+/// `FrameOverflowed`, `*out_ptr`/`*out_len` point at the frame, valid until the next
+/// `moq_mgr_poll` call on this handle or `moq_mgr_frame_free`; the caller does not
+/// own or need to free that memory directly.
+#[no_mangle]
+pub extern "C" fn moq_mgr_poll(
+    handle: *mut MoqMgrBufferedSubscription,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+    max_wait_ms: u32,
+) -> MoqMgrPollResult {
+    if handle.is_null() || out_ptr.is_null() || out_len.is_null() {
+        return MoqMgrPollResult::InvalidHandle;
+    }
+
+    let handle = unsafe { &*handle };
+    let outcome = handle
+        .inner
+        .poll(std::time::Duration::from_millis(max_wait_ms as u64));
+
+    let (ptr, len) = match outcome {
+        PollOutcome::Frame | PollOutcome::FrameOverflowed => handle.inner.current_frame_ptr(),
+        PollOutcome::Timeout | PollOutcome::Closed => (std::ptr::null(), 0),
+    };
+
+    unsafe {
+        *out_ptr = ptr;
+        *out_len = len;
+    }
+
+    match outcome {
+        PollOutcome::Frame => MoqMgrPollResult::Frame,
+        PollOutcome::FrameOverflowed => MoqMgrPollResult::FrameOverflowed,
+        PollOutcome::Timeout => MoqMgrPollResult::Timeout,
+        PollOutcome::Closed => MoqMgrPollResult::Closed,
+    }
+}
+
+/// Release the frame currently returned by `moq_mgr_poll` early, instead of waiting
+/// for it to be replaced by the next `moq_mgr_poll` call.
+#[no_mangle]
+pub extern "C" fn moq_mgr_frame_free(handle: *mut MoqMgrBufferedSubscription) {
+    if handle.is_null() {
+        return;
+    }
+    let handle = unsafe { &*handle };
+    handle.inner.free_current_frame();
+}
+
+/// Destroy a buffered subscription created by `moq_mgr_session_subscribe_buffered`.
+#[no_mangle]
+pub extern "C" fn moq_mgr_buffered_subscription_destroy(handle: *mut MoqMgrBufferedSubscription) {
+    if !handle.is_null() {
+        unsafe {
+            drop(Box::from_raw(handle));
+        }
+    }
+}
+
 /// Start the session and connect to the MoQ server
 #[no_mangle]
 pub extern "C" fn moq_mgr_session_start(session: *mut MoqMgrSession) -> MoqMgrResult {
@@ -325,15 +991,19 @@ pub extern "C" fn moq_mgr_session_start(session: *mut MoqMgrSession) -> MoqMgrRe
     let session = unsafe { &*session };
     let session_arc = session.session.clone();
     let runtime = session.runtime.clone();
-    
+
     // Spawn the start operation in the background
     runtime.spawn(async move {
         // Call start on the session
         if let Err(e) = session_arc.start().await {
             tracing::error!("Failed to start session: {}", e);
+            set_last_error(
+                MoqMgrErrorCategory::ConnectionFailed,
+                format!("session failed to start: {}", e),
+            );
         }
     });
-    
+
     MoqMgrResult::Success
 }
 
@@ -376,11 +1046,26 @@ pub extern "C" fn moq_mgr_session_destroy(session: *mut MoqMgrSession) {
     }
 }
 
-/// Get the last error message (thread-local)
+/// Get the last error message recorded on this thread, or null if none is set.
+/// The returned pointer is valid until the next `moq_mgr_*` call on this thread.
 #[no_mangle]
 pub extern "C" fn moq_mgr_get_last_error() -> *const c_char {
-    // TODO: Implement thread-local error storage
-    std::ptr::null()
+    LAST_ERROR.with(|cell| match &cell.borrow().1 {
+        Some(message) => message.as_ptr(),
+        None => std::ptr::null(),
+    })
+}
+
+/// Get the category of the last error recorded on this thread; see [`MoqMgrErrorCategory`].
+#[no_mangle]
+pub extern "C" fn moq_mgr_get_last_error_category() -> MoqMgrErrorCategory {
+    LAST_ERROR.with(|cell| cell.borrow().0)
+}
+
+/// Clear the last error recorded on this thread.
+#[no_mangle]
+pub extern "C" fn moq_mgr_clear_last_error() {
+    LAST_ERROR.with(|cell| *cell.borrow_mut() = (MoqMgrErrorCategory::None, None));
 }
 
 // Custom tracing layer that forwards logs to the C callback
@@ -394,76 +1079,155 @@ impl CallbackLayer {
 
 impl<S> tracing_subscriber::Layer<S> for CallbackLayer
 where
-    S: tracing::Subscriber,
+    S: tracing::Subscriber + for<'lookup> tracing_subscriber::registry::LookupSpan<'lookup>,
 {
-    fn on_event(
-        &self,
-        event: &tracing::Event<'_>,
-        _ctx: tracing_subscriber::layer::Context<'_, S>,
-    ) {
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
         let _lock = LOG_CALLBACK_LOCK.lock().unwrap();
-        
+
         unsafe {
+            if LOG_CALLBACK.is_none() && STRUCTURED_LOG_CALLBACK.is_none() {
+                return;
+            }
+
+            // Convert tracing level to our integer representation
+            let level = match *event.metadata().level() {
+                tracing::Level::ERROR => 0,
+                tracing::Level::WARN => 1,
+                tracing::Level::INFO => 2,
+                tracing::Level::DEBUG => 3,
+                tracing::Level::TRACE => 4,
+            };
+
+            // Extract the message and fields once; both callbacks see identical
+            // message extraction.
+            let mut visitor = FieldVisitor::new();
+            event.record(&mut visitor);
+
             if let Some((callback, user_data)) = LOG_CALLBACK {
-                // Convert tracing level to our integer representation
-                let level = match *event.metadata().level() {
-                    tracing::Level::ERROR => 0,
-                    tracing::Level::WARN => 1,
-                    tracing::Level::INFO => 2,
-                    tracing::Level::DEBUG => 3,
-                    tracing::Level::TRACE => 4,
-                };
-                
-                // Format the message
-                let mut visitor = MessageVisitor::new();
-                event.record(&mut visitor);
-                
-                // Create a C string for the message
-                if let Ok(c_message) = CString::new(visitor.message) {
+                if let Ok(c_message) = CString::new(visitor.flat_message()) {
                     callback(level, c_message.as_ptr(), user_data);
                 }
             }
+
+            if let Some((callback, user_data)) = STRUCTURED_LOG_CALLBACK {
+                let target = event.metadata().target();
+
+                let mut fields = visitor.fields.clone();
+                if let Some(scope) = ctx.event_scope(event) {
+                    let spans: Vec<serde_json::Value> = scope
+                        .from_root()
+                        .map(|span| serde_json::Value::String(span.name().to_string()))
+                        .collect();
+                    if !spans.is_empty() {
+                        fields.insert("spans".to_string(), serde_json::Value::Array(spans));
+                    }
+                }
+
+                let fields_json =
+                    serde_json::to_string(&fields).unwrap_or_else(|_| "{}".to_string());
+
+                if let (Ok(c_target), Ok(c_message), Ok(c_fields)) = (
+                    CString::new(target),
+                    CString::new(visitor.message.clone()),
+                    CString::new(fields_json),
+                ) {
+                    callback(
+                        level,
+                        c_target.as_ptr(),
+                        c_message.as_ptr(),
+                        c_fields.as_ptr(),
+                        user_data,
+                    );
+                }
+            }
         }
     }
 }
 
-// Visitor to extract the message from tracing events
-struct MessageVisitor {
+// Visitor that extracts the message and the remaining fields from a tracing event,
+// shared by the flat (`MoqMgrLogCallback`) and structured (`MoqMgrStructuredLogCallback`)
+// logging paths so both see identical message extraction.
+struct FieldVisitor {
     message: String,
+    fields: serde_json::Map<String, serde_json::Value>,
 }
 
-impl MessageVisitor {
+impl FieldVisitor {
     fn new() -> Self {
         Self {
             message: String::new(),
+            fields: serde_json::Map::new(),
+        }
+    }
+
+    /// Renders the message with `key=value` fields appended, matching the flat
+    /// format `MoqMgrLogCallback` has always received.
+    fn flat_message(&self) -> String {
+        let mut flat = self.message.clone();
+        for (key, value) in &self.fields {
+            if !flat.is_empty() {
+                flat.push(' ');
+            }
+            let value_str = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            flat.push_str(&format!("{}={}", key, value_str));
         }
+        flat
     }
 }
 
-impl tracing::field::Visit for MessageVisitor {
+impl tracing::field::Visit for FieldVisitor {
     fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
         if field.name() == "message" {
             self.message = format!("{:?}", value);
             // Remove quotes from debug formatted strings
             if self.message.starts_with('"') && self.message.ends_with('"') {
-                self.message = self.message[1..self.message.len()-1].to_string();
+                self.message = self.message[1..self.message.len() - 1].to_string();
             }
         } else {
-            if !self.message.is_empty() {
-                self.message.push(' ');
-            }
-            self.message.push_str(&format!("{}={:?}", field.name(), value));
+            self.fields.insert(
+                field.name().to_string(),
+                serde_json::Value::String(format!("{:?}", value)),
+            );
         }
     }
-    
+
     fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
         if field.name() == "message" {
             self.message = value.to_string();
         } else {
-            if !self.message.is_empty() {
-                self.message.push(' ');
-            }
-            self.message.push_str(&format!("{}={}", field.name(), value));
+            self.fields.insert(
+                field.name().to_string(),
+                serde_json::Value::String(value.to_string()),
+            );
+        }
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.fields
+            .insert(field.name().to_string(), serde_json::Value::Bool(value));
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.fields.insert(
+            field.name().to_string(),
+            serde_json::Value::Number(value.into()),
+        );
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.fields.insert(
+            field.name().to_string(),
+            serde_json::Value::Number(value.into()),
+        );
+    }
+
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        if let Some(number) = serde_json::Number::from_f64(value) {
+            self.fields
+                .insert(field.name().to_string(), serde_json::Value::Number(number));
         }
     }
 }
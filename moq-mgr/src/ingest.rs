@@ -0,0 +1,299 @@
+use anyhow::{Context, Result};
+use moq_lite::BroadcastProducer;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::catalog::{CatalogTrack, StandardCatalog};
+use crate::producer::{BroadcastConfig, Producer};
+
+const CATALOG_TRACK_NAME: &str = "catalog.json";
+const CATALOG_PRIORITY: u8 = 255;
+
+/// A track discovered while parsing the init segment, along with the bits of its
+/// `moov` metadata needed to route and pace later fragments.
+struct Fmp4Track {
+    producer: Producer,
+    timescale: u32,
+}
+
+/// Ingests a fragmented MP4 (CMAF) source and drives one [`Producer`] per contained
+/// track plus a `catalog.json` track describing them, the way a CLI publisher would.
+///
+/// Call [`Self::publish_init`] once with the `ftyp`+`moov` init segment, then feed
+/// fragments either by reading a whole file with [`Self::publish_file`] (paced to
+/// wall-clock time using each fragment's decode timestamp) or one at a time with
+/// [`Self::push_fragment`] (for a live encoder producing `moof`+`mdat` pairs as it
+/// goes).
+pub struct Fmp4Ingestor {
+    broadcast_producer: BroadcastProducer,
+    tracks: HashMap<u32, Fmp4Track>,
+    catalog_producer: Option<Producer>,
+}
+
+impl Fmp4Ingestor {
+    pub fn new(broadcast_producer: BroadcastProducer) -> Self {
+        Self {
+            broadcast_producer,
+            tracks: HashMap::new(),
+            catalog_producer: None,
+        }
+    }
+
+    /// Parse the `ftyp`+`moov` init segment, creating one [`Producer`] per `trak` and
+    /// publishing the whole init segment as that track's first group. Also builds and
+    /// publishes a `catalog.json` describing the discovered tracks. Must be called
+    /// once before [`Self::push_fragment`] or [`Self::publish_file`].
+    pub fn publish_init(&mut self, init_segment: &[u8]) -> Result<()> {
+        let moov = fmp4_find_box(init_segment, b"moov").context("no moov box in init segment")?;
+
+        let mut catalog_tracks = Vec::new();
+
+        for (kind, start, end) in fmp4_top_level_boxes(moov) {
+            if &kind != b"trak" {
+                continue;
+            }
+            let trak_payload = &moov[start + 8..end];
+
+            let track_id = fmp4_find_box(trak_payload, b"tkhd")
+                .and_then(fmp4_track_id)
+                .context("trak missing a usable tkhd")?;
+
+            let mdia = fmp4_find_box(trak_payload, b"mdia");
+            let timescale = mdia
+                .and_then(|mdia| fmp4_find_box(mdia, b"mdhd"))
+                .and_then(fmp4_mdhd_timescale);
+            let codec = mdia
+                .and_then(|mdia| fmp4_find_box(mdia, b"minf"))
+                .and_then(|minf| fmp4_find_box(minf, b"stbl"))
+                .and_then(|stbl| fmp4_find_box(stbl, b"stsd"))
+                .and_then(fmp4_sample_entry_fourcc)
+                .map(|fourcc| String::from_utf8_lossy(&fourcc).to_string());
+            let track_type = mdia
+                .and_then(|mdia| fmp4_find_box(mdia, b"hdlr"))
+                .and_then(fmp4_handler_type)
+                .unwrap_or_else(|| "data".to_string());
+
+            let track_name = format!("track{track_id}");
+            let config = BroadcastConfig {
+                moq_track_name: track_name.clone(),
+                priority: 128,
+            };
+            let mut producer = Producer::new(config, self.broadcast_producer.clone());
+            producer.initialize()?;
+            producer.write_object(init_segment)?;
+
+            catalog_tracks.push(CatalogTrack {
+                track_name: track_name.clone(),
+                track_type,
+                priority: 128,
+                codec,
+                bitrate: None,
+                timescale,
+                track_id: Some(track_id),
+            });
+
+            self.tracks.insert(
+                track_id,
+                Fmp4Track {
+                    producer,
+                    timescale: timescale.unwrap_or(1000),
+                },
+            );
+        }
+
+        if self.tracks.is_empty() {
+            anyhow::bail!("no usable tracks found in moov");
+        }
+
+        self.publish_catalog(catalog_tracks)?;
+        Ok(())
+    }
+
+    fn publish_catalog(&mut self, tracks: Vec<CatalogTrack>) -> Result<()> {
+        if self.catalog_producer.is_none() {
+            let config = BroadcastConfig {
+                moq_track_name: CATALOG_TRACK_NAME.to_string(),
+                priority: CATALOG_PRIORITY,
+            };
+            let mut producer = Producer::new(config, self.broadcast_producer.clone());
+            producer.initialize()?;
+            self.catalog_producer = Some(producer);
+        }
+
+        let json = StandardCatalog::from_tracks(tracks).to_json()?;
+        self.catalog_producer
+            .as_ref()
+            .expect("catalog producer just initialized")
+            .write_object(json.as_bytes())
+    }
+
+    /// Push one already-framed `moof`+`mdat` fragment, routing it to the track named
+    /// by that fragment's `tfhd` track ID and starting a new group for it. CMAF
+    /// fragments are produced GOP-aligned, so one group per fragment lines up with
+    /// keyframe boundaries in practice.
+    pub fn push_fragment(&mut self, fragment: &[u8]) -> Result<()> {
+        let moof = fmp4_find_box(fragment, b"moof").context("fragment has no moof box")?;
+        if fmp4_find_box(fragment, b"mdat").is_none() {
+            anyhow::bail!("fragment has no mdat box");
+        }
+
+        // `tfhd` puts `track_ID` right after its 4-byte version/flags header.
+        let track_id = fmp4_find_box(moof, b"traf")
+            .and_then(|traf| fmp4_find_box(traf, b"tfhd"))
+            .and_then(|tfhd| tfhd.get(4..8))
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_be_bytes)
+            .context("fragment's tfhd is missing a track ID")?;
+
+        let track = self
+            .tracks
+            .get(&track_id)
+            .context("fragment references a track not seen in the init segment")?;
+
+        track.producer.write_object(fragment)
+    }
+
+    /// Read a whole fMP4/CMAF file, publish its init segment, then push each
+    /// `moof`+`mdat` fragment paced to wall-clock time using the fragment's decode
+    /// timestamp (`tfdt`) and that track's `mdhd` timescale.
+    pub async fn publish_file(&mut self, path: &str) -> Result<()> {
+        let data = std::fs::read(path).with_context(|| format!("failed to read {path}"))?;
+
+        let top_boxes = fmp4_top_level_boxes(&data);
+        let moov_end = top_boxes
+            .iter()
+            .find(|(kind, _, _)| kind == b"moov")
+            .map(|(_, _, end)| *end)
+            .context("no moov box found")?;
+        self.publish_init(&data[0..moov_end])?;
+
+        let mut anchor: Option<(Instant, f64)> = None;
+
+        let mut i = 0;
+        while i < top_boxes.len() {
+            let (kind, moof_start, moof_end) = top_boxes[i];
+            if &kind != b"moof" {
+                i += 1;
+                continue;
+            }
+
+            let mut j = i + 1;
+            let mut fragment_end = None;
+            while j < top_boxes.len() {
+                let (next_kind, _, next_end) = top_boxes[j];
+                if &next_kind == b"mdat" {
+                    fragment_end = Some(next_end);
+                    break;
+                }
+                if &next_kind == b"moof" {
+                    break;
+                }
+                j += 1;
+            }
+            let Some(fragment_end) = fragment_end else {
+                i += 1;
+                continue;
+            };
+
+            let moof_payload = &data[moof_start + 8..moof_end];
+            let traf = fmp4_find_box(moof_payload, b"traf");
+            let track_id = traf
+                .and_then(|traf| fmp4_find_box(traf, b"tfhd"))
+                .and_then(|tfhd| tfhd.get(4..8))
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(u32::from_be_bytes);
+            let decode_time = fmp4_tfdt_time(moof_payload);
+
+            if let Some(track) = track_id.and_then(|id| self.tracks.get(&id)) {
+                if let Some(decode_time) = decode_time {
+                    let pts = decode_time as f64 / track.timescale as f64;
+                    let (anchor_wall, anchor_pts) = *anchor.get_or_insert((Instant::now(), pts));
+                    let target = anchor_wall + Duration::from_secs_f64((pts - anchor_pts).max(0.0));
+                    let now = Instant::now();
+                    if target > now {
+                        tokio::time::sleep(target - now).await;
+                    }
+                }
+
+                if let Err(err) = track.producer.write_object(&data[moof_start..fragment_end]) {
+                    tracing::warn!("failed to publish fMP4 fragment: {}", err);
+                }
+            }
+
+            i = j + 1;
+        }
+
+        Ok(())
+    }
+}
+
+fn fmp4_top_level_boxes(data: &[u8]) -> Vec<([u8; 4], usize, usize)> {
+    let mut boxes = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        if size < 8 || offset + size > data.len() {
+            break;
+        }
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[offset + 4..offset + 8]);
+        boxes.push((kind, offset, offset + size));
+        offset += size;
+    }
+    boxes
+}
+
+fn fmp4_find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    fmp4_top_level_boxes(data)
+        .into_iter()
+        .find(|(box_kind, _, _)| box_kind == kind)
+        .map(|(_, start, end)| &data[start + 8..end])
+}
+
+fn fmp4_track_id(box_payload: &[u8]) -> Option<u32> {
+    let version = *box_payload.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = box_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+fn fmp4_tfdt_time(moof_payload: &[u8]) -> Option<u64> {
+    let traf = fmp4_find_box(moof_payload, b"traf")?;
+    let tfdt = fmp4_find_box(traf, b"tfdt")?;
+    let version = *tfdt.first()?;
+    if version == 1 {
+        let bytes = tfdt.get(4..12)?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    } else {
+        let bytes = tfdt.get(4..8)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?) as u64)
+    }
+}
+
+/// Timescale (ticks per second) from a `mdhd` box's payload.
+fn fmp4_mdhd_timescale(mdhd_payload: &[u8]) -> Option<u32> {
+    let version = *mdhd_payload.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = mdhd_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// The handler type fourcc from a `hdlr` box's payload, mapped to a catalog track type.
+/// Anything other than a recognized video/audio handler is reported as "data" rather
+/// than guessed at.
+fn fmp4_handler_type(hdlr_payload: &[u8]) -> Option<String> {
+    let bytes = hdlr_payload.get(8..12)?;
+    match bytes {
+        b"vide" => Some("video".to_string()),
+        b"soun" => Some("audio".to_string()),
+        _ => Some("data".to_string()),
+    }
+}
+
+/// First sample entry's format fourcc from a `stsd` box's payload (e.g. "avc1",
+/// "mp4a"). This is a deliberately shallow codec identifier, not a full
+/// WebCodecs-style codec string with profile/level.
+fn fmp4_sample_entry_fourcc(stsd_payload: &[u8]) -> Option<[u8; 4]> {
+    let bytes = stsd_payload.get(12..16)?;
+    Some(bytes.try_into().ok()?)
+}
@@ -1,10 +1,22 @@
-pub mod session;
-pub mod producer;
-pub mod consumer;
 pub mod catalog;
+pub mod consumer;
 pub mod ffi;
+pub mod ingest;
+pub mod pool;
+pub mod producer;
+pub mod relay;
+pub mod session;
 
-pub use session::{Session, SessionConfig, SessionMode};
-pub use producer::{Producer, BroadcastConfig};
-pub use consumer::{Consumer, SubscriptionConfig};
-pub use catalog::{CatalogTrack, CatalogProcessor};
+pub use catalog::{CatalogEvent, CatalogProcessor, CatalogTrack};
+pub use consumer::{
+    BufferedSubscription, Consumer, OverflowPolicy, PollOutcome, ReconnectCallback,
+    StartPosition, SubscriptionConfig, TrackPattern,
+};
+pub use ingest::Fmp4Ingestor;
+pub use pool::SessionPool;
+pub use producer::{BroadcastConfig, Producer};
+pub use relay::{Broadcasts, RelayTrack};
+pub use session::{
+    ConnectionState, ReconnectPolicy, Session, SessionConfig, SessionMode, SubscriptionState,
+    TrackHandle,
+};
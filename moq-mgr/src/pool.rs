@@ -0,0 +1,97 @@
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::session::{Session, SessionConfig, SessionMode};
+
+/// Manages a bounded number of [`Session`]s per server origin, so an app consuming
+/// many broadcasts from the same relay doesn't open a separate QUIC connection for
+/// each one. A pooled [`Session`] is cheap to clone (its internal state is all
+/// `Arc`-based), so "sharing a connection" is just handing out a clone of the same
+/// `Session` to multiple callers.
+///
+/// Each [`Session`] still owns exactly one underlying connection (see
+/// [`crate::session::Session::start`]), so sharing only happens at the granularity
+/// of a whole session: a request for the same server URL, namespace and
+/// [`SessionMode`] as a pooled session reuses it outright; a request for a
+/// different namespace on an origin that's already at `max_connections_per_origin`
+/// falls back to the least-recently-used session for that origin rather than
+/// opening one more connection than the cap allows.
+pub struct SessionPool {
+    max_connections_per_origin: usize,
+    origins: Arc<RwLock<HashMap<String, Vec<Session>>>>,
+}
+
+impl SessionPool {
+    pub fn new(max_connections_per_origin: usize) -> Self {
+        Self {
+            max_connections_per_origin: max_connections_per_origin.max(1),
+            origins: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Returns a [`Session`] for `config`/`mode`, created fresh, reused from the
+    /// pool, or shared with another namespace on the same origin - see the type's
+    /// docs. The caller is responsible for calling [`Session::start`] on a
+    /// freshly-created session; a reused one is assumed to already be started (or
+    /// starting).
+    pub fn session(&self, config: SessionConfig, mode: SessionMode) -> Session {
+        let origin = Self::origin_key(&config);
+        let mut origins = self.origins.write();
+        let pooled = origins.entry(origin).or_insert_with(Vec::new);
+
+        pooled.retain(|session| self.is_valid(session));
+
+        if let Some(index) = pooled
+            .iter()
+            .position(|session| Self::matches(session, &config, mode))
+        {
+            // Move it to the back so `pooled[0]` stays the least-recently-used
+            // entry for the capacity branch below.
+            let session = pooled.remove(index);
+            pooled.push(session.clone());
+            return session;
+        }
+
+        if pooled.len() < self.max_connections_per_origin {
+            let session = Session::new(config, mode);
+            pooled.push(session.clone());
+            return session;
+        }
+
+        // At capacity for this origin: share the least-recently-used pooled
+        // connection rather than open one more than the cap allows, even though it
+        // serves a different namespace. Moved to the back so the next overflow
+        // shares a different connection instead of always this one.
+        tracing::warn!(
+            "SessionPool at capacity ({}) for origin, sharing the least-recently-used connection for a different namespace",
+            self.max_connections_per_origin
+        );
+        let session = pooled.remove(0);
+        pooled.push(session.clone());
+        session
+    }
+
+    /// Whether a pooled session's connection is still worth handing out, rather
+    /// than being dropped and replaced on the next [`Self::session`] call.
+    pub fn is_valid(&self, session: &Session) -> bool {
+        session.is_running()
+    }
+
+    fn matches(session: &Session, config: &SessionConfig, mode: SessionMode) -> bool {
+        session.mode() == mode
+            && session.config().moq_server_url == config.moq_server_url
+            && session.config().moq_namespace == config.moq_namespace
+            && session.config().subscribe_namespace == config.subscribe_namespace
+    }
+
+    fn origin_key(config: &SessionConfig) -> String {
+        let url = &config.moq_server_url;
+        format!(
+            "{}://{}:{}",
+            url.scheme(),
+            url.host_str().unwrap_or(""),
+            url.port_or_known_default().unwrap_or(0)
+        )
+    }
+}
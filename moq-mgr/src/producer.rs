@@ -1,8 +1,8 @@
 use anyhow::Result;
-use std::sync::Arc;
 use parking_lot::Mutex;
+use std::sync::Arc;
 
-use moq_lite::{BroadcastProducer, Track, TrackProducer, GroupProducer, Group};
+use moq_lite::{BroadcastProducer, Group, GroupProducer, Track, TrackProducer};
 
 #[derive(Clone)]
 pub struct BroadcastConfig {
@@ -10,6 +10,7 @@ pub struct BroadcastConfig {
     pub priority: u8,
 }
 
+#[derive(Clone)]
 pub struct Producer {
     config: BroadcastConfig,
     broadcast_producer: BroadcastProducer,
@@ -37,8 +38,11 @@ impl Producer {
 
         let track_producer = self.broadcast_producer.create_track(track);
         *self.track_producer.lock() = Some(track_producer);
-        
-        tracing::info!("Producer initialized for track: {}", self.config.moq_track_name);
+
+        tracing::info!(
+            "Producer initialized for track: {}",
+            self.config.moq_track_name
+        );
         Ok(())
     }
 
@@ -63,7 +67,7 @@ impl Producer {
             .create_group(group)
             .ok_or_else(|| anyhow::anyhow!("Failed to create group"))?;
         *self.group_producer.lock() = Some(group_producer);
-        
+
         Ok(())
     }
 
@@ -90,7 +94,78 @@ impl Producer {
         Ok(())
     }
 
+    /// Open the current group's next frame for incremental writes, optionally
+    /// declaring its total size up front so the buffer can be pre-allocated.
+    ///
+    /// `moq_lite::GroupProducer` only exposes whole-frame writes today, so chunks are
+    /// accumulated here and shipped as a single frame once the returned [`FrameWriter`]
+    /// is finished or dropped. Callers still avoid assembling and copying their own
+    /// `Vec` up front - e.g. a CMAF fragment or a camera frame can be piped straight
+    /// out of a decoder buffer one chunk at a time.
+    pub fn begin_frame(&self, total_size: Option<u64>) -> Result<FrameWriter<'_>> {
+        if self.group_producer.lock().is_none() {
+            return Err(anyhow::anyhow!("Group not started"));
+        }
+
+        Ok(FrameWriter {
+            group_producer: &self.group_producer,
+            buffer: match total_size {
+                Some(size) => Vec::with_capacity(size as usize),
+                None => Vec::new(),
+            },
+            finished: false,
+        })
+    }
+
     pub fn get_track_name(&self) -> &str {
         &self.config.moq_track_name
     }
 }
+
+/// A frame opened via [`Producer::begin_frame`], filled incrementally via
+/// [`Self::write_chunk`] instead of requiring the whole payload up front.
+///
+/// Dropping the writer without calling [`Self::finish`] ships whatever was written so
+/// far, the same as `finish()` - this mirrors `Producer::finish_group` dropping the
+/// group to close it.
+pub struct FrameWriter<'a> {
+    group_producer: &'a Arc<Mutex<Option<GroupProducer>>>,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl FrameWriter<'_> {
+    /// Append a chunk to the frame
+    pub fn write_chunk(&mut self, data: &[u8]) -> Result<()> {
+        if self.finished {
+            return Err(anyhow::anyhow!("Frame already finished"));
+        }
+        self.buffer.extend_from_slice(data);
+        Ok(())
+    }
+
+    /// Ship the accumulated frame
+    pub fn finish(mut self) -> Result<()> {
+        self.finish_inner()
+    }
+
+    fn finish_inner(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        let mut group_producer = self.group_producer.lock();
+        let group_producer = group_producer
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("Group not started"))?;
+        group_producer.write_frame(std::mem::take(&mut self.buffer));
+        Ok(())
+    }
+}
+
+impl Drop for FrameWriter<'_> {
+    fn drop(&mut self) {
+        let _ = self.finish_inner();
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+use moq_lite::{BroadcastProducer, TrackConsumer};
+
+use crate::producer::{BroadcastConfig, Producer};
+
+/// Registry of broadcasts this process can serve, mapping a broadcast name to the
+/// producer that should answer a subscribe for it - whether that producer publishes
+/// locally-generated content or bridges an upstream broadcast via [`RelayTrack`].
+///
+/// A single process can announce several broadcasts at once, letting it act as a
+/// fan-out relay (one upstream session in, many downstream subscribers out) instead
+/// of only as a leaf publisher or subscriber.
+#[derive(Clone, Default)]
+pub struct Broadcasts {
+    entries: Arc<RwLock<HashMap<String, BroadcastProducer>>>,
+}
+
+impl Broadcasts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Announce `producer` under `name`, making it available to [`Self::subscribe`].
+    pub fn announce(&self, name: impl Into<String>, producer: BroadcastProducer) {
+        self.entries.write().insert(name.into(), producer);
+    }
+
+    /// Stop announcing the broadcast registered under `name`.
+    pub fn unannounce(&self, name: &str) {
+        self.entries.write().remove(name);
+    }
+
+    /// Look up the producer announced under `name`, for routing an incoming
+    /// subscribe request.
+    pub fn subscribe(&self, name: &str) -> Option<BroadcastProducer> {
+        self.entries.read().get(name).cloned()
+    }
+
+    /// Names of all currently announced broadcasts.
+    pub fn names(&self) -> Vec<String> {
+        self.entries.read().keys().cloned().collect()
+    }
+}
+
+/// Bridges a single subscribed track back out as a produced one: pumps each group and
+/// frame read from a [`TrackConsumer`] into a matching [`Producer`], preserving group
+/// boundaries (one produced group per consumed group, frames forwarded in order)
+/// without decoding the frame contents.
+pub struct RelayTrack {
+    track_name: String,
+    running: Arc<Mutex<bool>>,
+    worker_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl RelayTrack {
+    /// Start relaying `track_consumer` into a new [`Producer`] for `broadcast_producer`,
+    /// named `track_name` and published with `priority` (normally the priority the
+    /// upstream track was itself subscribed with).
+    pub fn start(
+        track_consumer: TrackConsumer,
+        broadcast_producer: BroadcastProducer,
+        track_name: String,
+        priority: u8,
+    ) -> Result<Self> {
+        let config = BroadcastConfig {
+            moq_track_name: track_name.clone(),
+            priority,
+        };
+        let mut producer = Producer::new(config, broadcast_producer);
+        producer.initialize()?;
+
+        let running = Arc::new(Mutex::new(true));
+
+        let pump_running = running.clone();
+        let pump_track_name = track_name.clone();
+        let handle = tokio::spawn(async move {
+            Self::pump(track_consumer, producer, pump_running, pump_track_name).await;
+        });
+
+        Ok(Self {
+            track_name,
+            running,
+            worker_handle: Arc::new(Mutex::new(Some(handle))),
+        })
+    }
+
+    async fn pump(
+        mut track_consumer: TrackConsumer,
+        producer: Producer,
+        running: Arc<Mutex<bool>>,
+        track_name: String,
+    ) {
+        while *running.lock() {
+            match track_consumer.next_group().await {
+                Ok(Some(mut group)) => {
+                    if let Err(e) = producer.start_group() {
+                        tracing::error!(
+                            "relay: failed to start group for track {}: {}",
+                            track_name,
+                            e
+                        );
+                        break;
+                    }
+
+                    loop {
+                        if !*running.lock() {
+                            return;
+                        }
+
+                        match group.read_frame().await {
+                            Ok(Some(frame)) => {
+                                if let Err(e) = producer.write_frame(&frame) {
+                                    tracing::error!(
+                                        "relay: failed to write frame for track {}: {}",
+                                        track_name,
+                                        e
+                                    );
+                                    break;
+                                }
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                tracing::error!(
+                                    "relay: error reading frame for track {}: {}",
+                                    track_name,
+                                    e
+                                );
+                                break;
+                            }
+                        }
+                    }
+
+                    if let Err(e) = producer.finish_group() {
+                        tracing::error!(
+                            "relay: failed to finish group for track {}: {}",
+                            track_name,
+                            e
+                        );
+                        break;
+                    }
+                }
+                Ok(None) => {
+                    tracing::info!("relay: upstream track {} ended", track_name);
+                    break;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "relay: error getting next group for track {}: {}",
+                        track_name,
+                        e
+                    );
+                    break;
+                }
+            }
+        }
+
+        tracing::info!("relay pump ended for track: {}", track_name);
+    }
+
+    pub fn get_track_name(&self) -> &str {
+        &self.track_name
+    }
+
+    pub fn is_running(&self) -> bool {
+        *self.running.lock()
+    }
+
+    pub fn stop(&self) {
+        *self.running.lock() = false;
+
+        if let Some(handle) = self.worker_handle.lock().take() {
+            handle.abort();
+        }
+
+        tracing::info!("relay stopped for track: {}", self.track_name);
+    }
+}
+
+impl Drop for RelayTrack {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
 use parking_lot::RwLock;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 use url::Url;
 
@@ -9,22 +11,136 @@ use moq_lite::*;
 use moq_native::{Client, ClientConfig};
 
 use crate::catalog::CatalogProcessor;
-use crate::consumer::{Consumer, SubscriptionConfig};
+use crate::consumer::{Consumer, DataCallback, StartPosition, SubscriptionConfig, TrackPattern};
 use crate::producer::{BroadcastConfig, Producer};
 
+/// Handle returned by [`Session::subscribe`]/[`Session::announce`], passed back to
+/// [`Session::unsubscribe`]/[`Session::unannounce`] to cancel a specific track without
+/// affecting any other track added the same way.
+pub type TrackHandle = u64;
+
+/// What a [`TrackHandle`] refers to - kept so `unsubscribe`/`unannounce` can refuse a
+/// handle that names the other kind instead of silently doing nothing useful.
+enum TrackHandleEntry {
+    Subscription(Consumer),
+    Announcement(TrackProducer),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SessionMode {
     PublishOnly,
     SubscribeOnly,
+    /// Publishes [`SessionConfig::moq_namespace`] like [`Self::PublishOnly`] while
+    /// also subscribing to [`SessionConfig::subscribe_namespace`] (or
+    /// `moq_namespace` if unset) like [`Self::SubscribeOnly`], over a single
+    /// connection. Lets a relay republish to a downstream namespace while consuming
+    /// from an upstream one.
     PublishAndSubscribe,
+    /// Subscribes to no broadcast up front; instead watches ANNOUNCE messages for
+    /// every broadcast path under [`SessionConfig::moq_namespace`] and reports them
+    /// through the callback registered via [`Session::set_announce_callback`]. Lets a
+    /// consumer enumerate what a publisher is offering instead of hardcoding a track
+    /// path, mirroring how a relay resolves the broadcasts available for a namespace.
+    DiscoverOnly,
+}
+
+/// Exponential backoff with full jitter for [`Session`]'s reconnect loop, modeled on
+/// the MQTT client reconnection idiom: `delay = min(max_backoff, initial_backoff *
+/// multiplier^attempt)`, then (if `jitter` is set) resampled uniformly in
+/// `[0, delay]` so many clients reconnecting at once don't all retry in lockstep.
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub multiplier: f64,
+    /// `0` means retry forever.
+    pub max_retries: u32,
+    pub jitter: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            multiplier: 2.0,
+            max_retries: 0,
+            jitter: true,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    fn delay_for_attempt(&self, attempt: u32) -> std::time::Duration {
+        let uncapped = self.initial_backoff_ms as f64 * self.multiplier.powi(attempt as i32);
+        let capped = uncapped.min(self.max_backoff_ms as f64).max(0.0);
+        let delay_ms = if self.jitter {
+            rand::random::<f64>() * capped
+        } else {
+            capped
+        };
+        std::time::Duration::from_millis(delay_ms as u64)
+    }
+
+    /// Whether another attempt should be made after `attempt` failed attempts so far.
+    fn allows_attempt(&self, attempt: u32) -> bool {
+        self.max_retries == 0 || attempt < self.max_retries
+    }
+}
+
+/// Connection lifecycle states delivered to a callback registered via
+/// [`Session::set_connection_callback`], alongside the current reconnect attempt
+/// count (`0` outside of reconnection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    Reconnecting,
+    Disconnected,
+    /// Reconnection gave up after [`ReconnectPolicy::max_retries`] failed attempts.
+    Failed,
 }
 
 #[derive(Clone)]
 pub struct SessionConfig {
     pub moq_server_url: Url,
     pub moq_namespace: String,
+    /// Namespace to subscribe from in [`SessionMode::SubscribeOnly`]/
+    /// [`SessionMode::PublishAndSubscribe`], defaulting to `moq_namespace` when
+    /// `None`. Lets `PublishAndSubscribe` republish to one namespace while
+    /// subscribing to another - a relay fan-in/fan-out.
+    pub subscribe_namespace: Option<String>,
     pub reconnect_on_failure: bool,
+    pub reconnect_policy: ReconnectPolicy,
     pub client_config: ClientConfig,
+    /// Strict subscription validation: once set, [`Session::start`] waits this long
+    /// after the catalog consumer comes up and then fails if any track added via
+    /// [`Session::add_subscription`] has not reached [`SubscriptionState::Active`]
+    /// (marking it [`SubscriptionState::NotInCatalog`]), instead of leaving it
+    /// pending forever. `None` disables the check - a
+    /// misnamed track just never activates.
+    pub subscription_grace: Option<Duration>,
+}
+
+/// State of a track requested via [`Session::add_subscription`], reported through
+/// [`Session::set_subscription_status_callback`] and [`Session::subscription_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionState {
+    /// Matched in the most recent catalog and has a live [`Consumer`].
+    Active,
+    /// No catalog has been processed yet, so whether the track exists is unknown.
+    Pending,
+    /// At least one catalog was processed and the track wasn't in it.
+    NotInCatalog,
+}
+
+/// Keepalive settings registered via [`Session::set_keepalive`], probing liveness by
+/// watching for catalog activity instead of waiting for the transport to notice a
+/// wedged QUIC path.
+#[derive(Debug, Clone, Copy)]
+struct KeepaliveConfig {
+    interval: Duration,
+    timeout: Duration,
 }
 
 pub struct Session {
@@ -33,25 +149,48 @@ pub struct Session {
     client: Arc<RwLock<Option<Client>>>,
     session: Arc<RwLock<Option<Arc<moq_lite::Session<moq_native::web_transport_quinn::Session>>>>>,
     broadcast_consumer: Arc<RwLock<Option<Arc<BroadcastConsumer>>>>,
-    broadcast_producer: Arc<RwLock<Option<Arc<BroadcastProducer>>>>,
-    
+    broadcast_producer: Arc<RwLock<Option<BroadcastProducer>>>,
+
     // For consumers
     catalog_processor: Arc<RwLock<Option<CatalogProcessor>>>,
     catalog_consumer: Arc<RwLock<Option<Consumer>>>,
     active_consumers: Arc<RwLock<HashMap<String, Consumer>>>,
     requested_subscriptions: Arc<RwLock<HashMap<String, SubscriptionConfig>>>,
-    
+    /// Patterns registered via [`Self::add_subscription_pattern`], matched against
+    /// every track named in the catalog so newly-appearing tracks are picked up
+    /// without knowing their names in advance.
+    requested_subscription_patterns: Arc<RwLock<Vec<(TrackPattern, DataCallback)>>>,
+    /// Last known [`SubscriptionState`] for each track added via
+    /// [`Self::add_subscription`], refreshed on every catalog update.
+    subscription_status: Arc<RwLock<HashMap<String, SubscriptionState>>>,
+
     // For producers
     active_producers: Arc<RwLock<HashMap<String, Producer>>>,
     broadcast_configs: Arc<RwLock<Vec<BroadcastConfig>>>,
-    
+
+    // Runtime-managed tracks added via `subscribe`/`announce` after `start`, keyed by
+    // the `TrackHandle` returned to the caller
+    next_track_handle: Arc<AtomicU64>,
+    track_handles: Arc<RwLock<HashMap<TrackHandle, TrackHandleEntry>>>,
+
     // Control
     shutdown_tx: broadcast::Sender<()>,
     is_connected: Arc<RwLock<bool>>,
-    
+    keepalive: Arc<RwLock<Option<KeepaliveConfig>>>,
+    /// Last time catalog activity was observed; compared against
+    /// `KeepaliveConfig::timeout` by the heartbeat monitor.
+    last_activity: Arc<RwLock<Instant>>,
+
     // Callbacks
     error_callback: Arc<RwLock<Option<Box<dyn Fn(&str) + Send + Sync>>>>,
     status_callback: Arc<RwLock<Option<Box<dyn Fn(&str) + Send + Sync>>>>,
+    connection_callback: Arc<RwLock<Option<Box<dyn Fn(ConnectionState, u32) + Send + Sync>>>>,
+    /// Fired from [`SessionMode::DiscoverOnly`]'s announce watcher with the full
+    /// broadcast path and `true` if it just appeared, `false` if it just went away.
+    announce_callback: Arc<RwLock<Option<Box<dyn Fn(&str, bool) + Send + Sync>>>>,
+    /// Fired from the catalog consumer whenever a requested track's
+    /// [`SubscriptionState`] changes.
+    subscription_status_callback: Arc<RwLock<Option<Box<dyn Fn(&str, SubscriptionState) + Send + Sync>>>>,
 }
 
 impl Clone for Session {
@@ -67,12 +206,21 @@ impl Clone for Session {
             catalog_consumer: self.catalog_consumer.clone(),
             active_consumers: self.active_consumers.clone(),
             requested_subscriptions: self.requested_subscriptions.clone(),
+            requested_subscription_patterns: self.requested_subscription_patterns.clone(),
+            subscription_status: self.subscription_status.clone(),
             active_producers: self.active_producers.clone(),
             broadcast_configs: self.broadcast_configs.clone(),
+            next_track_handle: self.next_track_handle.clone(),
+            track_handles: self.track_handles.clone(),
             shutdown_tx: self.shutdown_tx.clone(),
             is_connected: self.is_connected.clone(),
+            keepalive: self.keepalive.clone(),
+            last_activity: self.last_activity.clone(),
             error_callback: self.error_callback.clone(),
             status_callback: self.status_callback.clone(),
+            connection_callback: self.connection_callback.clone(),
+            announce_callback: self.announce_callback.clone(),
+            subscription_status_callback: self.subscription_status_callback.clone(),
         }
     }
 }
@@ -80,7 +228,7 @@ impl Clone for Session {
 impl Session {
     pub fn new(config: SessionConfig, mode: SessionMode) -> Self {
         let (shutdown_tx, _) = broadcast::channel(16);
-        
+
         Self {
             config,
             mode,
@@ -92,12 +240,21 @@ impl Session {
             catalog_consumer: Arc::new(RwLock::new(None)),
             active_consumers: Arc::new(RwLock::new(HashMap::new())),
             requested_subscriptions: Arc::new(RwLock::new(HashMap::new())),
+            requested_subscription_patterns: Arc::new(RwLock::new(Vec::new())),
+            subscription_status: Arc::new(RwLock::new(HashMap::new())),
             active_producers: Arc::new(RwLock::new(HashMap::new())),
             broadcast_configs: Arc::new(RwLock::new(Vec::new())),
+            next_track_handle: Arc::new(AtomicU64::new(1)),
+            track_handles: Arc::new(RwLock::new(HashMap::new())),
             shutdown_tx,
             is_connected: Arc::new(RwLock::new(false)),
+            keepalive: Arc::new(RwLock::new(None)),
+            last_activity: Arc::new(RwLock::new(Instant::now())),
             error_callback: Arc::new(RwLock::new(None)),
             status_callback: Arc::new(RwLock::new(None)),
+            connection_callback: Arc::new(RwLock::new(None)),
+            announce_callback: Arc::new(RwLock::new(None)),
+            subscription_status_callback: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -115,17 +272,237 @@ impl Session {
         *self.status_callback.write() = Some(Box::new(callback));
     }
 
+    /// Registers a callback for connection lifecycle transitions (see
+    /// [`ConnectionState`]), so a caller can render status deterministically instead
+    /// of parsing the freeform strings [`Self::set_status_callback`] delivers.
+    pub fn set_connection_callback<F>(&self, callback: F)
+    where
+        F: Fn(ConnectionState, u32) + Send + Sync + 'static,
+    {
+        *self.connection_callback.write() = Some(Box::new(callback));
+    }
+
+    fn notify_connection(&self, state: ConnectionState, attempt: u32) {
+        if let Some(callback) = self.connection_callback.read().as_ref() {
+            callback(state, attempt);
+        }
+    }
+
+    /// Registers a callback for [`SessionMode::DiscoverOnly`]'s announce watcher. Has
+    /// no effect in any other mode.
+    pub fn set_announce_callback<F>(&self, callback: F)
+    where
+        F: Fn(&str, bool) + Send + Sync + 'static,
+    {
+        *self.announce_callback.write() = Some(Box::new(callback));
+    }
+
+    /// Enables a heartbeat subsystem that probes liveness by watching for catalog
+    /// activity: if no catalog object arrives within `timeout` of the last one, the
+    /// session is marked disconnected and [`Self::reconnect`] is triggered directly
+    /// instead of waiting for `session.closed()` to notice a silently wedged QUIC
+    /// path. A no-op when either argument is `None`; pass `None` for both to disable
+    /// a previously enabled keepalive.
+    pub fn set_keepalive(&self, interval: Option<Duration>, timeout: Option<Duration>) {
+        *self.keepalive.write() = match (interval, timeout) {
+            (Some(interval), Some(timeout)) => Some(KeepaliveConfig { interval, timeout }),
+            (None, None) => None,
+            _ => return,
+        };
+    }
+
     pub fn add_subscription(&self, subscription: SubscriptionConfig) {
+        self.subscription_status
+            .write()
+            .entry(subscription.moq_track_name.clone())
+            .or_insert(SubscriptionState::Pending);
         self.requested_subscriptions
             .write()
             .insert(subscription.moq_track_name.clone(), subscription);
     }
 
+    /// Registers a callback fired whenever a requested track's [`SubscriptionState`]
+    /// changes, e.g. to surface a misnamed track name to the caller instead of it
+    /// silently never activating.
+    pub fn set_subscription_status_callback<F>(&self, callback: F)
+    where
+        F: Fn(&str, SubscriptionState) + Send + Sync + 'static,
+    {
+        *self.subscription_status_callback.write() = Some(Box::new(callback));
+    }
+
+    /// Snapshot of every requested track's last-known [`SubscriptionState`].
+    pub fn subscription_status(&self) -> HashMap<String, SubscriptionState> {
+        self.subscription_status.read().clone()
+    }
+
+    /// Updates `track_name`'s recorded state and fires
+    /// [`Self::set_subscription_status_callback`] if it actually changed.
+    fn report_subscription_state(&self, track_name: &str, state: SubscriptionState) {
+        let changed = self
+            .subscription_status
+            .write()
+            .insert(track_name.to_string(), state)
+            != Some(state);
+
+        if changed {
+            if let Some(callback) = self.subscription_status_callback.read().as_ref() {
+                callback(track_name, state);
+            }
+        }
+    }
+
+    /// Waits up to `grace` for every track added via [`Self::add_subscription`] to
+    /// reach [`SubscriptionState::Active`]; fails with any still missing marked
+    /// [`SubscriptionState::NotInCatalog`], so a misconfigured track name fails
+    /// `start()` loudly instead of hanging forever.
+    async fn validate_subscriptions(&self, grace: Duration) -> Result<()> {
+        tokio::time::sleep(grace).await;
+
+        let missing: Vec<String> = self
+            .requested_subscriptions
+            .read()
+            .keys()
+            .filter(|name| {
+                !matches!(
+                    self.subscription_status.read().get(name.as_str()),
+                    Some(SubscriptionState::Active)
+                )
+            })
+            .cloned()
+            .collect();
+
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        for track_name in &missing {
+            self.report_subscription_state(track_name, SubscriptionState::NotInCatalog);
+        }
+
+        let message = format!(
+            "subscription(s) not found in catalog after {:?}: {}",
+            grace,
+            missing.join(", ")
+        );
+        self.notify_error(&message);
+        anyhow::bail!(message)
+    }
+
+    /// Subscribes to every track whose name matches `pattern` (see [`TrackPattern`]),
+    /// instead of naming one exact track up front. Matched against the catalog as it
+    /// updates, so a track added mid-session is picked up the next time it appears,
+    /// and a dynamically-created consumer is torn down the same way an exact
+    /// [`Self::add_subscription`] one is: when its track drops out of the catalog.
+    pub fn add_subscription_pattern(&self, pattern: &str, data_callback: DataCallback) {
+        self.requested_subscription_patterns
+            .write()
+            .push((TrackPattern::parse(pattern), data_callback));
+    }
+
     pub fn add_broadcast(&self, broadcast: BroadcastConfig) {
         self.broadcast_configs.write().push(broadcast);
     }
 
-        /// Notifies the error callback
+    /// The configuration this session was constructed with, for callers (like
+    /// [`crate::pool::SessionPool`]) that need to tell two sessions' origins apart.
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    /// The [`SessionMode`] this session was constructed with.
+    pub fn mode(&self) -> SessionMode {
+        self.mode
+    }
+
+    /// Clones out the [`Producer`] for `track_name`, configured via [`Self::add_broadcast`]
+    /// and created once [`Self::start`] runs in [`SessionMode::PublishOnly`]. Returns `None`
+    /// before `start` has run, or if no broadcast was configured for that track name.
+    pub fn producer(&self, track_name: &str) -> Option<Producer> {
+        self.active_producers.read().get(track_name).cloned()
+    }
+
+    /// Clones out the live broadcast consumer, for callers (like
+    /// [`crate::consumer::BufferedSubscription`]) that want to open their own
+    /// subscription outside the `add_subscription`/catalog-driven path. Returns
+    /// `None` before [`Self::start`] has connected.
+    pub fn broadcast_consumer(&self) -> Option<Arc<BroadcastConsumer>> {
+        self.broadcast_consumer.read().clone()
+    }
+
+    /// Subscribes to `track_name` on the live broadcast consumer, without requiring a
+    /// restart the way [`Self::add_subscription`] does - only works once [`Self::start`]
+    /// has connected. Returns a [`TrackHandle`] that [`Self::unsubscribe`] later cancels.
+    pub fn subscribe(&self, track_name: &str, data_callback: DataCallback) -> Result<TrackHandle> {
+        let broadcast_consumer = self
+            .broadcast_consumer
+            .read()
+            .as_ref()
+            .context("session is not connected")?
+            .clone();
+
+        let consumer = Consumer::new(
+            broadcast_consumer,
+            SubscriptionConfig {
+                moq_track_name: track_name.to_string(),
+                data_callback,
+                reconnect_callback: None,
+                start_position: StartPosition::default(),
+                priority: None,
+            },
+        )?;
+
+        let handle = self.next_track_handle.fetch_add(1, Ordering::Relaxed);
+        self.track_handles
+            .write()
+            .insert(handle, TrackHandleEntry::Subscription(consumer));
+        Ok(handle)
+    }
+
+    /// Cancels a subscription created by [`Self::subscribe`]. Returns `false` if
+    /// `handle` doesn't name an active subscription (already unsubscribed, names an
+    /// announcement instead, or never existed).
+    pub fn unsubscribe(&self, handle: TrackHandle) -> bool {
+        matches!(
+            self.track_handles.write().remove(&handle),
+            Some(TrackHandleEntry::Subscription(_))
+        )
+    }
+
+    /// Publishes a new track named `track_name` on the live broadcast producer,
+    /// without requiring a restart the way [`Self::add_broadcast`] does - only works
+    /// once [`Self::start`] has connected. Returns a [`TrackHandle`] that
+    /// [`Self::unannounce`] later ends.
+    pub fn announce(&self, track_name: &str, priority: u32) -> Result<TrackHandle> {
+        let track_producer = self
+            .broadcast_producer
+            .write()
+            .as_mut()
+            .context("session is not connected")?
+            .create_track(Track {
+                name: track_name.to_string(),
+                priority,
+            });
+
+        let handle = self.next_track_handle.fetch_add(1, Ordering::Relaxed);
+        self.track_handles
+            .write()
+            .insert(handle, TrackHandleEntry::Announcement(track_producer));
+        Ok(handle)
+    }
+
+    /// Ends a track announced by [`Self::announce`] by dropping its producer, which
+    /// signals the track's end to subscribers. Returns `false` if `handle` doesn't
+    /// name an active announcement (already unannounced, names a subscription
+    /// instead, or never existed).
+    pub fn unannounce(&self, handle: TrackHandle) -> bool {
+        matches!(
+            self.track_handles.write().remove(&handle),
+            Some(TrackHandleEntry::Announcement(_))
+        )
+    }
+
+    /// Notifies the error callback
     #[allow(dead_code)]
     fn notify_error(&self, error: &str) {
         if let Some(callback) = self.error_callback.read().as_ref() {
@@ -146,6 +523,8 @@ impl Session {
             return Ok(());
         }
 
+        self.notify_connection(ConnectionState::Connecting, 0);
+
         // Initialize client
         let client = self
             .config
@@ -153,9 +532,9 @@ impl Session {
             .clone()
             .init()
             .context("Failed to initialize MoQ client")?;
-        
+
         self.notify_status("MoQ client initialized");
-        
+
         // Connect to server
         self.notify_status(&format!("Connecting to {}", self.config.moq_server_url));
         let session = client
@@ -167,58 +546,117 @@ impl Session {
         let moq_session = match self.mode {
             SessionMode::PublishOnly => {
                 let origin = Origin::produce();
-                
+
                 // Create broadcast producer
                 let broadcast = moq_lite::Broadcast::produce();
-                
+
                 // Setup producers for each configured broadcast
                 let broadcast_configs = self.broadcast_configs.read().clone();
                 for broadcast_config in &broadcast_configs {
-                    let producer = Producer::new(
-                        broadcast_config.clone(),
-                        broadcast.producer.clone(),
-                    );
+                    let mut producer =
+                        Producer::new(broadcast_config.clone(), broadcast.producer.clone());
+                    producer
+                        .initialize()
+                        .context("Failed to initialize track producer")?;
                     self.active_producers
                         .write()
                         .insert(broadcast_config.moq_track_name.clone(), producer);
                 }
-                
+
                 origin
                     .producer
                     .publish_broadcast(&self.config.moq_namespace, broadcast.consumer);
-                
-                *self.broadcast_producer.write() = Some(Arc::new(broadcast.producer));
-                
+
+                *self.broadcast_producer.write() = Some(broadcast.producer);
+
                 moq_lite::Session::connect(session, origin.consumer, None).await?
             }
             SessionMode::SubscribeOnly => {
                 let origin = Origin::produce();
-                let moq_session = 
+                let moq_session =
                     moq_lite::Session::connect(session, None, Some(origin.producer)).await?;
-                
+
                 // Setup broadcast consumer with retry logic
-                let broadcast_consumer = self.consume_broadcast_with_retry(&origin.consumer).await?;
-                
+                let broadcast_consumer = self
+                    .consume_broadcast_with_retry(&origin.consumer, self.subscribe_namespace())
+                    .await?;
+
                 *self.broadcast_consumer.write() = Some(Arc::new(broadcast_consumer));
-                
+
                 // Setup catalog processor
                 let catalog_processor = CatalogProcessor::new();
                 *self.catalog_processor.write() = Some(catalog_processor);
-                
+
                 // Start catalog consumer
                 self.start_catalog_consumer()?;
-                
+
+                if let Some(grace) = self.config.subscription_grace {
+                    self.validate_subscriptions(grace).await?;
+                }
+
                 moq_session
             }
             SessionMode::PublishAndSubscribe => {
+                // Two independent origins: one carries our own broadcast out to the
+                // peer as an ANNOUNCE, the other receives the peer's ANNOUNCEs for us
+                // to discover and consume - kept separate so we never echo back what
+                // the peer announced to us.
+                let publish_origin = Origin::produce();
+                let subscribe_origin = Origin::produce();
+
+                let moq_session = moq_lite::Session::connect(
+                    session,
+                    publish_origin.consumer,
+                    Some(subscribe_origin.producer),
+                )
+                .await?;
+
+                // Producer setup, mirroring `PublishOnly`
+                let broadcast = moq_lite::Broadcast::produce();
+                let broadcast_configs = self.broadcast_configs.read().clone();
+                for broadcast_config in &broadcast_configs {
+                    let mut producer =
+                        Producer::new(broadcast_config.clone(), broadcast.producer.clone());
+                    producer
+                        .initialize()
+                        .context("Failed to initialize track producer")?;
+                    self.active_producers
+                        .write()
+                        .insert(broadcast_config.moq_track_name.clone(), producer);
+                }
+                publish_origin
+                    .producer
+                    .publish_broadcast(&self.config.moq_namespace, broadcast.consumer);
+                *self.broadcast_producer.write() = Some(broadcast.producer);
+
+                // Consumer setup, mirroring `SubscribeOnly`, against the (possibly
+                // different) subscribe namespace
+                let broadcast_consumer = self
+                    .consume_broadcast_with_retry(
+                        &subscribe_origin.consumer,
+                        self.subscribe_namespace(),
+                    )
+                    .await?;
+                *self.broadcast_consumer.write() = Some(Arc::new(broadcast_consumer));
+
+                let catalog_processor = CatalogProcessor::new();
+                *self.catalog_processor.write() = Some(catalog_processor);
+
+                self.start_catalog_consumer()?;
+
+                if let Some(grace) = self.config.subscription_grace {
+                    self.validate_subscriptions(grace).await?;
+                }
+
+                moq_session
+            }
+            SessionMode::DiscoverOnly => {
                 let origin = Origin::produce();
-                let moq_session = 
-                    moq_lite::Session::connect(session, origin.consumer, Some(origin.producer))
-                        .await?;
-                
-                // Setup both producer and consumer
-                // TODO: Implement dual mode
-                
+                let moq_session =
+                    moq_lite::Session::connect(session, None, Some(origin.producer)).await?;
+
+                self.start_announce_watcher(origin.consumer);
+
                 moq_session
             }
         };
@@ -227,39 +665,136 @@ impl Session {
         *self.session.write() = Some(moq_session_arc.clone());
         *self.client.write() = Some(client);
         *self.is_connected.write() = true;
-        
+
         self.notify_status("Connected to MoQ server");
-        
+        self.notify_connection(ConnectionState::Connected, 0);
+
         // Start connection monitoring task for reconnection
         if self.config.reconnect_on_failure {
             self.start_connection_monitor(moq_session_arc);
         }
-        
+
+        // Start the heartbeat monitor if the caller opted in via `set_keepalive`
+        if self.keepalive.read().is_some() {
+            *self.last_activity.write() = Instant::now();
+            self.start_heartbeat_monitor();
+        }
+
         Ok(())
     }
-    
-    async fn consume_broadcast_with_retry(&self, consumer: &OriginConsumer) -> Result<BroadcastConsumer> {
-        let mut retry_count = 0;
+
+    /// Watches [`Self::last_activity`] on the interval from [`KeepaliveConfig::interval`]
+    /// and, if no catalog activity has been observed within [`KeepaliveConfig::timeout`],
+    /// marks the session disconnected and triggers [`Self::reconnect`] directly. Exits
+    /// once it fires a reconnect (a fresh monitor is started by the resulting
+    /// [`Self::start`]) or the session is shut down.
+    fn start_heartbeat_monitor(&self) {
+        let Some(keepalive) = *self.keepalive.read() else {
+            return;
+        };
+
+        let is_connected = self.is_connected.clone();
+        let last_activity = self.last_activity.clone();
+        let status_callback = self.status_callback.clone();
+        let self_clone = self.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(keepalive.interval);
+            ticker.tick().await; // first tick fires immediately
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        let elapsed = last_activity.read().elapsed();
+                        if elapsed <= keepalive.timeout {
+                            continue;
+                        }
+
+                        tracing::warn!(
+                            "Keepalive timeout: no catalog activity for {:?} (limit {:?})",
+                            elapsed,
+                            keepalive.timeout
+                        );
+                        *is_connected.write() = false;
+                        if let Some(cb) = status_callback.read().as_ref() {
+                            cb("Keepalive timeout, reconnecting...");
+                        }
+                        self_clone.notify_connection(ConnectionState::Disconnected, 0);
+
+                        let reconnect_target = self_clone.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = reconnect_target.reconnect().await {
+                                tracing::error!("Keepalive-triggered reconnect failed: {}", e);
+                            }
+                        });
+                        break;
+                    }
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("Heartbeat monitor shutting down");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// The namespace to subscribe from, defaulting to [`SessionConfig::moq_namespace`]
+    /// when [`SessionConfig::subscribe_namespace`] is unset.
+    fn subscribe_namespace(&self) -> &str {
+        self.config
+            .subscribe_namespace
+            .as_deref()
+            .unwrap_or(&self.config.moq_namespace)
+    }
+
+    /// Waits for `namespace` to appear on `consumer`, retrying on
+    /// [`SessionConfig::reconnect_policy`]'s backoff schedule instead of a fixed
+    /// delay - `FixedInterval`-like behavior falls out of a `multiplier` of `1.0`,
+    /// `ExponentialBackoff` out of a `multiplier` above `1.0`, and `FailAfter` out of
+    /// `max_retries`. Gives up and fires [`Self::notify_error`] with a terminal
+    /// message once [`ReconnectPolicy::allows_attempt`] refuses another attempt.
+    async fn consume_broadcast_with_retry(
+        &self,
+        consumer: &OriginConsumer,
+        namespace: &str,
+    ) -> Result<BroadcastConsumer> {
+        let policy = self.config.reconnect_policy;
+        let mut attempt = 0u32;
         let mut shutdown_rx = self.shutdown_tx.subscribe();
-        
+
         loop {
-            match consumer.consume_broadcast(&self.config.moq_namespace) {
+            match consumer.consume_broadcast(namespace) {
                 Some(broadcast_consumer) => {
-                    if retry_count > 0 {
-                        self.notify_status(&format!("Successfully connected to broadcast '{}' after {} retries", 
-                                                   self.config.moq_namespace, retry_count));
+                    if attempt > 0 {
+                        self.notify_status(&format!(
+                            "Successfully connected to broadcast '{}' after {} retries",
+                            namespace, attempt
+                        ));
                     }
                     return Ok(broadcast_consumer);
                 }
                 None => {
-                    retry_count += 1;
-                    
-                    self.notify_status(&format!("Broadcast '{}' not available, retrying in 2 seconds... (attempt {})", 
-                                               self.config.moq_namespace, retry_count));
-                    
-                    // Wait 2 seconds but allow cancellation via shutdown signal
+                    if !policy.allows_attempt(attempt) {
+                        let message = format!(
+                            "Giving up waiting for broadcast '{}' after {} attempts",
+                            namespace, attempt
+                        );
+                        self.notify_error(&message);
+                        return Err(anyhow::anyhow!(message));
+                    }
+
+                    let delay = policy.delay_for_attempt(attempt);
+                    attempt += 1;
+
+                    self.notify_status(&format!(
+                        "Broadcast '{}' not available, retrying in {:?}... (attempt {})",
+                        namespace, delay, attempt
+                    ));
+
+                    // Wait out the backoff delay but allow cancellation via shutdown signal
                     tokio::select! {
-                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(2)) => {
+                        _ = tokio::time::sleep(delay) => {
                             // Continue retrying
                         }
                         _ = shutdown_rx.recv() => {
@@ -270,45 +805,105 @@ impl Session {
             }
         }
     }
-    
-    fn start_connection_monitor(&self, session: Arc<moq_lite::Session<moq_native::web_transport_quinn::Session>>) {
+
+    /// Watches ANNOUNCE messages on `consumer` for the lifetime of the session,
+    /// reporting every broadcast path under [`SessionConfig::moq_namespace`] through
+    /// [`Self::announce_callback`] - `true` when the path appears, `false` when it's
+    /// withdrawn. Exits once `consumer` stops yielding announcements (session closed)
+    /// or the session is shut down.
+    fn start_announce_watcher(&self, mut consumer: OriginConsumer) {
+        let announce_callback = self.announce_callback.clone();
+        let namespace_prefix = self.config.moq_namespace.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    next = consumer.announced() => {
+                        let Some((path, broadcast)) = next else {
+                            tracing::info!("Announce watcher ended: origin closed");
+                            break;
+                        };
+
+                        if !path.as_ref().starts_with(&namespace_prefix) {
+                            continue;
+                        }
+
+                        let added = broadcast.is_some();
+                        tracing::debug!(
+                            "Broadcast {} under '{}': {}",
+                            if added { "announced" } else { "unannounced" },
+                            namespace_prefix,
+                            path
+                        );
+
+                        if let Some(callback) = announce_callback.read().as_ref() {
+                            callback(path.as_ref(), added);
+                        }
+                    }
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("Announce watcher shutting down");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    fn start_connection_monitor(
+        &self,
+        session: Arc<moq_lite::Session<moq_native::web_transport_quinn::Session>>,
+    ) {
         let is_connected = self.is_connected.clone();
         let status_callback = self.status_callback.clone();
+        let policy = self.config.reconnect_policy;
         let self_clone = self.clone();
         let mut shutdown_rx = self.shutdown_tx.subscribe();
-        
+
         tokio::spawn(async move {
             loop {
                 tokio::select! {
                     result = session.closed() => {
                         tracing::warn!("MoQ session closed: {:?}", result);
                         *is_connected.write() = false;
-                        
+
                         if let Some(cb) = status_callback.read().as_ref() {
                             cb("Connection lost, attempting to reconnect...");
                         }
-                        
-                        // Wait a bit before reconnecting
-                        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                        
-                        // Attempt to reconnect
-                        match self_clone.reconnect().await {
-                            Ok(_) => {
-                                tracing::info!("Successfully reconnected to MoQ server");
-                                if let Some(cb) = status_callback.read().as_ref() {
-                                    cb("Reconnected to MoQ server");
+                        self_clone.notify_connection(ConnectionState::Disconnected, 0);
+
+                        let mut attempt = 0u32;
+                        loop {
+                            self_clone.notify_connection(ConnectionState::Reconnecting, attempt);
+                            tokio::time::sleep(policy.delay_for_attempt(attempt)).await;
+
+                            match self_clone.reconnect().await {
+                                Ok(_) => {
+                                    tracing::info!("Successfully reconnected to MoQ server");
+                                    if let Some(cb) = status_callback.read().as_ref() {
+                                        cb("Reconnected to MoQ server");
+                                    }
+                                    self_clone.notify_connection(ConnectionState::Connected, attempt);
+                                    // After successful reconnect, exit this monitor (new one will be created)
+                                    return;
                                 }
-                                // After successful reconnect, exit this monitor (new one will be created)
-                                break;
-                            }
-                            Err(e) => {
-                                tracing::error!("Failed to reconnect: {}", e);
-                                if let Some(cb) = status_callback.read().as_ref() {
-                                    cb(&format!("Reconnection failed: {}", e));
+                                Err(e) => {
+                                    tracing::error!("Failed to reconnect: {}", e);
+                                    if let Some(cb) = status_callback.read().as_ref() {
+                                        cb(&format!("Reconnection failed: {}", e));
+                                    }
+
+                                    attempt += 1;
+                                    if !policy.allows_attempt(attempt) {
+                                        tracing::error!(
+                                            "Giving up reconnecting after {} attempts",
+                                            attempt
+                                        );
+                                        self_clone.notify_connection(ConnectionState::Failed, attempt);
+                                        return;
+                                    }
+                                    // Loop will retry with the next backoff delay
                                 }
-                                // Wait before trying again
-                                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                                // Loop will retry
                             }
                         }
                     }
@@ -320,10 +915,10 @@ impl Session {
             }
         });
     }
-    
+
     async fn reconnect(&self) -> Result<()> {
         tracing::info!("Attempting to reconnect...");
-        
+
         // Stop all active consumers
         {
             let mut consumers = self.active_consumers.write();
@@ -331,24 +926,33 @@ impl Session {
                 consumer.stop();
             }
         }
-        
+
         // Stop catalog consumer
         if let Some(consumer) = self.catalog_consumer.write().take() {
             consumer.stop();
         }
-        
+
+        // Drop any runtime-managed subscriptions/announcements from the old connection
+        self.track_handles.write().clear();
+
+        // Tear down both halves of the old connection so `start()` rebuilds them from
+        // scratch - matters for `PublishAndSubscribe`, where both sides are live.
+        self.active_producers.write().clear();
+        *self.broadcast_producer.write() = None;
+        *self.broadcast_consumer.write() = None;
+
         // Clear session and client
         *self.session.write() = None;
         *self.client.write() = None;
         *self.is_connected.write() = false;
-        
+
         // Wait a moment
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        
+
         // Reconnect - start() already has broadcast retry logic
         self.start().await
     }
-    
+
     fn start_catalog_consumer(&self) -> Result<()> {
         let broadcast_consumer = self
             .broadcast_consumer
@@ -362,62 +966,126 @@ impl Session {
             data_callback: {
                 let catalog_processor = self.catalog_processor.clone();
                 let requested_subs = self.requested_subscriptions.clone();
+                let requested_patterns = self.requested_subscription_patterns.clone();
                 let active_consumers = self.active_consumers.clone();
                 let broadcast_consumer = broadcast_consumer.clone();
                 let status_callback = self.status_callback.clone();
                 let session_for_reconnect = self.clone(); // Clone self for reconnection callbacks
-                
+                let last_activity = self.last_activity.clone();
+
                 Arc::new(move |data: &[u8]| {
+                    *last_activity.write() = Instant::now();
+
                     if let Some(processor) = catalog_processor.read().as_ref() {
-                        if let Err(e) = processor.process_catalog_data(data) {
-                            tracing::error!("Failed to process catalog: {}", e);
-                            return;
+                        let events = match processor.process_catalog_data(data) {
+                            Ok(events) => events,
+                            Err(e) => {
+                                tracing::error!("Failed to process catalog: {}", e);
+                                return;
+                            }
+                        };
+                        for event in &events {
+                            tracing::debug!("Catalog event: {:?}", event);
                         }
-                        
+
                         // Check and update subscriptions based on catalog
                         let available_tracks = processor.get_available_tracks();
                         let requested = requested_subs.read();
+                        let patterns = requested_patterns.read();
                         let mut active = active_consumers.write();
-                        
-                        // Remove subscriptions for tracks no longer available
+
+                        // Remove subscriptions for tracks no longer available - this
+                        // covers both exact and pattern-matched consumers, since both
+                        // are keyed by track name in the same map.
                         active.retain(|track_name, _consumer| {
                             available_tracks.contains_key(track_name)
                         });
-                        
+
+                        // Report each exact subscription's state against this catalog
+                        // snapshot - patterns aren't tracked here since they have no
+                        // single name to report status for.
+                        for track_name in requested.keys() {
+                            let state = if available_tracks.contains_key(track_name) {
+                                SubscriptionState::Active
+                            } else {
+                                SubscriptionState::NotInCatalog
+                            };
+                            session_for_reconnect.report_subscription_state(track_name, state);
+                        }
+
+                        // Resolve each available track to a data callback and start
+                        // position: an exact `add_subscription` entry takes priority
+                        // (keeping its own `start_position`/`priority`), otherwise the
+                        // first matching pattern from `add_subscription_pattern`
+                        // (which always starts at the default position, since a
+                        // pattern has no single config to inherit one from).
+                        let wanted: Vec<(String, DataCallback, StartPosition, Option<u8>)> =
+                            available_tracks
+                                .keys()
+                                .filter_map(|track_name| {
+                                    if let Some(sub_config) = requested.get(track_name) {
+                                        Some((
+                                            track_name.clone(),
+                                            sub_config.data_callback.clone(),
+                                            sub_config.start_position,
+                                            sub_config.priority,
+                                        ))
+                                    } else {
+                                        patterns
+                                            .iter()
+                                            .find(|(pattern, _)| pattern.matches(track_name))
+                                            .map(|(_, callback)| {
+                                                (
+                                                    track_name.clone(),
+                                                    callback.clone(),
+                                                    StartPosition::default(),
+                                                    None,
+                                                )
+                                            })
+                                    }
+                                })
+                                .collect();
+
                         // Add new subscriptions for available tracks
-                        for (track_name, sub_config) in requested.iter() {
-                            if available_tracks.contains_key(track_name) && !active.contains_key(track_name) {
+                        for (track_name, data_callback, start_position, priority) in wanted {
+                            if !active.contains_key(&track_name) {
                                 if let Some(callback) = status_callback.read().as_ref() {
                                     callback(&format!("Starting subscription to: {}", track_name));
                                 }
-                                
+
                                 // Create a modified subscription config with reconnect callback
                                 let session_for_track = session_for_reconnect.clone();
                                 let track_name_for_log = track_name.clone();
                                 let modified_config = SubscriptionConfig {
-                                    moq_track_name: sub_config.moq_track_name.clone(),
-                                    data_callback: sub_config.data_callback.clone(),
+                                    moq_track_name: track_name.clone(),
+                                    data_callback: data_callback.clone(),
+                                    start_position,
+                                    priority,
                                     reconnect_callback: Some(Arc::new(move || {
                                         let session = session_for_track.clone();
                                         let track = track_name_for_log.clone();
                                         tokio::spawn(async move {
                                             tracing::info!("Consumer-triggered reconnection starting for track {}...", track);
                                             if let Err(e) = session.reconnect().await {
-                                                tracing::error!("Consumer-triggered reconnection failed: {}", e);
+                                                tracing::error!(
+                                                    "Consumer-triggered reconnection failed: {}",
+                                                    e
+                                                );
                                             }
                                         });
                                     })),
                                 };
-                                
-                                match Consumer::new(
-                                    broadcast_consumer.clone(),
-                                    modified_config,
-                                ) {
+
+                                match Consumer::new(broadcast_consumer.clone(), modified_config) {
                                     Ok(consumer) => {
                                         active.insert(track_name.clone(), consumer);
                                     }
                                     Err(e) => {
-                                        tracing::error!("Failed to create consumer for {}: {}", track_name, e);
+                                        tracing::error!(
+                                            "Failed to create consumer for {}: {}",
+                                            track_name,
+                                            e
+                                        );
                                     }
                                 }
                             }
@@ -432,35 +1100,43 @@ impl Session {
                     tokio::spawn(async move {
                         tracing::info!("Catalog consumer-triggered reconnection starting...");
                         if let Err(e) = session.reconnect().await {
-                            tracing::error!("Catalog consumer-triggered reconnection failed: {}", e);
+                            tracing::error!(
+                                "Catalog consumer-triggered reconnection failed: {}",
+                                e
+                            );
                         }
                     });
                 }))
             },
+            start_position: StartPosition::Latest,
+            priority: None,
         };
 
         let catalog_consumer = Consumer::new(broadcast_consumer, catalog_config)?;
         *self.catalog_consumer.write() = Some(catalog_consumer);
-        
+
         self.notify_status("Catalog consumer started");
-        
+
         Ok(())
     }
 
     pub fn stop(&self) {
         *self.is_connected.write() = false;
         let _ = self.shutdown_tx.send(());
-        
+
         // Stop all consumers
         self.active_consumers.write().clear();
         *self.catalog_consumer.write() = None;
-        
+
         // Stop all producers
         self.active_producers.write().clear();
-        
+
+        // Drop any runtime-managed subscriptions/announcements
+        self.track_handles.write().clear();
+
         *self.session.write() = None;
         *self.client.write() = None;
-        
+
         self.notify_status("Session stopped");
     }
 
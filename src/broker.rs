@@ -0,0 +1,125 @@
+//! Track broker: fan a single upstream subscription out to many local consumers.
+//!
+//! [`MoqSession::subscribe_track_internal`](crate::session::MoqSession::subscribe_track_internal)
+//! opens a brand new upstream subscription on every call, even when a caller already
+//! has one in flight for the same broadcast/track pair. [`TrackBroker`] sits in front
+//! of it and caches the resulting [`TrackConsumer`] per broadcast/track key, so
+//! repeated `subscribe_track` calls for the same pair hand back a clone of the
+//! existing stream instead of opening a duplicate subscription. Once the last local
+//! consumer goes away, the cached upstream subscription stays in the cache until the
+//! next `subscribe_track` call for that pair evicts it and opens a fresh one. This is the
+//! prerequisite for using a `MoqSession` as a relay/edge node: ingest each
+//! broadcast/track once, serve it to as many local consumers as ask for it.
+
+use anyhow::Result;
+use moq_lite::TrackConsumer;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::session::MoqSession;
+
+struct CachedTrack {
+    consumer: TrackConsumer,
+    // Cloned into every `BrokeredTrack` handed out for this entry; once those clones
+    // are all dropped, only this field's own copy remains, and `subscribe_track`
+    // detects that via `Arc::strong_count` to evict the stale entry.
+    refcount: Arc<()>,
+}
+
+/// A [`TrackConsumer`] handed out by [`TrackBroker::subscribe_track`]. Keeps the
+/// broker's cache entry for this broadcast/track pair alive for as long as this
+/// handle (or a clone of it) exists; once the last handle is dropped, the next
+/// `subscribe_track` call for the same pair evicts the stale entry and re-subscribes
+/// upstream instead of handing back a dead stream.
+#[derive(Clone)]
+pub struct BrokeredTrack {
+    pub consumer: TrackConsumer,
+    _refcount: Arc<()>,
+}
+
+/// Fans a single upstream track subscription out to many local callers. Wraps a
+/// [`MoqSession`] (typically a subscriber session relaying into a publisher session
+/// via [`MoqSession::relay_broadcast`](crate::session::MoqSession::relay_broadcast) or
+/// serving local consumers directly) and deduplicates subscriptions by broadcast/track
+/// name.
+pub struct TrackBroker {
+    session: MoqSession,
+    cache: Arc<RwLock<HashMap<(String, String), CachedTrack>>>,
+}
+
+impl TrackBroker {
+    /// Create a broker in front of `session`.
+    pub fn new(session: MoqSession) -> Self {
+        Self {
+            session,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Subscribe to `track_name` in `broadcast_name`, reusing an already-cached
+    /// upstream subscription for the same pair if one still has at least one live
+    /// [`BrokeredTrack`] handle outstanding, opening a fresh one otherwise.
+    pub async fn subscribe_track(
+        &self,
+        broadcast_name: &str,
+        track_name: &str,
+    ) -> Result<BrokeredTrack> {
+        let key = (broadcast_name.to_string(), track_name.to_string());
+
+        {
+            let mut cache = self.cache.write().await;
+            if let Some(cached) = cache.get(&key) {
+                if Arc::strong_count(&cached.refcount) > 1 {
+                    debug!(
+                        "[TrackBroker] Reusing cached subscription for '{}'/'{}'",
+                        broadcast_name, track_name
+                    );
+                    return Ok(BrokeredTrack {
+                        consumer: cached.consumer.clone(),
+                        _refcount: cached.refcount.clone(),
+                    });
+                }
+                // Last consumer went away; drop the stale entry and re-subscribe below.
+                cache.remove(&key);
+            }
+        }
+
+        let consumer = self
+            .session
+            .subscribe_track_internal(broadcast_name, track_name)
+            .await?;
+        let refcount = Arc::new(());
+
+        debug!(
+            "[TrackBroker] Opened upstream subscription for '{}'/'{}'",
+            broadcast_name, track_name
+        );
+
+        self.cache.write().await.insert(
+            key,
+            CachedTrack {
+                consumer: consumer.clone(),
+                refcount: refcount.clone(),
+            },
+        );
+
+        Ok(BrokeredTrack {
+            consumer,
+            _refcount: refcount,
+        })
+    }
+
+    /// Broadcast/track pairs currently cached, i.e. with at least one live
+    /// [`BrokeredTrack`] handle outstanding.
+    pub async fn active_tracks(&self) -> Vec<(String, String)> {
+        self.cache
+            .read()
+            .await
+            .iter()
+            .filter(|(_, cached)| Arc::strong_count(&cached.refcount) > 1)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
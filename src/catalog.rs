@@ -1,14 +1,31 @@
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use moq_lite::{BroadcastProducer, Track};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use crate::source::{
+    fmp4_codec_string, fmp4_esds_audio_config, fmp4_find_box, fmp4_handler_type,
+    fmp4_sample_entry_box, fmp4_top_level_boxes, fmp4_visual_dimensions, AUDIO_SAMPLE_ENTRY_FIXED,
+    VISUAL_SAMPLE_ENTRY_FIXED,
+};
+
+/// What kind of media a track carries. `Extension` is an escape hatch for track
+/// kinds this crate doesn't model directly: any `type` string prefixed with `x-`
+/// round-trips through it instead of failing to deserialize, so downstream tools can
+/// carry custom track kinds through a catalog untouched.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TrackType {
-    #[serde(rename = "video")]
     Video,
-    #[serde(rename = "audio")]
     Audio,
-    #[serde(rename = "data")]
     Data,
+    /// Timed text - subtitles, captions, or descriptions (see [`TrackRole`])
+    Caption,
+    /// A custom, `x-`-prefixed track kind not known to this crate, preserved verbatim
+    /// (including the `x-` prefix)
+    Extension(String),
 }
 
 impl std::fmt::Display for TrackType {
@@ -17,6 +34,53 @@ impl std::fmt::Display for TrackType {
             TrackType::Video => write!(f, "video"),
             TrackType::Audio => write!(f, "audio"),
             TrackType::Data => write!(f, "data"),
+            TrackType::Caption => write!(f, "caption"),
+            TrackType::Extension(kind) => write!(f, "{kind}"),
+        }
+    }
+}
+
+impl Serialize for TrackType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TrackType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "video" => Ok(TrackType::Video),
+            "audio" => Ok(TrackType::Audio),
+            "data" => Ok(TrackType::Data),
+            "caption" => Ok(TrackType::Caption),
+            _ if s.starts_with("x-") => Ok(TrackType::Extension(s)),
+            other => Err(serde::de::Error::custom(format!(
+                "unknown track type \"{other}\" (custom types must be prefixed with \"x-\")"
+            ))),
+        }
+    }
+}
+
+/// The role a [`TrackType::Caption`] track plays relative to the main program, so a
+/// consumer can distinguish closed captions (a transcript of dialogue and relevant
+/// sound) from open subtitles (translated dialogue only) and audio descriptions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrackRole {
+    #[serde(rename = "subtitles")]
+    Subtitles,
+    #[serde(rename = "captions")]
+    Captions,
+    #[serde(rename = "descriptions")]
+    Descriptions,
+}
+
+impl std::fmt::Display for TrackRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrackRole::Subtitles => write!(f, "subtitles"),
+            TrackRole::Captions => write!(f, "captions"),
+            TrackRole::Descriptions => write!(f, "descriptions"),
         }
     }
 }
@@ -27,6 +91,29 @@ pub struct TrackDefinition {
     pub priority: u32,
     #[serde(rename = "type")]
     pub track_type: TrackType,
+    /// RFC 6381 codec string (e.g. "avc1.64001f", "mp4a.40.2"), when known. Only
+    /// populated by sources that can actually inspect the media, such as
+    /// `source::File`; `None` means the catalog should fall back to a default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    /// How long (in milliseconds) a relay should retain each group published on this
+    /// track before it's safe to drop as stale, e.g. 60 seconds for a moq-clock-style
+    /// "one group per minute" track. `None` means groups are kept with no bound, the
+    /// previous behavior. `moq-lite`'s `GroupProducer::create_group` in this tree
+    /// takes no expiry parameter, so this isn't wire-level TTL metadata read by
+    /// relays - `MoqSession::start_group` enforces it locally by closing the group
+    /// once the TTL elapses (see `MoqSession::start_group`).
+    #[serde(skip)]
+    pub group_ttl_ms: Option<u64>,
+    /// BCP-47/ISO-639 language tag (e.g. "en", "es-419"), for tracks where it's
+    /// meaningful - captions/subtitles, or an alternate-language audio track. `None`
+    /// when not applicable or not known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// What role a [`TrackType::Caption`] track plays (subtitles vs captions vs
+    /// descriptions). `None` for non-caption tracks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<TrackRole>,
 }
 
 impl TrackDefinition {
@@ -35,6 +122,10 @@ impl TrackDefinition {
             name: name.into(),
             priority,
             track_type,
+            codec: None,
+            group_ttl_ms: None,
+            language: None,
+            role: None,
         }
     }
 
@@ -49,6 +140,37 @@ impl TrackDefinition {
     pub fn data(name: impl Into<String>, priority: u32) -> Self {
         Self::new(name, priority, TrackType::Data)
     }
+
+    /// A timed-text track (subtitles, captions, or descriptions - see [`TrackRole`])
+    pub fn caption(name: impl Into<String>, priority: u32) -> Self {
+        Self::new(name, priority, TrackType::Caption)
+    }
+
+    /// Attach a codec string detected from the source media
+    pub fn with_codec(mut self, codec: impl Into<String>) -> Self {
+        self.codec = Some(codec.into());
+        self
+    }
+
+    /// Bound how long a relay should retain each group published on this track
+    /// before it's safe to drop as stale (see [`Self::group_ttl_ms`])
+    pub fn with_group_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.group_ttl_ms = Some(ttl.as_millis() as u64);
+        self
+    }
+
+    /// Tag this track with a BCP-47/ISO-639 language (see [`Self::language`])
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Tag this track with a role, e.g. distinguishing captions from open subtitles
+    /// (see [`Self::role`])
+    pub fn with_role(mut self, role: TrackRole) -> Self {
+        self.role = Some(role);
+        self
+    }
 }
 
 impl From<TrackDefinition> for moq_lite::Track {
@@ -80,6 +202,10 @@ pub struct SesameCatalogTrack {
     #[serde(rename = "trackName")]
     pub track_name: String,
     pub priority: u32,
+    /// BCP-47/ISO-639 language tag, when the track carries one (see
+    /// [`TrackDefinition::language`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
 }
 
 impl From<&TrackDefinition> for SesameCatalogTrack {
@@ -88,6 +214,7 @@ impl From<&TrackDefinition> for SesameCatalogTrack {
             track_type: def.track_type.clone(),
             track_name: def.name.clone(),
             priority: def.priority,
+            language: def.language.clone(),
         }
     }
 }
@@ -110,6 +237,156 @@ impl SesameCatalog {
     pub fn find_track(&self, name: &str) -> Option<&SesameCatalogTrack> {
         self.tracks.iter().find(|t| t.track_name == name)
     }
+
+    /// Convert to a hang catalog, folding each flat [`SesameCatalogTrack`] into the
+    /// matching [`HangVideo`]/[`HangAudio`]/[`HangCaption`] section as a single
+    /// rendition, inferring defaults where Sesame lacks codec detail (see
+    /// [`HangCatalog::from_tracks`], which does the same for [`TrackDefinition`]s).
+    pub fn to_hang(&self) -> HangCatalog {
+        let mut catalog = HangCatalog::new();
+
+        for track in &self.tracks {
+            match &track.track_type {
+                TrackType::Video => {
+                    let mut renditions = HashMap::new();
+                    renditions.insert(
+                        track.track_name.clone(),
+                        HangVideoConfig {
+                            codec: "avc1.42001e".parse().unwrap(), // H.264 baseline profile fallback
+                            description: None,
+                            coded_width: None,
+                            coded_height: None,
+                            display_ratio_width: None,
+                            display_ratio_height: None,
+                            bitrate: None,
+                            framerate: None,
+                            optimize_for_latency: None,
+                        },
+                    );
+                    catalog.video = Some(HangVideo {
+                        renditions,
+                        priority: track.priority as u8,
+                        display: None,
+                        rotation: None,
+                        flip: None,
+                    });
+                }
+                TrackType::Audio => {
+                    let mut renditions = HashMap::new();
+                    renditions.insert(
+                        track.track_name.clone(),
+                        HangAudioConfig {
+                            codec: "opus".parse().unwrap(),
+                            sample_rate: 48000,
+                            channel_count: 2,
+                            bitrate: None,
+                            description: None,
+                        },
+                    );
+                    catalog.audio = Some(HangAudio {
+                        renditions,
+                        priority: track.priority as u8,
+                    });
+                }
+                TrackType::Caption => {
+                    let language = track.language.clone().unwrap_or_else(|| "und".to_string());
+                    let caption = catalog.caption.get_or_insert_with(|| HangCaption {
+                        renditions: HashMap::new(),
+                    });
+                    caption.renditions.insert(
+                        language,
+                        HangCaptionConfig {
+                            track: track.track_name.clone(),
+                            priority: track.priority as u8,
+                            role: None,
+                        },
+                    );
+                }
+                TrackType::Data | TrackType::Extension(_) => {
+                    if track.track_name == "catalog.json" {
+                        continue;
+                    }
+                    catalog.preview = Some(HangTrack {
+                        name: track.track_name.clone(),
+                        priority: track.priority as u8,
+                    });
+                }
+            }
+        }
+
+        catalog
+    }
+}
+
+impl From<&HangCatalog> for SesameCatalog {
+    /// Flatten every hang rendition plus the location/chat/preview tracks back into
+    /// the flat Sesame `tracks` list, preserving their priorities (and, for captions,
+    /// language tag).
+    fn from(hang: &HangCatalog) -> Self {
+        let mut tracks = Vec::new();
+
+        if let Some(video) = &hang.video {
+            for name in video.renditions.keys() {
+                tracks.push(SesameCatalogTrack {
+                    track_type: TrackType::Video,
+                    track_name: name.clone(),
+                    priority: video.priority as u32,
+                    language: None,
+                });
+            }
+        }
+
+        if let Some(audio) = &hang.audio {
+            for name in audio.renditions.keys() {
+                tracks.push(SesameCatalogTrack {
+                    track_type: TrackType::Audio,
+                    track_name: name.clone(),
+                    priority: audio.priority as u32,
+                    language: None,
+                });
+            }
+        }
+
+        if let Some(caption) = &hang.caption {
+            for (language, rendition) in &caption.renditions {
+                tracks.push(SesameCatalogTrack {
+                    track_type: TrackType::Caption,
+                    track_name: rendition.track.clone(),
+                    priority: rendition.priority as u32,
+                    language: Some(language.clone()),
+                });
+            }
+        }
+
+        if let Some(location) = &hang.location {
+            tracks.push(SesameCatalogTrack {
+                track_type: TrackType::Data,
+                track_name: location.track.clone(),
+                priority: location.priority as u32,
+                language: None,
+            });
+        }
+
+        if let Some(chat) = &hang.chat {
+            tracks.push(SesameCatalogTrack {
+                track_type: TrackType::Data,
+                track_name: chat.track.clone(),
+                priority: chat.priority as u32,
+                language: None,
+            });
+        }
+
+        if let Some(preview) = &hang.preview {
+            tracks.push(SesameCatalogTrack {
+                track_type: TrackType::Data,
+                track_name: preview.name.clone(),
+                priority: preview.priority as u32,
+                language: None,
+            });
+        }
+
+        Self { tracks }
+    }
 }
 
 /// Hang format catalog (JSON-based, compatible with hang crate)
@@ -136,6 +413,10 @@ pub struct HangCatalog {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chat: Option<HangChat>,
 
+    /// Caption/subtitle tracks, keyed by language
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<HangCaption>,
+
     /// Preview information about the broadcast
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preview: Option<HangTrack>,
@@ -164,12 +445,264 @@ pub struct HangVideo {
     pub flip: Option<bool>,
 }
 
+/// One target rendition in an adaptive-bitrate ladder - see [`HangCatalog::with_ladder`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LadderRung {
+    /// Rendition name, e.g. "video-720p"
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// Bitrate in bits per second
+    pub bitrate: u64,
+    pub framerate: Option<f64>,
+}
+
+impl LadderRung {
+    pub fn new(name: impl Into<String>, width: u32, height: u32, bitrate: u64) -> Self {
+        Self {
+            name: name.into(),
+            width,
+            height,
+            bitrate,
+            framerate: None,
+        }
+    }
+
+    pub fn with_framerate(mut self, framerate: f64) -> Self {
+        self.framerate = Some(framerate);
+        self
+    }
+
+    /// A sensible default ABR ladder: 1080p/6Mbps, 720p/3Mbps, 480p/1.5Mbps, 360p/0.8Mbps
+    pub fn default_ladder() -> Vec<LadderRung> {
+        vec![
+            LadderRung::new("video-1080p", 1920, 1080, 6_000_000),
+            LadderRung::new("video-720p", 1280, 720, 3_000_000),
+            LadderRung::new("video-480p", 854, 480, 1_500_000),
+            LadderRung::new("video-360p", 640, 360, 800_000),
+        ]
+    }
+}
+
+/// A video codec family and its parameters, modeled from (and serialized to/from) its
+/// WebCodecs/RFC 6381 codec string (e.g. "avc1.64001f", "vp09.00.10.08"). A codec
+/// string that doesn't match one of the known families round-trips losslessly via
+/// [`VideoCodec::Unknown`] instead of failing to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VideoCodec {
+    /// H.264/AVC - `avc1.PPCCLL` (profile_idc, constraint_flags, level_idc, each a raw
+    /// byte as found in the `avcC` box). Always serialized with the `avc1` prefix,
+    /// regardless of whether it was parsed from `avc1.` or `avc3.`.
+    Avc {
+        profile: u8,
+        constraint_flags: u8,
+        level: u8,
+    },
+    /// H.265/HEVC - `hvc1.<profile_space+profile_idc>.<profile_compatibility (hex)>.
+    /// <tier><level_idc>.<constraint_flags>`. `constraint_flags` is kept as the raw
+    /// dot-joined suffix so an HEVC string round-trips exactly even though this crate
+    /// doesn't interpret those bytes.
+    Hevc {
+        profile_space: u8,
+        profile_idc: u8,
+        profile_compatibility: u32,
+        high_tier: bool,
+        level_idc: u8,
+        constraint_flags: String,
+    },
+    /// VP8 - `vp8`, no parameters
+    Vp8,
+    /// VP9 - `vp09.PP.LL.DD` (profile, level, bit depth)
+    Vp9 {
+        profile: u8,
+        level: u8,
+        bit_depth: u8,
+    },
+    /// AV1 - `av01.P.LLT.DD` (profile, level, tier, bit depth; tier is 'M'ain or
+    /// 'H'igh)
+    Av1 {
+        profile: u8,
+        level: u8,
+        high_tier: bool,
+        bit_depth: u8,
+    },
+    /// Any codec string that doesn't match one of the families above, preserved
+    /// verbatim
+    Unknown(String),
+}
+
+impl std::fmt::Display for VideoCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoCodec::Avc {
+                profile,
+                constraint_flags,
+                level,
+            } => write!(f, "avc1.{profile:02x}{constraint_flags:02x}{level:02x}"),
+            VideoCodec::Hevc {
+                profile_space,
+                profile_idc,
+                profile_compatibility,
+                high_tier,
+                level_idc,
+                constraint_flags,
+            } => {
+                let space = match profile_space {
+                    1 => "A",
+                    2 => "B",
+                    3 => "C",
+                    _ => "",
+                };
+                let tier = if *high_tier { "H" } else { "L" };
+                write!(
+                    f,
+                    "hvc1.{space}{profile_idc}.{profile_compatibility:x}.{tier}{level_idc}"
+                )?;
+                if !constraint_flags.is_empty() {
+                    write!(f, ".{constraint_flags}")?;
+                }
+                Ok(())
+            }
+            VideoCodec::Vp8 => write!(f, "vp8"),
+            VideoCodec::Vp9 {
+                profile,
+                level,
+                bit_depth,
+            } => write!(f, "vp09.{profile:02}.{level:02}.{bit_depth:02}"),
+            VideoCodec::Av1 {
+                profile,
+                level,
+                high_tier,
+                bit_depth,
+            } => {
+                let tier = if *high_tier { "H" } else { "M" };
+                write!(f, "av01.{profile}.{level:02}{tier}.{bit_depth:02}")
+            }
+            VideoCodec::Unknown(codec) => write!(f, "{codec}"),
+        }
+    }
+}
+
+impl FromStr for VideoCodec {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        if let Some(codec) = Self::parse_avc(s)
+            .or_else(|| Self::parse_hevc(s))
+            .or_else(|| Self::parse_vp9(s))
+            .or_else(|| Self::parse_av1(s))
+        {
+            return Ok(codec);
+        }
+        if s == "vp8" {
+            return Ok(VideoCodec::Vp8);
+        }
+        Ok(VideoCodec::Unknown(s.to_string()))
+    }
+}
+
+impl VideoCodec {
+    fn parse_avc(s: &str) -> Option<Self> {
+        let suffix = s
+            .strip_prefix("avc1.")
+            .or_else(|| s.strip_prefix("avc3."))?;
+        if suffix.len() != 6 {
+            return None;
+        }
+        Some(VideoCodec::Avc {
+            profile: u8::from_str_radix(&suffix[0..2], 16).ok()?,
+            constraint_flags: u8::from_str_radix(&suffix[2..4], 16).ok()?,
+            level: u8::from_str_radix(&suffix[4..6], 16).ok()?,
+        })
+    }
+
+    fn parse_hevc(s: &str) -> Option<Self> {
+        let suffix = s
+            .strip_prefix("hvc1.")
+            .or_else(|| s.strip_prefix("hev1."))?;
+        let mut parts = suffix.splitn(4, '.');
+        let profile_part = parts.next()?;
+        let compat_part = parts.next()?;
+        let tier_level_part = parts.next()?;
+        let constraint_flags = parts.next().unwrap_or("").to_string();
+
+        let (space_char, profile_idc_str) = match profile_part.chars().next() {
+            Some(c) if c.is_ascii_alphabetic() => (Some(c), &profile_part[1..]),
+            _ => (None, profile_part),
+        };
+        let profile_space = match space_char {
+            Some('A') => 1,
+            Some('B') => 2,
+            Some('C') => 3,
+            _ => 0,
+        };
+        let profile_idc = profile_idc_str.parse().ok()?;
+        let profile_compatibility = u32::from_str_radix(compat_part, 16).ok()?;
+        if tier_level_part.len() < 2 {
+            return None;
+        }
+        let (tier_char, level_str) = tier_level_part.split_at(1);
+        let level_idc = level_str.parse().ok()?;
+
+        Some(VideoCodec::Hevc {
+            profile_space,
+            profile_idc,
+            profile_compatibility,
+            high_tier: tier_char == "H",
+            level_idc,
+            constraint_flags,
+        })
+    }
+
+    fn parse_vp9(s: &str) -> Option<Self> {
+        let suffix = s.strip_prefix("vp09.")?;
+        let mut parts = suffix.split('.');
+        Some(VideoCodec::Vp9 {
+            profile: parts.next()?.parse().ok()?,
+            level: parts.next()?.parse().ok()?,
+            bit_depth: parts.next()?.parse().ok()?,
+        })
+    }
+
+    fn parse_av1(s: &str) -> Option<Self> {
+        let suffix = s.strip_prefix("av01.")?;
+        let mut parts = suffix.split('.');
+        let profile = parts.next()?.parse().ok()?;
+        let level_tier = parts.next()?;
+        if level_tier.len() < 2 {
+            return None;
+        }
+        let (level_str, tier_char) = level_tier.split_at(level_tier.len() - 1);
+        let bit_depth = parts.next()?.parse().ok()?;
+        Some(VideoCodec::Av1 {
+            profile,
+            level: level_str.parse().ok()?,
+            high_tier: tier_char == "H",
+            bit_depth,
+        })
+    }
+}
+
+impl Serialize for VideoCodec {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoCodec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().expect("VideoCodec::from_str is infallible"))
+    }
+}
+
 /// Video decoder configuration based on WebCodecs
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HangVideoConfig {
-    /// Codec string (e.g., "avc1.64001f", "vp09.00.10.08")
-    pub codec: String,
+    /// Codec family and parameters (e.g. H.264 profile/level), serialized as a
+    /// WebCodecs codec string (e.g. "avc1.64001f", "vp09.00.10.08")
+    pub codec: VideoCodec,
 
     /// Codec-specific description data (hex-encoded)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -211,12 +744,103 @@ pub struct HangAudio {
     pub priority: u8,
 }
 
+/// MPEG-4 Audio Object Type for an [`AudioCodec::Aac`] stream, as carried in the
+/// `mp4a.40.<object_type>` codec string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AacProfile {
+    /// Object type 2 - AAC-LC (Low Complexity), the common case
+    Lc,
+    /// Object type 5 - HE-AAC v1 (LC + SBR)
+    HeV1,
+    /// Object type 29 - HE-AAC v2 (LC + SBR + Parametric Stereo)
+    HeV2,
+    /// Any other MPEG-4 Audio Object Type, preserved as its raw numeric value
+    Other(u8),
+}
+
+impl AacProfile {
+    fn object_type(self) -> u8 {
+        match self {
+            AacProfile::Lc => 2,
+            AacProfile::HeV1 => 5,
+            AacProfile::HeV2 => 29,
+            AacProfile::Other(object_type) => object_type,
+        }
+    }
+
+    pub(crate) fn from_object_type(object_type: u8) -> Self {
+        match object_type {
+            2 => AacProfile::Lc,
+            5 => AacProfile::HeV1,
+            29 => AacProfile::HeV2,
+            other => AacProfile::Other(other),
+        }
+    }
+}
+
+/// An audio codec family, modeled from (and serialized to/from) its WebCodecs/RFC 6381
+/// codec string (e.g. "opus", "mp4a.40.2"). A codec string that doesn't match one of
+/// the known families round-trips losslessly via [`AudioCodec::Unknown`] instead of
+/// failing to parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AudioCodec {
+    /// AAC - `mp4a.40.<profile>`
+    Aac(AacProfile),
+    /// Opus - `opus`
+    Opus,
+    /// FLAC - `flac`
+    Flac,
+    /// Any codec string that doesn't match one of the families above, preserved
+    /// verbatim
+    Unknown(String),
+}
+
+impl std::fmt::Display for AudioCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioCodec::Aac(profile) => write!(f, "mp4a.40.{}", profile.object_type()),
+            AudioCodec::Opus => write!(f, "opus"),
+            AudioCodec::Flac => write!(f, "flac"),
+            AudioCodec::Unknown(codec) => write!(f, "{codec}"),
+        }
+    }
+}
+
+impl FromStr for AudioCodec {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        if let Some(object_type) = s.strip_prefix("mp4a.40.").and_then(|n| n.parse().ok()) {
+            return Ok(AudioCodec::Aac(AacProfile::from_object_type(object_type)));
+        }
+        match s {
+            "opus" => Ok(AudioCodec::Opus),
+            "flac" => Ok(AudioCodec::Flac),
+            _ => Ok(AudioCodec::Unknown(s.to_string())),
+        }
+    }
+}
+
+impl Serialize for AudioCodec {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for AudioCodec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().expect("AudioCodec::from_str is infallible"))
+    }
+}
+
 /// Audio decoder configuration based on WebCodecs
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HangAudioConfig {
-    /// Codec string (e.g., "opus", "mp4a.40.2")
-    pub codec: String,
+    /// Codec family and parameters, serialized as a WebCodecs codec string (e.g.
+    /// "opus", "mp4a.40.2")
+    pub codec: AudioCodec,
 
     /// Sample rate in Hz
     pub sample_rate: u32,
@@ -243,7 +867,7 @@ pub struct HangDisplay {
 }
 
 /// Location track for spatial positioning
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HangLocation {
     /// Track name for location data
@@ -253,7 +877,7 @@ pub struct HangLocation {
 }
 
 /// User metadata
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HangUser {
     /// Display name
@@ -265,7 +889,7 @@ pub struct HangUser {
 }
 
 /// Chat track metadata
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HangChat {
     /// Track name for chat messages
@@ -274,9 +898,119 @@ pub struct HangChat {
     pub priority: u8,
 }
 
-/// Generic track reference
+/// Caption/subtitle track information in hang format
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct HangCaption {
+    /// Map of BCP-47/ISO-639 language tag to caption track configuration
+    pub renditions: HashMap<String, HangCaptionConfig>,
+}
+
+/// A single caption/subtitle rendition's configuration
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HangCaptionConfig {
+    /// Track name carrying this language's captions
+    pub track: String,
+    /// Priority relative to other tracks
+    pub priority: u8,
+    /// Subtitles vs captions vs descriptions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<TrackRole>,
+}
+
+/// Added/changed and removed renditions for one keyed section of a [`HangCatalog`]
+/// (video, audio, or caption), as produced by [`HangCatalog::diff`] and consumed by
+/// [`HangCatalog::apply`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenditionDelta<T> {
+    /// Renditions that were added or whose configuration changed, keyed by rendition
+    /// name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub upserted: HashMap<String, T>,
+    /// Names of renditions that were removed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+}
+
+impl<T> Default for RenditionDelta<T> {
+    fn default() -> Self {
+        Self {
+            upserted: HashMap::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> RenditionDelta<T> {
+    fn is_empty(&self) -> bool {
+        self.upserted.is_empty() && self.removed.is_empty()
+    }
+
+    fn diff(old: &HashMap<String, T>, new: &HashMap<String, T>) -> Self {
+        let upserted = new
+            .iter()
+            .filter(|(name, config)| old.get(name.as_str()) != Some(*config))
+            .map(|(name, config)| (name.clone(), config.clone()))
+            .collect();
+        let removed = old
+            .keys()
+            .filter(|name| !new.contains_key(*name))
+            .cloned()
+            .collect();
+        Self { upserted, removed }
+    }
+
+    fn apply(&self, renditions: &mut HashMap<String, T>) {
+        for name in &self.removed {
+            renditions.remove(name);
+        }
+        for (name, config) in &self.upserted {
+            renditions.insert(name.clone(), config.clone());
+        }
+    }
+}
+
+/// A compact description of the difference between two [`HangCatalog`] snapshots -
+/// which renditions were added, removed, or changed, and whether any of the
+/// singleton sections (location/chat/user/preview) changed - so a publisher can push
+/// just the delta on the catalog track instead of re-sending the full document on
+/// every track add/remove. Produced by [`HangCatalog::diff`], consumed by
+/// [`HangCatalog::apply`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogDelta {
+    #[serde(default, skip_serializing_if = "RenditionDelta::is_empty")]
+    pub video: RenditionDelta<HangVideoConfig>,
+    /// New priority for the video section itself, if it changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video_priority: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "RenditionDelta::is_empty")]
+    pub audio: RenditionDelta<HangAudioConfig>,
+    /// New priority for the audio section itself, if it changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_priority: Option<u8>,
+
+    #[serde(default, skip_serializing_if = "RenditionDelta::is_empty")]
+    pub caption: RenditionDelta<HangCaptionConfig>,
+
+    /// `Some(new_value)` if the location section changed, where `new_value` is
+    /// `None` if it was removed; `None` (the field is omitted) if it's unchanged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Option<HangLocation>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat: Option<Option<HangChat>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<Option<HangUser>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<Option<HangTrack>>,
+}
+
+/// Generic track reference
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HangTrack {
     /// Track name
     pub name: String,
@@ -299,10 +1033,61 @@ impl HangCatalog {
             location: None,
             user: None,
             chat: None,
+            caption: None,
             preview: None,
         }
     }
 
+    /// Build a hang catalog advertising a full adaptive-bitrate ladder of video
+    /// renditions for a single source track, so a subscriber can switch renditions on
+    /// the fly. Each rung's [`HangVideoConfig`] copies `base`'s codec string and
+    /// scales `coded_width`/`coded_height`/`bitrate`/`framerate` to that rung, keyed
+    /// by the rung's name (e.g. "video-720p"); `base`'s own name/track is not itself
+    /// inserted as a rendition.
+    pub fn with_ladder(base: &TrackDefinition, rungs: &[LadderRung]) -> Self {
+        let codec: VideoCodec = base
+            .codec
+            .clone()
+            .unwrap_or_else(|| "avc1.42001e".to_string()) // H.264 baseline profile fallback
+            .parse()
+            .unwrap();
+
+        let renditions = rungs
+            .iter()
+            .map(|rung| {
+                (
+                    rung.name.clone(),
+                    HangVideoConfig {
+                        codec: codec.clone(),
+                        description: None,
+                        coded_width: Some(rung.width),
+                        coded_height: Some(rung.height),
+                        display_ratio_width: None,
+                        display_ratio_height: None,
+                        bitrate: Some(rung.bitrate),
+                        framerate: rung.framerate,
+                        optimize_for_latency: Some(true),
+                    },
+                )
+            })
+            .collect();
+
+        let mut catalog = Self::new();
+        catalog.video = Some(HangVideo {
+            renditions,
+            priority: base.priority as u8,
+            display: None,
+            rotation: None,
+            flip: None,
+        });
+        catalog
+    }
+
+    /// [`Self::with_ladder`] using [`LadderRung::default_ladder`]
+    pub fn with_default_ladder(base: &TrackDefinition) -> Self {
+        Self::with_ladder(base, &LadderRung::default_ladder())
+    }
+
     /// Create a hang catalog from track definitions
     pub fn from_tracks(tracks: &[TrackDefinition]) -> Self {
         let mut catalog = Self::new();
@@ -315,7 +1100,12 @@ impl HangCatalog {
                     renditions.insert(
                         track.name.clone(),
                         HangVideoConfig {
-                            codec: "avc1.42001e".to_string(), // H.264 baseline profile
+                            codec: track
+                                .codec
+                                .clone()
+                                .unwrap_or_else(|| "avc1.42001e".to_string()) // H.264 baseline profile fallback
+                                .parse()
+                                .unwrap(),
                             description: None,
                             coded_width: Some(1280),
                             coded_height: Some(720),
@@ -341,7 +1131,12 @@ impl HangCatalog {
                     renditions.insert(
                         track.name.clone(),
                         HangAudioConfig {
-                            codec: "opus".to_string(),
+                            codec: track
+                                .codec
+                                .clone()
+                                .unwrap_or_else(|| "opus".to_string())
+                                .parse()
+                                .unwrap(),
                             sample_rate: 48000,
                             channel_count: 2,
                             bitrate: Some(128_000), // 128 kbps default
@@ -354,8 +1149,22 @@ impl HangCatalog {
                         priority: track.priority as u8,
                     });
                 }
-                TrackType::Data => {
-                    // For data tracks, we can set them as preview or other metadata
+                TrackType::Caption => {
+                    let language = track.language.clone().unwrap_or_else(|| "und".to_string());
+                    let caption = catalog.caption.get_or_insert_with(|| HangCaption {
+                        renditions: HashMap::new(),
+                    });
+                    caption.renditions.insert(
+                        language,
+                        HangCaptionConfig {
+                            track: track.name.clone(),
+                            priority: track.priority as u8,
+                            role: track.role,
+                        },
+                    );
+                }
+                TrackType::Data | TrackType::Extension(_) => {
+                    // For data/unknown tracks, we can set them as preview or other metadata
                     if track.name == "catalog.json" {
                         // Skip catalog.json itself
                         continue;
@@ -372,6 +1181,121 @@ impl HangCatalog {
         catalog
     }
 
+    /// Build a hang catalog directly from an fMP4/ISOBMFF initialization segment (the
+    /// `ftyp`+`moov` prefix used for Media Source Extensions), deriving each track's
+    /// WebCodecs configuration from the actual `moov > trak > mdia > minf > stbl >
+    /// stsd` box tree instead of the hardcoded placeholders [`Self::from_tracks`] falls
+    /// back to when given bare [`TrackDefinition`]s with no media to inspect.
+    ///
+    /// Video tracks (`avc1`/`avc3`, `hvc1`/`hev1`, `vp09`) get `coded_width`/
+    /// `coded_height` from the sample entry, plus for `avc1`/`avc3` a hex-encoded
+    /// `avcC` `description` and a synthesized `avc1.PPCCLL` codec string. Audio tracks
+    /// (`mp4a`) get their sample rate/channel count decoded from the `esds`
+    /// `AudioSpecificConfig`, embedded as the hex-encoded `description`; `Opus` tracks
+    /// just get the `opus` codec string, since Opus has no equivalent decoder config to
+    /// extract here.
+    pub fn from_init_segment(data: &[u8]) -> Result<Self> {
+        let moov = fmp4_find_box(data, b"moov").context("no moov box found in init segment")?;
+
+        let mut catalog = Self::new();
+        let mut found_track = false;
+
+        for (kind, start, end) in fmp4_top_level_boxes(moov) {
+            if &kind != b"trak" {
+                continue;
+            }
+            let trak = &moov[start + 8..end];
+
+            let Some(mdia) = fmp4_find_box(trak, b"mdia") else {
+                continue;
+            };
+            let handler = fmp4_find_box(mdia, b"hdlr").and_then(fmp4_handler_type);
+            let Some(stsd) = fmp4_find_box(mdia, b"minf")
+                .and_then(|minf| fmp4_find_box(minf, b"stbl"))
+                .and_then(|stbl| fmp4_find_box(stbl, b"stsd"))
+            else {
+                continue;
+            };
+
+            match handler.as_deref() {
+                Some("video") => {
+                    let mut config = HangVideoConfig {
+                        codec: fmp4_codec_string(stsd)
+                            .unwrap_or_else(|| "avc1.42001e".to_string())
+                            .parse()
+                            .unwrap(),
+                        description: None,
+                        coded_width: None,
+                        coded_height: None,
+                        display_ratio_width: None,
+                        display_ratio_height: None,
+                        bitrate: None,
+                        framerate: None,
+                        optimize_for_latency: None,
+                    };
+                    if let Some((width, height)) = fmp4_visual_dimensions(stsd) {
+                        config.coded_width = Some(width as u32);
+                        config.coded_height = Some(height as u32);
+                    }
+                    if let Some(avcc) =
+                        fmp4_sample_entry_box(stsd, VISUAL_SAMPLE_ENTRY_FIXED, b"avcC")
+                    {
+                        config.description = Some(hex_encode(avcc));
+                    }
+
+                    found_track = true;
+                    let mut renditions = HashMap::new();
+                    renditions.insert("video".to_string(), config);
+                    catalog.video = Some(HangVideo {
+                        renditions,
+                        priority: 1,
+                        display: None,
+                        rotation: None,
+                        flip: None,
+                    });
+                }
+                Some("audio") => {
+                    let mut config = HangAudioConfig {
+                        codec: fmp4_codec_string(stsd)
+                            .unwrap_or_else(|| "opus".to_string())
+                            .parse()
+                            .unwrap(),
+                        sample_rate: 48000,
+                        channel_count: 2,
+                        bitrate: None,
+                        description: None,
+                    };
+                    if let Some(esds) =
+                        fmp4_sample_entry_box(stsd, AUDIO_SAMPLE_ENTRY_FIXED, b"esds")
+                    {
+                        if let Some((sample_rate, channel_count, asc)) =
+                            fmp4_esds_audio_config(esds)
+                        {
+                            config.sample_rate = sample_rate;
+                            config.channel_count = channel_count;
+                            config.description = Some(hex_encode(asc));
+                        }
+                    }
+
+                    found_track = true;
+                    let mut renditions = HashMap::new();
+                    renditions.insert("audio".to_string(), config);
+                    catalog.audio = Some(HangAudio {
+                        renditions,
+                        priority: 1,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if !found_track {
+            anyhow::bail!("no usable video/audio tracks found in moov");
+        }
+
+        Ok(catalog)
+    }
+
     /// Find if a track exists in this catalog
     pub fn find_track(&self, name: &str) -> bool {
         // Check video renditions
@@ -442,6 +1366,121 @@ impl HangCatalog {
 
         audio.renditions.insert(name, config);
     }
+
+    /// Compare this catalog against a newer snapshot, producing a [`CatalogDelta`]
+    /// covering just what changed - added/removed/changed renditions in each section,
+    /// plus whether the location/chat/user/preview sections themselves changed - so a
+    /// publisher can push the delta on the catalog track instead of the full document
+    /// every time a track joins or leaves.
+    pub fn diff(&self, newer: &HangCatalog) -> CatalogDelta {
+        let empty = HashMap::new();
+
+        let video = RenditionDelta::diff(
+            self.video.as_ref().map(|v| &v.renditions).unwrap_or(&empty),
+            newer
+                .video
+                .as_ref()
+                .map(|v| &v.renditions)
+                .unwrap_or(&empty),
+        );
+        let video_priority = match (&self.video, &newer.video) {
+            (Some(old), Some(new)) if old.priority != new.priority => Some(new.priority),
+            (None, Some(new)) => Some(new.priority),
+            _ => None,
+        };
+
+        let audio = RenditionDelta::diff(
+            self.audio.as_ref().map(|a| &a.renditions).unwrap_or(&empty),
+            newer
+                .audio
+                .as_ref()
+                .map(|a| &a.renditions)
+                .unwrap_or(&empty),
+        );
+        let audio_priority = match (&self.audio, &newer.audio) {
+            (Some(old), Some(new)) if old.priority != new.priority => Some(new.priority),
+            (None, Some(new)) => Some(new.priority),
+            _ => None,
+        };
+
+        let caption = RenditionDelta::diff(
+            self.caption
+                .as_ref()
+                .map(|c| &c.renditions)
+                .unwrap_or(&empty),
+            newer
+                .caption
+                .as_ref()
+                .map(|c| &c.renditions)
+                .unwrap_or(&empty),
+        );
+
+        CatalogDelta {
+            video,
+            video_priority,
+            audio,
+            audio_priority,
+            caption,
+            location: (self.location != newer.location).then(|| newer.location.clone()),
+            chat: (self.chat != newer.chat).then(|| newer.chat.clone()),
+            user: (self.user != newer.user).then(|| newer.user.clone()),
+            preview: (self.preview != newer.preview).then(|| newer.preview.clone()),
+        }
+    }
+
+    /// Mutate this catalog in place by applying a delta produced by [`Self::diff`].
+    pub fn apply(&mut self, delta: &CatalogDelta) {
+        if !delta.video.is_empty() || delta.video_priority.is_some() {
+            let video = self.video.get_or_insert_with(|| HangVideo {
+                renditions: HashMap::new(),
+                priority: delta.video_priority.unwrap_or(0),
+                display: None,
+                rotation: None,
+                flip: None,
+            });
+            delta.video.apply(&mut video.renditions);
+            if let Some(priority) = delta.video_priority {
+                video.priority = priority;
+            }
+        }
+
+        if !delta.audio.is_empty() || delta.audio_priority.is_some() {
+            let audio = self.audio.get_or_insert_with(|| HangAudio {
+                renditions: HashMap::new(),
+                priority: delta.audio_priority.unwrap_or(0),
+            });
+            delta.audio.apply(&mut audio.renditions);
+            if let Some(priority) = delta.audio_priority {
+                audio.priority = priority;
+            }
+        }
+
+        if !delta.caption.is_empty() {
+            let caption = self.caption.get_or_insert_with(|| HangCaption {
+                renditions: HashMap::new(),
+            });
+            delta.caption.apply(&mut caption.renditions);
+        }
+
+        if let Some(location) = &delta.location {
+            self.location = location.clone();
+        }
+        if let Some(chat) = &delta.chat {
+            self.chat = chat.clone();
+        }
+        if let Some(user) = &delta.user {
+            self.user = user.clone();
+        }
+        if let Some(preview) = &delta.preview {
+            self.preview = preview.clone();
+        }
+    }
+}
+
+/// Lowercase hex encoding, used for [`HangVideoConfig::description`]/
+/// [`HangAudioConfig::description`] (WebCodecs expects these as hex strings).
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
 }
 
 #[derive(Clone, Debug)]
@@ -466,6 +1505,37 @@ impl Catalog {
         }
     }
 
+    /// Builds a catalog from `tracks` in `catalog_type`'s wire format and publishes it
+    /// as a single frame on a `catalog.json` track created on `broadcast` - `None` if
+    /// `catalog_type` is [`CatalogType::None`], since there's nothing to publish. This
+    /// is the low-level counterpart to [`crate::session::MoqSession::set_catalog`] for
+    /// callers (like [`crate::track::Fmp4Publisher`]) driving a `BroadcastProducer`
+    /// directly with no `MoqSession` involved, closing the loop so such a publisher can
+    /// announce its own catalog.
+    pub fn publish(
+        broadcast: &mut BroadcastProducer,
+        catalog_type: CatalogType,
+        tracks: &[TrackDefinition],
+    ) -> Result<Option<Self>> {
+        let Some(catalog) = Self::new(catalog_type, tracks) else {
+            return Ok(None);
+        };
+
+        let json = catalog.to_json().context("failed to serialize catalog")?;
+
+        let mut track_producer = broadcast.create_track(Track {
+            name: "catalog.json".to_string(),
+            priority: u32::MAX,
+        });
+        let group = track_producer
+            .create_group(0)
+            .context("failed to create catalog.json group")?;
+        group.write_frame(Bytes::from(json));
+        group.close();
+
+        Ok(Some(catalog))
+    }
+
     pub fn find_track(&self, name: &str) -> bool {
         match self {
             Catalog::Sesame(catalog) => catalog.find_track(name).is_some(),
@@ -480,6 +1550,171 @@ impl Catalog {
     pub fn parse_hang(json: &str) -> Result<HangCatalog, serde_json::Error> {
         HangCatalog::from_json(json)
     }
+
+    /// Convert this catalog to another wire format, so a gateway can bridge clients
+    /// that speak different catalog dialects over the same relay. `None` for
+    /// `CatalogType::None`, since there's no catalog representation for it.
+    pub fn convert(&self, target: CatalogType) -> Option<Catalog> {
+        match target {
+            CatalogType::None => None,
+            CatalogType::Sesame => Some(Catalog::Sesame(match self {
+                Catalog::Sesame(catalog) => catalog.clone(),
+                Catalog::Hang(catalog) => SesameCatalog::from(catalog.as_ref()),
+            })),
+            CatalogType::Hang => Some(Catalog::Hang(Box::new(match self {
+                Catalog::Sesame(catalog) => catalog.to_hang(),
+                Catalog::Hang(catalog) => catalog.as_ref().clone(),
+            }))),
+        }
+    }
+
+    /// Flatten this catalog, whichever format it's in, into a single typed list of
+    /// [`CatalogTrackDescriptor`]s so callers can inspect what's on offer - codec,
+    /// resolution, framerate, sample rate - before picking a track name to pass to
+    /// `subscribe_track_internal`.
+    pub fn describe_tracks(&self) -> Vec<CatalogTrackDescriptor> {
+        match self {
+            Catalog::Sesame(catalog) => catalog
+                .tracks
+                .iter()
+                .map(CatalogTrackDescriptor::from_sesame)
+                .collect(),
+            Catalog::Hang(catalog) => catalog.describe_tracks(),
+        }
+    }
+}
+
+/// Track-type-agnostic summary of what a catalog track offers: role, codec, and
+/// whatever decode parameters the source format describes. Returned by
+/// [`Catalog::describe_tracks`]; unknown/unavailable fields are `None` rather than
+/// guessed, since not every catalog format (or every rendition within one) carries
+/// them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CatalogTrackDescriptor {
+    pub name: String,
+    pub track_type: TrackType,
+    pub priority: u32,
+    /// RFC 6381 codec string (e.g. "avc1.640028", "mp4a.40.2"), when the catalog
+    /// describes one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub framerate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel_count: Option<u32>,
+    /// Name of the init segment/track this track's media depends on, for formats that
+    /// separate init data from media data. `None` when the format doesn't model one
+    /// (e.g. Sesame) or the track is self-contained.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_track: Option<String>,
+    /// BCP-47/ISO-639 language tag, when the catalog tags this track with one - e.g.
+    /// captions/subtitles, or the catalog's caption renditions are keyed by language
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl CatalogTrackDescriptor {
+    fn from_sesame(track: &SesameCatalogTrack) -> Self {
+        Self {
+            name: track.track_name.clone(),
+            track_type: track.track_type.clone(),
+            priority: track.priority,
+            codec: None,
+            width: None,
+            height: None,
+            framerate: None,
+            sample_rate: None,
+            channel_count: None,
+            init_track: None,
+            language: track.language.clone(),
+        }
+    }
+}
+
+impl HangCatalog {
+    /// See [`Catalog::describe_tracks`]; this is the hang-specific half, since hang
+    /// spreads tracks across per-role rendition maps rather than a flat list.
+    fn describe_tracks(&self) -> Vec<CatalogTrackDescriptor> {
+        let mut tracks = Vec::new();
+
+        if let Some(video) = &self.video {
+            for (name, rendition) in &video.renditions {
+                tracks.push(CatalogTrackDescriptor {
+                    name: name.clone(),
+                    track_type: TrackType::Video,
+                    priority: video.priority as u32,
+                    codec: Some(rendition.codec.to_string()),
+                    width: rendition.coded_width,
+                    height: rendition.coded_height,
+                    framerate: rendition.framerate,
+                    sample_rate: None,
+                    channel_count: None,
+                    init_track: Some(name.clone()),
+                    language: None,
+                });
+            }
+        }
+
+        if let Some(audio) = &self.audio {
+            for (name, rendition) in &audio.renditions {
+                tracks.push(CatalogTrackDescriptor {
+                    name: name.clone(),
+                    track_type: TrackType::Audio,
+                    priority: audio.priority as u32,
+                    codec: Some(rendition.codec.to_string()),
+                    width: None,
+                    height: None,
+                    framerate: None,
+                    sample_rate: Some(rendition.sample_rate),
+                    channel_count: Some(rendition.channel_count),
+                    init_track: Some(name.clone()),
+                    language: None,
+                });
+            }
+        }
+
+        if let Some(caption) = &self.caption {
+            for (language, rendition) in &caption.renditions {
+                tracks.push(CatalogTrackDescriptor {
+                    name: rendition.track.clone(),
+                    track_type: TrackType::Caption,
+                    priority: rendition.priority as u32,
+                    codec: None,
+                    width: None,
+                    height: None,
+                    framerate: None,
+                    sample_rate: None,
+                    channel_count: None,
+                    init_track: None,
+                    language: Some(language.clone()),
+                });
+            }
+        }
+
+        if let Some(preview) = &self.preview {
+            tracks.push(CatalogTrackDescriptor {
+                name: preview.name.clone(),
+                track_type: TrackType::Data,
+                priority: preview.priority as u32,
+                codec: None,
+                width: None,
+                height: None,
+                framerate: None,
+                sample_rate: None,
+                channel_count: None,
+                init_track: None,
+                language: None,
+            });
+        }
+
+        tracks
+    }
 }
 
 #[cfg(test)]
@@ -492,6 +1727,14 @@ mod tests {
         assert_eq!(track.name, "test-video");
         assert_eq!(track.priority, 1);
         assert_eq!(track.track_type, TrackType::Video);
+        assert_eq!(track.group_ttl_ms, None);
+    }
+
+    #[test]
+    fn test_track_definition_with_group_ttl() {
+        let track =
+            TrackDefinition::data("clock", 0).with_group_ttl(std::time::Duration::from_secs(60));
+        assert_eq!(track.group_ttl_ms, Some(60_000));
     }
 
     #[test]
@@ -572,7 +1815,7 @@ mod tests {
         catalog.add_video_track(
             "video".to_string(),
             HangVideoConfig {
-                codec: "avc1.64001f".to_string(),
+                codec: "avc1.64001f".parse().unwrap(),
                 description: None,
                 coded_width: Some(1280),
                 coded_height: Some(720),
@@ -589,7 +1832,7 @@ mod tests {
         catalog.add_audio_track(
             "audio".to_string(),
             HangAudioConfig {
-                codec: "opus".to_string(),
+                codec: "opus".parse().unwrap(),
                 sample_rate: 48000,
                 channel_count: 2,
                 bitrate: Some(128_000),
@@ -612,4 +1855,63 @@ mod tests {
         assert!(json.contains("\"codec\""));
         assert!(json.contains("\"priority\""));
     }
+
+    #[test]
+    fn test_describe_tracks_sesame() {
+        let tracks = vec![
+            TrackDefinition::video("video1", 1),
+            TrackDefinition::audio("audio1", 2),
+        ];
+        let catalog = Catalog::Sesame(SesameCatalog::from_tracks(&tracks));
+
+        let described = catalog.describe_tracks();
+        assert_eq!(described.len(), 2);
+        assert!(described
+            .iter()
+            .any(|t| t.name == "video1" && t.track_type == TrackType::Video));
+        assert!(described.iter().all(|t| t.codec.is_none()));
+    }
+
+    #[test]
+    fn test_describe_tracks_hang() {
+        let mut catalog = HangCatalog::new();
+        catalog.add_video_track(
+            "video".to_string(),
+            HangVideoConfig {
+                codec: "avc1.640028".parse().unwrap(),
+                description: None,
+                coded_width: Some(1920),
+                coded_height: Some(1080),
+                display_ratio_width: None,
+                display_ratio_height: None,
+                bitrate: None,
+                framerate: Some(30.0),
+                optimize_for_latency: None,
+            },
+            1,
+        );
+        catalog.add_audio_track(
+            "audio".to_string(),
+            HangAudioConfig {
+                codec: "opus".parse().unwrap(),
+                sample_rate: 48000,
+                channel_count: 2,
+                bitrate: None,
+                description: None,
+            },
+            2,
+        );
+
+        let described = Catalog::Hang(Box::new(catalog)).describe_tracks();
+        assert_eq!(described.len(), 2);
+
+        let video = described.iter().find(|t| t.name == "video").unwrap();
+        assert_eq!(video.codec.as_deref(), Some("avc1.640028"));
+        assert_eq!(video.width, Some(1920));
+        assert_eq!(video.height, Some(1080));
+
+        let audio = described.iter().find(|t| t.name == "audio").unwrap();
+        assert_eq!(audio.sample_rate, Some(48000));
+        assert_eq!(audio.channel_count, Some(2));
+    }
 }
@@ -40,31 +40,68 @@ pub struct ConnectionConfig {
     /// Force IPv4-only connections (Windows compatibility)
     pub ipv4_only: bool,
 
-    /// Client configuration for the underlying moq-native client
+    /// Client configuration for the underlying moq-native client.
+    ///
+    /// `moq_native::ClientConfig` owns the QUIC endpoint's TLS setup (root store,
+    /// certificate verification) entirely; this crate only flattens it in via clap
+    /// and never touches its internals (see `moq_native::ClientConfig::init`, called
+    /// from `MoqSession::publisher`/`subscriber`). Swapping certificate-verification
+    /// backends (rustls' native-roots vs. webpki-roots vs. an OpenSSL stack) would
+    /// need to be a Cargo feature on `moq_native` itself - there's nothing in this
+    /// wrapper crate to gate, and no vendored copy of `moq_native` in this tree to add
+    /// one to.
     pub client_config: moq_native::ClientConfig,
 }
 
 impl Default for ConnectionConfig {
     fn default() -> Self {
         let mut client_config = moq_native::ClientConfig::default();
-        
+
         // Force IPv4 binding on Windows to avoid IPv6 issues
         #[cfg(windows)]
         {
             client_config.bind = "0.0.0.0:0".parse().expect("Valid IPv4 bind address");
         }
-        
+
         Self {
             url: url::Url::parse("https://relay.moq.dev/anon").unwrap(),
             max_reconnect_attempts: 0, // Infinite reconnection attempts
             reconnect_delay: Duration::from_millis(500), // Faster initial reconnection
             max_reconnect_delay: Duration::from_secs(10), // Shorter max delay for better responsiveness
-            ipv4_only: cfg!(windows), // Default to IPv4-only on Windows
+            ipv4_only: cfg!(windows),                     // Default to IPv4-only on Windows
             client_config,
         }
     }
 }
 
+/// Strategy `MoqSession::start` uses to reconnect after the connection drops.
+#[derive(Clone, Debug)]
+pub enum ReconnectStrategy {
+    /// Never reconnect; the session's management task ends when the connection drops.
+    None,
+    /// Wait the same fixed interval before every reconnection attempt.
+    Fixed { interval: Duration },
+    /// Back off exponentially between attempts, capped at `max`.
+    ExponentialBackoff {
+        initial: Duration,
+        max: Duration,
+        factor: f64,
+        /// Apply up to +/-10% random jitter to each computed delay.
+        jitter: bool,
+    },
+}
+
+impl Default for ReconnectStrategy {
+    fn default() -> Self {
+        Self::ExponentialBackoff {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(10),
+            factor: 2.0,
+            jitter: true,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SessionConfig {
     /// Name/path of the broadcast
@@ -75,6 +112,27 @@ pub struct SessionConfig {
 
     /// Enable automatic reconnection
     pub auto_reconnect: bool,
+
+    /// How to reconnect after the connection drops; see [`ReconnectStrategy`]
+    pub reconnect: ReconnectStrategy,
+
+    /// How often to check for connection activity and, for publishers, emit a
+    /// zero-length keepalive frame on the reserved `.moq-keepalive` track
+    pub heartbeat_interval: Duration,
+
+    /// Force-close the connection if no activity has been observed for this long,
+    /// so a half-open QUIC connection is detected instead of hanging until the OS
+    /// times it out
+    pub heartbeat_timeout: Duration,
+
+    /// Maximum round-trip time a clock-sync sample taken while connecting may have
+    /// before it's discarded as unreliable (e.g. a momentary network spike)
+    pub max_clock_sync_rtt: Duration,
+
+    /// How often to emit a `SessionEvent::Metrics` snapshot on the session's event
+    /// channel; `None` (the default) disables periodic emission, leaving
+    /// `MoqSession::metrics` as the only way to read telemetry
+    pub metrics_interval: Option<Duration>,
 }
 
 impl SessionConfig {
@@ -86,6 +144,11 @@ impl SessionConfig {
                 ..Default::default()
             },
             auto_reconnect: true,
+            reconnect: ReconnectStrategy::default(),
+            heartbeat_interval: Duration::from_secs(5),
+            heartbeat_timeout: Duration::from_secs(15),
+            max_clock_sync_rtt: Duration::from_millis(200),
+            metrics_interval: None,
         }
     }
 }
@@ -0,0 +1,324 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long a `(time, accumulated delay)` sample stays in the sliding window used to
+/// fit the delay-gradient slope. Matches the ~1s window libwebrtc's GCC implementation
+/// uses.
+const WINDOW: Duration = Duration::from_secs(1);
+/// A gap this long between consecutive groups is treated as idle time rather than
+/// congestion: there's nothing in flight to build a queue, so the accumulator and
+/// window are reset instead of measuring a (meaningless) delay gradient across the gap.
+const IDLE_GAP: Duration = Duration::from_secs(2);
+/// Starting value for the adaptive over-use threshold (ms of accumulated delay drift).
+const INITIAL_THRESHOLD_MS: f64 = 12.5;
+/// How far the adaptive threshold is nudged towards the observed slope magnitude per
+/// sample, so it tracks the channel's typical jitter instead of firing on every blip.
+const THRESHOLD_ADAPT_RATE: f64 = 0.01;
+/// Multiplicative decrease applied to the target bitrate when the slope signals queue
+/// buildup (an "overuse").
+const DECREASE_FACTOR: f64 = 0.85;
+/// Additive increase applied per sample when the slope is near zero (no congestion).
+const INCREASE_STEP_BPS: f64 = 8_000.0;
+const MIN_BITRATE_BPS: f64 = 50_000.0;
+/// Fewest samples the sliding window must hold before a fitted slope is trusted.
+/// [`least_squares_slope`] can technically fit a line through 2 points, but a
+/// same-direction pair is just as likely to be jitter as the start of a real trend -
+/// this keeps the estimator quiet until there's enough of the window to tell.
+const MIN_SAMPLES: usize = 5;
+
+/// One `(id, send_time, arrival_time)` observation for a group of frames, the unit
+/// [`BandwidthEstimator::on_group_delivered`] consumes. "Group" here matches this
+/// crate's MoQ group, i.e. a batch of frames delivered together (typically a keyframe
+/// and its dependent frames). `id` is that group's sequence number, used to detect
+/// reordered or duplicate deliveries (e.g. a relay retransmitting, or groups arriving
+/// out of order over independent QUIC streams) - a gradient computed across one would
+/// be meaningless, not a real delay measurement.
+#[derive(Clone, Copy, Debug)]
+pub struct DeliverySample {
+    pub id: u64,
+    pub send_time: Instant,
+    pub arrival_time: Instant,
+}
+
+/// Which way [`BandwidthEstimator::on_group_delivered`] classified the fitted slope,
+/// mirroring Google Congestion Control's overuse detector states.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CongestionState {
+    /// Slope is above the adaptive threshold: the channel's queue is growing, so the
+    /// target bitrate was multiplicatively decreased.
+    Overuse,
+    /// Slope is near zero: no queue buildup, so the target bitrate was additively
+    /// increased.
+    Normal,
+    /// Slope is clearly negative (below `-threshold`): the queue is draining, so the
+    /// target bitrate is held rather than grown back while it's still catching up.
+    Underuse,
+}
+
+/// GCC-style delay-based congestion controller: a target bitrate that
+/// [`crate::track::StreamPublisher`]/[`crate::track::TrackManager`] can consult before
+/// picking a rendition or track priority, driven purely by the one-way delay trend
+/// between consecutive group deliveries (no packet loss signal, matching the
+/// delay-gradient half of Google Congestion Control).
+///
+/// For each consecutive pair of samples, the one-way delay variation is
+/// `d_i = (arrival_i - arrival_{i-1}) - (send_i - send_{i-1})`, accumulated into a
+/// running `m_i = m_{i-1} + d_i`. A least-squares slope of `m_i` over the last
+/// [`WINDOW`] of samples above an adaptive threshold means the channel's queue is
+/// growing (reduce target bitrate); a slope near zero means it's stable (grow it back
+/// additively); a slope below `-threshold` means it's draining (hold the target).
+pub struct BandwidthEstimator {
+    target_bitrate_bps: f64,
+    accumulated_delay_ms: f64,
+    threshold_ms: f64,
+    last_sample: Option<DeliverySample>,
+    // (time since the estimator's first sample, accumulated delay in ms)
+    window: VecDeque<(Duration, f64)>,
+    window_start: Option<Instant>,
+}
+
+impl BandwidthEstimator {
+    pub fn new(initial_bitrate_bps: f64) -> Self {
+        Self {
+            target_bitrate_bps: initial_bitrate_bps.max(MIN_BITRATE_BPS),
+            accumulated_delay_ms: 0.0,
+            threshold_ms: INITIAL_THRESHOLD_MS,
+            last_sample: None,
+            window: VecDeque::new(),
+            window_start: None,
+        }
+    }
+
+    /// Whether `sample` is a duplicate or reorders behind the last sample fed to
+    /// [`Self::on_group_delivered`] - by `id`, not arrival time, since arrival time is
+    /// exactly what's unreliable under reordering.
+    fn is_stale(&self, sample: &DeliverySample) -> bool {
+        self.last_sample
+            .is_some_and(|previous| sample.id <= previous.id)
+    }
+
+    /// Current target bitrate, in bits per second.
+    pub fn target_bitrate_bps(&self) -> f64 {
+        self.target_bitrate_bps
+    }
+
+    /// Feeds one group's id/send/arrival timestamps into the estimator, returning the
+    /// current target bitrate and the slope's classification once there's enough
+    /// window history to trust it (see [`MIN_SAMPLES`]). Returns `None` for a
+    /// duplicate or reordered `id` (see [`Self::is_stale`]), the first sample, right
+    /// after an idle-gap reset, or while the window is still below [`MIN_SAMPLES`].
+    pub fn on_group_delivered(&mut self, sample: DeliverySample) -> Option<(f64, CongestionState)> {
+        if self.is_stale(&sample) {
+            return None;
+        }
+
+        let Some(previous) = self.last_sample.replace(sample) else {
+            self.window_start = Some(sample.arrival_time);
+            return None;
+        };
+
+        if sample
+            .arrival_time
+            .saturating_duration_since(previous.arrival_time)
+            > IDLE_GAP
+        {
+            // Nothing was in flight across this gap; a gradient computed over it would
+            // reflect idle time, not congestion, so start over as if this were the
+            // first sample.
+            self.accumulated_delay_ms = 0.0;
+            self.window.clear();
+            self.window_start = Some(sample.arrival_time);
+            return None;
+        }
+
+        let send_delta = sample
+            .send_time
+            .saturating_duration_since(previous.send_time)
+            .as_secs_f64()
+            * 1000.0;
+        let arrival_delta = sample
+            .arrival_time
+            .saturating_duration_since(previous.arrival_time)
+            .as_secs_f64()
+            * 1000.0;
+        let delay_variation_ms = arrival_delta - send_delta;
+        self.accumulated_delay_ms += delay_variation_ms;
+
+        let window_start = *self.window_start.get_or_insert(sample.arrival_time);
+        let elapsed = sample.arrival_time.saturating_duration_since(window_start);
+        self.window.push_back((elapsed, self.accumulated_delay_ms));
+        while let Some((oldest, _)) = self.window.front() {
+            if elapsed.saturating_sub(*oldest) > WINDOW {
+                self.window.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.window.len() < MIN_SAMPLES {
+            return None;
+        }
+
+        let Some(slope) = least_squares_slope(&self.window) else {
+            return None;
+        };
+
+        // Nudge the adaptive threshold towards the observed slope magnitude, clamped
+        // to a sane range so a single large spike can't blow it out permanently.
+        self.threshold_ms +=
+            THRESHOLD_ADAPT_RATE * (slope.abs() - self.threshold_ms).clamp(-1.0, 1.0);
+        self.threshold_ms = self.threshold_ms.clamp(2.0, 60.0);
+
+        let state = if slope > self.threshold_ms {
+            self.target_bitrate_bps =
+                (self.target_bitrate_bps * DECREASE_FACTOR).max(MIN_BITRATE_BPS);
+            CongestionState::Overuse
+        } else if slope.abs() <= self.threshold_ms {
+            self.target_bitrate_bps += INCREASE_STEP_BPS;
+            CongestionState::Normal
+        } else {
+            // A strongly negative slope (draining queue) leaves the target as-is;
+            // it'll climb back additively on the next near-zero sample rather than
+            // overshooting.
+            CongestionState::Underuse
+        };
+
+        Some((self.target_bitrate_bps, state))
+    }
+}
+
+/// Least-squares slope (ms of accumulated delay per second) of `(elapsed, value)`
+/// samples, or `None` with fewer than two samples (no line to fit).
+fn least_squares_slope(samples: &VecDeque<(Duration, f64)>) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let n = samples.len() as f64;
+    let xs: Vec<f64> = samples.iter().map(|(t, _)| t.as_secs_f64()).collect();
+    let ys: Vec<f64> = samples.iter().map(|(_, m)| *m).collect();
+
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        numerator += (x - mean_x) * (y - mean_y);
+        denominator += (x - mean_x).powi(2);
+    }
+
+    if denominator == 0.0 {
+        None
+    } else {
+        Some(numerator / denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(id: u64, send_ms: u64, arrival_ms: u64) -> DeliverySample {
+        let base = Instant::now();
+        DeliverySample {
+            id,
+            send_time: base + Duration::from_millis(send_ms),
+            arrival_time: base + Duration::from_millis(arrival_ms),
+        }
+    }
+
+    #[test]
+    fn least_squares_slope_needs_at_least_two_samples() {
+        let mut samples = VecDeque::new();
+        assert_eq!(least_squares_slope(&samples), None);
+
+        samples.push_back((Duration::from_millis(0), 0.0));
+        assert_eq!(least_squares_slope(&samples), None);
+    }
+
+    #[test]
+    fn least_squares_slope_fits_a_line() {
+        let samples: VecDeque<(Duration, f64)> = (0..5)
+            .map(|i| (Duration::from_secs(i), (i as f64) * 10.0))
+            .collect();
+        let slope = least_squares_slope(&samples).unwrap();
+        assert!((slope - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn first_sample_returns_none_and_seeds_the_window() {
+        let mut estimator = BandwidthEstimator::new(1_000_000.0);
+        assert_eq!(estimator.on_group_delivered(sample(0, 0, 0)), None);
+    }
+
+    #[test]
+    fn stays_quiet_below_min_samples() {
+        let mut estimator = BandwidthEstimator::new(1_000_000.0);
+        for i in 0..MIN_SAMPLES as u64 {
+            // Evenly paced deliveries with no delay drift.
+            let result = estimator.on_group_delivered(sample(i, i * 20, i * 20));
+            if i + 1 < MIN_SAMPLES as u64 {
+                assert_eq!(result, None, "sample {i} should not yet produce a result");
+            }
+        }
+    }
+
+    #[test]
+    fn growing_delay_is_classified_as_overuse() {
+        let mut estimator = BandwidthEstimator::new(1_000_000.0);
+        let mut last = None;
+        // Send deltas stay constant while arrival deltas grow, so accumulated delay
+        // climbs steadily - a textbook queue-buildup gradient.
+        for i in 0..(MIN_SAMPLES as u64 + 5) {
+            let send_ms = i * 20;
+            let arrival_ms = i * 20 + i * 15;
+            last = estimator.on_group_delivered(sample(i, send_ms, arrival_ms));
+        }
+        let (bitrate, state) = last.expect("enough samples to produce a classification");
+        assert_eq!(state, CongestionState::Overuse);
+        assert!(bitrate < 1_000_000.0);
+    }
+
+    #[test]
+    fn stable_delay_is_classified_as_normal_and_grows_bitrate() {
+        let mut estimator = BandwidthEstimator::new(1_000_000.0);
+        let mut last = None;
+        for i in 0..(MIN_SAMPLES as u64 + 5) {
+            last = estimator.on_group_delivered(sample(i, i * 20, i * 20));
+        }
+        let (bitrate, state) = last.expect("enough samples to produce a classification");
+        assert_eq!(state, CongestionState::Normal);
+        assert!(bitrate > 1_000_000.0);
+    }
+
+    #[test]
+    fn reordered_or_duplicate_ids_are_ignored() {
+        let mut estimator = BandwidthEstimator::new(1_000_000.0);
+        estimator.on_group_delivered(sample(5, 0, 0));
+        assert_eq!(estimator.on_group_delivered(sample(5, 20, 20)), None);
+        assert_eq!(estimator.on_group_delivered(sample(3, 40, 40)), None);
+    }
+
+    #[test]
+    fn idle_gap_resets_the_window() {
+        let mut estimator = BandwidthEstimator::new(1_000_000.0);
+        // The first sample only seeds `last_sample`, so one extra sample is needed
+        // to fill the window to MIN_SAMPLES entries.
+        for i in 0..=MIN_SAMPLES as u64 {
+            estimator.on_group_delivered(sample(i, i * 20, i * 20));
+        }
+        assert!(estimator.window.len() >= MIN_SAMPLES);
+
+        let gap_id = MIN_SAMPLES as u64 + 1;
+        let gap_ms = IDLE_GAP.as_millis() as u64 + 1000;
+        let result = estimator.on_group_delivered(sample(
+            gap_id,
+            gap_id * 20 + gap_ms,
+            gap_id * 20 + gap_ms,
+        ));
+        assert_eq!(result, None);
+        assert!(estimator.window.is_empty());
+    }
+}
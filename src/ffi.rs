@@ -10,6 +10,30 @@ use crate::{
     write_frame, write_single_frame, CatalogType, MoqSession, TrackDefinition, TrackType,
 };
 
+/// C-compatible mirror of [`crate::CatalogTrackDescriptor`]. String fields are
+/// heap-allocated `CString`s owned by this struct; free the whole array with
+/// [`moq_free_catalog_tracks`] rather than freeing fields individually.
+#[repr(C)]
+pub struct CCatalogTrackDescriptor {
+    pub name: *mut c_char,
+    pub track_type: u8,
+    pub priority: u32,
+    /// Null when the catalog doesn't describe a codec for this track
+    pub codec: *mut c_char,
+    pub has_width: c_int,
+    pub width: u32,
+    pub has_height: c_int,
+    pub height: u32,
+    pub has_framerate: c_int,
+    pub framerate: f64,
+    pub has_sample_rate: c_int,
+    pub sample_rate: u32,
+    pub has_channel_count: c_int,
+    pub channel_count: u32,
+    /// Null when the track has no separate init segment/track
+    pub init_track: *mut c_char,
+}
+
 // Opaque handles for C API
 pub struct CMoqSession {
     session: Arc<MoqSession>,
@@ -18,6 +42,7 @@ pub struct CMoqSession {
     broadcast_announced_callback: Arc<RwLock<Option<CBroadcastAnnouncedCallback>>>,
     broadcast_cancelled_callback: Arc<RwLock<Option<CBroadcastCancelledCallback>>>,
     connection_closed_callback: Arc<RwLock<Option<CConnectionClosedCallback>>>,
+    renewal_failed_callback: Arc<RwLock<Option<CRenewalFailedCallback>>>,
 }
 
 // C-compatible struct for passing track definitions
@@ -61,10 +86,129 @@ pub enum CCatalogType {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub enum MoqResult {
     Success = 0,
     InvalidArgument = 1,
     RuntimeError = 2,
+    /// The relay hostname couldn't be resolved.
+    DnsError = 3,
+    /// The TLS handshake with the relay failed (bad/expired/untrusted certificate).
+    TlsError = 4,
+    /// The underlying QUIC/WebTransport dial failed for a reason other than DNS or
+    /// TLS (connection refused, timed out, unreachable, ...).
+    NetworkError = 5,
+}
+
+thread_local! {
+    /// The category of the most recent non-`Success` result returned by an FFI entry
+    /// point on this thread, paired with a human-readable detail string. Fallible
+    /// `#[no_mangle]` functions set this via `invalid_argument`/`runtime_error`/
+    /// `set_last_error`/`set_last_connect_error` just before returning;
+    /// `moq_get_last_error`/`moq_get_last_connect_error` read it back. Thread-local
+    /// because these functions run to completion (via `Runtime::block_on`) on
+    /// whichever thread the caller invoked them from, with no opportunity for another
+    /// thread to race the write.
+    static LAST_ERROR: std::cell::RefCell<(MoqResult, Option<CString>)> =
+        const { std::cell::RefCell::new((MoqResult::Success, None)) };
+}
+
+/// Records `result` (and, for non-`Success` results, a human-readable `detail`) as
+/// the last error on this thread, for `moq_get_last_error`/`moq_get_last_connect_error`.
+fn set_last_error(result: MoqResult, detail: impl Into<String>) {
+    let detail = if matches!(result, MoqResult::Success) {
+        None
+    } else {
+        Some(CString::new(detail.into()).unwrap_or_default())
+    };
+    LAST_ERROR.with(|cell| *cell.borrow_mut() = (result, detail));
+}
+
+fn set_last_connect_error(result: MoqResult) {
+    let detail = match result {
+        MoqResult::Success => String::new(),
+        MoqResult::InvalidArgument => "invalid argument".to_string(),
+        MoqResult::RuntimeError => "failed to create the Tokio runtime".to_string(),
+        MoqResult::DnsError => "DNS resolution failed".to_string(),
+        MoqResult::TlsError => "TLS handshake failed".to_string(),
+        MoqResult::NetworkError => "network connection failed".to_string(),
+    };
+    set_last_error(result, detail);
+}
+
+/// Records `detail` as an `InvalidArgument` on this thread and returns the category,
+/// so a call site can just `return invalid_argument("session is null");` (or
+/// `as c_int`) instead of setting and returning separately.
+fn invalid_argument(detail: impl Into<String>) -> MoqResult {
+    set_last_error(MoqResult::InvalidArgument, detail);
+    MoqResult::InvalidArgument
+}
+
+/// Same as [`invalid_argument`] but for `RuntimeError`.
+fn runtime_error(detail: impl Into<String>) -> MoqResult {
+    set_last_error(MoqResult::RuntimeError, detail);
+    MoqResult::RuntimeError
+}
+
+/// Returns the reason the most recent `moq_create_publisher`/`moq_create_subscriber`
+/// call on this thread returned null, or `MoqResult::Success` if the last call on
+/// this thread succeeded (or none has been made yet). Superseded by the more general
+/// `moq_get_last_error`/`moq_result_to_string`, kept for existing callers.
+///
+/// `moq_native`'s client doesn't expose a structured error type for its connect
+/// failures, so this classifies the `anyhow` error chain by matching keywords in its
+/// `Display` output - best-effort, not a guarantee every failure mode is categorized
+/// correctly.
+#[no_mangle]
+pub extern "C" fn moq_get_last_connect_error() -> MoqResult {
+    LAST_ERROR.with(|cell| cell.borrow().0)
+}
+
+/// Returns a pointer to a human-readable detail string for the last non-`Success`
+/// result returned by an FFI call on this thread (DNS name, TLS failure reason, the
+/// invalid argument, ...), or null if the last call on this thread succeeded (or none
+/// has been made yet). The pointer is only valid until the next `moq_*` call on the
+/// same thread - copy it out before calling anything else. Use
+/// `moq_result_to_string` for a stable, allocation-free category label instead.
+#[no_mangle]
+pub extern "C" fn moq_get_last_error() -> *const c_char {
+    LAST_ERROR.with(|cell| match &cell.borrow().1 {
+        Some(detail) => detail.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+/// Returns a static, human-readable name for a `MoqResult` category. Unlike
+/// `moq_get_last_error`, the returned pointer is valid for the life of the process
+/// and does not need to be freed.
+#[no_mangle]
+pub extern "C" fn moq_result_to_string(result: MoqResult) -> *const c_char {
+    let s: &'static [u8] = match result {
+        MoqResult::Success => b"Success\0",
+        MoqResult::InvalidArgument => b"InvalidArgument\0",
+        MoqResult::RuntimeError => b"RuntimeError\0",
+        MoqResult::DnsError => b"DnsError\0",
+        MoqResult::TlsError => b"TlsError\0",
+        MoqResult::NetworkError => b"NetworkError\0",
+    };
+    s.as_ptr() as *const c_char
+}
+
+/// Classifies a connection failure for `moq_get_last_connect_error`, by sniffing the
+/// error chain's text for DNS/TLS keywords before falling back to a generic network
+/// error.
+fn classify_connect_error(err: &crate::WrapperError) -> MoqResult {
+    let message = format!("{:#}", err).to_lowercase();
+    if message.contains("dns") || message.contains("resolve") || message.contains("no such host") {
+        MoqResult::DnsError
+    } else if message.contains("tls")
+        || message.contains("certificate")
+        || message.contains("handshake")
+    {
+        MoqResult::TlsError
+    } else {
+        MoqResult::NetworkError
+    }
 }
 
 // Callback types with session context
@@ -76,6 +220,22 @@ pub type CBroadcastAnnouncedCallback = extern "C" fn(*const c_char);
 pub type CBroadcastCancelledCallback = extern "C" fn(*const c_char);
 pub type CConnectionClosedCallback = extern "C" fn(*const c_char);
 
+/// Called when a leased subscription's renewal task gives up (broadcast name, track name)
+pub type CRenewalFailedCallback = extern "C" fn(*const c_char, *const c_char);
+
+/// Called once per fragment (MoQ object) by `moq_session_subscribe_track_fragments`:
+/// `(track_name, group_sequence, object_sequence, has_declared_size, declared_size,
+/// data, data_len)`. `has_declared_size` is always non-zero in this tree - see
+/// [`crate::session::FragmentCallback`] for why `declared_size` can't yet diverge
+/// from `data_len`.
+pub type CFragmentCallback = extern "C" fn(*const c_char, u64, u64, c_int, u64, *const u8, usize);
+
+/// Called once per group by `moq_session_subscribe_track_fragments`, after that
+/// group's last `CFragmentCallback` invocation: `(track_name, group_sequence,
+/// is_error)`, with `is_error` non-zero if the group was cut short by a transport
+/// error rather than closing normally; see [`crate::session::GroupEndCallback`].
+pub type CGroupEndCallback = extern "C" fn(*const c_char, u64, c_int);
+
 impl From<CLogLevel> for Level {
     fn from(level: CLogLevel) -> Self {
         match level {
@@ -141,12 +301,16 @@ pub unsafe extern "C" fn moq_track_definition_new(
     track_type: u8,
 ) -> *mut CTrackDefinition {
     if name.is_null() {
+        set_last_error(MoqResult::InvalidArgument, "name is null");
         return ptr::null_mut();
     }
 
     let name_str = match CStr::from_ptr(name).to_str() {
         Ok(s) => s.to_string(),
-        Err(_) => return ptr::null_mut(),
+        Err(_) => {
+            set_last_error(MoqResult::InvalidArgument, "name is not valid UTF-8");
+            return ptr::null_mut();
+        }
     };
 
     let track_def = CTrackDefinition {
@@ -172,7 +336,10 @@ pub unsafe extern "C" fn moq_track_definition_free(track_def: *mut CTrackDefinit
     }
 }
 
-/// Create a publisher session
+/// Create a publisher session. Connects eagerly: by the time this returns a non-null
+/// session, the relay connection and MoQ handshake have already completed (there is
+/// no separate "connect" step in this API - see `MoqSession::start`). On failure,
+/// call `moq_get_last_connect_error` to find out why.
 ///
 /// # Safety
 ///
@@ -190,20 +357,27 @@ pub unsafe extern "C" fn moq_create_publisher(
     catalog_type: CCatalogType,
 ) -> *mut CMoqSession {
     if url.is_null() || broadcast_name.is_null() {
+        set_last_connect_error(MoqResult::InvalidArgument);
         return ptr::null_mut();
     }
 
     let url_str = unsafe {
         match CStr::from_ptr(url).to_str() {
             Ok(s) => s,
-            Err(_) => return ptr::null_mut(),
+            Err(_) => {
+                set_last_connect_error(MoqResult::InvalidArgument);
+                return ptr::null_mut();
+            }
         }
     };
 
     let broadcast_str = unsafe {
         match CStr::from_ptr(broadcast_name).to_str() {
             Ok(s) => s,
-            Err(_) => return ptr::null_mut(),
+            Err(_) => {
+                set_last_connect_error(MoqResult::InvalidArgument);
+                return ptr::null_mut();
+            }
         }
     };
 
@@ -245,7 +419,10 @@ pub unsafe extern "C" fn moq_create_publisher(
 
     let runtime = match Runtime::new() {
         Ok(rt) => Arc::new(rt),
-        Err(_) => return ptr::null_mut(),
+        Err(_) => {
+            set_last_connect_error(MoqResult::RuntimeError);
+            return ptr::null_mut();
+        }
     };
 
     let session = match runtime.block_on(create_publisher(
@@ -255,9 +432,13 @@ pub unsafe extern "C" fn moq_create_publisher(
         CatalogType::from(catalog_type),
     )) {
         Ok(s) => Arc::new(s),
-        Err(_) => return ptr::null_mut(),
+        Err(e) => {
+            set_last_error(classify_connect_error(&e), format!("{:#}", e));
+            return ptr::null_mut();
+        }
     };
 
+    set_last_connect_error(MoqResult::Success);
     let c_session = CMoqSession {
         session,
         runtime,
@@ -265,12 +446,14 @@ pub unsafe extern "C" fn moq_create_publisher(
         broadcast_announced_callback: Arc::new(RwLock::new(None)),
         broadcast_cancelled_callback: Arc::new(RwLock::new(None)),
         connection_closed_callback: Arc::new(RwLock::new(None)),
+        renewal_failed_callback: Arc::new(RwLock::new(None)),
     };
 
     Box::into_raw(Box::new(c_session))
 }
 
-/// Create a subscriber session
+/// Create a subscriber session. Connects eagerly, same as `moq_create_publisher`. On
+/// failure, call `moq_get_last_connect_error` to find out why.
 ///
 /// # Safety
 ///
@@ -288,20 +471,27 @@ pub unsafe extern "C" fn moq_create_subscriber(
     catalog_type: CCatalogType,
 ) -> *mut CMoqSession {
     if url.is_null() || broadcast_name.is_null() {
+        set_last_connect_error(MoqResult::InvalidArgument);
         return ptr::null_mut();
     }
 
     let url_str = unsafe {
         match CStr::from_ptr(url).to_str() {
             Ok(s) => s,
-            Err(_) => return ptr::null_mut(),
+            Err(_) => {
+                set_last_connect_error(MoqResult::InvalidArgument);
+                return ptr::null_mut();
+            }
         }
     };
 
     let broadcast_str = unsafe {
         match CStr::from_ptr(broadcast_name).to_str() {
             Ok(s) => s,
-            Err(_) => return ptr::null_mut(),
+            Err(_) => {
+                set_last_connect_error(MoqResult::InvalidArgument);
+                return ptr::null_mut();
+            }
         }
     };
 
@@ -343,7 +533,10 @@ pub unsafe extern "C" fn moq_create_subscriber(
 
     let runtime = match Runtime::new() {
         Ok(rt) => Arc::new(rt),
-        Err(_) => return ptr::null_mut(),
+        Err(_) => {
+            set_last_connect_error(MoqResult::RuntimeError);
+            return ptr::null_mut();
+        }
     };
 
     let session = match runtime.block_on(create_subscriber(
@@ -353,9 +546,13 @@ pub unsafe extern "C" fn moq_create_subscriber(
         CatalogType::from(catalog_type),
     )) {
         Ok(s) => Arc::new(s),
-        Err(_) => return ptr::null_mut(),
+        Err(e) => {
+            set_last_error(classify_connect_error(&e), format!("{:#}", e));
+            return ptr::null_mut();
+        }
     };
 
+    set_last_connect_error(MoqResult::Success);
     let c_session = CMoqSession {
         session,
         runtime,
@@ -363,6 +560,7 @@ pub unsafe extern "C" fn moq_create_subscriber(
         broadcast_announced_callback: Arc::new(RwLock::new(None)),
         broadcast_cancelled_callback: Arc::new(RwLock::new(None)),
         connection_closed_callback: Arc::new(RwLock::new(None)),
+        renewal_failed_callback: Arc::new(RwLock::new(None)),
     };
 
     Box::into_raw(Box::new(c_session))
@@ -523,6 +721,201 @@ pub unsafe extern "C" fn moq_is_connected(session: *mut CMoqSession) -> c_int {
     }
 }
 
+/// Enumerate the tracks described by the current auto-subscription catalog so a
+/// caller can inspect codec/resolution/framerate/sample-rate and pick which to
+/// subscribe to, instead of guessing track names. Writes the element count to
+/// `out_count` and returns a heap-allocated array (empty/null with `*out_count == 0`
+/// if no catalog has arrived yet); free it with [`moq_free_catalog_tracks`].
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences the raw `session` and `out_count`
+/// pointers. The caller must ensure `session` is a valid pointer to a `CMoqSession`
+/// and `out_count` is a valid pointer to a writable `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_get_available_tracks(
+    session: *mut CMoqSession,
+    out_count: *mut usize,
+) -> *mut CCatalogTrackDescriptor {
+    if session.is_null() || out_count.is_null() {
+        set_last_error(MoqResult::InvalidArgument, "session/out_count is null");
+        return ptr::null_mut();
+    }
+
+    let session_ref = unsafe { &*session };
+    let tracks = session_ref
+        .runtime
+        .block_on(session_ref.session.get_available_tracks());
+
+    unsafe {
+        *out_count = tracks.len();
+    }
+    if tracks.is_empty() {
+        return ptr::null_mut();
+    }
+
+    let c_tracks: Vec<CCatalogTrackDescriptor> = tracks
+        .into_iter()
+        .map(|track| {
+            let name = CString::new(track.name).unwrap_or_else(|_| CString::new("").unwrap());
+            let codec = track
+                .codec
+                .map(|c| CString::new(c).unwrap_or_else(|_| CString::new("").unwrap()));
+            let init_track = track
+                .init_track
+                .map(|t| CString::new(t).unwrap_or_else(|_| CString::new("").unwrap()));
+
+            CCatalogTrackDescriptor {
+                name: name.into_raw(),
+                track_type: match track.track_type {
+                    TrackType::Video => 0,
+                    TrackType::Audio => 1,
+                    TrackType::Data => 2,
+                    // No dedicated C representation yet for caption/custom tracks;
+                    // surface them like other non-audio/video tracks.
+                    TrackType::Caption | TrackType::Extension(_) => 2,
+                },
+                priority: track.priority,
+                codec: codec.map(CString::into_raw).unwrap_or(ptr::null_mut()),
+                has_width: track.width.is_some() as c_int,
+                width: track.width.unwrap_or(0),
+                has_height: track.height.is_some() as c_int,
+                height: track.height.unwrap_or(0),
+                has_framerate: track.framerate.is_some() as c_int,
+                framerate: track.framerate.unwrap_or(0.0),
+                has_sample_rate: track.sample_rate.is_some() as c_int,
+                sample_rate: track.sample_rate.unwrap_or(0),
+                has_channel_count: track.channel_count.is_some() as c_int,
+                channel_count: track.channel_count.unwrap_or(0),
+                init_track: init_track.map(CString::into_raw).unwrap_or(ptr::null_mut()),
+            }
+        })
+        .collect();
+
+    let mut boxed = c_tracks.into_boxed_slice();
+    let ptr = boxed.as_mut_ptr();
+    std::mem::forget(boxed);
+    ptr
+}
+
+/// Free an array returned by [`moq_session_get_available_tracks`]
+///
+/// # Safety
+///
+/// The caller must ensure `tracks` and `count` match exactly what
+/// `moq_session_get_available_tracks` returned, and that this is called at most once
+/// for that array.
+#[no_mangle]
+pub unsafe extern "C" fn moq_free_catalog_tracks(
+    tracks: *mut CCatalogTrackDescriptor,
+    count: usize,
+) {
+    if tracks.is_null() || count == 0 {
+        return;
+    }
+
+    let boxed = unsafe { Box::from_raw(std::slice::from_raw_parts_mut(tracks, count)) };
+    for track in boxed.iter() {
+        unsafe {
+            if !track.name.is_null() {
+                drop(CString::from_raw(track.name));
+            }
+            if !track.codec.is_null() {
+                drop(CString::from_raw(track.codec));
+            }
+            if !track.init_track.is_null() {
+                drop(CString::from_raw(track.init_track));
+            }
+        }
+    }
+}
+
+/// List the broadcast paths this session has seen announced (and not since
+/// unannounced) whose path starts with `prefix`, enabling a UI to build a live
+/// channel list instead of hard-coding broadcast names. Pass an empty string for
+/// every known broadcast. Writes the element count to `out_count` and returns a
+/// heap-allocated array of owned `CString`s (null with `*out_count == 0` if none
+/// match); free it with [`moq_free_announced_broadcasts`].
+///
+/// This is a point-in-time snapshot rather than a live stream: pair it with
+/// `moq_session_set_broadcast_announced_callback`/`_cancelled_callback` to also learn
+/// about broadcasts that come and go afterward.
+///
+/// # Safety
+///
+/// This function is unsafe because it dereferences the raw `session`, `prefix`, and
+/// `out_count` pointers. The caller must ensure `session` is a valid pointer to a
+/// `CMoqSession`, `prefix` is null or a valid null-terminated C string, and
+/// `out_count` is a valid pointer to a writable `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_get_announced_broadcasts(
+    session: *mut CMoqSession,
+    prefix: *const c_char,
+    out_count: *mut usize,
+) -> *mut *mut c_char {
+    if session.is_null() || out_count.is_null() {
+        set_last_error(MoqResult::InvalidArgument, "session/out_count is null");
+        return ptr::null_mut();
+    }
+
+    let prefix_str = if prefix.is_null() {
+        ""
+    } else {
+        match unsafe { CStr::from_ptr(prefix) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                set_last_error(MoqResult::InvalidArgument, "prefix is not valid UTF-8");
+                return ptr::null_mut();
+            }
+        }
+    };
+
+    let session_ref = unsafe { &*session };
+    let paths = session_ref
+        .runtime
+        .block_on(session_ref.session.get_announced_broadcasts(prefix_str));
+
+    unsafe {
+        *out_count = paths.len();
+    }
+    if paths.is_empty() {
+        return ptr::null_mut();
+    }
+
+    let c_paths: Vec<*mut c_char> = paths
+        .into_iter()
+        .map(|path| CString::new(path).unwrap_or_default().into_raw())
+        .collect();
+
+    let mut boxed = c_paths.into_boxed_slice();
+    let ptr = boxed.as_mut_ptr();
+    std::mem::forget(boxed);
+    ptr
+}
+
+/// Free an array returned by [`moq_session_get_announced_broadcasts`]
+///
+/// # Safety
+///
+/// The caller must ensure `names` and `count` match exactly what
+/// `moq_session_get_announced_broadcasts` returned, and that this is called at most
+/// once for that array.
+#[no_mangle]
+pub unsafe extern "C" fn moq_free_announced_broadcasts(names: *mut *mut c_char, count: usize) {
+    if names.is_null() || count == 0 {
+        return;
+    }
+
+    let boxed = unsafe { Box::from_raw(std::slice::from_raw_parts_mut(names, count)) };
+    for name in boxed.iter() {
+        if !name.is_null() {
+            unsafe {
+                drop(CString::from_raw(*name));
+            }
+        }
+    }
+}
+
 /// Close a session
 ///
 /// # Safety
@@ -562,7 +955,7 @@ pub unsafe extern "C" fn moq_session_set_log_callback(
     callback: Option<CLogCallback>,
 ) -> MoqResult {
     if session.is_null() {
-        return MoqResult::InvalidArgument;
+        return invalid_argument("session is null");
     }
 
     let session_ref = unsafe { &*session };
@@ -608,7 +1001,7 @@ pub unsafe extern "C" fn moq_session_set_broadcast_announced_callback(
     callback: CBroadcastAnnouncedCallback,
 ) -> c_int {
     if session.is_null() {
-        return MoqResult::InvalidArgument as c_int;
+        return invalid_argument("session is null") as c_int;
     }
 
     let session_ref = unsafe { &*session };
@@ -651,7 +1044,7 @@ pub unsafe extern "C" fn moq_session_set_broadcast_cancelled_callback(
     callback: CBroadcastCancelledCallback,
 ) -> c_int {
     if session.is_null() {
-        return MoqResult::InvalidArgument as c_int;
+        return invalid_argument("session is null") as c_int;
     }
 
     let session_ref = unsafe { &*session };
@@ -683,6 +1076,178 @@ pub unsafe extern "C" fn moq_session_set_broadcast_cancelled_callback(
     MoqResult::Success as c_int
 }
 
+/// Set the callback invoked when a leased subscription's renewal task gives up
+///
+/// # Safety
+/// The caller must ensure that `session` is a valid pointer returned from
+/// `moq_create_publisher` or `moq_create_subscriber`.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_set_renewal_failed_callback(
+    session: *mut CMoqSession,
+    callback: CRenewalFailedCallback,
+) -> c_int {
+    if session.is_null() {
+        return invalid_argument("session is null") as c_int;
+    }
+
+    let session_ref = unsafe { &*session };
+
+    // Store the C callback
+    if let Ok(mut cb) = session_ref.renewal_failed_callback.write() {
+        *cb = Some(callback);
+    }
+
+    // Set up the Rust callback that will call the C callback
+    let c_callback = session_ref.renewal_failed_callback.clone();
+    let rust_callback = Box::new(move |broadcast_name: &str, track_name: &str| {
+        if let Ok(guard) = c_callback.read() {
+            if let Some(cb) = *guard {
+                let c_broadcast =
+                    CString::new(broadcast_name).unwrap_or_else(|_| CString::new("").unwrap());
+                let c_track =
+                    CString::new(track_name).unwrap_or_else(|_| CString::new("").unwrap());
+                cb(c_broadcast.as_ptr(), c_track.as_ptr());
+            }
+        }
+    });
+
+    // Set the callback in the session
+    session_ref.runtime.block_on(async {
+        session_ref
+            .session
+            .set_renewal_failed_callback(rust_callback)
+            .await;
+    });
+
+    MoqResult::Success as c_int
+}
+
+/// Register a setup extension to advertise to the peer during connection setup.
+///
+/// `id` identifies the extension, `required` is non-zero if a peer that doesn't
+/// implement it should cause the session to fail, and `payload` / `payload_len` are
+/// the opaque bytes handed to the peer for it (e.g. an encoded delivery timeout or
+/// authorization token) - this library never interprets `payload` itself. Call this
+/// before starting the session so the first connection already advertises it.
+///
+/// # Safety
+/// The caller must ensure that `session` is a valid pointer returned from
+/// `moq_create_publisher` or `moq_create_subscriber`, and that `payload` points to a
+/// valid buffer of at least `payload_len` bytes (or is null, iff `payload_len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_register_setup_extension(
+    session: *mut CMoqSession,
+    id: u64,
+    required: c_int,
+    payload: *const u8,
+    payload_len: usize,
+) -> c_int {
+    if session.is_null() {
+        return invalid_argument("session is null") as c_int;
+    }
+
+    let session_ref = unsafe { &*session };
+
+    let payload_vec = if payload.is_null() || payload_len == 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+    };
+
+    session_ref.runtime.block_on(async {
+        session_ref
+            .session
+            .register_setup_extension(id, required != 0, payload_vec)
+            .await;
+    });
+
+    MoqResult::Success as c_int
+}
+
+/// Subscribe to `track_name` in `broadcast_name` and invoke `callback` once per
+/// fragment (MoQ object) as it arrives, enabling low-latency decode pipelines that
+/// don't buffer whole groups before starting to decode. The callback is invoked on
+/// the Rust async runtime's thread, for as long as the underlying subscription stays
+/// alive; see [`crate::session::FragmentCallback`]. `group_end_callback`, if not
+/// `None`, is invoked once per group right after that group's last fragment, so a
+/// caller can tell a group that closed normally apart from one cut short by an
+/// error - `callback` alone just stops being called either way.
+///
+/// # Safety
+/// The caller must ensure that `session` is a valid pointer returned from
+/// `moq_create_publisher` or `moq_create_subscriber`, and that `broadcast_name` /
+/// `track_name` are valid null-terminated C strings. `callback` and
+/// `group_end_callback` must remain valid for as long as the subscription is active
+/// - there is currently no API to cancel an individual fragment subscription short
+/// of closing the session.
+#[no_mangle]
+pub unsafe extern "C" fn moq_session_subscribe_track_fragments(
+    session: *mut CMoqSession,
+    broadcast_name: *const c_char,
+    track_name: *const c_char,
+    callback: CFragmentCallback,
+    group_end_callback: Option<CGroupEndCallback>,
+) -> c_int {
+    if session.is_null() || broadcast_name.is_null() || track_name.is_null() {
+        return invalid_argument("session/broadcast_name/track_name is null") as c_int;
+    }
+
+    let session_ref = unsafe { &*session };
+
+    let broadcast_str = unsafe {
+        match CStr::from_ptr(broadcast_name).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return invalid_argument("broadcast_name is not valid UTF-8") as c_int,
+        }
+    };
+    let track_str = unsafe {
+        match CStr::from_ptr(track_name).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return invalid_argument("track_name is not valid UTF-8") as c_int,
+        }
+    };
+
+    let rust_callback: crate::session::FragmentCallback = Arc::new(
+        move |track_name, group_sequence, object_sequence, declared_size, data| {
+            let track_cstr = CString::new(track_name).unwrap_or_else(|_| CString::new("").unwrap());
+            let (has_size, size) = match declared_size {
+                Some(size) => (1, size),
+                None => (0, 0),
+            };
+            callback(
+                track_cstr.as_ptr(),
+                group_sequence,
+                object_sequence,
+                has_size,
+                size,
+                data.as_ptr(),
+                data.len(),
+            );
+        },
+    );
+
+    let rust_group_end_callback: Option<crate::session::GroupEndCallback> =
+        group_end_callback.map(|callback| {
+            Arc::new(move |track_name: &str, group_sequence, is_error| {
+                let track_cstr =
+                    CString::new(track_name).unwrap_or_else(|_| CString::new("").unwrap());
+                callback(track_cstr.as_ptr(), group_sequence, is_error as c_int);
+            }) as crate::session::GroupEndCallback
+        });
+
+    match session_ref
+        .runtime
+        .block_on(session_ref.session.subscribe_track_fragments(
+            &broadcast_str,
+            &track_str,
+            rust_callback,
+            rust_group_end_callback,
+        )) {
+        Ok(()) => MoqResult::Success as c_int,
+        Err(e) => runtime_error(e.to_string()) as c_int,
+    }
+}
+
 /// Set connection closed callback
 ///
 /// # Safety
@@ -694,7 +1259,7 @@ pub unsafe extern "C" fn moq_session_set_connection_closed_callback(
     callback: CConnectionClosedCallback,
 ) -> c_int {
     if session.is_null() {
-        return MoqResult::InvalidArgument as c_int;
+        return invalid_argument("session is null") as c_int;
     }
 
     let session_ref = unsafe { &*session };
@@ -752,3 +1317,225 @@ pub unsafe extern "C" fn moq_session_free(session: *mut CMoqSession) {
         }
     }
 }
+
+/// Largest value the QUIC variable-length integer encoding can represent: 2^62 - 1.
+const MOQ_VARINT_MAX: u64 = (1u64 << 62) - 1;
+
+/// Encodes `value` into `buf` using the QUIC variable-length integer scheme (the top
+/// two bits of the first byte select a 1/2/4/8-byte encoding for a 6/14/30/62-bit
+/// value, big-endian), always choosing the shortest encoding that fits, and writes the
+/// number of bytes used to `*written_out`. Used to frame group/object lengths the same
+/// way the underlying moq-lite wire format does, so C++ code assembling or inspecting
+/// payloads directly can speak it without reimplementing the codec.
+///
+/// Returns `MoqResult::InvalidArgument` if `buf`/`written_out` is null, `value` is
+/// `>= 2^62`, or `buf_len` is too small for the chosen encoding.
+///
+/// # Safety
+/// The caller must ensure `buf` points to at least `buf_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn moq_varint_encode(
+    value: u64,
+    buf: *mut u8,
+    buf_len: usize,
+    written_out: *mut usize,
+) -> c_int {
+    if buf.is_null() || written_out.is_null() {
+        return invalid_argument("buf/written_out is null") as c_int;
+    }
+    if value > MOQ_VARINT_MAX {
+        return invalid_argument(format!("value {value} is >= 2^62")) as c_int;
+    }
+
+    let len: usize = if value < (1 << 6) {
+        1
+    } else if value < (1 << 14) {
+        2
+    } else if value < (1 << 30) {
+        4
+    } else {
+        8
+    };
+
+    if buf_len < len {
+        return invalid_argument(format!(
+            "buf_len {buf_len} is too small for a {len}-byte encoding"
+        )) as c_int;
+    }
+
+    let prefix: u8 = match len {
+        1 => 0b00,
+        2 => 0b01,
+        4 => 0b10,
+        _ => 0b11,
+    };
+    let mut bytes = value.to_be_bytes();
+    let first_byte_index = bytes.len() - len;
+    bytes[first_byte_index] |= prefix << 6;
+
+    let out = unsafe { std::slice::from_raw_parts_mut(buf, len) };
+    out.copy_from_slice(&bytes[first_byte_index..]);
+
+    unsafe {
+        *written_out = len;
+    }
+
+    MoqResult::Success as c_int
+}
+
+/// Decodes a QUIC variable-length integer from the start of `buf`, writing the value
+/// to `*value_out` and the number of bytes it occupied to `*consumed_out`. The
+/// encoding's length is determined entirely by the top two bits of `buf[0]`; see
+/// [`moq_varint_encode`].
+///
+/// Returns `MoqResult::InvalidArgument` if `buf`/`value_out`/`consumed_out` is null,
+/// `buf_len` is zero, or `buf_len` is smaller than the length indicated by `buf[0]`.
+///
+/// # Safety
+/// The caller must ensure `buf` points to at least `buf_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn moq_varint_decode(
+    buf: *const u8,
+    buf_len: usize,
+    value_out: *mut u64,
+    consumed_out: *mut usize,
+) -> c_int {
+    if buf.is_null() || value_out.is_null() || consumed_out.is_null() || buf_len == 0 {
+        return invalid_argument("buf/value_out/consumed_out is null, or buf_len is 0") as c_int;
+    }
+
+    let input = unsafe { std::slice::from_raw_parts(buf, buf_len) };
+    let len: usize = match input[0] >> 6 {
+        0b00 => 1,
+        0b01 => 2,
+        0b10 => 4,
+        _ => 8,
+    };
+
+    if buf_len < len {
+        return invalid_argument(format!(
+            "buf_len {buf_len} is smaller than the indicated {len}-byte encoding"
+        )) as c_int;
+    }
+
+    let mut bytes = [0u8; 8];
+    let first_byte_index = bytes.len() - len;
+    bytes[first_byte_index..].copy_from_slice(&input[..len]);
+    bytes[first_byte_index] &= 0b0011_1111;
+    let value = u64::from_be_bytes(bytes);
+
+    unsafe {
+        *value_out = value;
+        *consumed_out = len;
+    }
+
+    MoqResult::Success as c_int
+}
+
+#[cfg(test)]
+mod varint_tests {
+    use super::*;
+
+    fn encode(value: u64) -> (Vec<u8>, c_int) {
+        let mut buf = [0u8; 8];
+        let mut written = 0usize;
+        let result = unsafe {
+            moq_varint_encode(
+                value,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut written as *mut usize,
+            )
+        };
+        (buf[..written].to_vec(), result)
+    }
+
+    fn decode(buf: &[u8]) -> (u64, usize, c_int) {
+        let mut value = 0u64;
+        let mut consumed = 0usize;
+        let result = unsafe {
+            moq_varint_decode(
+                buf.as_ptr(),
+                buf.len(),
+                &mut value as *mut u64,
+                &mut consumed as *mut usize,
+            )
+        };
+        (value, consumed, result)
+    }
+
+    #[test]
+    fn round_trips_boundary_values() {
+        for &value in &[
+            0u64,
+            1,
+            (1 << 6) - 1,
+            1 << 6,
+            (1 << 14) - 1,
+            1 << 14,
+            (1 << 30) - 1,
+            1 << 30,
+            MOQ_VARINT_MAX,
+        ] {
+            let (encoded, encode_result) = encode(value);
+            assert_eq!(encode_result, MoqResult::Success as c_int);
+
+            let (decoded, consumed, decode_result) = decode(&encoded);
+            assert_eq!(decode_result, MoqResult::Success as c_int);
+            assert_eq!(decoded, value);
+            assert_eq!(consumed, encoded.len());
+        }
+    }
+
+    #[test]
+    fn encode_picks_the_shortest_length() {
+        assert_eq!(encode(0).0.len(), 1);
+        assert_eq!(encode(1 << 6).0.len(), 2);
+        assert_eq!(encode(1 << 14).0.len(), 4);
+        assert_eq!(encode(1 << 30).0.len(), 8);
+    }
+
+    #[test]
+    fn encode_rejects_values_at_or_above_2_62() {
+        let (_, result) = encode(MOQ_VARINT_MAX + 1);
+        assert_eq!(result, MoqResult::InvalidArgument as c_int);
+    }
+
+    #[test]
+    fn encode_rejects_a_buffer_too_small_for_the_chosen_length() {
+        let mut buf = [0u8; 1];
+        let mut written = 0usize;
+        let result = unsafe {
+            moq_varint_encode(
+                1 << 6,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut written as *mut usize,
+            )
+        };
+        assert_eq!(result, MoqResult::InvalidArgument as c_int);
+    }
+
+    #[test]
+    fn decode_rejects_a_buffer_shorter_than_the_indicated_length() {
+        // Prefix bits `01` indicate a 2-byte encoding, but only 1 byte is supplied.
+        let buf = [0b0100_0000u8];
+        let (_, _, result) = decode(&buf);
+        assert_eq!(result, MoqResult::InvalidArgument as c_int);
+    }
+
+    #[test]
+    fn decode_rejects_an_empty_buffer() {
+        let mut value = 0u64;
+        let mut consumed = 0usize;
+        let result = unsafe {
+            moq_varint_decode(
+                [].as_ptr(),
+                0,
+                &mut value as *mut u64,
+                &mut consumed as *mut usize,
+            )
+        };
+        assert_eq!(result, MoqResult::InvalidArgument as c_int);
+    }
+}
@@ -1,16 +1,32 @@
+pub mod broker;
 pub mod catalog;
 pub mod config;
+pub mod congestion;
 pub mod ffi;
+pub mod metrics;
+pub mod relay;
 pub mod session;
+pub mod source;
 pub mod subscription_manager;
 pub mod track;
 
-pub use catalog::{Catalog, CatalogType, HangCatalog, SesameCatalog, TrackDefinition, TrackType};
-pub use config::{ConnectionConfig, SessionConfig, WrapperError};
+pub use broker::{BrokeredTrack, TrackBroker};
+pub use catalog::{
+    Catalog, CatalogTrackDescriptor, CatalogType, HangCatalog, SesameCatalog, TrackDefinition,
+    TrackType,
+};
+pub use config::{ConnectionConfig, ReconnectStrategy, SessionConfig, WrapperError};
+pub use congestion::{BandwidthEstimator, CongestionState, DeliverySample};
+pub use metrics::{SessionMetrics, TrackMetrics};
+pub use relay::Forwarder;
 pub use session::{
-    ConnectionInfo, DataCallback, MoqSession, SessionEvent, SessionLogCallback, SessionType,
+    ConnectionInfo, DataCallback, FragmentCallback, MoqSession, SessionEvent, SessionLogCallback,
+    SessionType, TrackRequestedCallback,
+};
+pub use subscription_manager::{
+    BroadcastSubscriptionManager, DeliveryMode, RetryConfig, StartPosition, SubscriptionMode,
+    SubscriptionOptions,
 };
-pub use subscription_manager::BroadcastSubscriptionManager;
 pub use track::{StreamPublisher, TrackManager};
 
 // Re-export commonly used types from moq-lite for convenience
@@ -22,6 +38,7 @@ pub use moq_lite::{
 // Re-export tracing types for logging
 use anyhow::Result;
 use std::sync::Once;
+use std::time::Duration;
 pub use tracing::Level;
 
 static TRACING_INIT: Once = Once::new();
@@ -51,6 +68,13 @@ pub fn set_log_level(log_level: Level) {
     });
 }
 
+/// How long [`create_publisher`] waits for the first connection attempt to either
+/// succeed or report a concrete error before giving up. `MoqSession::start` retries
+/// forever on its own (per `SessionConfig::reconnect`), so without a deadline here a
+/// permanently bad URL (unresolvable host, refused port, ...) would hang this call
+/// indefinitely instead of surfacing the failure.
+const INITIAL_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Create a quick publisher session with specified tracks and catalog
 pub async fn create_publisher(
     url: &str,
@@ -71,14 +95,41 @@ pub async fn create_publisher(
     .await?;
 
     session.start().await?;
+    wait_for_initial_connection(&session).await?;
+
+    Ok(session)
+}
 
-    // Wait for initial connection (track producers will be created automatically)
-    use tokio::time::{sleep, Duration};
-    while !session.is_connected().await {
-        sleep(Duration::from_millis(100)).await;
+/// Waits for the session's background connection task (spawned by
+/// `MoqSession::start`) to report its first connection attempt's outcome, bounded by
+/// [`INITIAL_CONNECT_TIMEOUT`]. Uses `MoqSession::initial_connect_watch` rather than
+/// `next_event` so it doesn't consume from the session's single-consumer event
+/// channel - doing that here would silently drop any event that isn't the one this
+/// function is waiting for, since nothing else is left to replay it to.
+async fn wait_for_initial_connection(session: &MoqSession) -> Result<(), WrapperError> {
+    let mut rx = session.initial_connect_watch();
+
+    if let Some(outcome) = rx.borrow().clone() {
+        return outcome.map_err(|error| WrapperError::Connection(anyhow::anyhow!(error)));
     }
 
-    Ok(session)
+    match tokio::time::timeout(INITIAL_CONNECT_TIMEOUT, rx.changed()).await {
+        Ok(Ok(())) => match rx.borrow().clone() {
+            Some(outcome) => {
+                outcome.map_err(|error| WrapperError::Connection(anyhow::anyhow!(error)))
+            }
+            None => Err(WrapperError::Connection(anyhow::anyhow!(
+                "initial connection watch fired without recording an outcome"
+            ))),
+        },
+        Ok(Err(_)) => Err(WrapperError::Connection(anyhow::anyhow!(
+            "session's connection task ended before the initial connection completed"
+        ))),
+        Err(_) => Err(WrapperError::Connection(anyhow::anyhow!(
+            "timed out after {:?} waiting for the initial connection",
+            INITIAL_CONNECT_TIMEOUT
+        ))),
+    }
 }
 
 /// Write a frame to a track, optionally starting a new group
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How long a bitrate sample window is accumulated before being folded into the EWMA
+const BITRATE_WINDOW: Duration = Duration::from_secs(1);
+/// Weight given to the newest sample when smoothing the bitrate EWMA
+const BITRATE_EWMA_ALPHA: f64 = 0.3;
+
+/// Smooths a byte counter into a current/peak bits-per-second estimate, using an
+/// exponentially-weighted moving average over `BITRATE_WINDOW`-sized samples so a
+/// short burst of traffic doesn't make the reported rate spike and then vanish
+struct BitrateTracker {
+    window_start: Instant,
+    window_bytes: u64,
+    current_bps: f64,
+    peak_bps: f64,
+}
+
+impl BitrateTracker {
+    fn new() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_bytes: 0,
+            current_bps: 0.0,
+            peak_bps: 0.0,
+        }
+    }
+
+    fn record(&mut self, bytes: u64) {
+        self.window_bytes += bytes;
+
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= BITRATE_WINDOW {
+            let sample_bps = (self.window_bytes as f64 * 8.0) / elapsed.as_secs_f64();
+            self.current_bps = if self.current_bps == 0.0 {
+                sample_bps
+            } else {
+                BITRATE_EWMA_ALPHA * sample_bps + (1.0 - BITRATE_EWMA_ALPHA) * self.current_bps
+            };
+            self.peak_bps = self.peak_bps.max(self.current_bps);
+            self.window_bytes = 0;
+            self.window_start = Instant::now();
+        }
+    }
+}
+
+/// Counters for a single track, aggregated into a [`SessionMetrics`] snapshot
+#[derive(Clone, Debug, Default)]
+pub struct TrackMetrics {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub frames_sent: u64,
+    pub frames_received: u64,
+    pub groups_opened: u64,
+    pub groups_closed: u64,
+    pub current_bitrate_bps: f64,
+    pub peak_bitrate_bps: f64,
+}
+
+/// A point-in-time snapshot of session telemetry, returned by [`crate::MoqSession::metrics`]
+/// and optionally emitted periodically as `SessionEvent::Metrics`; see
+/// `SessionConfig::metrics_interval`.
+///
+/// `bytes_received`/`frames_received` (here and in [`TrackMetrics`]) are not updated by
+/// any receive path in this crate and always report 0; only the publish path
+/// (`write_frame`, group open/close) feeds the byte, frame and bitrate counters.
+#[derive(Clone, Debug, Default)]
+pub struct SessionMetrics {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub frames_sent: u64,
+    pub frames_received: u64,
+    pub groups_opened: u64,
+    pub groups_closed: u64,
+    pub announcements_received: u64,
+    pub reconnect_count: u64,
+    pub current_bitrate_bps: f64,
+    pub peak_bitrate_bps: f64,
+    pub average_rtt: Option<Duration>,
+    pub tracks: HashMap<String, TrackMetrics>,
+}
+
+/// Live, mutable counters backing a [`SessionMetrics`] snapshot. Held behind an
+/// `Arc<RwLock<_>>` on `MoqSession` and updated from the frame-write path, the
+/// announcement monitor, and the reconnect/clock-sync logic in `start()`.
+#[derive(Default)]
+pub(crate) struct MetricsRegistry {
+    aggregate: SessionMetrics,
+    overall_bitrate: Option<BitrateTracker>,
+    track_bitrates: HashMap<String, BitrateTracker>,
+    rtt_sum: Duration,
+    rtt_samples: u32,
+}
+
+impl MetricsRegistry {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_frame_sent(&mut self, track_name: &str, bytes: usize) {
+        let bytes = bytes as u64;
+
+        self.aggregate.bytes_sent += bytes;
+        self.aggregate.frames_sent += 1;
+
+        let overall = self.overall_bitrate.get_or_insert_with(BitrateTracker::new);
+        overall.record(bytes);
+        self.aggregate.current_bitrate_bps = overall.current_bps;
+        self.aggregate.peak_bitrate_bps = overall.peak_bps;
+
+        let track = self
+            .aggregate
+            .tracks
+            .entry(track_name.to_string())
+            .or_default();
+        track.bytes_sent += bytes;
+        track.frames_sent += 1;
+
+        let track_bitrate = self
+            .track_bitrates
+            .entry(track_name.to_string())
+            .or_insert_with(BitrateTracker::new);
+        track_bitrate.record(bytes);
+        track.current_bitrate_bps = track_bitrate.current_bps;
+        track.peak_bitrate_bps = track_bitrate.peak_bps;
+    }
+
+    pub(crate) fn record_group_opened(&mut self, track_name: &str) {
+        self.aggregate.groups_opened += 1;
+        self.aggregate
+            .tracks
+            .entry(track_name.to_string())
+            .or_default()
+            .groups_opened += 1;
+    }
+
+    pub(crate) fn record_group_closed(&mut self, track_name: &str) {
+        self.aggregate.groups_closed += 1;
+        self.aggregate
+            .tracks
+            .entry(track_name.to_string())
+            .or_default()
+            .groups_closed += 1;
+    }
+
+    pub(crate) fn record_announcement(&mut self) {
+        self.aggregate.announcements_received += 1;
+    }
+
+    pub(crate) fn record_reconnect(&mut self) {
+        self.aggregate.reconnect_count += 1;
+    }
+
+    pub(crate) fn record_rtt(&mut self, rtt: Duration) {
+        self.rtt_sum += rtt;
+        self.rtt_samples += 1;
+        self.aggregate.average_rtt = Some(self.rtt_sum / self.rtt_samples);
+    }
+
+    pub(crate) fn snapshot(&self) -> SessionMetrics {
+        self.aggregate.clone()
+    }
+}
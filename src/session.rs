@@ -14,7 +14,10 @@ use moq_lite::{
 use moq_native::Client;
 
 use crate::catalog::{Catalog, CatalogType, TrackDefinition};
-use crate::config::{SessionConfig, WrapperError};
+use crate::config::{ReconnectStrategy, SessionConfig, WrapperError};
+use crate::congestion::{BandwidthEstimator, CongestionState, DeliverySample};
+use crate::metrics::{MetricsRegistry, SessionMetrics};
+use crate::subscription_manager::SubscriptionOptions;
 
 /// Log callback function type for session-specific logging
 pub type SessionLogCallback = Box<dyn Fn(&str, Level, &str) + Send + Sync>;
@@ -22,6 +25,30 @@ pub type SessionLogCallback = Box<dyn Fn(&str, Level, &str) + Send + Sync>;
 /// Type alias for data callback function
 pub type DataCallback = Arc<dyn Fn(String, Vec<u8>) + Send + Sync>;
 
+/// Per-fragment (MoQ object) callback invoked by
+/// [`MoqSession::subscribe_track_fragments`]: `(track_name, group_sequence,
+/// object_sequence, declared_size, payload)`. `declared_size` is always
+/// `Some(payload.len())` in this tree - `moq-lite`'s `GroupConsumer::read_frame` only
+/// hands back an object once it has arrived in full, so there's no separate in-flight
+/// declared length to report ahead of the payload bytes themselves; the field stays
+/// `Option` to match the wire model's object framing for a future `moq-lite` that
+/// streams partial objects.
+pub type FragmentCallback = Arc<dyn Fn(&str, u64, u64, Option<u64>, Bytes) + Send + Sync>;
+
+/// Fired by [`MoqSession::subscribe_track_fragments`] once per group, after its last
+/// fragment callback, to report how the group ended: `(track_name, group_sequence,
+/// is_error)`. Lets a caller distinguish a group that closed normally from one that
+/// was cut short by a transport error, which the per-fragment callback alone can't -
+/// both cases just stop calling it.
+pub type GroupEndCallback = Arc<dyn Fn(&str, u64, bool) + Send + Sync>;
+
+/// Reserved track name the heartbeat companion task publishes zero-length frames to
+const KEEPALIVE_TRACK_NAME: &str = ".moq-keepalive";
+
+/// Reserved track name used to exchange each side's registered setup extensions
+/// immediately after connecting; see [`MoqSession::register_setup_extension`]
+const SETUP_EXTENSIONS_TRACK_NAME: &str = ".moq-setup-extensions";
+
 /// Macro for session-aware logging that sends to both tracing and session callback
 macro_rules! session_log {
     ($session:expr, info, $($arg:tt)*) => {
@@ -83,11 +110,34 @@ pub enum SessionType {
 #[derive(Clone, Debug)]
 pub enum SessionEvent {
     Connected,
-    Disconnected { reason: String },
-    BroadcastAnnounced { path: String },
-    BroadcastUnannounced { path: String },
-    TrackRequested { name: String },
-    Error { error: String },
+    Disconnected {
+        reason: String,
+    },
+    BroadcastAnnounced {
+        path: String,
+    },
+    BroadcastUnannounced {
+        path: String,
+    },
+    TrackRequested {
+        name: String,
+    },
+    Error {
+        error: String,
+    },
+    /// Periodic telemetry snapshot; emitted when `SessionConfig::metrics_interval` is set
+    Metrics(SessionMetrics),
+    /// A track's delay-based bandwidth estimator (see `enable_bandwidth_estimation`)
+    /// produced a trustworthy slope classification; emitted by
+    /// `record_bandwidth_sample`. `state` tells a publisher *why* `target_bitrate_bps`
+    /// is what it is, so it can react beyond just the number - e.g. drop to a
+    /// lower-bitrate rendition immediately on `Overuse` rather than waiting for the
+    /// additive increase to reverse on its own.
+    BandwidthEstimate {
+        track_name: String,
+        target_bitrate_bps: f64,
+        state: CongestionState,
+    },
 }
 
 /// Callback function types for session events
@@ -95,6 +145,98 @@ pub type BroadcastAnnouncedCallback = Box<dyn Fn(&str) + Send + Sync>;
 pub type BroadcastCancelledCallback = Box<dyn Fn(&str) + Send + Sync>;
 pub type ConnectionClosedCallback = Box<dyn Fn(&str) + Send + Sync>;
 
+/// Callback consulted by [`MoqSession::handle_track_requested`] when a subscriber asks
+/// for a track with no producer yet; return `Some(TrackDefinition)` to create it on
+/// demand (with whatever priority the app decides), or `None` to decline the request
+pub type TrackRequestedCallback = Box<dyn Fn(&str) -> Option<TrackDefinition> + Send + Sync>;
+
+/// Called by [`MoqSession::subscribe_track_internal_with_lease`]'s renewal task when it
+/// gives up on renewing a leased subscription (broadcast name, track name), so the
+/// application can tear the subscription down instead of silently going stale
+pub type RenewalFailedCallback = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+/// A [`TrackConsumer`] paired with the lifetime of its subscription.
+///
+/// `moq-lite`'s `subscribe_track` in this tree doesn't surface a SUBSCRIBE_OK-style
+/// response carrying a server lease, so `expires_ms` is accepted from the caller (an
+/// `expires` value read off the wire by a layer above this one, or a configured
+/// default) rather than parsed from a control message here. Given that lease length,
+/// [`MoqSession::subscribe_track_internal_with_lease`] spawns a background task that
+/// re-issues the subscribe a configurable margin before `expires_at` and updates
+/// `expires_at` on success, so [`Self::remaining_lease`] always reflects the current
+/// lease rather than the original one.
+pub struct TrackLease {
+    pub consumer: TrackConsumer,
+    expires_at: Arc<RwLock<Option<Instant>>>,
+}
+
+impl TrackLease {
+    /// Time remaining before this subscription's lease expires, or `None` if it has no
+    /// expiry (an `expires` of zero, per the protocol's "no expiry" convention).
+    pub async fn remaining_lease(&self) -> Option<std::time::Duration> {
+        let expires_at = *self.expires_at.read().await;
+        expires_at.map(|at| at.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// A locally registered setup extension: an opaque capability, identified by `id`,
+/// that this session advertises to its peer over the reserved
+/// [`SETUP_EXTENSIONS_TRACK_NAME`] control track right after connecting.
+///
+/// `moq-lite`'s `Session::connect` in this tree has no extension parameter of its own
+/// - the wire-level SETUP message it sends is fixed - so this negotiation happens one
+/// layer up, as an ordinary track exchanged immediately after the handshake succeeds.
+/// `payload` is whatever opaque bytes the application wants the peer to see for this
+/// extension (e.g. an encoded delivery timeout or an authorization token); this crate
+/// never interprets it.
+#[derive(Clone, Debug)]
+struct SetupExtensionEntry {
+    required: bool,
+    payload: Vec<u8>,
+}
+
+/// Encode a set of setup extensions as `count(u32) | (id(u64) | required(u8) |
+/// len(u32) | payload)*`, all big-endian, for transmission on the reserved setup
+/// extensions track.
+fn encode_setup_extensions(
+    extensions: &std::collections::BTreeMap<u64, SetupExtensionEntry>,
+) -> Bytes {
+    let mut buf = Vec::with_capacity(4 + extensions.len() * 13);
+    buf.extend_from_slice(&(extensions.len() as u32).to_be_bytes());
+    for (id, entry) in extensions {
+        buf.extend_from_slice(&id.to_be_bytes());
+        buf.push(entry.required as u8);
+        buf.extend_from_slice(&(entry.payload.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&entry.payload);
+    }
+    Bytes::from(buf)
+}
+
+/// Decode a frame produced by [`encode_setup_extensions`]. Returns `None` on any
+/// malformed input rather than panicking, since this is read off the wire from a peer.
+fn decode_setup_extensions(
+    data: &[u8],
+) -> Option<std::collections::BTreeMap<u64, SetupExtensionEntry>> {
+    let mut extensions = std::collections::BTreeMap::new();
+    if data.len() < 4 {
+        return None;
+    }
+    let count = u32::from_be_bytes(data[0..4].try_into().ok()?);
+    let mut offset = 4;
+    for _ in 0..count {
+        let id = u64::from_be_bytes(data.get(offset..offset + 8)?.try_into().ok()?);
+        offset += 8;
+        let required = *data.get(offset)? != 0;
+        offset += 1;
+        let len = u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        offset += 4;
+        let payload = data.get(offset..offset + len)?.to_vec();
+        offset += len;
+        extensions.insert(id, SetupExtensionEntry { required, payload });
+    }
+    Some(extensions)
+}
+
 /// A high-level wrapper around moq-native that provides:
 /// - Automatic reconnection for both publish and subscribe sessions
 /// - Session lifecycle management
@@ -114,6 +256,25 @@ pub struct MoqSession {
     tracks: Arc<RwLock<HashMap<String, TrackHandle>>>,
     current_groups: Arc<RwLock<HashMap<String, GroupProducer>>>,
     sequence_numbers: Arc<RwLock<HashMap<String, u64>>>,
+    // Sequence number of the group currently stored in `current_groups` for each
+    // track, so a TTL task (see `start_group`) can tell whether the group it was
+    // asked to expire is still the one active, or whether a newer one has already
+    // replaced it
+    current_group_sequences: Arc<RwLock<HashMap<String, u64>>>,
+
+    // Per-track delay-based bandwidth estimator, enabled on demand via
+    // `enable_bandwidth_estimation`; see `record_bandwidth_sample`
+    bandwidth_estimators: Arc<RwLock<HashMap<String, BandwidthEstimator>>>,
+
+    // Reserved internal track used by the heartbeat companion task (publishers only)
+    keepalive_track: Arc<RwLock<Option<TrackProducer>>>,
+
+    // Locally registered setup extensions, advertised to the peer over the reserved
+    // setup extensions track right after connecting; see `register_setup_extension`
+    setup_extensions: Arc<RwLock<std::collections::BTreeMap<u64, SetupExtensionEntry>>>,
+
+    // Session/track telemetry; see `MoqSession::metrics`
+    metrics: Arc<RwLock<MetricsRegistry>>,
 
     // Catalog management
     catalog: Arc<RwLock<Option<Catalog>>>,
@@ -128,9 +289,24 @@ pub struct MoqSession {
     // Internal broadcast channel for announcement events (for BroadcastSubscriptionManager)
     announcement_tx: broadcast::Sender<String>,
 
-    // Subscription management
+    // Subscription management. Wrapped in an `Arc` (rather than bare
+    // `BroadcastSubscriptionManager`) so `watch_auto_subscription` can hand out a
+    // reference without racing the manager's own `Drop` (which aborts its tasks).
     broadcast_subscription_manager:
-        Arc<RwLock<Option<crate::subscription_manager::BroadcastSubscriptionManager>>>,
+        Arc<RwLock<Option<Arc<crate::subscription_manager::BroadcastSubscriptionManager>>>>,
+
+    // Readiness primitives (turbo's OptionalWatch pattern): lets callers `await`
+    // connectivity and auto-subscription readiness instead of polling `is_connected`/
+    // `is_auto_subscription_active` and retrying on `Err`
+    connected_tx: watch::Sender<bool>,
+    // Outcome of the very first connection attempt only, set once from the same
+    // spots `SessionEvent::Connected`/`SessionEvent::Error` are sent for that
+    // attempt. Lets callers like `create_publisher` await a conclusive answer
+    // without reading from `event_rx`, which would steal events from whatever
+    // drains `next_event()` afterward.
+    initial_connect_tx: watch::Sender<Option<Result<(), String>>>,
+    auto_subscription_tx:
+        watch::Sender<Option<Arc<crate::subscription_manager::BroadcastSubscriptionManager>>>,
 
     // Shutdown signal
     shutdown_tx: watch::Sender<bool>,
@@ -143,7 +319,11 @@ pub struct MoqSession {
     broadcast_announced_callback: Arc<RwLock<Option<BroadcastAnnouncedCallback>>>,
     broadcast_cancelled_callback: Arc<RwLock<Option<BroadcastCancelledCallback>>>,
     connection_closed_callback: Arc<RwLock<Option<ConnectionClosedCallback>>>,
+    track_requested_callback: Arc<RwLock<Option<TrackRequestedCallback>>>,
+    renewal_failed_callback: Arc<RwLock<Option<RenewalFailedCallback>>>,
 
+    // Broadcast names currently bridged from an upstream session via `relay_broadcast`
+    relayed_broadcasts: Arc<RwLock<std::collections::HashSet<String>>>,
     // Catalog management is now handled by BroadcastSubscriptionManager
 }
 
@@ -152,10 +332,27 @@ struct SessionState {
     connected: bool,
     connection_attempts: usize,
     last_connection_time: Option<Instant>,
+    // Timestamp of the most recent keepalive write (publishers) or announcement
+    // (announce/unannounce) seen by the announcement monitors (subscribers; group
+    // deliveries do not update it), used by the heartbeat companion task to detect a
+    // half-open connection
+    last_activity: Instant,
+    // Clock-sync state: `rtt` is measured from the handshake round-trip performed in
+    // `establish_connection`; `time_delta_millis` is the offset applied to the local
+    // clock to approximate the server's. The relay this client connects to doesn't
+    // expose an authoritative clock-exchange protocol, so `time_delta_millis` stays 0
+    // until one becomes available - only RTT is genuinely measured for now.
+    rtt: Option<std::time::Duration>,
+    time_delta_millis: i64,
     current_session: Option<SessionHandle>,
     broadcast: Option<BroadcastHandle>,
     // Cache the broadcast consumer to avoid multiple calls to subscribe_broadcast
     broadcast_consumer_cache: Option<(String, BroadcastConsumer)>,
+    // Snapshot of currently-announced broadcast paths, kept in sync by
+    // `monitor_announcements`/`monitor_announcements_simple` so `announced_broadcasts`
+    // can answer "what's out there right now" without the caller having to have
+    // subscribed to announcements from the very start of the session
+    announced_broadcasts: std::collections::BTreeSet<String>,
 }
 
 #[derive(Clone)]
@@ -181,6 +378,34 @@ struct SessionHandle {
     origin_consumer: Option<OriginConsumer>,
 }
 
+/// Compute the delay to wait before reconnection attempt number `attempt` (1-based),
+/// or `None` if `strategy` says not to reconnect at all.
+fn compute_reconnect_delay(
+    strategy: &ReconnectStrategy,
+    attempt: usize,
+) -> Option<std::time::Duration> {
+    match strategy {
+        ReconnectStrategy::None => None,
+        ReconnectStrategy::Fixed { interval } => Some(*interval),
+        ReconnectStrategy::ExponentialBackoff {
+            initial,
+            max,
+            factor,
+            jitter,
+        } => {
+            let exponent = attempt.saturating_sub(1) as i32;
+            let scaled = initial.as_secs_f64() * factor.powi(exponent);
+            let capped = scaled.min(max.as_secs_f64());
+            let delay = if *jitter {
+                capped * rand::thread_rng().gen_range(0.9..=1.1)
+            } else {
+                capped
+            };
+            Some(std::time::Duration::from_secs_f64(delay.max(0.0)))
+        }
+    }
+}
+
 impl MoqSession {
     /// Create a new publisher session
     pub async fn publisher(config: SessionConfig, broadcast_name: String) -> Result<Self> {
@@ -192,6 +417,67 @@ impl MoqSession {
         Self::new(config, SessionType::Subscriber, broadcast_name).await
     }
 
+    /// Create a connected publisher/subscriber pair that exchange groups and frames
+    /// entirely in-process, without a relay or a `Session::connect` WebTransport
+    /// handshake.
+    ///
+    /// `moq-lite`'s `Broadcast`/`Track` producer-consumer pairs are already in-memory
+    /// constructs - `Session::connect` only exists to hand them to a remote peer over
+    /// QUIC. This skips that exchange and wires a single `Broadcast::produce()` pair
+    /// directly into both sessions' state, so the publisher's `write_frame`/
+    /// `close_group` are observed by the very `BroadcastConsumer` the subscriber reads
+    /// from through `subscribe_track_internal`. Useful for exercising publish/subscribe
+    /// code (the clock example, a `Consumer` worker loop, frame-processing callbacks)
+    /// deterministically in tests, without standing up a real relay.
+    ///
+    /// Both sessions come back already `is_connected()`; don't call [`Self::start`] on
+    /// either one - there's no real connection for it to (re)establish. Add tracks to
+    /// the publisher with [`Self::add_track_definition`] and call
+    /// [`Self::create_track_producers`] to materialize them, same as after `start`
+    /// would have.
+    pub async fn loopback_pair(broadcast_name: &str) -> Result<(Self, Self)> {
+        let url = url::Url::parse("https://loopback.invalid/")
+            .expect("hardcoded loopback placeholder URL is valid");
+
+        let publisher = Self::new(
+            SessionConfig::new(broadcast_name, url.clone()),
+            SessionType::Publisher,
+            broadcast_name.to_string(),
+        )
+        .await?;
+        let subscriber = Self::new(
+            SessionConfig::new(broadcast_name, url),
+            SessionType::Subscriber,
+            broadcast_name.to_string(),
+        )
+        .await?;
+
+        let (broadcast_producer, broadcast_consumer) = Broadcast::produce();
+
+        {
+            let mut state = publisher.state.write().await;
+            state.connected = true;
+            state.broadcast = Some(BroadcastHandle {
+                producer: Some(broadcast_producer),
+                consumer: None,
+            });
+        }
+        let _ = publisher.connected_tx.send(true);
+        let _ = publisher.initial_connect_tx.send(Some(Ok(())));
+        let _ = publisher.event_tx.send(SessionEvent::Connected);
+
+        {
+            let mut state = subscriber.state.write().await;
+            state.connected = true;
+            state.broadcast_consumer_cache = Some((broadcast_name.to_string(), broadcast_consumer));
+        }
+        let _ = subscriber.connected_tx.send(true);
+        let _ = subscriber.initial_connect_tx.send(Some(Ok(())));
+        let _ = subscriber.event_tx.send(SessionEvent::Connected);
+
+        Ok((publisher, subscriber))
+    }
+
     async fn new(
         config: SessionConfig,
         session_type: SessionType,
@@ -217,14 +503,24 @@ impl MoqSession {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
         let (announcement_tx, _) = broadcast::channel(100); // Buffer up to 100 announcements
+        let (connected_tx, _) = watch::channel(false);
+        let (initial_connect_tx, _) = watch::channel(None);
+        let (auto_subscription_tx, _): (
+            watch::Sender<Option<Arc<crate::subscription_manager::BroadcastSubscriptionManager>>>,
+            _,
+        ) = watch::channel(None);
 
         let state = Arc::new(RwLock::new(SessionState {
             connected: false,
             connection_attempts: 0,
             last_connection_time: None,
+            last_activity: Instant::now(),
+            rtt: None,
+            time_delta_millis: 0,
             current_session: None,
             broadcast: None,
             broadcast_consumer_cache: None,
+            announced_broadcasts: std::collections::BTreeSet::new(),
         }));
 
         let session = Self {
@@ -236,6 +532,11 @@ impl MoqSession {
             tracks: Arc::new(RwLock::new(HashMap::new())),
             current_groups: Arc::new(RwLock::new(HashMap::new())),
             sequence_numbers: Arc::new(RwLock::new(HashMap::new())),
+            current_group_sequences: Arc::new(RwLock::new(HashMap::new())),
+            bandwidth_estimators: Arc::new(RwLock::new(HashMap::new())),
+            keepalive_track: Arc::new(RwLock::new(None)),
+            setup_extensions: Arc::new(RwLock::new(std::collections::BTreeMap::new())),
+            metrics: Arc::new(RwLock::new(MetricsRegistry::new())),
             catalog: Arc::new(RwLock::new(None)),
             catalog_type: Arc::new(RwLock::new(CatalogType::None)),
             catalog_published: Arc::new(RwLock::new(false)),
@@ -244,19 +545,25 @@ impl MoqSession {
             event_rx: Arc::new(RwLock::new(Some(event_rx))),
             announcement_tx,
             broadcast_subscription_manager: Arc::new(RwLock::new(None)),
+            connected_tx,
+            initial_connect_tx,
+            auto_subscription_tx,
             shutdown_tx,
             shutdown_rx,
             log_callback: Arc::new(RwLock::new(None)),
             broadcast_announced_callback: Arc::new(RwLock::new(None)),
             broadcast_cancelled_callback: Arc::new(RwLock::new(None)),
             connection_closed_callback: Arc::new(RwLock::new(None)),
-
+            track_requested_callback: Arc::new(RwLock::new(None)),
+            renewal_failed_callback: Arc::new(RwLock::new(None)),
+            relayed_broadcasts: Arc::new(RwLock::new(std::collections::HashSet::new())),
         };
 
         Ok(session)
     }
 
-    /// Start the session and connect once (no reconnection logic)
+    /// Start the session, reconnecting according to `config.reconnect` whenever the
+    /// connection drops (unless the strategy is [`ReconnectStrategy::None`])
     pub async fn start(&self) -> Result<()> {
         session_log!(self, info, "Starting MoQ session: {:?}", self.session_type);
 
@@ -269,6 +576,9 @@ impl MoqSession {
         let mut shutdown_rx = self.shutdown_rx.clone();
         let announcement_tx = self.announcement_tx.clone();
         let session_clone = self.clone();
+        let metrics = self.metrics.clone();
+        let connected_tx = self.connected_tx.clone();
+        let initial_connect_tx = self.initial_connect_tx.clone();
 
         // Get callback references for announcements
         let broadcast_announced_cb = self.broadcast_announced_callback.clone();
@@ -276,143 +586,310 @@ impl MoqSession {
         let connection_closed_cb = self.connection_closed_callback.clone();
 
         tokio::spawn(async move {
-            // Check for shutdown signal before connecting
-            if *shutdown_rx.borrow() {
-                info!("Shutdown signal received before connection, stopping session");
-                return;
-            }
+            let mut attempt: usize = 0;
+            let mut is_first_connection = true;
+
+            // Records the first connection attempt's outcome only; later reconnects
+            // leave it untouched since `initial_connect_watch` callers only care about
+            // whether the session ever came up in the first place.
+            let mark_initial_connect = |outcome: Result<(), String>| {
+                if initial_connect_tx.borrow().is_none() {
+                    let _ = initial_connect_tx.send(Some(outcome));
+                }
+            };
 
-            let result = Self::establish_connection(
-                &config,
-                &client,
-                &session_type,
-                &broadcast_name,
-                state.clone(),
-                event_tx.clone(),
-                announcement_tx.clone(),
-            )
-            .await;
+            loop {
+                // Check for shutdown signal before connecting
+                if *shutdown_rx.borrow() {
+                    info!("Shutdown signal received before connection, stopping session");
+                    return;
+                }
 
-            match result {
-                Ok(session_handle) => {
-                    info!("Successfully established MoQ connection");
-
-                    // Update connection state
-                    {
-                        let mut state_guard = state.write().await;
-                        state_guard.connected = true;
-                        state_guard.connection_attempts = 1;
-                        state_guard.last_connection_time = Some(Instant::now());
-                        state_guard.current_session = Some(session_handle.clone());
-                    }
+                let result = Self::establish_connection(
+                    &config,
+                    &client,
+                    &session_type,
+                    &broadcast_name,
+                    state.clone(),
+                    event_tx.clone(),
+                    announcement_tx.clone(),
+                    metrics.clone(),
+                )
+                .await;
 
-                    // Create track producers for publisher sessions
-                    if matches!(session_type, SessionType::Publisher) {
-                        let session_for_tracks = session_clone.clone();
-                        if let Err(e) = session_for_tracks.create_track_producers().await {
-                            warn!("Failed to create track producers: {}", e);
-                            let _ = event_tx.send(SessionEvent::Error {
-                                error: format!("Failed to create track producers: {}", e),
-                            });
+                match result {
+                    Ok(session_handle) => {
+                        info!("Successfully established MoQ connection");
+                        attempt = 0;
+
+                        if is_first_connection {
+                            is_first_connection = false;
                         } else {
-                            info!("Successfully created track producers");
-                            let _ = event_tx.send(SessionEvent::Connected);
+                            metrics.write().await.record_reconnect();
                         }
-                    } else {
-                        // For subscribers, send Connected immediately and setup monitoring with callbacks
-                        let _ = event_tx.send(SessionEvent::Connected);
-
-                        // Setup announcement monitoring with callbacks for subscribers
-                        if let Some(origin_consumer) = &session_handle.origin_consumer {
-                            Self::monitor_announcements(
-                                origin_consumer.clone(),
-                                event_tx.clone(),
-                                announcement_tx.clone(),
-                                broadcast_announced_cb.clone(),
-                                broadcast_cancelled_cb.clone(),
-                            )
-                            .await;
+
+                        // Update connection state
+                        {
+                            let mut state_guard = state.write().await;
+                            state_guard.connected = true;
+                            state_guard.connection_attempts += 1;
+                            state_guard.last_connection_time = Some(Instant::now());
+                            state_guard.current_session = Some(session_handle.clone());
                         }
-                    }
+                        let _ = connected_tx.send(true);
+
+                        // Create track producers for publisher sessions, replaying the
+                        // catalog publish on every (re)connection
+                        if matches!(session_type, SessionType::Publisher) {
+                            *session_clone.catalog_published.write().await = false;
+
+                            let session_for_tracks = session_clone.clone();
+                            if let Err(e) = session_for_tracks.create_track_producers().await {
+                                warn!("Failed to create track producers: {}", e);
+                                let _ = event_tx.send(SessionEvent::Error {
+                                    error: format!("Failed to create track producers: {}", e),
+                                });
+                                mark_initial_connect(Err(format!(
+                                    "Failed to create track producers: {}",
+                                    e
+                                )));
+                            } else {
+                                info!("Successfully created track producers");
+                                let _ = event_tx.send(SessionEvent::Connected);
+                                mark_initial_connect(Ok(()));
+                            }
 
-                    // Auto-subscription is now handled by BroadcastSubscriptionManager
-                    // Users should call enable_auto_subscription() to set up automatic catalog and track management
+                            if let Err(e) = session_clone.ensure_keepalive_track().await {
+                                warn!("Failed to create keepalive track: {}", e);
+                            }
+
+                            if let Err(e) = session_clone.publish_setup_extensions().await {
+                                warn!("Failed to publish setup extensions: {}", e);
+                            }
+                        } else {
+                            // For subscribers, send Connected immediately and setup monitoring with callbacks
+                            let _ = event_tx.send(SessionEvent::Connected);
+                            mark_initial_connect(Ok(()));
+
+                            // Negotiate setup extensions in the background: it waits on a
+                            // frame from the peer, which shouldn't hold up Connected/
+                            // announcement monitoring above
+                            let negotiate_session = session_clone.clone();
+                            let negotiate_handle = session_handle.clone();
+                            let negotiate_event_tx = event_tx.clone();
+                            tokio::spawn(async move {
+                                negotiate_session
+                                    .negotiate_setup_extensions(
+                                        &negotiate_handle,
+                                        &negotiate_event_tx,
+                                    )
+                                    .await;
+                            });
+
+                            // Setup announcement monitoring with callbacks for subscribers
+                            if let Some(origin_consumer) = &session_handle.origin_consumer {
+                                Self::monitor_announcements(
+                                    origin_consumer.clone(),
+                                    event_tx.clone(),
+                                    announcement_tx.clone(),
+                                    broadcast_announced_cb.clone(),
+                                    broadcast_cancelled_cb.clone(),
+                                    state.clone(),
+                                    metrics.clone(),
+                                )
+                                .await;
+                            }
 
-                    // Wait for session to close or shutdown signal
-                    let disconnect_reason = tokio::select! {
-                        result = session_handle.session.closed() => {
-                            match result {
-                                Ok(()) => {
-                                    info!("Session closed normally");
-                                    "Session closed normally".to_string()
+                            // BroadcastSubscriptionManager keeps its own retry loop
+                            // running against this session's shared state, so it
+                            // resubscribes to its tracked broadcasts and tracks on
+                            // its own as soon as `state.current_session` is set above
+                        }
+
+                        // Spawn a heartbeat companion task for this connection: writes
+                        // keepalive frames for publishers and force-closes the session
+                        // if no activity has been observed for `heartbeat_timeout`
+                        let heartbeat_state = state.clone();
+                        let heartbeat_config = config.clone();
+                        let heartbeat_session_type = session_type.clone();
+                        let heartbeat_session = session_handle.session.clone();
+                        let heartbeat_moq_session = session_clone.clone();
+                        let heartbeat_handle = tokio::spawn(async move {
+                            let mut interval =
+                                tokio::time::interval(heartbeat_config.heartbeat_interval);
+                            interval.tick().await; // first tick fires immediately, skip it
+
+                            loop {
+                                interval.tick().await;
+
+                                if matches!(heartbeat_session_type, SessionType::Publisher) {
+                                    if let Err(e) =
+                                        heartbeat_moq_session.write_keepalive_frame().await
+                                    {
+                                        warn!("Failed to write keepalive frame: {}", e);
+                                    }
                                 }
-                                Err(e) => {
-                                    error!("Session closed with error: {}", e);
-                                    format!("Session error: {}", e)
+
+                                let elapsed = heartbeat_state.read().await.last_activity.elapsed();
+                                if elapsed > heartbeat_config.heartbeat_timeout {
+                                    warn!(
+                                        "No session activity for {:?} (timeout {:?}), forcing reconnect",
+                                        elapsed, heartbeat_config.heartbeat_timeout
+                                    );
+                                    (*heartbeat_session).clone().close(moq_lite::Error::App(1));
+                                    break;
                                 }
                             }
-                        }
-                        _ = shutdown_rx.changed() => {
-                            if *shutdown_rx.borrow() {
-                                info!("Shutdown requested, closing session");
-                                "Shutdown requested".to_string()
-                            } else {
-                                "Unknown shutdown reason".to_string()
+                        });
+
+                        // Optionally spawn a companion task that periodically emits a
+                        // SessionEvent::Metrics snapshot, so FFI consumers can render
+                        // live dashboards without polling `MoqSession::metrics`
+                        let metrics_handle = config.metrics_interval.map(|metrics_interval| {
+                            let metrics_registry = metrics.clone();
+                            let metrics_event_tx = event_tx.clone();
+                            tokio::spawn(async move {
+                                let mut interval = tokio::time::interval(metrics_interval);
+                                interval.tick().await; // first tick fires immediately, skip it
+
+                                loop {
+                                    interval.tick().await;
+                                    let snapshot = metrics_registry.read().await.snapshot();
+                                    let _ = metrics_event_tx.send(SessionEvent::Metrics(snapshot));
+                                }
+                            })
+                        });
+
+                        // Wait for session to close or shutdown signal
+                        let disconnect_reason = tokio::select! {
+                            result = session_handle.session.closed() => {
+                                match result {
+                                    Ok(()) => {
+                                        info!("Session closed normally");
+                                        "Session closed normally".to_string()
+                                    }
+                                    Err(e) => {
+                                        error!("Session closed with error: {}", e);
+                                        format!("Session error: {}", e)
+                                    }
+                                }
+                            }
+                            _ = shutdown_rx.changed() => {
+                                if *shutdown_rx.borrow() {
+                                    info!("Shutdown requested, closing session");
+                                    "Shutdown requested".to_string()
+                                } else {
+                                    "Unknown shutdown reason".to_string()
+                                }
                             }
+                        };
+                        let is_shutdown = *shutdown_rx.borrow();
+
+                        // Stop the heartbeat and metrics companion tasks now that this
+                        // connection cycle is over
+                        heartbeat_handle.abort();
+                        if let Some(handle) = metrics_handle {
+                            handle.abort();
                         }
-                    };
 
-                    // Call connection closed callback if set
-                    let callback_guard = connection_closed_cb.read().await;
-                    if let Some(callback) = callback_guard.as_ref() {
-                        callback(&disconnect_reason);
-                    }
-                    drop(callback_guard);
-
-                    // Send disconnected event
-                    let _ = event_tx.send(SessionEvent::Disconnected {
-                        reason: disconnect_reason,
-                    });
-
-                    // Mark as disconnected and clean up session state
-                    {
-                        let mut state_guard = state.write().await;
-                        state_guard.connected = false;
-                        state_guard.current_session = None;
-                        state_guard.broadcast = None;
-                        state_guard.broadcast_consumer_cache = None;
-                    }
+                        // Call connection closed callback if set
+                        let callback_guard = connection_closed_cb.read().await;
+                        if let Some(callback) = callback_guard.as_ref() {
+                            callback(&disconnect_reason);
+                        }
+                        drop(callback_guard);
 
-                    // Clear session state
-                    session_clone.current_groups.write().await.clear();
-                    *session_clone.catalog_published.write().await = false;
+                        // Send disconnected event
+                        let _ = event_tx.send(SessionEvent::Disconnected {
+                            reason: disconnect_reason,
+                        });
 
-                    info!("Session closed and cleaned up");
-                }
-                Err(e) => {
-                    let mut state_guard = state.write().await;
-                    state_guard.connected = false;
-                    state_guard.connection_attempts = 1;
-                    state_guard.current_session = None;
-
-                    error!("Failed to establish connection: {}", e);
-
-                    // Call connection closed callback if set
-                    let callback_guard = connection_closed_cb.read().await;
-                    if let Some(callback) = callback_guard.as_ref() {
-                        callback(&format!("Connection failed: {}", e));
+                        // Mark as disconnected and clean up session state
+                        {
+                            let mut state_guard = state.write().await;
+                            state_guard.connected = false;
+                            state_guard.current_session = None;
+                            state_guard.broadcast = None;
+                            state_guard.broadcast_consumer_cache = None;
+                            state_guard.announced_broadcasts.clear();
+                        }
+                        let _ = connected_tx.send(false);
+
+                        // Clear session state
+                        session_clone.current_groups.write().await.clear();
+                        *session_clone.catalog_published.write().await = false;
+
+                        if is_shutdown {
+                            info!("Session closed and cleaned up");
+                            return;
+                        }
+
+                        attempt += 1;
+                        match compute_reconnect_delay(&config.reconnect, attempt) {
+                            Some(delay) => {
+                                warn!(
+                                    "Connection lost, reconnecting in {:?} (attempt {})",
+                                    delay, attempt
+                                );
+                                tokio::select! {
+                                    _ = tokio::time::sleep(delay) => {}
+                                    _ = shutdown_rx.changed() => {}
+                                }
+                                if *shutdown_rx.borrow() {
+                                    info!("Shutdown requested during reconnect backoff");
+                                    return;
+                                }
+                            }
+                            None => {
+                                info!("Reconnection disabled, session management task terminated");
+                                return;
+                            }
+                        }
                     }
-                    drop(callback_guard);
+                    Err(e) => {
+                        {
+                            let mut state_guard = state.write().await;
+                            state_guard.connected = false;
+                            state_guard.connection_attempts += 1;
+                            state_guard.current_session = None;
+                        }
+                        let _ = connected_tx.send(false);
 
-                    let _ = event_tx.send(SessionEvent::Error {
-                        error: format!("Connection failed: {}", e),
-                    });
+                        error!("Failed to establish connection: {}", e);
 
-                    drop(state_guard);
+                        // Call connection closed callback if set
+                        let callback_guard = connection_closed_cb.read().await;
+                        if let Some(callback) = callback_guard.as_ref() {
+                            callback(&format!("Connection failed: {}", e));
+                        }
+                        drop(callback_guard);
+
+                        let _ = event_tx.send(SessionEvent::Error {
+                            error: format!("Connection failed: {}", e),
+                        });
+                        mark_initial_connect(Err(format!("Connection failed: {}", e)));
+
+                        attempt += 1;
+                        match compute_reconnect_delay(&config.reconnect, attempt) {
+                            Some(delay) => {
+                                warn!("Retrying connection in {:?} (attempt {})", delay, attempt);
+                                tokio::select! {
+                                    _ = tokio::time::sleep(delay) => {}
+                                    _ = shutdown_rx.changed() => {}
+                                }
+                                if *shutdown_rx.borrow() {
+                                    info!("Shutdown requested during reconnect backoff");
+                                    return;
+                                }
+                            }
+                            None => {
+                                info!("Reconnection disabled, session management task terminated");
+                                return;
+                            }
+                        }
+                    }
                 }
             }
-
-            info!("Session management task terminated");
         });
 
         Ok(())
@@ -426,14 +903,19 @@ impl MoqSession {
         state: Arc<RwLock<SessionState>>,
         event_tx: mpsc::UnboundedSender<SessionEvent>,
         announcement_tx: broadcast::Sender<String>,
+        metrics: Arc<RwLock<MetricsRegistry>>,
     ) -> Result<SessionHandle> {
         debug!("Establishing connection to: {}", config.connection.url);
 
-        // Establish WebTransport/QUIC connection
+        // Establish WebTransport/QUIC connection. The handshake is the one real
+        // round-trip to the relay we have available, so we use it as our clock-sync
+        // probe's RTT sample (see the note on `SessionState::time_delta_millis`)
+        let connect_started = Instant::now();
         let connection = client
             .connect(config.connection.url.clone())
             .await
             .context("Failed to connect to relay")?;
+        let handshake_rtt = connect_started.elapsed();
 
         // Set up origin for publish/subscribe operations
         let origin = Origin::produce();
@@ -481,6 +963,22 @@ impl MoqSession {
             state_guard.broadcast = Some(broadcast_handle);
         }
 
+        // Record the clock-sync RTT sample, discarding it if it exceeds the
+        // configured threshold (e.g. a momentary network spike during connect)
+        {
+            let mut state_guard = state.write().await;
+            if handshake_rtt <= config.max_clock_sync_rtt {
+                state_guard.rtt = Some(handshake_rtt);
+                metrics.write().await.record_rtt(handshake_rtt);
+            } else {
+                warn!(
+                    "Discarding clock-sync sample: handshake rtt {:?} exceeds threshold {:?}",
+                    handshake_rtt, config.max_clock_sync_rtt
+                );
+                state_guard.rtt = None;
+            }
+        }
+
         // For subscribers, start monitoring announcements
         if let SessionType::Subscriber = session_type {
             if let Some(origin_consumer) = &session_handle.origin_consumer {
@@ -490,6 +988,8 @@ impl MoqSession {
                     origin_consumer.clone(),
                     event_tx.clone(),
                     announcement_tx.clone(),
+                    state.clone(),
+                    metrics.clone(),
                 )
                 .await;
             }
@@ -512,12 +1012,22 @@ impl MoqSession {
         announcement_tx: broadcast::Sender<String>,
         broadcast_announced_cb: Arc<RwLock<Option<BroadcastAnnouncedCallback>>>,
         broadcast_cancelled_cb: Arc<RwLock<Option<BroadcastCancelledCallback>>>,
+        state: Arc<RwLock<SessionState>>,
+        metrics: Arc<RwLock<MetricsRegistry>>,
     ) {
         tokio::spawn(async move {
             while let Some((path, broadcast)) = origin_consumer.announced().await {
+                state.write().await.last_activity = Instant::now();
+                metrics.write().await.record_announcement();
+
                 match broadcast {
                     Some(_) => {
                         debug!("Broadcast announced: {}", path);
+                        state
+                            .write()
+                            .await
+                            .announced_broadcasts
+                            .insert(path.to_string());
                         let _ = event_tx.send(SessionEvent::BroadcastAnnounced {
                             path: path.to_string(),
                         });
@@ -532,6 +1042,7 @@ impl MoqSession {
                     }
                     None => {
                         debug!("Broadcast unannounced: {}", path);
+                        state.write().await.announced_broadcasts.remove(path.as_ref());
                         let _ = event_tx.send(SessionEvent::BroadcastUnannounced {
                             path: path.to_string(),
                         });
@@ -554,12 +1065,22 @@ impl MoqSession {
         mut origin_consumer: OriginConsumer,
         event_tx: mpsc::UnboundedSender<SessionEvent>,
         announcement_tx: broadcast::Sender<String>,
+        state: Arc<RwLock<SessionState>>,
+        metrics: Arc<RwLock<MetricsRegistry>>,
     ) {
         tokio::spawn(async move {
             while let Some((path, broadcast)) = origin_consumer.announced().await {
+                state.write().await.last_activity = Instant::now();
+                metrics.write().await.record_announcement();
+
                 match broadcast {
                     Some(_) => {
                         debug!("Broadcast announced: {}", path);
+                        state
+                            .write()
+                            .await
+                            .announced_broadcasts
+                            .insert(path.to_string());
                         let _ = event_tx.send(SessionEvent::BroadcastAnnounced {
                             path: path.to_string(),
                         });
@@ -568,6 +1089,7 @@ impl MoqSession {
                     }
                     None => {
                         debug!("Broadcast unannounced: {}", path);
+                        state.write().await.announced_broadcasts.remove(path.as_ref());
                         let _ = event_tx.send(SessionEvent::BroadcastUnannounced {
                             path: path.to_string(),
                         });
@@ -597,6 +1119,40 @@ impl MoqSession {
         self.state.read().await.connected
     }
 
+    /// Raw connection-state watch receiver, for callers (like
+    /// [`ResilientTrackConsumer`](crate::subscription::ResilientTrackConsumer)) that
+    /// want to `changed().await` on connect/disconnect transitions directly instead of
+    /// polling [`Self::is_connected`] on a fixed interval.
+    pub fn connection_watch(&self) -> watch::Receiver<bool> {
+        self.connected_tx.subscribe()
+    }
+
+    /// Watches the outcome of this session's very first connection attempt: `None`
+    /// until it concludes, then `Some(Ok(()))` or `Some(Err(reason))` exactly once.
+    /// Set from the same spots `SessionEvent::Connected`/`SessionEvent::Error` are
+    /// sent for that attempt, but via its own channel instead of the shared event
+    /// queue, so callers like [`crate::create_publisher`] can wait for a conclusive
+    /// answer without stealing events from whatever drains [`Self::next_event`]
+    /// afterward.
+    pub fn initial_connect_watch(&self) -> watch::Receiver<Option<Result<(), String>>> {
+        self.initial_connect_tx.subscribe()
+    }
+
+    /// Wait until the session is connected, resolving immediately if it already is.
+    /// Suspends across reconnects instead of requiring callers to poll
+    /// [`Self::is_connected`] and retry on `Err`.
+    pub async fn await_connected(&self) {
+        let mut rx = self.connected_tx.subscribe();
+        if *rx.borrow() {
+            return;
+        }
+        while !*rx.borrow() {
+            if rx.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
     /// Get connection statistics
     pub async fn connection_info(&self) -> ConnectionInfo {
         let state = self.state.read().await;
@@ -604,9 +1160,27 @@ impl MoqSession {
             connected: state.connected,
             connection_attempts: state.connection_attempts,
             last_connection_time: state.last_connection_time,
+            last_activity: state.last_activity,
+            rtt: state.rtt,
         }
     }
 
+    /// Best-effort estimate of the server's wall-clock time, in milliseconds since
+    /// the Unix epoch, correcting the local clock by the measured `time_delta`. See
+    /// the note on `SessionState::time_delta_millis` for the current limitation.
+    pub async fn server_time(&self) -> i64 {
+        let local_millis = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64;
+        local_millis + self.state.read().await.time_delta_millis
+    }
+
+    /// Get a point-in-time snapshot of session/track telemetry
+    pub async fn metrics(&self) -> SessionMetrics {
+        self.metrics.read().await.snapshot()
+    }
+
     /// Stop the session and close all connections
     pub async fn stop(&self) -> Result<()> {
         info!("Stopping MoQ session");
@@ -639,6 +1213,35 @@ impl MoqSession {
         *self.connection_closed_callback.write().await = Some(callback);
     }
 
+    /// Set callback consulted by [`Self::handle_track_requested`] for tracks requested
+    /// on demand by subscribers (see [`TrackRequestedCallback`])
+    pub async fn set_track_requested_callback(&self, callback: TrackRequestedCallback) {
+        *self.track_requested_callback.write().await = Some(callback);
+    }
+
+    /// Set callback invoked when a leased subscription's renewal task gives up; see
+    /// [`RenewalFailedCallback`]
+    pub async fn set_renewal_failed_callback(&self, callback: RenewalFailedCallback) {
+        *self.renewal_failed_callback.write().await = Some(callback);
+    }
+
+    /// Register a setup extension this session supports, so it gets advertised to the
+    /// peer over the reserved [`SETUP_EXTENSIONS_TRACK_NAME`] control track as soon as
+    /// the connection is established (or re-established after a reconnect).
+    ///
+    /// `id` identifies the extension (application-defined, e.g. a delivery-timeout or
+    /// authorization-token extension), `required` says whether a peer that doesn't
+    /// implement it should fail the session, and `payload` is the opaque bytes to hand
+    /// the peer for it - this crate only ever transports `payload`, it never interprets
+    /// it. Call this before [`Self::start`] so the first connection already advertises
+    /// it; calling it afterwards takes effect from the next reconnect onward.
+    pub async fn register_setup_extension(&self, id: u64, required: bool, payload: Vec<u8>) {
+        self.setup_extensions
+            .write()
+            .await
+            .insert(id, SetupExtensionEntry { required, payload });
+    }
+
     // clear_catalog_cache method removed - catalog caching is now handled by BroadcastSubscriptionManager
 
     /// Create a BroadcastSubscriptionManager for a specific broadcast
@@ -649,11 +1252,37 @@ impl MoqSession {
         catalog_type: CatalogType,
         requested_tracks: Vec<TrackDefinition>,
     ) -> Result<crate::subscription_manager::BroadcastSubscriptionManager> {
-        crate::subscription_manager::BroadcastSubscriptionManager::new(
+        self.create_subscription_manager_with_options(
+            broadcast_name,
+            catalog_type,
+            requested_tracks,
+            SubscriptionOptions::default(),
+            HashMap::new(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::create_subscription_manager`], but with full
+    /// [`SubscriptionOptions`] control (start position, durability, per-track priority,
+    /// sharing mode) and a set of durable cursors to resume from - pass the result of a
+    /// prior [`crate::subscription_manager::BroadcastSubscriptionManager::get_cursors`]
+    /// call to pick up where an earlier session left off.
+    pub async fn create_subscription_manager_with_options(
+        &self,
+        broadcast_name: String,
+        catalog_type: CatalogType,
+        requested_tracks: Vec<TrackDefinition>,
+        options: SubscriptionOptions,
+        initial_cursors: HashMap<String, u64>,
+    ) -> Result<crate::subscription_manager::BroadcastSubscriptionManager> {
+        crate::subscription_manager::BroadcastSubscriptionManager::with_options(
             self.clone(),
             broadcast_name,
             catalog_type,
             requested_tracks,
+            crate::subscription_manager::RetryConfig::default(),
+            options,
+            initial_cursors,
         )
         .await
     }
@@ -666,25 +1295,110 @@ impl MoqSession {
         broadcast_name: String,
         catalog_type: CatalogType,
         requested_tracks: Vec<TrackDefinition>,
+    ) -> Result<()> {
+        self.enable_auto_subscription_with_options(
+            broadcast_name,
+            catalog_type,
+            requested_tracks,
+            SubscriptionOptions::default(),
+            HashMap::new(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::enable_auto_subscription`], but with full [`SubscriptionOptions`]
+    /// control and a set of durable cursors to seed on startup. If a manager is already
+    /// enabled for the same `broadcast_name`, the call either shares it (returns `Ok`
+    /// without creating a second manager) or is rejected, depending on
+    /// [`SubscriptionOptions::mode`] - see
+    /// [`crate::subscription_manager::SubscriptionMode`].
+    pub async fn enable_auto_subscription_with_options(
+        &self,
+        broadcast_name: String,
+        catalog_type: CatalogType,
+        requested_tracks: Vec<TrackDefinition>,
+        options: SubscriptionOptions,
+        initial_cursors: HashMap<String, u64>,
     ) -> Result<()> {
         // Check if already enabled
         {
             let manager_guard = self.broadcast_subscription_manager.read().await;
-            if manager_guard.is_some() {
-                info!("Automatic subscription management already enabled - ignoring duplicate call");
-                return Ok(());
+            if let Some(existing) = manager_guard.as_ref() {
+                if existing.broadcast_name() != broadcast_name {
+                    info!("Automatic subscription management already enabled for a different broadcast - ignoring duplicate call");
+                    return Ok(());
+                }
+                if existing.mode() == crate::subscription_manager::SubscriptionMode::Shared
+                    && options.mode == crate::subscription_manager::SubscriptionMode::Shared
+                {
+                    info!(
+                        "Sharing existing auto-subscription for broadcast: {}",
+                        broadcast_name
+                    );
+                    return Ok(());
+                }
+                return Err(anyhow::anyhow!(
+                    "Auto-subscription for broadcast '{}' is already active in Exclusive mode",
+                    broadcast_name
+                ));
             }
         }
 
         let manager = self
-            .create_subscription_manager(broadcast_name, catalog_type, requested_tracks)
+            .create_subscription_manager_with_options(
+                broadcast_name,
+                catalog_type,
+                requested_tracks,
+                options,
+                initial_cursors,
+            )
             .await?;
 
-        *self.broadcast_subscription_manager.write().await = Some(manager);
+        let manager = Arc::new(manager);
+        *self.broadcast_subscription_manager.write().await = Some(manager.clone());
+        let _ = self.auto_subscription_tx.send(Some(manager));
         info!("Enabled automatic subscription management");
         Ok(())
     }
 
+    /// Wait until `enable_auto_subscription` (or its `_with_options` variant) has
+    /// created a subscription manager, resolving immediately if one already exists.
+    /// Lets callers suspend here instead of polling [`Self::is_auto_subscription_active`].
+    pub async fn watch_auto_subscription(
+        &self,
+    ) -> Arc<crate::subscription_manager::BroadcastSubscriptionManager> {
+        let mut rx = self.auto_subscription_tx.subscribe();
+        loop {
+            if let Some(manager) = rx.borrow().clone() {
+                return manager;
+            }
+            if rx.changed().await.is_err() {
+                // Sender was dropped alongside the session; park forever rather than
+                // returning a fabricated manager.
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// Wait for the auto-subscription catalog to become available, resolving once
+    /// `enable_auto_subscription` has run and its manager has received a catalog.
+    pub async fn await_catalog(&self) -> Catalog {
+        let manager = self.watch_auto_subscription().await;
+        manager.await_catalog().await
+    }
+
+    /// Get current durable cursors (last fully-received group sequence per track) from
+    /// the internal subscription manager. Only works if `enable_auto_subscription` (or
+    /// its `_with_options` variant) was called first; empty if it wasn't, or if
+    /// [`SubscriptionOptions::durable`] wasn't set.
+    pub async fn get_auto_subscription_cursors(&self) -> HashMap<String, u64> {
+        if let Some(manager) = self.broadcast_subscription_manager.read().await.as_ref() {
+            manager.get_cursors().await
+        } else {
+            HashMap::new()
+        }
+    }
+
     /// Set data callback for the internal subscription manager
     /// Only works if enable_auto_subscription was called first
     pub async fn set_auto_subscription_data_callback<F>(&self, callback: F) -> Result<()>
@@ -709,6 +1423,34 @@ impl MoqSession {
         }
     }
 
+    /// Typed view of every track the current auto-subscription catalog describes -
+    /// role, codec, resolution, framerate, sample rate - so callers can pick which
+    /// tracks to subscribe to without guessing names. Empty until a catalog has
+    /// arrived; see [`Self::await_catalog`] to wait for one instead of polling this.
+    pub async fn get_available_tracks(&self) -> Vec<crate::catalog::CatalogTrackDescriptor> {
+        match self.get_auto_subscription_catalog().await {
+            Some(catalog) => catalog.describe_tracks(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Snapshot of broadcast paths this session has seen announced (and not since
+    /// unannounced) whose path starts with `prefix` - pass `""` for every known
+    /// broadcast. Reflects whatever's accumulated since this session last connected;
+    /// it isn't backfilled from the relay's full announcement history, since
+    /// `OriginConsumer::announced` only yields announcements going forward from
+    /// `consume_only`.
+    pub async fn get_announced_broadcasts(&self, prefix: &str) -> Vec<String> {
+        self.state
+            .read()
+            .await
+            .announced_broadcasts
+            .iter()
+            .filter(|path| path.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
     /// Get active tracks from the internal subscription manager
     /// Only works if enable_auto_subscription was called first
     pub async fn get_auto_subscription_active_tracks(&self) -> Vec<String> {
@@ -731,6 +1473,7 @@ impl MoqSession {
     /// Disable automatic subscription management and stop all subscriptions
     pub async fn disable_auto_subscription(&self) {
         if let Some(manager) = self.broadcast_subscription_manager.write().await.take() {
+            let _ = self.auto_subscription_tx.send(None);
             manager.stop().await;
             info!("Disabled automatic subscription management");
         }
@@ -742,6 +1485,8 @@ pub struct ConnectionInfo {
     pub connected: bool,
     pub connection_attempts: usize,
     pub last_connection_time: Option<Instant>,
+    pub last_activity: Instant,
+    pub rtt: Option<std::time::Duration>,
 }
 
 /// Publisher-specific functionality
@@ -818,6 +1563,47 @@ impl MoqSession {
         Ok(())
     }
 
+    /// Enables delay-based congestion control (see [`BandwidthEstimator`]) for
+    /// `track_name`, seeded with `initial_bitrate_bps`. Safe to call again to reset it
+    /// (e.g. after a rendition switch that invalidates the current estimate).
+    pub async fn enable_bandwidth_estimation(&self, track_name: &str, initial_bitrate_bps: f64) {
+        self.bandwidth_estimators.write().await.insert(
+            track_name.to_string(),
+            BandwidthEstimator::new(initial_bitrate_bps),
+        );
+    }
+
+    /// Feeds one group's id/send/arrival timestamps into `track_name`'s bandwidth
+    /// estimator, emitting [`SessionEvent::BandwidthEstimate`] once its slope is
+    /// trustworthy (see [`BandwidthEstimator::on_group_delivered`]). A no-op if
+    /// [`Self::enable_bandwidth_estimation`] hasn't been called for this track.
+    pub async fn record_bandwidth_sample(&self, track_name: &str, sample: DeliverySample) {
+        let estimate = self
+            .bandwidth_estimators
+            .write()
+            .await
+            .get_mut(track_name)
+            .and_then(|estimator| estimator.on_group_delivered(sample));
+
+        if let Some((target_bitrate_bps, state)) = estimate {
+            let _ = self.event_tx.send(SessionEvent::BandwidthEstimate {
+                track_name: track_name.to_string(),
+                target_bitrate_bps,
+                state,
+            });
+        }
+    }
+
+    /// Current target bitrate from `track_name`'s bandwidth estimator, if
+    /// [`Self::enable_bandwidth_estimation`] has been called for it.
+    pub async fn target_bitrate_bps(&self, track_name: &str) -> Option<f64> {
+        self.bandwidth_estimators
+            .read()
+            .await
+            .get(track_name)
+            .map(|estimator| estimator.target_bitrate_bps())
+    }
+
     /// Set catalog type for subscriber
     pub fn set_catalog_type(&mut self, catalog_type: CatalogType) -> Result<()> {
         tokio::task::block_in_place(|| {
@@ -887,6 +1673,8 @@ impl MoqSession {
             name: track.name.clone(),
             priority: track.priority.into(),
             track_type: crate::catalog::TrackType::Data, // Default to data
+            codec: None,
+            group_ttl_ms: None,
         };
         self.add_track_definition(track_def)
     }
@@ -914,17 +1702,22 @@ impl MoqSession {
         // Close any existing group for this track
         self.close_group(track_name).await?;
 
-        // Get track producer
-        let mut track_producer = {
+        // Get track producer and its configured group TTL, if any
+        let (mut track_producer, group_ttl_ms) = {
             let tracks = self.tracks.read().await;
             let track_handle = tracks
                 .get(track_name)
                 .ok_or_else(|| WrapperError::TrackNotFound(track_name.to_string()))?;
-            track_handle
+            let producer = track_handle
                 .producer
                 .as_ref()
                 .ok_or_else(|| WrapperError::Session("Track producer not available".to_string()))?
-                .clone()
+                .clone();
+            let group_ttl_ms = track_handle
+                .track_definition
+                .as_ref()
+                .and_then(|def| def.group_ttl_ms);
+            (producer, group_ttl_ms)
         };
 
         // Get and increment sequence number
@@ -948,8 +1741,46 @@ impl MoqSession {
             .write()
             .await
             .insert(track_name.to_string(), group);
+        self.current_group_sequences
+            .write()
+            .await
+            .insert(track_name.to_string(), sequence);
+
+        self.metrics.write().await.record_group_opened(track_name);
 
         debug!("Started group {} for track {}", sequence, track_name);
+
+        // `moq-lite`'s `create_group` in this tree has no expiry parameter of its
+        // own, so a track's `group_ttl_ms` (see `TrackDefinition::group_ttl_ms`) is
+        // enforced here: close this group after the TTL elapses, unless a newer
+        // group has already replaced it for this track.
+        if let Some(ttl_ms) = group_ttl_ms {
+            let session = self.clone();
+            let track_name = track_name.to_string();
+            tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(ttl_ms)).await;
+
+                let is_still_current = session
+                    .current_group_sequences
+                    .read()
+                    .await
+                    .get(&track_name)
+                    == Some(&sequence);
+                if is_still_current {
+                    debug!(
+                        "Group {} TTL elapsed for track {}, closing",
+                        sequence, track_name
+                    );
+                    if let Err(e) = session.close_group(&track_name).await {
+                        warn!(
+                            "Failed to close expired group for track {}: {}",
+                            track_name, e
+                        );
+                    }
+                }
+            });
+        }
+
         Ok(())
     }
 
@@ -977,6 +1808,7 @@ impl MoqSession {
             }
         }
 
+        let frame_len = data.len();
         let mut groups = self.current_groups.write().await;
         let group = groups.get_mut(track_name).ok_or_else(|| {
             WrapperError::Session(format!(
@@ -986,6 +1818,13 @@ impl MoqSession {
         })?;
 
         group.write_frame(data);
+        drop(groups);
+
+        self.metrics
+            .write()
+            .await
+            .record_frame_sent(track_name, frame_len);
+
         Ok(())
     }
 
@@ -1024,6 +1863,8 @@ impl MoqSession {
         let mut groups = self.current_groups.write().await;
         if let Some(group) = groups.remove(track_name) {
             group.close();
+            drop(groups);
+            self.metrics.write().await.record_group_closed(track_name);
             debug!("Closed group for track {}", track_name);
         }
         Ok(())
@@ -1034,7 +1875,12 @@ impl MoqSession {
         self.tracks.read().await.keys().cloned().collect()
     }
 
-    /// Simplified publish data function that handles group creation internally  
+    /// Name of the broadcast this session publishes or subscribes to
+    pub fn broadcast_name(&self) -> &str {
+        &self.broadcast_name
+    }
+
+    /// Simplified publish data function that handles group creation internally
     pub async fn publish_data(&self, track_name: &str, data: Vec<u8>) -> Result<(), WrapperError> {
         if !matches!(self.session_type, SessionType::Publisher) {
             return Err(WrapperError::Session("Not a publisher session".to_string()));
@@ -1046,6 +1892,57 @@ impl MoqSession {
             .map_err(|e| WrapperError::Session(format!("Failed to publish data: {}", e)))
     }
 
+    /// Publish a fragmented-MP4 (CMAF) file: parse its `moov` init segment to register a
+    /// catalog and one track per `trak`, then spawn a background task that streams each
+    /// `moof`+`mdat` pair as a group, paced to the fragment's decode timestamp so playback
+    /// proceeds in real time. The task stops on EOF or when [`Self::close_session`] fires.
+    pub async fn publish_file(&mut self, path: impl Into<std::path::PathBuf>) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Publisher) {
+            return Err(WrapperError::Session("Not a publisher session".to_string()).into());
+        }
+
+        let source = crate::source::File::new(path.into());
+        let parsed = source.configure(self, CatalogType::Hang).await?;
+
+        let session = self.clone();
+        let shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = source.run(&session, parsed, shutdown).await {
+                warn!("File source stopped: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Publish a fragmented-MP4 (CMAF) byte stream read from `reader` (e.g.
+    /// `tokio::io::stdin()`) - see [`crate::source::Stdin`]. Unlike
+    /// [`Self::publish_file`], the whole stream is buffered to EOF before anything is
+    /// published, so this suits a finite capture piped in over stdin rather than an
+    /// unbounded live feed.
+    pub async fn publish_stdin(
+        &mut self,
+        reader: impl tokio::io::AsyncRead + Unpin + Send + 'static,
+    ) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Publisher) {
+            return Err(WrapperError::Session("Not a publisher session".to_string()).into());
+        }
+
+        let source = crate::source::Stdin::new();
+        let mut session = self.clone();
+        let shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = source
+                .run(&mut session, CatalogType::Hang, reader, shutdown)
+                .await
+            {
+                warn!("Stdin source stopped: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
     /// Publish a broadcast (only available for publisher sessions)
     pub async fn publish_broadcast(&self, broadcast: BroadcastConsumer) -> Result<()> {
         let state = self.state.read().await;
@@ -1076,6 +1973,117 @@ impl MoqSession {
         }
     }
 
+    /// Relay an upstream broadcast through this (publisher) session: subscribes to
+    /// `broadcast_name` on `upstream` (a subscriber session) and re-publishes the
+    /// resulting [`BroadcastConsumer`] via [`Self::publish_broadcast`]. Since
+    /// `BroadcastConsumer`/`BroadcastProducer` are already a lazy, per-track pull path
+    /// in `moq_lite`, this wires the two sessions together without buffering whole
+    /// groups - tracks, groups, and frames flow straight from upstream to downstream
+    /// subscribers.
+    ///
+    /// Registers `upstream`'s broadcast-cancelled and connection-closed callbacks (see
+    /// [`Self::set_broadcast_cancelled_callback`]/[`Self::set_connection_closed_callback`],
+    /// overwriting any previously-set ones) to drop this relay's bookkeeping once the
+    /// upstream broadcast goes away. There's no "unpublish" primitive on `OriginProducer`
+    /// in this wrapper, so teardown here only clears [`Self::active_relays`] bookkeeping;
+    /// it can't force moq-lite to stop serving the broadcast to downstream subscribers
+    /// earlier than upstream naturally stopping.
+    pub async fn relay_broadcast(&self, upstream: &MoqSession, broadcast_name: &str) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Publisher) {
+            return Err(WrapperError::Session("Not a publisher session".to_string()).into());
+        }
+        if !matches!(upstream.session_type, SessionType::Subscriber) {
+            return Err(
+                WrapperError::Session("Upstream is not a subscriber session".to_string()).into(),
+            );
+        }
+
+        let consumer = upstream.subscribe_broadcast(broadcast_name).await?;
+        self.publish_broadcast(consumer).await?;
+
+        self.relayed_broadcasts
+            .write()
+            .await
+            .insert(broadcast_name.to_string());
+
+        let relayed = self.relayed_broadcasts.clone();
+        let cancelled_name = broadcast_name.to_string();
+        upstream
+            .set_broadcast_cancelled_callback(Box::new(move |path| {
+                if path != cancelled_name {
+                    return;
+                }
+                let relayed = relayed.clone();
+                let path = path.to_string();
+                tokio::spawn(async move {
+                    relayed.write().await.remove(&path);
+                    warn!(
+                        "Relay for broadcast '{}' torn down: upstream broadcast cancelled",
+                        path
+                    );
+                });
+            }))
+            .await;
+
+        let relayed = self.relayed_broadcasts.clone();
+        let closed_name = broadcast_name.to_string();
+        upstream
+            .set_connection_closed_callback(Box::new(move |reason| {
+                let relayed = relayed.clone();
+                let name = closed_name.clone();
+                let reason = reason.to_string();
+                tokio::spawn(async move {
+                    relayed.write().await.remove(&name);
+                    warn!(
+                        "Relay for broadcast '{}' torn down: upstream connection closed ({})",
+                        name, reason
+                    );
+                });
+            }))
+            .await;
+
+        info!(
+            "Relaying broadcast '{}' from upstream session",
+            broadcast_name
+        );
+        Ok(())
+    }
+
+    /// Broadcast names currently bridged from an upstream session via [`Self::relay_broadcast`]
+    pub async fn active_relays(&self) -> Vec<String> {
+        self.relayed_broadcasts
+            .read()
+            .await
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Forward `tracks` from this (subscriber) session's broadcast onto `downstream` (a
+    /// publisher session), one group/frame at a time, preserving group boundaries - see
+    /// [`crate::relay::Forwarder`] for the per-track relay this spawns. Unlike
+    /// [`Self::relay_broadcast`], which hands the whole broadcast to `moq_lite` to
+    /// forward, this goes through the normal publish/subscribe API, so it works even
+    /// when `downstream` republishes under a different broadcast name or track set.
+    pub async fn forward_to(
+        &self,
+        downstream: Arc<MoqSession>,
+        tracks: Vec<TrackDefinition>,
+    ) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Subscriber) {
+            return Err(WrapperError::Session("Not a subscriber session".to_string()).into());
+        }
+        if !matches!(downstream.session_type, SessionType::Publisher) {
+            return Err(
+                WrapperError::Session("Downstream is not a publisher session".to_string()).into(),
+            );
+        }
+
+        crate::relay::Forwarder::new(self.clone(), (*downstream).clone(), tracks)
+            .start()
+            .await
+    }
+
     /// Create track producers from the existing broadcast (internal method, called automatically)
     pub async fn create_track_producers(&self) -> Result<()> {
         if !matches!(self.session_type, SessionType::Publisher) {
@@ -1130,6 +2138,223 @@ impl MoqSession {
         Ok(())
     }
 
+    /// Handle a subscriber requesting `track_name`, creating its producer on demand if
+    /// it doesn't have one yet. A no-op if the track already has a producer. If no
+    /// [`Self::set_track_requested_callback`] is set, or the callback declines by
+    /// returning `None`, the request is logged and otherwise ignored - the track stays
+    /// unproduced.
+    ///
+    /// Unlike [`Self::create_track_producers`] (which eagerly materializes every track
+    /// added via [`Self::add_track_definition`]), this is the entry point for tracks
+    /// that were never declared up front: calling it is what lets "unrequested tracks
+    /// never get producers" actually hold. Wire this to wherever the underlying session
+    /// surfaces an unknown-track request; this wrapper's `moq_lite` dependency doesn't
+    /// expose that notification as a concrete API in this tree, so nothing here calls it
+    /// automatically yet.
+    pub async fn handle_track_requested(&self, track_name: &str) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Publisher) {
+            return Err(WrapperError::Session("Not a publisher session".to_string()).into());
+        }
+
+        {
+            let tracks = self.tracks.read().await;
+            if let Some(handle) = tracks.get(track_name) {
+                if handle.producer.is_some() {
+                    return Ok(());
+                }
+            }
+        }
+
+        let _ = self.event_tx.send(SessionEvent::TrackRequested {
+            name: track_name.to_string(),
+        });
+
+        let track_def = {
+            let callback_guard = self.track_requested_callback.read().await;
+            callback_guard
+                .as_ref()
+                .and_then(|callback| callback(track_name))
+        };
+
+        let Some(track_def) = track_def else {
+            debug!(
+                "No track definition provided for requested track '{}', ignoring",
+                track_name
+            );
+            return Ok(());
+        };
+
+        let track = Track {
+            name: track_def.name.clone(),
+            priority: track_def.priority.try_into().unwrap_or(0),
+        };
+
+        let broadcast_producer = {
+            let state = self.state.read().await;
+            state
+                .broadcast
+                .as_ref()
+                .and_then(|handle| handle.producer.clone())
+        };
+
+        let Some(mut broadcast_producer) = broadcast_producer else {
+            return Err(WrapperError::Session(
+                "No broadcast available for on-demand track creation".to_string(),
+            )
+            .into());
+        };
+
+        let track_producer = broadcast_producer.create_track(track.clone());
+
+        let mut rng = rand::thread_rng();
+        let random_start: u64 = rng.gen_range(1..=10000);
+
+        self.tracks.write().await.insert(
+            track_def.name.clone(),
+            TrackHandle {
+                producer: Some(track_producer),
+                consumer: None,
+                track_info: track,
+                track_definition: Some(track_def.clone()),
+            },
+        );
+        self.sequence_numbers
+            .write()
+            .await
+            .insert(track_def.name.clone(), random_start);
+
+        info!(
+            "Created on-demand track producer for requested track '{}'",
+            track_def.name
+        );
+        Ok(())
+    }
+
+    /// Create the reserved keepalive track for a publisher session, if it doesn't
+    /// already exist, so the heartbeat companion task has somewhere to write to
+    async fn ensure_keepalive_track(&self) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Publisher) {
+            return Ok(());
+        }
+
+        if self.keepalive_track.read().await.is_some() {
+            return Ok(());
+        }
+
+        let broadcast_producer = {
+            let state = self.state.read().await;
+            state
+                .broadcast
+                .as_ref()
+                .and_then(|handle| handle.producer.clone())
+        };
+
+        if let Some(mut broadcast_producer) = broadcast_producer {
+            let track_producer = broadcast_producer.create_track(Track {
+                name: KEEPALIVE_TRACK_NAME.to_string(),
+                priority: 0,
+            });
+            *self.keepalive_track.write().await = Some(track_producer);
+        }
+
+        Ok(())
+    }
+
+    /// Write a zero-length frame to the reserved keepalive track, marking the
+    /// session as active for the heartbeat companion task
+    async fn write_keepalive_frame(&self) -> Result<()> {
+        let track_producer = self.keepalive_track.read().await.clone();
+        if let Some(mut track_producer) = track_producer {
+            track_producer.write_frame(Bytes::new());
+            self.state.write().await.last_activity = Instant::now();
+            Ok(())
+        } else {
+            Err(WrapperError::Session("Keepalive track not initialized".to_string()).into())
+        }
+    }
+
+    /// Advertise our registered setup extensions to the peer, for publisher sessions.
+    /// Writes a single frame encoding [`Self::setup_extensions`] to the reserved
+    /// [`SETUP_EXTENSIONS_TRACK_NAME`] control track, mirroring how [`Self::ensure_keepalive_track`]
+    /// publishes the keepalive track - this runs once per (re)connection.
+    async fn publish_setup_extensions(&self) -> Result<()> {
+        if !matches!(self.session_type, SessionType::Publisher) {
+            return Ok(());
+        }
+
+        let broadcast_producer = {
+            let state = self.state.read().await;
+            state
+                .broadcast
+                .as_ref()
+                .and_then(|handle| handle.producer.clone())
+        };
+
+        let Some(mut broadcast_producer) = broadcast_producer else {
+            return Ok(());
+        };
+
+        let mut track_producer = broadcast_producer.create_track(Track {
+            name: SETUP_EXTENSIONS_TRACK_NAME.to_string(),
+            priority: 0,
+        });
+        let payload = encode_setup_extensions(&*self.setup_extensions.read().await);
+        track_producer.write_frame(payload);
+
+        Ok(())
+    }
+
+    /// Subscribe to the peer's advertised setup extensions (published on the reserved
+    /// [`SETUP_EXTENSIONS_TRACK_NAME`] control track) and enforce required/optional
+    /// semantics: if the peer marks an extension as required that we haven't
+    /// registered via [`Self::register_setup_extension`], force-close `session_handle`
+    /// and emit a [`SessionEvent::Error`] describing which extension we're missing.
+    /// Unrecognized optional extensions are silently ignored. A peer that doesn't
+    /// publish this track at all (e.g. an older build) is treated as advertising no
+    /// extensions, not as a failure.
+    async fn negotiate_setup_extensions(
+        &self,
+        session_handle: &SessionHandle,
+        event_tx: &mpsc::UnboundedSender<SessionEvent>,
+    ) {
+        let mut track_consumer = match self
+            .subscribe_track_internal(&self.broadcast_name, SETUP_EXTENSIONS_TRACK_NAME)
+            .await
+        {
+            Ok(track_consumer) => track_consumer,
+            Err(_) => return,
+        };
+
+        let Ok(Some(mut group)) = track_consumer.next_group().await else {
+            return;
+        };
+        let Ok(Some(frame)) = group.read_frame().await else {
+            return;
+        };
+        let Some(peer_extensions) = decode_setup_extensions(&frame) else {
+            warn!("Received malformed setup extensions frame from peer, ignoring");
+            return;
+        };
+
+        let local_extensions = self.setup_extensions.read().await;
+        for (id, entry) in &peer_extensions {
+            if entry.required && !local_extensions.contains_key(id) {
+                let error = format!(
+                    "Peer requires setup extension {} which this session does not implement",
+                    id
+                );
+                error!("{}", error);
+                let _ = event_tx.send(SessionEvent::Error {
+                    error: error.clone(),
+                });
+                (*session_handle.session)
+                    .clone()
+                    .close(moq_lite::Error::App(2));
+                return;
+            }
+        }
+    }
+
     /// Set a data callback for receiving track data automatically
     /// This is an alias for set_auto_subscription_data_callback for backward compatibility
     pub async fn set_data_callback<F>(&self, callback: F) -> Result<()>
@@ -1156,6 +2381,7 @@ impl MoqSession {
             state.broadcast = None;
             state.broadcast_consumer_cache = None;
         }
+        let _ = self.connected_tx.send(false);
 
         // Clear tracks and groups
         {
@@ -1224,10 +2450,11 @@ impl MoqSession {
                             "[MoqSession] Successfully consumed broadcast: '{}' - caching for reuse",
                             broadcast_name
                         );
-                        
+
                         // Cache the broadcast consumer for future use
-                        state.broadcast_consumer_cache = Some((broadcast_name.to_string(), broadcast_consumer.clone()));
-                        
+                        state.broadcast_consumer_cache =
+                            Some((broadcast_name.to_string(), broadcast_consumer.clone()));
+
                         Ok(broadcast_consumer)
                     }
                     None => {
@@ -1254,15 +2481,33 @@ impl MoqSession {
         }
     }
 
-    /// Internal method to subscribe to a track without the resilient wrapper
+    /// Internal method to subscribe to a track without the resilient wrapper.
+    /// Shorthand for [`Self::subscribe_track_internal_with_priority`] with priority 0,
+    /// for callers (like the `catalog.json` control track) that don't care where they
+    /// land in the relay's delivery scheduling.
     pub async fn subscribe_track_internal(
         &self,
         broadcast_name: &str,
         track_name: &str,
+    ) -> Result<TrackConsumer> {
+        self.subscribe_track_internal_with_priority(broadcast_name, track_name, 0)
+            .await
+    }
+
+    /// Subscribe to a track without the resilient wrapper, with an explicit delivery
+    /// `priority`. The relay uses this to decide which groups/objects to drop first
+    /// under contended bandwidth, so callers should pass the real per-track priority
+    /// (e.g. [`TrackDefinition::priority`](crate::catalog::TrackDefinition::priority))
+    /// rather than a placeholder.
+    pub async fn subscribe_track_internal_with_priority(
+        &self,
+        broadcast_name: &str,
+        track_name: &str,
+        priority: i32,
     ) -> Result<TrackConsumer> {
         info!(
-            "ðŸŽµ [MoqSession] TRACK SUBSCRIPTION REQUEST: track '{}' in broadcast '{}'",
-            track_name, broadcast_name
+            "ðŸŽµ [MoqSession] TRACK SUBSCRIPTION REQUEST: track '{}' in broadcast '{}' (priority {})",
+            track_name, broadcast_name, priority
         );
 
         // Catalog validation is now handled by BroadcastSubscriptionManager
@@ -1280,7 +2525,7 @@ impl MoqSession {
 
         let track = Track {
             name: track_name.to_string(),
-            priority: 0, // Priority doesn't matter for subscription
+            priority: priority.try_into().unwrap_or(0),
         };
 
         debug!(
@@ -1296,6 +2541,162 @@ impl MoqSession {
         Ok(track_consumer)
     }
 
+    /// Same as [`Self::subscribe_track_internal`], but honors a subscription lease:
+    /// `expires_ms` is the lease length in milliseconds (zero means no expiry, in which
+    /// case no renewal task is spawned), and `renewal_margin` is the fraction of that
+    /// interval to wait before renewing (e.g. `0.8` renews at 80% of the lease).
+    /// Renewal re-issues `subscribe_broadcast`/`subscribe_track_internal`; the first
+    /// renewal attempt that fails fires [`RenewalFailedCallback`] and the task gives up,
+    /// without retrying.
+    pub async fn subscribe_track_internal_with_lease(
+        &self,
+        broadcast_name: &str,
+        track_name: &str,
+        expires_ms: u64,
+        renewal_margin: f64,
+    ) -> Result<TrackLease> {
+        let consumer = self
+            .subscribe_track_internal(broadcast_name, track_name)
+            .await?;
+
+        if expires_ms == 0 {
+            return Ok(TrackLease {
+                consumer,
+                expires_at: Arc::new(RwLock::new(None)),
+            });
+        }
+
+        let lease_duration = std::time::Duration::from_millis(expires_ms);
+        let expires_at = Arc::new(RwLock::new(Some(Instant::now() + lease_duration)));
+
+        let session = self.clone();
+        let broadcast_name = broadcast_name.to_string();
+        let track_name = track_name.to_string();
+        let renewal_expires_at = expires_at.clone();
+        let mut shutdown_rx = self.shutdown_rx.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let renew_in = std::time::Duration::from_secs_f64(
+                    lease_duration.as_secs_f64() * renewal_margin.clamp(0.0, 1.0),
+                );
+
+                tokio::select! {
+                    _ = tokio::time::sleep(renew_in) => {}
+                    _ = shutdown_rx.changed() => {
+                        if *shutdown_rx.borrow() {
+                            return;
+                        }
+                    }
+                }
+                if *shutdown_rx.borrow() {
+                    return;
+                }
+
+                match session
+                    .subscribe_track_internal(&broadcast_name, &track_name)
+                    .await
+                {
+                    Ok(_) => {
+                        *renewal_expires_at.write().await = Some(Instant::now() + lease_duration);
+                        debug!(
+                            "[MoqSession] Renewed subscription lease for track '{}' in broadcast '{}'",
+                            track_name, broadcast_name
+                        );
+                    }
+                    Err(e) => {
+                        warn!(
+                            "[MoqSession] Failed to renew subscription lease for track '{}' in broadcast '{}': {}",
+                            track_name, broadcast_name, e
+                        );
+                        if let Some(callback) =
+                            session.renewal_failed_callback.read().await.as_ref()
+                        {
+                            callback(&broadcast_name, &track_name);
+                        }
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(TrackLease {
+            consumer,
+            expires_at,
+        })
+    }
+
+    /// Subscribe to `track_name` in `broadcast_name` and invoke `callback` once per
+    /// fragment (MoQ object) as it arrives, instead of requiring the caller to drain
+    /// whole groups via `TrackConsumer::next_group`/`GroupConsumer::read_frame`
+    /// themselves. `read_frame` is already per-object, so this just wires up
+    /// group/object sequence bookkeeping and a callback around it on a background
+    /// task, letting a decode pipeline start working on a group's fragments as they
+    /// show up rather than waiting for the whole group to finish. `group_end_callback`,
+    /// if given, fires once per group after its last `callback` invocation with
+    /// whether the group ended normally or on a transport error; see
+    /// [`GroupEndCallback`].
+    pub async fn subscribe_track_fragments(
+        &self,
+        broadcast_name: &str,
+        track_name: &str,
+        callback: FragmentCallback,
+        group_end_callback: Option<GroupEndCallback>,
+    ) -> Result<()> {
+        let mut track_consumer = self
+            .subscribe_track_internal(broadcast_name, track_name)
+            .await?;
+        let track_name = track_name.to_string();
+        let mut shutdown_rx = self.shutdown_rx.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let mut group = match track_consumer.next_group().await {
+                    Ok(Some(group)) => group,
+                    Ok(None) => return,
+                    Err(_) => return,
+                };
+                let group_sequence = group.sequence;
+                let mut object_sequence: u64 = 0;
+                let mut group_errored = false;
+
+                loop {
+                    tokio::select! {
+                        frame = group.read_frame() => {
+                            match frame {
+                                Ok(Some(data)) => {
+                                    let size = data.len() as u64;
+                                    callback(&track_name, group_sequence, object_sequence, Some(size), data);
+                                    object_sequence += 1;
+                                }
+                                Ok(None) => break,
+                                Err(_) => {
+                                    group_errored = true;
+                                    break;
+                                }
+                            }
+                        }
+                        _ = shutdown_rx.changed() => {
+                            if *shutdown_rx.borrow() {
+                                return;
+                            }
+                        }
+                    }
+                }
+
+                if let Some(group_end_callback) = &group_end_callback {
+                    group_end_callback(&track_name, group_sequence, group_errored);
+                }
+
+                if *shutdown_rx.borrow() {
+                    return;
+                }
+            }
+        });
+
+        Ok(())
+    }
+
     // Removed validate_track_against_catalog method - catalog validation is now handled by BroadcastSubscriptionManager
 
     // Removed fetch_catalog and fetch_catalog_internal methods - catalog fetching is now handled by BroadcastSubscriptionManager
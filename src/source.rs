@@ -0,0 +1,749 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::catalog::{
+    AacProfile, AudioCodec, Catalog, CatalogType, TrackDefinition, TrackType, VideoCodec,
+};
+use crate::session::MoqSession;
+
+/// A track discovered while parsing a file's `moov` init segment, along with the bits
+/// needed to route, pace, and group its fragments.
+struct ParsedTrack {
+    track_name: String,
+    timescale: u32,
+    track_type: TrackType,
+}
+
+/// Result of [`File::configure`]: the whole file plus enough per-track metadata to
+/// drive [`File::run`] without re-parsing the `moov` box.
+pub struct ParsedFile {
+    data: Vec<u8>,
+    tracks: HashMap<u32, ParsedTrack>,
+}
+
+/// Parse a `moov` init segment (`data` is the whole init segment, e.g. `ftyp`+`moov`),
+/// returning one [`TrackDefinition`] and [`ParsedTrack`] per usable `trak`. Shared by
+/// [`File::configure`] and [`Stdin::run`].
+fn parse_init_segment(data: &[u8]) -> Result<(Vec<TrackDefinition>, HashMap<u32, ParsedTrack>)> {
+    let moov = fmp4_find_box(data, b"moov").context("no moov box found in init segment")?;
+
+    let mut track_defs = Vec::new();
+    let mut tracks = HashMap::new();
+
+    for (kind, start, end) in fmp4_top_level_boxes(moov) {
+        if &kind != b"trak" {
+            continue;
+        }
+        let trak = &moov[start + 8..end];
+
+        let track_id = fmp4_find_box(trak, b"tkhd")
+            .and_then(fmp4_track_id)
+            .context("trak missing a usable tkhd")?;
+
+        let mdia = fmp4_find_box(trak, b"mdia");
+        let timescale = mdia
+            .and_then(|mdia| fmp4_find_box(mdia, b"mdhd"))
+            .and_then(fmp4_mdhd_timescale)
+            .unwrap_or(1000);
+        let handler = mdia
+            .and_then(|mdia| fmp4_find_box(mdia, b"hdlr"))
+            .and_then(fmp4_handler_type);
+        let stsd = mdia
+            .and_then(|mdia| fmp4_find_box(mdia, b"minf"))
+            .and_then(|minf| fmp4_find_box(minf, b"stbl"))
+            .and_then(|stbl| fmp4_find_box(stbl, b"stsd"));
+        let codec = stsd.and_then(fmp4_codec_string);
+
+        let track_type = match handler.as_deref() {
+            Some("video") => TrackType::Video,
+            Some("audio") => TrackType::Audio,
+            _ => TrackType::Data,
+        };
+
+        let track_name = format!("track{track_id}");
+        let mut track_def = TrackDefinition::new(track_name.clone(), 128, track_type);
+        if let Some(codec) = codec {
+            track_def = track_def.with_codec(codec);
+        }
+        track_defs.push(track_def);
+
+        tracks.insert(
+            track_id,
+            ParsedTrack {
+                track_name,
+                timescale,
+                track_type,
+            },
+        );
+    }
+
+    if tracks.is_empty() {
+        anyhow::bail!("no usable tracks found in moov");
+    }
+
+    Ok((track_defs, tracks))
+}
+
+/// Register `track_defs` as a catalog and one track definition each on `session`.
+/// Shared by [`File::configure`] and [`Stdin::run`].
+fn publish_init_segment(
+    session: &mut MoqSession,
+    catalog_type: CatalogType,
+    track_defs: &[TrackDefinition],
+) -> Result<()> {
+    for track_def in track_defs {
+        session.add_track_definition(track_def.clone())?;
+    }
+
+    let catalog = Catalog::new(catalog_type, track_defs)
+        .context("failed to build catalog from parsed tracks")?;
+    session.set_catalog(catalog)?;
+
+    Ok(())
+}
+
+/// Stream `moof`+`mdat` fragments found in `data` as groups on `session`, pacing
+/// releases by each fragment's decode timestamp (`tfdt` / track timescale) so playback
+/// proceeds in real time. `init_segment` (everything before the first `moof`, i.e.
+/// `ftyp`+`moov`) is written as the first frame of each new group, mirroring how a real
+/// player expects to see an init segment before the fragments that depend on it - every
+/// track shares the same bytes since this crate doesn't split the init segment per
+/// track. Stops at EOF (the end of `data`) or when `shutdown` fires.
+///
+/// A new group starts whenever a video track's fragment is a sync sample (keyframe),
+/// or the first time any track is seen; fragments in between ride along in the
+/// currently open group, same as [`MoqSession::write_frame`] appending to it. Audio and
+/// data tracks have no keyframe concept, so every fragment of theirs starts its own
+/// group, as before.
+async fn run_fragments(
+    session: &MoqSession,
+    data: &[u8],
+    tracks: &HashMap<u32, ParsedTrack>,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    let top_boxes = fmp4_top_level_boxes(data);
+    let init_segment_end = top_boxes
+        .iter()
+        .find(|(kind, _, _)| kind == b"moof")
+        .map(|(_, start, _)| *start)
+        .unwrap_or(data.len());
+    let init_segment = bytes::Bytes::copy_from_slice(&data[..init_segment_end]);
+
+    let mut anchor: Option<(Instant, f64)> = None;
+    let mut group_open: HashMap<u32, bool> = HashMap::new();
+
+    let mut i = 0;
+    while i < top_boxes.len() {
+        if *shutdown.borrow() {
+            break;
+        }
+
+        let (kind, moof_start, moof_end) = top_boxes[i];
+        if &kind != b"moof" {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        let mut fragment_end = None;
+        while j < top_boxes.len() {
+            let (next_kind, _, next_end) = top_boxes[j];
+            if &next_kind == b"mdat" {
+                fragment_end = Some(next_end);
+                break;
+            }
+            if &next_kind == b"moof" {
+                break;
+            }
+            j += 1;
+        }
+        let Some(fragment_end) = fragment_end else {
+            i += 1;
+            continue;
+        };
+
+        let moof_payload = &data[moof_start + 8..moof_end];
+        let traf = fmp4_find_box(moof_payload, b"traf");
+        // tfhd carries track_ID right after version/flags, unlike tkhd.
+        let track_id = traf
+            .and_then(|traf| fmp4_find_box(traf, b"tfhd"))
+            .and_then(|tfhd| tfhd.get(4..8))
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_be_bytes);
+        let decode_time = fmp4_tfdt_time(moof_payload);
+
+        if let Some((track_id, track)) = track_id.and_then(|id| tracks.get(&id).map(|t| (id, t))) {
+            if let Some(decode_time) = decode_time {
+                let pts = decode_time as f64 / track.timescale as f64;
+                let (anchor_wall, anchor_pts) = *anchor.get_or_insert((Instant::now(), pts));
+                let target = anchor_wall + Duration::from_secs_f64((pts - anchor_pts).max(0.0));
+                let now = Instant::now();
+                if target > now {
+                    tokio::select! {
+                        _ = tokio::time::sleep(target - now) => {}
+                        _ = shutdown.changed() => {
+                            if *shutdown.borrow() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let is_keyframe = match track.track_type {
+                TrackType::Video => traf.map(fmp4_is_keyframe).unwrap_or(true),
+                _ => true,
+            };
+            let needs_new_group =
+                is_keyframe || !group_open.get(&track_id).copied().unwrap_or(false);
+
+            if needs_new_group {
+                if let Err(e) = session.start_group(&track.track_name).await {
+                    warn!("failed to start group for {}: {}", track.track_name, e);
+                } else if let Err(e) = session
+                    .write_frame(&track.track_name, init_segment.clone())
+                    .await
+                {
+                    warn!(
+                        "failed to write init segment for {}: {}",
+                        track.track_name, e
+                    );
+                }
+                group_open.insert(track_id, true);
+            }
+
+            let fragment = bytes::Bytes::copy_from_slice(&data[moof_start..fragment_end]);
+            if let Err(e) = session.write_frame(&track.track_name, fragment).await {
+                warn!("failed to write fragment for {}: {}", track.track_name, e);
+            }
+        }
+
+        i = j + 1;
+    }
+
+    for (track_id, track) in tracks {
+        if group_open.get(track_id).copied().unwrap_or(false) {
+            if let Err(e) = session.close_group(&track.track_name).await {
+                warn!("failed to close group for {}: {}", track.track_name, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Disk-backed fragmented-MP4 (CMAF) publisher source, mirroring the file source in the
+/// moq-rs demo. Parses a file's `moov` init box to build a [`Catalog`] and one
+/// [`TrackDefinition`] per `trak`, then streams each `moof`+`mdat` pair as a frame,
+/// starting a new group on each video keyframe and pacing releases to wall-clock time
+/// using the fragment's decode timestamp.
+///
+/// Most callers should use [`MoqSession::publish_file`] instead of driving this
+/// directly.
+pub struct File {
+    path: PathBuf,
+}
+
+impl File {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Parse the init segment, register a catalog and one track definition per `trak`
+    /// on `session`, and return the parsed file ready for [`Self::run`].
+    pub async fn configure(
+        &self,
+        session: &mut MoqSession,
+        catalog_type: CatalogType,
+    ) -> Result<ParsedFile> {
+        let data = std::fs::read(&self.path)
+            .with_context(|| format!("failed to read {}", self.path.display()))?;
+
+        let (track_defs, tracks) = parse_init_segment(&data)?;
+        publish_init_segment(session, catalog_type, &track_defs)?;
+
+        Ok(ParsedFile { data, tracks })
+    }
+
+    /// Stream `moof`+`mdat` fragments from `parsed` as groups on `session` - see
+    /// [`run_fragments`] for the grouping/pacing rules. Stops at EOF or when
+    /// `shutdown` fires.
+    pub async fn run(
+        &self,
+        session: &MoqSession,
+        parsed: ParsedFile,
+        shutdown: watch::Receiver<bool>,
+    ) -> Result<()> {
+        run_fragments(session, &parsed.data, &parsed.tracks, shutdown).await
+    }
+}
+
+/// Live fragmented-MP4 (CMAF) publisher source for an incoming byte stream (e.g.
+/// stdin) rather than a seekable file on disk, for cases like `moq-pub`'s stdin mode
+/// where a transcoder pipes fragments directly into the publisher.
+///
+/// Unlike [`File`], this reads the whole stream to EOF before parsing - it doesn't
+/// incrementally parse box-by-box as bytes arrive. For a process piping a live fMP4
+/// stream through (rather than a finite capture), prefer chunking the input into
+/// separate invocations, or extend this to parse incrementally once that's needed.
+pub struct Stdin;
+
+impl Stdin {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read `reader` to EOF, parse it as a fragmented MP4, register a catalog and
+    /// track definitions on `session`, then stream its fragments exactly like
+    /// [`File::run`]. Stops early if `shutdown` fires while reading or streaming.
+    pub async fn run(
+        &self,
+        session: &mut MoqSession,
+        catalog_type: CatalogType,
+        mut reader: impl tokio::io::AsyncRead + Unpin,
+        shutdown: watch::Receiver<bool>,
+    ) -> Result<()> {
+        use tokio::io::AsyncReadExt;
+
+        let mut data = Vec::new();
+        reader
+            .read_to_end(&mut data)
+            .await
+            .context("failed to read fMP4 stream")?;
+
+        let (track_defs, tracks) = parse_init_segment(&data)?;
+        publish_init_segment(session, catalog_type, &track_defs)?;
+
+        run_fragments(session, &data, &tracks, shutdown).await
+    }
+}
+
+impl Default for Stdin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn fmp4_top_level_boxes(data: &[u8]) -> Vec<([u8; 4], usize, usize)> {
+    let mut boxes = Vec::new();
+    let mut offset = 0;
+    while offset + 8 <= data.len() {
+        let size = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        if size < 8 || offset + size > data.len() {
+            break;
+        }
+        let mut kind = [0u8; 4];
+        kind.copy_from_slice(&data[offset + 4..offset + 8]);
+        boxes.push((kind, offset, offset + size));
+        offset += size;
+    }
+    boxes
+}
+
+pub(crate) fn fmp4_find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    fmp4_top_level_boxes(data)
+        .into_iter()
+        .find(|(box_kind, _, _)| box_kind == kind)
+        .map(|(_, start, end)| &data[start + 8..end])
+}
+
+pub(crate) fn fmp4_track_id(box_payload: &[u8]) -> Option<u32> {
+    let version = *box_payload.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = box_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Track IDs (from each `trak`'s `tkhd`) found in an init segment's `moov` box, in
+/// `moov`'s `trak` order. Used by [`crate::track::Fmp4Publisher`] to create one track
+/// per `trak` without needing the rest of [`parse_init_segment`]'s catalog bookkeeping.
+pub(crate) fn fmp4_trak_track_ids(data: &[u8]) -> Result<Vec<u32>> {
+    let moov = fmp4_find_box(data, b"moov").context("no moov box found in init segment")?;
+
+    Ok(fmp4_top_level_boxes(moov)
+        .into_iter()
+        .filter(|(kind, _, _)| kind == b"trak")
+        .filter_map(|(_, start, end)| {
+            fmp4_find_box(&moov[start + 8..end], b"tkhd").and_then(fmp4_track_id)
+        })
+        .collect())
+}
+
+/// Every `moof`+`mdat` pair found in `data`, paired with the track ID read from the
+/// fragment's `traf`/`tfhd`. Fragments with no `tfhd` track ID are dropped, since there's
+/// nowhere to route them. Unlike [`run_fragments`], this does no pacing or keyframe
+/// grouping - it's the simpler per-fragment split [`crate::track::Fmp4Publisher`] needs.
+pub(crate) fn fmp4_iter_fragments(data: &[u8]) -> Vec<(u32, bytes::Bytes)> {
+    let top_boxes = fmp4_top_level_boxes(data);
+    let mut fragments = Vec::new();
+
+    let mut i = 0;
+    while i < top_boxes.len() {
+        let (kind, moof_start, moof_end) = top_boxes[i];
+        if &kind != b"moof" {
+            i += 1;
+            continue;
+        }
+
+        let mut j = i + 1;
+        let mut fragment_end = None;
+        while j < top_boxes.len() {
+            let (next_kind, _, next_end) = top_boxes[j];
+            if &next_kind == b"mdat" {
+                fragment_end = Some(next_end);
+                break;
+            }
+            if &next_kind == b"moof" {
+                break;
+            }
+            j += 1;
+        }
+        let Some(fragment_end) = fragment_end else {
+            i += 1;
+            continue;
+        };
+
+        let moof_payload = &data[moof_start + 8..moof_end];
+        // tfhd carries track_ID right after version/flags, unlike tkhd.
+        let track_id = fmp4_find_box(moof_payload, b"traf")
+            .and_then(|traf| fmp4_find_box(traf, b"tfhd"))
+            .and_then(|tfhd| tfhd.get(4..8))
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u32::from_be_bytes);
+
+        if let Some(track_id) = track_id {
+            fragments.push((
+                track_id,
+                bytes::Bytes::copy_from_slice(&data[moof_start..fragment_end]),
+            ));
+        }
+
+        i = j + 1;
+    }
+
+    fragments
+}
+
+fn fmp4_tfdt_time(moof_payload: &[u8]) -> Option<u64> {
+    let traf = fmp4_find_box(moof_payload, b"traf")?;
+    let tfdt = fmp4_find_box(traf, b"tfdt")?;
+    let version = *tfdt.first()?;
+    if version == 1 {
+        let bytes = tfdt.get(4..12)?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    } else {
+        let bytes = tfdt.get(4..8)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?) as u64)
+    }
+}
+
+/// Sample-flags bit marking a sample as not a sync sample (i.e. not a keyframe), per
+/// ISO/IEC 14496-12's `sample_depends_on`/`sample_is_non_sync_sample` layout.
+const SAMPLE_IS_NON_SYNC: u32 = 0x0001_0000;
+
+/// Whether `traf_payload` (a `traf` box's payload) describes a sync sample (keyframe)
+/// fragment, read from `trun`'s first-sample-flags if present, else `tfhd`'s
+/// default-sample-flags. Fragments that carry neither field are conservatively treated
+/// as keyframes, which just falls back to starting a new group for them - the same as
+/// this source's behavior before keyframe-aware grouping was added.
+fn fmp4_is_keyframe(traf_payload: &[u8]) -> bool {
+    let sample_flags = fmp4_find_box(traf_payload, b"trun")
+        .and_then(fmp4_trun_first_sample_flags)
+        .or_else(|| fmp4_find_box(traf_payload, b"tfhd").and_then(fmp4_tfhd_default_sample_flags));
+
+    match sample_flags {
+        Some(flags) => flags & SAMPLE_IS_NON_SYNC == 0,
+        None => true,
+    }
+}
+
+/// First sample's flags from a `trun` box's payload, if the `trun` actually carries a
+/// per-fragment first-sample-flags field (flag bit `0x000004`).
+fn fmp4_trun_first_sample_flags(trun_payload: &[u8]) -> Option<u32> {
+    let flags = u32::from_be_bytes(trun_payload.get(0..4)?.try_into().ok()?) & 0x00FF_FFFF;
+    if flags & 0x0000_0004 == 0 {
+        return None;
+    }
+    let mut offset = 8; // version+flags(4) + sample_count(4)
+    if flags & 0x0000_0001 != 0 {
+        offset += 4; // data-offset present
+    }
+    let bytes = trun_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Default sample flags from a `tfhd` box's payload, if it actually carries a
+/// default-sample-flags field (flag bit `0x000020`).
+fn fmp4_tfhd_default_sample_flags(tfhd_payload: &[u8]) -> Option<u32> {
+    let flags = u32::from_be_bytes(tfhd_payload.get(0..4)?.try_into().ok()?) & 0x00FF_FFFF;
+    let mut offset = 4 + 4; // version+flags(4) + track_ID(4), always present
+    if flags & 0x0000_0001 != 0 {
+        offset += 8; // base-data-offset
+    }
+    if flags & 0x0000_0002 != 0 {
+        offset += 4; // sample-description-index
+    }
+    if flags & 0x0000_0008 != 0 {
+        offset += 4; // default-sample-duration
+    }
+    if flags & 0x0000_0010 != 0 {
+        offset += 4; // default-sample-size
+    }
+    if flags & 0x0000_0020 == 0 {
+        return None;
+    }
+    let bytes = tfhd_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// Timescale (ticks per second) from a `mdhd` box's payload.
+fn fmp4_mdhd_timescale(mdhd_payload: &[u8]) -> Option<u32> {
+    let version = *mdhd_payload.first()?;
+    let offset = if version == 1 { 20 } else { 12 };
+    let bytes = mdhd_payload.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes(bytes.try_into().ok()?))
+}
+
+/// The handler type from a `hdlr` box's payload, mapped to "video"/"audio"/"data".
+pub(crate) fn fmp4_handler_type(hdlr_payload: &[u8]) -> Option<String> {
+    let bytes = hdlr_payload.get(8..12)?;
+    match bytes {
+        b"vide" => Some("video".to_string()),
+        b"soun" => Some("audio".to_string()),
+        _ => Some("data".to_string()),
+    }
+}
+
+/// First sample entry's format fourcc from a `stsd` box's payload (e.g. "avc1", "mp4a").
+pub(crate) fn fmp4_sample_entry_fourcc(stsd_payload: &[u8]) -> Option<[u8; 4]> {
+    let bytes = stsd_payload.get(12..16)?;
+    Some(bytes.try_into().ok()?)
+}
+
+/// Coded width/height from a `stsd` box's first `VisualSampleEntry`.
+pub(crate) fn fmp4_visual_dimensions(stsd_payload: &[u8]) -> Option<(u16, u16)> {
+    // The stsd payload opens with version/flags(4) + entry_count(4), the entry with
+    // size(4) + format(4). Within the entry, after those: reserved(6) +
+    // data_reference_index(2) + pre_defined(2) + reserved(2) + pre_defined[3](12) = 24
+    // bytes before width/height.
+    const STSD_HEADER: usize = 8;
+    const SAMPLE_ENTRY_HEADER: usize = 8;
+    const WIDTH_OFFSET: usize = STSD_HEADER + SAMPLE_ENTRY_HEADER + 24;
+
+    let width = u16::from_be_bytes(
+        stsd_payload
+            .get(WIDTH_OFFSET..WIDTH_OFFSET + 2)?
+            .try_into()
+            .ok()?,
+    );
+    let height = u16::from_be_bytes(
+        stsd_payload
+            .get(WIDTH_OFFSET + 2..WIDTH_OFFSET + 4)?
+            .try_into()
+            .ok()?,
+    );
+    Some((width, height))
+}
+
+/// An MPEG-4 descriptor's tag, payload length, and the offset its payload starts at,
+/// read from `data` starting at `offset`. Descriptor sizes use the base-128 varint
+/// encoding from ISO/IEC 14496-1 (continuation bit `0x80`, up to 4 size bytes).
+fn fmp4_read_descriptor(data: &[u8], offset: usize) -> Option<(u8, usize, usize)> {
+    let tag = *data.get(offset)?;
+    let mut pos = offset + 1;
+    let mut size = 0usize;
+    for _ in 0..4 {
+        let byte = *data.get(pos)?;
+        pos += 1;
+        size = (size << 7) | (byte & 0x7f) as usize;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Some((tag, size, pos))
+}
+
+/// Sample rate (Hz) and channel count decoded from an `AudioSpecificConfig` (ISO/IEC
+/// 14496-3), in its plain (non-extended) form: 5-bit `audioObjectType`, 4-bit
+/// `samplingFrequencyIndex`, 4-bit `channelConfiguration`.
+fn fmp4_audio_specific_config(asc: &[u8]) -> Option<(u32, u32)> {
+    const SAMPLE_RATES: [u32; 13] = [
+        96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+    ];
+
+    let byte0 = *asc.first()?;
+    let byte1 = *asc.get(1)?;
+    let sampling_frequency_index = ((byte0 & 0x07) << 1) | (byte1 >> 7);
+    let channel_configuration = (byte1 >> 3) & 0x0f;
+
+    let sample_rate = *SAMPLE_RATES.get(sampling_frequency_index as usize)?;
+    Some((sample_rate, channel_configuration as u32))
+}
+
+/// Walks an `esds` box's payload (`ES_Descriptor` > `DecoderConfigDescriptor` >
+/// `DecoderSpecificInfo`) down to its `AudioSpecificConfig` bytes, then decodes that
+/// into a sample rate and channel count. Returns the raw `AudioSpecificConfig` bytes
+/// too, for embedding as the catalog's hex-encoded `description`.
+pub(crate) fn fmp4_esds_audio_config(esds_payload: &[u8]) -> Option<(u32, u32, &[u8])> {
+    const ES_DESCRIPTOR_TAG: u8 = 0x03;
+    const DECODER_CONFIG_DESCRIPTOR_TAG: u8 = 0x04;
+    const DECODER_SPECIFIC_INFO_TAG: u8 = 0x05;
+    // objectTypeIndication(1) + streamType/upStream/reserved(1) + bufferSizeDB(3) +
+    // maxBitrate(4) + avgBitrate(4) precede DecoderSpecificInfo in DecoderConfigDescriptor.
+    const DECODER_CONFIG_FIXED_FIELDS: usize = 13;
+
+    let (tag, _size, es_start) = fmp4_read_descriptor(esds_payload, 4)?;
+    if tag != ES_DESCRIPTOR_TAG {
+        return None;
+    }
+
+    // ES_ID(2) + flags(1), then optional fields gated by the flags bits.
+    let flags = *esds_payload.get(es_start + 2)?;
+    let mut pos = es_start + 3;
+    if flags & 0x80 != 0 {
+        pos += 2; // dependsOn_ES_ID
+    }
+    if flags & 0x40 != 0 {
+        let url_len = *esds_payload.get(pos)? as usize;
+        pos += 1 + url_len;
+    }
+    if flags & 0x20 != 0 {
+        pos += 2; // OCR_ES_Id
+    }
+
+    let (tag, _size, dec_config_start) = fmp4_read_descriptor(esds_payload, pos)?;
+    if tag != DECODER_CONFIG_DESCRIPTOR_TAG {
+        return None;
+    }
+
+    let (tag, size, asc_start) =
+        fmp4_read_descriptor(esds_payload, dec_config_start + DECODER_CONFIG_FIXED_FIELDS)?;
+    if tag != DECODER_SPECIFIC_INFO_TAG {
+        return None;
+    }
+
+    let asc = esds_payload.get(asc_start..asc_start + size)?;
+    let (sample_rate, channel_count) = fmp4_audio_specific_config(asc)?;
+    Some((sample_rate, channel_count, asc))
+}
+
+/// An RFC 6381 codec string derived from a `stsd` box's first sample entry. H.264
+/// (`avc1`/`avc3`) profile/compatibility/level bytes are read out of the nested `avcC`
+/// box, HEVC (`hev1`/`hvc1`) out of `hvcC`, and AAC (`mp4a`) out of `esds`'s
+/// `AudioSpecificConfig`; other codecs fall back to a reasonable common default since
+/// describing their full parameter set (e.g. VP9 level) would need deeper parsing than
+/// this source attempts.
+pub(crate) fn fmp4_codec_string(stsd_payload: &[u8]) -> Option<String> {
+    let fourcc = fmp4_sample_entry_fourcc(stsd_payload)?;
+    let codec = match &fourcc {
+        b"avc1" | b"avc3" => {
+            fmp4_avc1_codec_string(stsd_payload).unwrap_or_else(|| "avc1.42001e".to_string())
+        }
+        b"hev1" | b"hvc1" => {
+            fmp4_hevc_codec_string(stsd_payload).unwrap_or_else(|| "hvc1.1.6.L93.B0".to_string())
+        }
+        b"mp4a" => fmp4_aac_codec_string(stsd_payload).unwrap_or_else(|| "mp4a.40.2".to_string()),
+        b"Opus" | b"opus" => "opus".to_string(),
+        other => String::from_utf8_lossy(other).to_string(),
+    };
+    Some(codec)
+}
+
+/// Size, in bytes, of the fixed (non-box) fields after a `VisualSampleEntry`'s
+/// size/format header - its `SampleEntry` base (`reserved(6)` +
+/// `data_reference_index(2)`) plus the visual fields - before any nested boxes like
+/// `avcC` begin.
+pub(crate) const VISUAL_SAMPLE_ENTRY_FIXED: usize = 78;
+
+/// Size, in bytes, of the fixed (non-box) fields after an `AudioSampleEntry`'s
+/// size/format header - its `SampleEntry` base plus the audio fields - before any
+/// nested boxes like `esds` begin.
+pub(crate) const AUDIO_SAMPLE_ENTRY_FIXED: usize = 28;
+
+/// Finds `kind` among the boxes nested inside a `stsd` box's first sample entry (e.g.
+/// `avcC` inside `avc1`, `esds` inside `mp4a`). `fixed_header_len` is the size of the
+/// sample-entry-specific fixed fields preceding those nested boxes - see
+/// [`VISUAL_SAMPLE_ENTRY_FIXED`]/[`AUDIO_SAMPLE_ENTRY_FIXED`].
+pub(crate) fn fmp4_sample_entry_box<'a>(
+    stsd_payload: &'a [u8],
+    fixed_header_len: usize,
+    kind: &[u8; 4],
+) -> Option<&'a [u8]> {
+    // The stsd payload opens with version/flags(4) + entry_count(4). Sample entry
+    // layout: size(4) + format(4) + reserved(6) + data_reference_index(2) + the
+    // type-specific fixed fields, before any child boxes.
+    const STSD_HEADER: usize = 8;
+    const SAMPLE_ENTRY_HEADER: usize = 8;
+
+    let entry_size = u32::from_be_bytes(stsd_payload.get(8..12)?.try_into().ok()?) as usize;
+    let entry_end = STSD_HEADER + entry_size;
+    let boxes_start = STSD_HEADER + SAMPLE_ENTRY_HEADER + fixed_header_len;
+    if boxes_start >= entry_end || entry_end > stsd_payload.len() {
+        return None;
+    }
+
+    fmp4_find_box(&stsd_payload[boxes_start..entry_end], kind)
+}
+
+/// Parses the `avcC` box nested in an `avc1`/`avc3` sample entry into
+/// "avc1.PPCCLL" (profile, profile-compatibility, level, each hex-encoded).
+fn fmp4_avc1_codec_string(stsd_payload: &[u8]) -> Option<String> {
+    let avcc = fmp4_sample_entry_box(stsd_payload, VISUAL_SAMPLE_ENTRY_FIXED, b"avcC")?;
+    let profile = *avcc.get(1)?;
+    let compatibility = *avcc.get(2)?;
+    let level = *avcc.get(3)?;
+    Some(format!("avc1.{profile:02x}{compatibility:02x}{level:02x}"))
+}
+
+/// Parses the `hvcC` box (`HEVCDecoderConfigurationRecord`, ISO/IEC 14496-15) nested in
+/// an `hev1`/`hvc1` sample entry into a canonical "hvc1.*" codec string via
+/// [`VideoCodec::Hevc`]'s `Display` impl. Trailing all-zero constraint-flag bytes are
+/// dropped (keeping at least one), matching how encoders conventionally shorten the
+/// flags suffix.
+fn fmp4_hevc_codec_string(stsd_payload: &[u8]) -> Option<String> {
+    let hvcc = fmp4_sample_entry_box(stsd_payload, VISUAL_SAMPLE_ENTRY_FIXED, b"hvcC")?;
+
+    let byte1 = *hvcc.get(1)?;
+    let profile_space = byte1 >> 6;
+    let high_tier = (byte1 >> 5) & 0x1 != 0;
+    let profile_idc = byte1 & 0x1f;
+    let profile_compatibility = u32::from_be_bytes(hvcc.get(2..6)?.try_into().ok()?);
+    let constraint_bytes = hvcc.get(6..12)?;
+    let level_idc = *hvcc.get(12)?;
+
+    let mut kept = constraint_bytes.len();
+    while kept > 1 && constraint_bytes[kept - 1] == 0 {
+        kept -= 1;
+    }
+    let constraint_flags = constraint_bytes[..kept]
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(".");
+
+    Some(
+        VideoCodec::Hevc {
+            profile_space,
+            profile_idc,
+            profile_compatibility,
+            high_tier,
+            level_idc,
+            constraint_flags,
+        }
+        .to_string(),
+    )
+}
+
+/// Parses the `esds` box nested in an `mp4a` sample entry into a canonical
+/// "mp4a.40.<object_type>" codec string via [`AudioCodec::Aac`], reading the MPEG-4
+/// Audio Object Type out of the first 5 bits of its `AudioSpecificConfig`.
+fn fmp4_aac_codec_string(stsd_payload: &[u8]) -> Option<String> {
+    let esds = fmp4_sample_entry_box(stsd_payload, AUDIO_SAMPLE_ENTRY_FIXED, b"esds")?;
+    let (_, _, asc) = fmp4_esds_audio_config(esds)?;
+    let object_type = asc.first()? >> 3;
+    Some(AudioCodec::Aac(AacProfile::from_object_type(object_type)).to_string())
+}
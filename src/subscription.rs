@@ -1,19 +1,118 @@
 use anyhow::Result;
-use std::collections::HashSet;
+use bytes::Bytes;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::Stream;
 use tracing::{debug, info, warn};
 
 use moq_lite::{GroupConsumer, TrackConsumer};
 
+use crate::catalog::TrackDefinition;
 use crate::session::MoqSession;
+use crate::subscription_manager::DEFAULT_FRAME_QUEUE_CAPACITY;
 
 /// Type alias for data callback function
 pub type DataCallback = Arc<dyn Fn(String, Vec<u8>) + Send + Sync>;
 
+/// Where a [`ResilientTrackConsumer`] should pick up group delivery from, the first
+/// time it subscribes and again every time it has to resubscribe after a reconnect
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResumePolicy {
+    /// Always land wherever the publisher's live edge currently is - the original
+    /// behavior, which can skip or replay groups across a reconnect gap
+    Latest,
+    /// Resume strictly after the highest group sequence this consumer has already
+    /// handed to the caller, so a reconnect is gap-minimized instead of a cold restart
+    FromLastSeen,
+    /// Resume strictly after a caller-supplied group sequence on the very first
+    /// subscribe; after that, behaves like `FromLastSeen`
+    FromSequence(u64),
+}
+
+impl Default for ResumePolicy {
+    fn default() -> Self {
+        ResumePolicy::Latest
+    }
+}
+
+/// Tunable truncated-exponential backoff for [`ResilientTrackConsumer`]'s
+/// subscribe/resubscribe retries, so a flapping relay isn't hammered at a fixed rate by
+/// every consumer simultaneously
+#[derive(Clone, Copy, Debug)]
+pub struct BackoffConfig {
+    /// Delay before the first retry
+    pub base: Duration,
+    /// Upper bound the delay backs off to
+    pub max: Duration,
+    /// Factor the delay grows by after each consecutive failure
+    pub multiplier: f64,
+    /// Add random jitter in `[0, delay)` before each wait, to de-synchronize many
+    /// consumers that started backing off at the same time ("thundering herd" avoidance)
+    pub jitter: bool,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_millis(500),
+            max: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+/// Stateful backoff timer driven by a [`BackoffConfig`], tracking the current delay and
+/// consecutive-failure count across loop iterations
+///
+/// Call `wait()` before each retry attempt and `reset()` once an attempt succeeds.
+struct Backoff {
+    current: Duration,
+    attempt: u32,
+    config: BackoffConfig,
+}
+
+impl Backoff {
+    fn new(config: BackoffConfig) -> Self {
+        Self {
+            current: config.base,
+            attempt: 0,
+            config,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.current = self.config.base;
+        self.attempt = 0;
+    }
+
+    /// Sleep for the current delay (with full jitter in `[0, delay)` if configured),
+    /// then grow the delay for the next call
+    async fn wait(&mut self) {
+        self.attempt += 1;
+        let delay = if self.config.jitter {
+            self.current.mul_f64(rand::thread_rng().gen_range(0.0..1.0))
+        } else {
+            self.current
+        };
+        debug!(
+            "[ResilientTrackConsumer] Backing off for {:?} (attempt {})",
+            delay, self.attempt
+        );
+        sleep(delay).await;
+
+        let grown = self.current.as_secs_f64() * self.config.multiplier;
+        self.current = Duration::from_secs_f64(grown.min(self.config.max.as_secs_f64()));
+    }
+}
+
 /// A resilient track consumer that automatically handles reconnections and broadcast announcements
 #[derive(Clone)]
 pub struct ResilientTrackConsumer {
@@ -21,19 +120,80 @@ pub struct ResilientTrackConsumer {
     broadcast_name: String,
     track_name: String,
     current_consumer: Arc<RwLock<Option<TrackConsumer>>>,
+    resume_policy: ResumePolicy,
+    backoff_config: BackoffConfig,
+    /// Highest group sequence delivered to the caller so far. Updated every time
+    /// `next_group()` yields a group; read back on resubscribe so we don't hand out a
+    /// group we've already delivered (see [`ResumePolicy`]).
+    last_seen: Arc<RwLock<Option<u64>>>,
+    /// Fires the instant `current_consumer` transitions between `None` and `Some`, so
+    /// the subscription loop and `next_group` can `changed().await` on it instead of
+    /// polling on a fixed sleep.
+    consumer_ready_tx: watch::Sender<bool>,
 }
 
 impl ResilientTrackConsumer {
+    /// Shorthand for [`Self::with_options`] with [`ResumePolicy::Latest`] and
+    /// [`BackoffConfig::default`], preserving the original cold-restart-on-reconnect
+    /// behavior.
     pub async fn new(
         session: MoqSession,
         broadcast_name: String,
         track_name: String,
     ) -> Result<Self> {
+        Self::with_options(
+            session,
+            broadcast_name,
+            track_name,
+            ResumePolicy::default(),
+            BackoffConfig::default(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::new`], but lets the caller control where group delivery resumes
+    /// from across reconnects via `resume_policy`.
+    pub async fn with_resume_policy(
+        session: MoqSession,
+        broadcast_name: String,
+        track_name: String,
+        resume_policy: ResumePolicy,
+    ) -> Result<Self> {
+        Self::with_options(
+            session,
+            broadcast_name,
+            track_name,
+            resume_policy,
+            BackoffConfig::default(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::new`], but lets the caller control both the resume policy and
+    /// the subscribe/resubscribe retry backoff.
+    pub async fn with_options(
+        session: MoqSession,
+        broadcast_name: String,
+        track_name: String,
+        resume_policy: ResumePolicy,
+        backoff_config: BackoffConfig,
+    ) -> Result<Self> {
+        let last_seen = match resume_policy {
+            ResumePolicy::FromSequence(seq) => Some(seq),
+            ResumePolicy::Latest | ResumePolicy::FromLastSeen => None,
+        };
+
+        let (consumer_ready_tx, _) = watch::channel(false);
+
         let resilient = Self {
             session: session.clone(),
             broadcast_name: broadcast_name.clone(),
             track_name: track_name.clone(),
             current_consumer: Arc::new(RwLock::new(None)),
+            resume_policy,
+            backoff_config,
+            last_seen: Arc::new(RwLock::new(last_seen)),
+            consumer_ready_tx,
         };
 
         // Start the simple subscription manager
@@ -49,6 +209,8 @@ impl ResilientTrackConsumer {
         let broadcast_name = self.broadcast_name.clone();
         let track_name = self.track_name.clone();
         let current_consumer = self.current_consumer.clone();
+        let consumer_ready_tx = self.consumer_ready_tx.clone();
+        let backoff_config = self.backoff_config;
 
         // Start the subscription management task
         let subscription_task = {
@@ -56,6 +218,7 @@ impl ResilientTrackConsumer {
             let broadcast_name = broadcast_name.clone();
             let track_name = track_name.clone();
             let current_consumer = current_consumer.clone();
+            let consumer_ready_tx = consumer_ready_tx.clone();
 
             tokio::spawn(async move {
                 info!(
@@ -63,18 +226,23 @@ impl ResilientTrackConsumer {
                     broadcast_name
                 );
 
+                let mut connected_rx = session.connection_watch();
+                let mut consumer_ready_rx = consumer_ready_tx.subscribe();
+                let mut backoff = Backoff::new(backoff_config);
+
                 loop {
-                    // Step 1: Wait for session to be connected
-                    while !session.is_connected().await {
+                    // Step 1: Wait for session to be connected - `changed().await`s on
+                    // the connection watch channel instead of polling `is_connected()`
+                    while !*connected_rx.borrow() {
                         debug!("[ResilientTrackConsumer] Waiting for session connection...");
-                        sleep(Duration::from_millis(500)).await;
+                        if connected_rx.changed().await.is_err() {
+                            debug!("[ResilientTrackConsumer] Session dropped, exiting subscription manager");
+                            return;
+                        }
                     }
 
                     // Step 2: Check if we have a consumer
-                    let has_consumer = {
-                        let consumer_guard = current_consumer.read().await;
-                        consumer_guard.is_some()
-                    };
+                    let has_consumer = *consumer_ready_rx.borrow();
 
                     if !has_consumer {
                         // Step 3: Try to subscribe when we don't have a consumer
@@ -88,18 +256,27 @@ impl ResilientTrackConsumer {
                                     track_name
                                 );
                                 *current_consumer.write().await = Some(consumer);
+                                let _ = consumer_ready_tx.send(true);
+                                backoff.reset();
                             }
                             Err(e) => {
                                 debug!(
                                     "[ResilientTrackConsumer] Subscription failed (will retry): {}",
                                     e
                                 );
+                                // The subscribe failure itself isn't an observable state
+                                // change, so there's nothing to `changed().await` on -
+                                // fall back to a backoff delay instead of hammering the
+                                // relay at a fixed rate from every consumer at once.
+                                backoff.wait().await;
                             }
                         }
+                    } else {
+                        // Step 4: We already have a consumer - wait for it to be
+                        // cleared (by `next_group` on error/EOF, or the announcement
+                        // listener below) instead of polling on a fixed interval
+                        let _ = consumer_ready_rx.changed().await;
                     }
-
-                    // Step 4: Sleep before checking again
-                    sleep(Duration::from_millis(1000)).await;
                 }
             })
         };
@@ -109,6 +286,7 @@ impl ResilientTrackConsumer {
             let session = session.clone();
             let broadcast_name = broadcast_name.clone();
             let current_consumer = current_consumer.clone();
+            let consumer_ready_tx = consumer_ready_tx.clone();
 
             tokio::spawn(async move {
                 info!(
@@ -126,6 +304,7 @@ impl ResilientTrackConsumer {
 
                                 // Immediately clear the current consumer to force a new subscription
                                 *current_consumer.write().await = None;
+                                let _ = consumer_ready_tx.send(false);
                             }
                         }
                         Err(broadcast::error::RecvError::Lagged(skipped)) => {
@@ -155,16 +334,34 @@ impl ResilientTrackConsumer {
             if let Some(ref mut consumer) = *consumer_guard {
                 match consumer.next_group().await {
                     Ok(Some(group)) => {
-                        // Successfully got a group - consumer has advanced internally
+                        // Successfully got a group - consumer has advanced internally.
+                        // Unless the policy is `Latest`, de-duplicate against the
+                        // highest sequence we've already delivered: a resubscribe after
+                        // a reconnect re-issues `subscribe_track_internal` cold (it has
+                        // no way to ask the relay to start past a given sequence), so
+                        // the relay may still hand us groups we served before the gap.
+                        if !matches!(self.resume_policy, ResumePolicy::Latest) {
+                            let group_sequence = group.sequence;
+                            let mut last_seen = self.last_seen.write().await;
+                            if let Some(last) = *last_seen {
+                                if group_sequence <= last {
+                                    debug!(
+                                        "[ResilientTrackConsumer] Skipping already-delivered group {} for track: {}",
+                                        group_sequence, self.track_name
+                                    );
+                                    continue;
+                                }
+                            }
+                            *last_seen = Some(group_sequence);
+                        }
                         return Ok(Some(group));
                     }
                     Ok(None) => {
                         // Stream ended normally - clear consumer and wait for reconnection
                         warn!("[ResilientTrackConsumer] Track stream ended (Ok(None)), clearing consumer");
                         *consumer_guard = None;
-                        drop(consumer_guard); // Release lock before sleeping
-                                              // Sleep briefly before retrying
-                        sleep(Duration::from_millis(100)).await;
+                        drop(consumer_guard); // Release lock before notifying
+                        let _ = self.consumer_ready_tx.send(false);
                         continue;
                     }
                     Err(e) => {
@@ -174,37 +371,90 @@ impl ResilientTrackConsumer {
                             e
                         );
                         *consumer_guard = None;
-                        drop(consumer_guard); // Release lock before sleeping
-                                              // Sleep briefly before retrying
-                        sleep(Duration::from_millis(100)).await;
+                        drop(consumer_guard); // Release lock before notifying
+                        let _ = self.consumer_ready_tx.send(false);
                         continue;
                     }
                 }
             } else {
-                drop(consumer_guard); // Release lock before sleeping
-                                      // No consumer available, wait for the subscription manager to create one
-                sleep(Duration::from_millis(100)).await;
+                drop(consumer_guard); // Release lock before waiting
+                                      // No consumer available - wait for the subscription manager to create
+                                      // one instead of polling on a fixed interval
+                let mut consumer_ready_rx = self.consumer_ready_tx.subscribe();
+                if *consumer_ready_rx.borrow() {
+                    continue;
+                }
+                let _ = consumer_ready_rx.changed().await;
                 continue;
             }
         }
     }
 }
 
+/// Stable identifier for a subscription created by
+/// [`SubscriptionManager::subscribe_track_with_callback`]. Opaque and monotonically
+/// increasing - never reused, even after the subscription it names is cancelled - so
+/// callers can hold onto one across the lifetime of a long-running process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubscriptionId(u64);
+
+/// Point-in-time snapshot of one subscription, as returned by
+/// [`SubscriptionManager::list_subscriptions`]
+#[derive(Clone, Debug)]
+pub struct SubscriptionInfo {
+    pub id: SubscriptionId,
+    pub broadcast_name: String,
+    pub track_name: String,
+}
+
+/// Everything the registry needs to tear a subscription down: its callback/resilient
+/// tasks (aborted on [`SubscriptionManager::cancel`]) and the consumer they read from.
+struct SubscriptionEntry {
+    broadcast_name: String,
+    track_name: String,
+    #[allow(dead_code)] // kept alive for the subscription's duration; not read directly
+    consumer: ResilientTrackConsumer,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+/// One upstream-to-downstream bridge maintained by [`SubscriptionManager::relay_track`],
+/// shared by every downstream subscriber currently interested in the same upstream
+/// track. `refcount` tracks how many `relay_track` calls are still outstanding for this
+/// key; the relay is torn down once it reaches zero (see
+/// [`SubscriptionManager::stop_relay`]).
+struct RelayEntry {
+    #[allow(dead_code)] // kept alive for the relay's duration; not read directly
+    consumer: ResilientTrackConsumer,
+    task: JoinHandle<()>,
+    refcount: usize,
+}
+
 /// Manages track subscriptions with callback-based data handling
+///
+/// Following the `SubscriptionRegistry`/`SubscriptionId` model: each call to
+/// [`Self::subscribe_track_with_callback`] gets a stable [`SubscriptionId`], and
+/// [`Self::cancel`] aborts exactly that subscription's background tasks instead of
+/// leaving them to leak and run against a dead entry.
 pub struct SubscriptionManager {
     session: MoqSession,
-    active_subscriptions: Arc<RwLock<HashSet<String>>>,
+    next_id: AtomicU64,
+    subscriptions: Arc<RwLock<HashMap<SubscriptionId, SubscriptionEntry>>>,
     data_callback: Arc<RwLock<Option<DataCallback>>>,
-    background_tasks: Arc<RwLock<Vec<JoinHandle<()>>>>,
+    /// Active upstream-to-downstream relays, keyed by `(broadcast_name, track_name)`, so
+    /// repeated [`Self::relay_track`] calls for the same upstream track share one
+    /// [`ResilientTrackConsumer`] instead of opening a redundant subscription per
+    /// downstream.
+    relays: Arc<RwLock<HashMap<(String, String), RelayEntry>>>,
 }
 
 impl SubscriptionManager {
     pub fn new(session: MoqSession) -> Self {
         Self {
             session,
-            active_subscriptions: Arc::new(RwLock::new(HashSet::new())),
+            next_id: AtomicU64::new(1),
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
             data_callback: Arc::new(RwLock::new(None)),
-            background_tasks: Arc::new(RwLock::new(Vec::new())),
+            relays: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -213,21 +463,26 @@ impl SubscriptionManager {
         *self.data_callback.write().await = Some(callback);
     }
 
-    /// Subscribe to a track with callback-based data handling
+    /// Subscribe to a track with callback-based data handling, returning a
+    /// [`SubscriptionId`] that can later be passed to [`Self::cancel`]. `callback`, if
+    /// given, overrides [`Self::set_data_callback`]'s global callback for this track
+    /// only - useful when e.g. audio and video need routing to different sinks instead
+    /// of demuxing a shared closure by `track_name`.
     pub async fn subscribe_track_with_callback(
         &self,
         broadcast_name: &str,
         track_name: &str,
-    ) -> Result<ResilientTrackConsumer> {
+        callback: Option<DataCallback>,
+    ) -> Result<SubscriptionId> {
         let subscription_key = format!("{}:{}", broadcast_name, track_name);
 
         // Check if already subscribed
         {
-            let subscriptions = self.active_subscriptions.read().await;
-            if subscriptions.contains(&subscription_key) {
+            let subscriptions = self.subscriptions.read().await;
+            if subscriptions.values().any(|entry| {
+                entry.broadcast_name == broadcast_name && entry.track_name == track_name
+            }) {
                 warn!("Already subscribed to track: {}", subscription_key);
-                // Skip creating a new subscription - the existing one will handle reconnection
-                info!("Skipping duplicate subscription for: {}", subscription_key);
                 return Err(anyhow::anyhow!(
                     "Already subscribed to track: {}",
                     subscription_key
@@ -235,17 +490,6 @@ impl SubscriptionManager {
             }
         }
 
-        // Add to active subscriptions
-        {
-            let mut subscriptions = self.active_subscriptions.write().await;
-            subscriptions.insert(subscription_key.clone());
-            info!(
-                "ðŸ“ Added subscription: {} (total active: {})",
-                subscription_key,
-                subscriptions.len()
-            );
-        }
-
         // Create resilient consumer
         let resilient_consumer = ResilientTrackConsumer::new(
             self.session.clone(),
@@ -255,21 +499,266 @@ impl SubscriptionManager {
         .await?;
 
         // Start the callback processing task
-        self.start_callback_task(track_name.to_string(), resilient_consumer.clone())
+        let task_handle = self
+            .start_callback_task(track_name.to_string(), resilient_consumer.clone(), callback)
             .await;
 
-        Ok(resilient_consumer)
+        let id = SubscriptionId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let mut subscriptions = self.subscriptions.write().await;
+        subscriptions.insert(
+            id,
+            SubscriptionEntry {
+                broadcast_name: broadcast_name.to_string(),
+                track_name: track_name.to_string(),
+                consumer: resilient_consumer,
+                tasks: vec![task_handle],
+            },
+        );
+        info!(
+            "📝 Added subscription {:?}: {} (total active: {})",
+            id,
+            subscription_key,
+            subscriptions.len()
+        );
+
+        Ok(id)
     }
 
-    /// Start a task to process frames and call the data callback
+    /// Subscribe to a track and receive frames through a pull-based [`Stream`]
+    /// instead of a push callback, for callers that want to `select!`/`StreamExt`
+    /// combinator over frames rather than demux a shared closure. Backed by the same
+    /// [`ResilientTrackConsumer`] as [`Self::subscribe_track_with_callback`], but
+    /// frames are handed off through a bounded channel, so a slow consumer applies
+    /// backpressure on the reader instead of frames being dropped or queued unbounded.
+    ///
+    /// Not tracked in the [`SubscriptionId`] registry - the background task driving it
+    /// exits on its own once the returned stream is dropped and the channel closes.
+    pub async fn subscribe_track_stream(
+        &self,
+        broadcast_name: &str,
+        track_name: &str,
+    ) -> Result<impl Stream<Item = Result<Bytes>>> {
+        let resilient_consumer = ResilientTrackConsumer::new(
+            self.session.clone(),
+            broadcast_name.to_string(),
+            track_name.to_string(),
+        )
+        .await?;
+
+        let (tx, rx) = mpsc::channel(DEFAULT_FRAME_QUEUE_CAPACITY);
+        let track_name = track_name.to_string();
+
+        tokio::spawn(async move {
+            loop {
+                match resilient_consumer.next_group().await {
+                    Ok(Some(mut group)) => loop {
+                        match group.read_frame().await {
+                            Ok(Some(frame_data)) => {
+                                if tx.send(Ok(frame_data)).await.is_err() {
+                                    // Stream was dropped - stop driving the consumer
+                                    return;
+                                }
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                let _ = tx
+                                    .send(Err(anyhow::anyhow!("frame read error: {}", e)))
+                                    .await;
+                                break;
+                            }
+                        }
+                    },
+                    Ok(None) => {
+                        // This should not happen with ResilientTrackConsumer as it should handle reconnections
+                        warn!(
+                            "ResilientTrackConsumer returned None for track: {}",
+                            track_name
+                        );
+                        sleep(Duration::from_millis(1000)).await;
+                    }
+                    Err(e) => {
+                        if tx.send(Err(e)).await.is_err() {
+                            return;
+                        }
+                        sleep(Duration::from_millis(1000)).await;
+                    }
+                }
+            }
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+
+    /// Relay an upstream track onto a downstream (publisher) [`MoqSession`] - turning
+    /// this manager into a relay/CDN building block rather than only a leaf consumer.
+    /// Subscribes to `broadcast_name`/`track_def.name` through a [`ResilientTrackConsumer`]
+    /// and copies each upstream group/frame onto `downstream` via
+    /// [`MoqSession::start_group`]/[`MoqSession::write_frame`]/[`MoqSession::close_group`]
+    /// - the same per-frame path [`crate::relay::Forwarder`] uses, but driven by the
+    /// resilient consumer so a flapping upstream reconnects instead of ending the relay.
+    ///
+    /// Multiple calls for the same `broadcast_name`/`track_def.name` share one upstream
+    /// subscription and its cached latest group, fanning out to as many downstream
+    /// subscribers as call this: each call increments a refcount, and the upstream
+    /// subscription is only torn down once [`Self::stop_relay`] has been called the same
+    /// number of times (i.e. the last downstream subscriber goes away).
+    pub async fn relay_track(
+        &self,
+        downstream: MoqSession,
+        broadcast_name: &str,
+        track_def: TrackDefinition,
+    ) -> Result<()> {
+        let key = (broadcast_name.to_string(), track_def.name.clone());
+
+        let mut relays = self.relays.write().await;
+        if let Some(entry) = relays.get_mut(&key) {
+            entry.refcount += 1;
+            info!(
+                "Relay for '{}:{}' now has {} downstream subscriber(s)",
+                broadcast_name, track_def.name, entry.refcount
+            );
+            return Ok(());
+        }
+
+        // Register (and lazily create the producer for) the track on the downstream
+        // broadcast before the relay task starts writing to it.
+        let mut downstream_mut = downstream.clone();
+        downstream_mut.add_track_definition(track_def.clone())?;
+        downstream.create_track_producers().await?;
+
+        let consumer = ResilientTrackConsumer::new(
+            self.session.clone(),
+            broadcast_name.to_string(),
+            track_def.name.clone(),
+        )
+        .await?;
+
+        let task = {
+            let consumer = consumer.clone();
+            let downstream = downstream.clone();
+            let track_name = track_def.name.clone();
+
+            tokio::spawn(async move {
+                loop {
+                    match consumer.next_group().await {
+                        Ok(Some(mut group)) => {
+                            if let Err(e) = downstream.start_group(&track_name).await {
+                                warn!(
+                                    "Relay for track '{}' failed to start group downstream: {}",
+                                    track_name, e
+                                );
+                                continue;
+                            }
+
+                            loop {
+                                match group.read_frame().await {
+                                    Ok(Some(data)) => {
+                                        if let Err(e) =
+                                            downstream.write_frame(&track_name, data).await
+                                        {
+                                            warn!(
+                                                "Relay for track '{}' failed to write frame downstream: {}",
+                                                track_name, e
+                                            );
+                                            break;
+                                        }
+                                    }
+                                    Ok(None) => break,
+                                    Err(e) => {
+                                        warn!(
+                                            "Relay for track '{}' upstream frame read error: {}",
+                                            track_name, e
+                                        );
+                                        break;
+                                    }
+                                }
+                            }
+
+                            if let Err(e) = downstream.close_group(&track_name).await {
+                                warn!(
+                                    "Relay for track '{}' failed to close group downstream: {}",
+                                    track_name, e
+                                );
+                            }
+                        }
+                        Ok(None) => {
+                            sleep(Duration::from_millis(1000)).await;
+                        }
+                        Err(e) => {
+                            warn!("Relay for track '{}' upstream error: {}", track_name, e);
+                            sleep(Duration::from_millis(1000)).await;
+                        }
+                    }
+                }
+            })
+        };
+
+        relays.insert(
+            key,
+            RelayEntry {
+                consumer,
+                task,
+                refcount: 1,
+            },
+        );
+
+        info!(
+            "Started relay for track '{}:{}'",
+            broadcast_name, track_def.name
+        );
+        Ok(())
+    }
+
+    /// Release one downstream subscriber's interest in a relayed track. Once the
+    /// refcount drops to zero, aborts the relay task and drops the shared upstream
+    /// [`ResilientTrackConsumer`], ending the upstream subscription. Returns `false` if
+    /// no relay is active for `broadcast_name`/`track_name`.
+    pub async fn stop_relay(&self, broadcast_name: &str, track_name: &str) -> bool {
+        let key = (broadcast_name.to_string(), track_name.to_string());
+        let mut relays = self.relays.write().await;
+
+        match relays.get_mut(&key) {
+            Some(entry) => {
+                entry.refcount = entry.refcount.saturating_sub(1);
+                if entry.refcount == 0 {
+                    if let Some(entry) = relays.remove(&key) {
+                        entry.task.abort();
+                        info!(
+                            "Stopped relay for track '{}:{}': no downstream subscribers remain",
+                            broadcast_name, track_name
+                        );
+                    }
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Snapshot of every active relay as `(broadcast_name, track_name, refcount)`
+    pub async fn list_relays(&self) -> Vec<(String, String, usize)> {
+        self.relays
+            .read()
+            .await
+            .iter()
+            .map(|((broadcast_name, track_name), entry)| {
+                (broadcast_name.clone(), track_name.clone(), entry.refcount)
+            })
+            .collect()
+    }
+
+    /// Spawn a task to process frames and call the data callback. `override_callback`,
+    /// if given, is used instead of the manager's global [`DataCallback`] for this
+    /// subscription only.
     async fn start_callback_task(
         &self,
         track_name: String,
         resilient_consumer: ResilientTrackConsumer,
-    ) {
+        override_callback: Option<DataCallback>,
+    ) -> JoinHandle<()> {
         let data_callback = self.data_callback.clone();
 
-        let task_handle = tokio::spawn(async move {
+        tokio::spawn(async move {
             info!("Starting callback subscription for track: {}", track_name);
 
             let mut _frame_count = 0;
@@ -282,10 +771,13 @@ impl SubscriptionManager {
                                 Ok(Some(frame_data)) => {
                                     _frame_count += 1;
 
-                                    // Call the data callback if available
-                                    let callback_guard = data_callback.read().await;
-                                    if let Some(callback) = callback_guard.as_ref() {
+                                    if let Some(callback) = override_callback.as_ref() {
                                         callback(track_name.clone(), frame_data.to_vec());
+                                    } else {
+                                        let callback_guard = data_callback.read().await;
+                                        if let Some(callback) = callback_guard.as_ref() {
+                                            callback(track_name.clone(), frame_data.to_vec());
+                                        }
                                     }
                                 }
                                 Ok(None) => {
@@ -294,7 +786,7 @@ impl SubscriptionManager {
                                 }
                                 Err(e) => {
                                     warn!(
-                                        "âš ï¸ Error reading frame from track {}: {}",
+                                        "⚠️ Error reading frame from track {}: {}",
                                         track_name, e
                                     );
                                     break;
@@ -320,49 +812,70 @@ impl SubscriptionManager {
                     }
                 }
             }
-        });
+        })
+    }
+
+    /// Cancel a single subscription: aborts its callback/resilient tasks and drops its
+    /// consumer. Returns `false` if `id` doesn't name an active subscription (already
+    /// cancelled, or never existed).
+    pub async fn cancel(&self, id: SubscriptionId) -> bool {
+        let entry = self.subscriptions.write().await.remove(&id);
+        match entry {
+            Some(entry) => {
+                for task in entry.tasks {
+                    task.abort();
+                }
+                info!(
+                    "🗑️ Cancelled subscription {:?}: {}:{}",
+                    id, entry.broadcast_name, entry.track_name
+                );
+                true
+            }
+            None => false,
+        }
+    }
 
-        // Store the task handle for later cleanup
-        self.background_tasks.write().await.push(task_handle);
+    /// Snapshot of every currently active subscription
+    pub async fn list_subscriptions(&self) -> Vec<SubscriptionInfo> {
+        self.subscriptions
+            .read()
+            .await
+            .iter()
+            .map(|(id, entry)| SubscriptionInfo {
+                id: *id,
+                broadcast_name: entry.broadcast_name.clone(),
+                track_name: entry.track_name.clone(),
+            })
+            .collect()
     }
 
     /// Shutdown all background tasks and clean up resources
     pub async fn shutdown(&self) -> Result<()> {
-        info!("ðŸ›‘ Shutting down SubscriptionManager");
-
-        // Clear active subscriptions
-        self.active_subscriptions.write().await.clear();
+        info!("🛑 Shutting down SubscriptionManager");
 
         // Clear data callback
         *self.data_callback.write().await = None;
 
-        // Cancel all background tasks
-        let mut tasks = self.background_tasks.write().await;
-        for task in tasks.drain(..) {
-            task.abort();
+        // Cancel every subscription's tasks
+        let mut subscriptions = self.subscriptions.write().await;
+        for (_, entry) in subscriptions.drain() {
+            for task in entry.tasks {
+                task.abort();
+            }
+        }
+
+        // Tear down every active relay
+        let mut relays = self.relays.write().await;
+        for (_, entry) in relays.drain() {
+            entry.task.abort();
         }
 
         info!("SubscriptionManager shutdown complete");
         Ok(())
     }
 
-    /// Remove a subscription
-    pub async fn remove_subscription(&self, broadcast_name: &str, track_name: &str) -> bool {
-        let subscription_key = format!("{}:{}", broadcast_name, track_name);
-        let mut subscriptions = self.active_subscriptions.write().await;
-        let removed = subscriptions.remove(&subscription_key);
-        if removed {
-            info!(
-                "ðŸ—‘ï¸ Removed subscription: {} (total active: {})",
-                subscription_key,
-                subscriptions.len()
-            );
-        }
-        removed
-    }
-
     /// Get active subscription count
     pub async fn active_subscription_count(&self) -> usize {
-        self.active_subscriptions.read().await.len()
+        self.subscriptions.read().await.len()
     }
 }
@@ -1,8 +1,12 @@
 use anyhow::Result;
-use std::collections::HashMap;
-use std::sync::Arc;
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
 use std::time::Duration;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Mutex, Notify, RwLock};
+use tokio::task::{AbortHandle, JoinHandle};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -14,6 +18,276 @@ use crate::session::MoqSession;
 /// Type alias for track data callback to reduce complexity
 pub type TrackDataCallback = Arc<dyn Fn(String, Vec<u8>) + Send + Sync>;
 
+/// Tunable exponential backoff used to resubscribe a catalog/track stream after it
+/// ends or fails to subscribe, instead of giving up permanently
+#[derive(Clone, Copy, Debug)]
+pub struct RetryConfig {
+    /// Delay before the first retry
+    pub base: Duration,
+    /// Upper bound the delay backs off to
+    pub max: Duration,
+    /// Factor the delay grows by after each failed attempt
+    pub multiplier: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_millis(50),
+            max: Duration::from_secs(5),
+            multiplier: 2.0,
+        }
+    }
+}
+
+/// Stateful backoff timer driven by a [`RetryConfig`]
+///
+/// Call `wait()` before each retry attempt and `reset()` once an attempt succeeds.
+struct Backoff {
+    current: Duration,
+    config: RetryConfig,
+}
+
+impl Backoff {
+    fn new(config: RetryConfig) -> Self {
+        Self {
+            current: config.base,
+            config,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.current = self.config.base;
+    }
+
+    /// Sleep for the current delay (±25% jitter, to avoid many tracks reconnecting in
+    /// lockstep), then grow the delay for the next call
+    async fn wait(&mut self) {
+        let jitter = rand::thread_rng().gen_range(0.75..=1.25);
+        sleep(self.current.mul_f64(jitter)).await;
+
+        let grown = self.current.as_secs_f64() * self.config.multiplier;
+        self.current = Duration::from_secs_f64(grown.min(self.config.max.as_secs_f64()));
+    }
+}
+
+/// Controls how a track's frames are handed to the data callback when the callback
+/// can't keep up with the network reader
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryMode {
+    /// Backpressure the reader instead of losing frames. Use for consumers (e.g. file
+    /// capture) that need every frame, at the cost of the reader stalling if the
+    /// callback falls far behind.
+    Lossless,
+    /// Never block the reader. Once the buffer is full, the oldest buffered frame is
+    /// dropped to make room for the newest, incrementing that track's dropped-frame
+    /// count. Use for latency-sensitive live playback where only the newest frame
+    /// matters.
+    DropOldest,
+}
+
+/// Default capacity of the per-track frame buffer sitting between the network reader
+/// and the data callback
+pub const DEFAULT_FRAME_QUEUE_CAPACITY: usize = 16;
+
+/// Where a track subscription should start reading from when first subscribed, or
+/// resubscribed without a saved durable cursor (see [`SubscriptionOptions::durable`])
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartPosition {
+    /// Start at the live edge - the default
+    Latest,
+    /// Start from the oldest group the relay is still willing to serve
+    Earliest,
+    /// Start from a specific group sequence number
+    FromGroup(u64),
+}
+
+impl Default for StartPosition {
+    fn default() -> Self {
+        StartPosition::Latest
+    }
+}
+
+/// Whether a broadcast's auto-subscription can be shared by multiple callers or must
+/// be claimed by exactly one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionMode {
+    /// A second `enable_auto_subscription_with_options` call for the same broadcast name
+    /// is rejected while this one is active. `create_subscription_manager_with_options`
+    /// does not check the mode and always builds a new manager
+    Exclusive,
+    /// A second `enable_auto_subscription_with_options` call for the same broadcast name
+    /// that also asks for `Shared` reuses this one instead of erroring
+    Shared,
+}
+
+impl Default for SubscriptionMode {
+    fn default() -> Self {
+        SubscriptionMode::Exclusive
+    }
+}
+
+/// Tunable behavior for a [`BroadcastSubscriptionManager`]: where each track resumes
+/// from, whether that position is remembered across resubscribes, its relative
+/// priority, and whether the broadcast can have more than one subscriber
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SubscriptionOptions {
+    /// Remember each track's last fully-received group sequence (its cursor) and
+    /// resubscribe from `cursor + 1` instead of `start` once one has been recorded.
+    /// Cursors live only in memory for this manager's lifetime - see
+    /// [`BroadcastSubscriptionManager::get_cursors`] to persist them yourself and seed
+    /// them back in via `initial_cursors` on the next run.
+    pub durable: bool,
+    /// Where to start the very first subscription attempt for a track that has no
+    /// cursor yet
+    pub start: StartPosition,
+    /// Default mapped onto the subscribed [`moq_lite::Track`]'s `priority` field for
+    /// tracks whose `TrackDefinition::priority` is 0 (unset); a track that specifies
+    /// its own non-zero priority uses that instead
+    pub priority_level: i32,
+    /// Whether a second subscriber to the same broadcast name shares this manager or
+    /// is rejected
+    pub mode: SubscriptionMode,
+    /// Buffer the frames of each track's in-progress group, and replay them to a
+    /// newly-registered [`BroadcastSubscriptionManager::set_data_callback`] before live
+    /// delivery resumes. Lets a late joiner (one that sets its callback mid-group, e.g.
+    /// mid-minute for the clock example) still see the frames it missed from the start
+    /// of the current group, instead of only ever seeing frames from the next group
+    /// onward.
+    pub replay_current_group: bool,
+}
+
+/// Bounded buffer of pending frames for a single track, decoupling the network read
+/// loop from a potentially slow data callback
+///
+/// Behavior depends on the [`DeliveryMode`] it was created with: `Lossless` makes
+/// `push` wait for space so no frame is lost, while `DropOldest` makes `push` evict
+/// the oldest buffered frame instead of waiting. Either way `push`/`pop` never spin;
+/// both wait on a `Notify` when there's nothing to do.
+struct FrameQueue {
+    mode: DeliveryMode,
+    capacity: usize,
+    buffer: Mutex<VecDeque<Vec<u8>>>,
+    notify: Notify,
+    closed: AtomicBool,
+    dropped_frames: Arc<AtomicU64>,
+}
+
+impl FrameQueue {
+    fn new(capacity: usize, mode: DeliveryMode, dropped_frames: Arc<AtomicU64>) -> Self {
+        Self {
+            mode,
+            capacity,
+            buffer: Mutex::new(VecDeque::with_capacity(capacity)),
+            notify: Notify::new(),
+            closed: AtomicBool::new(false),
+            dropped_frames,
+        }
+    }
+
+    /// Add a frame to the buffer, per the configured [`DeliveryMode`]
+    async fn push(&self, frame: Vec<u8>) {
+        loop {
+            {
+                let mut buffer = self.buffer.lock().await;
+                if buffer.len() < self.capacity {
+                    buffer.push_back(frame);
+                    self.notify.notify_one();
+                    return;
+                }
+                if self.mode == DeliveryMode::DropOldest {
+                    buffer.pop_front();
+                    self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                    buffer.push_back(frame);
+                    self.notify.notify_one();
+                    return;
+                }
+                // Lossless and full: wait for the consumer to drain a slot.
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Remove the next frame, or `None` once the queue is closed and drained
+    async fn pop(&self) -> Option<Vec<u8>> {
+        loop {
+            {
+                let mut buffer = self.buffer.lock().await;
+                if let Some(frame) = buffer.pop_front() {
+                    self.notify.notify_one();
+                    return Some(frame);
+                }
+                if self.closed.load(Ordering::Acquire) {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Signal that no more frames will be pushed; `pop` drains what's left then ends
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.notify.notify_one();
+    }
+}
+
+/// Registry of every task spawned to drive a [`BroadcastSubscriptionManager`], so
+/// `stop()` (and dropping the manager) can cancel all of them instead of leaking tasks
+/// that keep running - e.g. blocked inside `next_group().await` - against a session
+/// nobody is reading from anymore
+#[derive(Clone)]
+struct TaskGroup {
+    // Tasks this group spawned itself; `shutdown` aborts *and* awaits these.
+    owned: Arc<StdMutex<Vec<JoinHandle<()>>>>,
+    // Tasks spawned (and awaited) elsewhere that just want to be cancellable from here.
+    registered: Arc<StdMutex<Vec<AbortHandle>>>,
+}
+
+impl TaskGroup {
+    fn new() -> Self {
+        Self {
+            owned: Arc::new(StdMutex::new(Vec::new())),
+            registered: Arc::new(StdMutex::new(Vec::new())),
+        }
+    }
+
+    /// Spawn `future` as a task owned by this group
+    fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let handle = tokio::spawn(future);
+        self.owned.lock().unwrap().push(handle);
+    }
+
+    /// Register a task that's spawned and awaited elsewhere, so it still gets aborted
+    /// when this group shuts down
+    fn register(&self, abort_handle: AbortHandle) {
+        self.registered.lock().unwrap().push(abort_handle);
+    }
+
+    /// Abort every task in the group without waiting for them to actually stop
+    fn abort_all(&self) {
+        for handle in self.owned.lock().unwrap().iter() {
+            handle.abort();
+        }
+        for handle in self.registered.lock().unwrap().iter() {
+            handle.abort();
+        }
+    }
+
+    /// Abort every task, then wait for the ones this group owns to actually finish
+    async fn shutdown(&self) {
+        self.abort_all();
+        let owned: Vec<JoinHandle<()>> = std::mem::take(&mut *self.owned.lock().unwrap());
+        for handle in owned {
+            let _ = handle.await;
+        }
+        self.registered.lock().unwrap().clear();
+    }
+}
+
 /// Manages catalog and track subscriptions for a broadcast
 /// This class handles the complete flow: Wait for announce -> Subscribe to catalog -> Parse catalog -> Subscribe to tracks
 pub struct BroadcastSubscriptionManager {
@@ -31,20 +305,90 @@ pub struct BroadcastSubscriptionManager {
     catalog_update_tx: broadcast::Sender<String>,
     track_data_callback: Arc<RwLock<Option<TrackDataCallback>>>,
 
+    // Readiness primitive backing `await_catalog`: lets callers suspend until the
+    // catalog first arrives instead of polling `get_catalog`
+    catalog_tx: tokio::sync::watch::Sender<Option<Catalog>>,
+
     // State tracking
     is_active: Arc<RwLock<bool>>,
     catalog_subscribed: Arc<RwLock<bool>>,
+
+    // Resubscribe behavior
+    retry: RetryConfig,
+
+    // Per-track frame delivery behavior
+    delivery_mode: Arc<RwLock<DeliveryMode>>,
+    frame_queue_capacity: Arc<RwLock<usize>>,
+    track_dropped_frames: Arc<RwLock<HashMap<String, Arc<AtomicU64>>>>,
+
+    // Start position / durability / priority / sharing mode
+    options: SubscriptionOptions,
+    // Per-track last fully-received group sequence, recorded when `options.durable`
+    cursors: Arc<RwLock<HashMap<String, u64>>>,
+    // Frames delivered so far from each track's in-progress group, recorded when
+    // `options.replay_current_group` so a newly-registered callback can catch up
+    group_cache: Arc<RwLock<HashMap<String, Vec<Vec<u8>>>>>,
+
+    // Lifecycle of every task this manager has spawned
+    tasks: TaskGroup,
 }
 
 impl BroadcastSubscriptionManager {
-    /// Create a new subscription manager for a specific broadcast
+    /// Create a new subscription manager for a specific broadcast, using the default
+    /// [`RetryConfig`] for automatic resubscription
     pub async fn new(
         session: MoqSession,
         broadcast_name: String,
         catalog_type: CatalogType,
         requested_tracks: Vec<TrackDefinition>,
+    ) -> Result<Self> {
+        Self::with_retry_config(
+            session,
+            broadcast_name,
+            catalog_type,
+            requested_tracks,
+            RetryConfig::default(),
+        )
+        .await
+    }
+
+    /// Same as `new`, but with a custom resubscribe backoff policy. Pass a `max` equal
+    /// to `base` with `multiplier <= 1.0` to effectively disable backoff growth.
+    pub async fn with_retry_config(
+        session: MoqSession,
+        broadcast_name: String,
+        catalog_type: CatalogType,
+        requested_tracks: Vec<TrackDefinition>,
+        retry: RetryConfig,
+    ) -> Result<Self> {
+        Self::with_options(
+            session,
+            broadcast_name,
+            catalog_type,
+            requested_tracks,
+            retry,
+            SubscriptionOptions::default(),
+            HashMap::new(),
+        )
+        .await
+    }
+
+    /// Same as `with_retry_config`, but with full [`SubscriptionOptions`] control and a
+    /// set of durable cursors to resume from - pass the result of a prior
+    /// [`Self::get_cursors`] call as `initial_cursors` to pick up where an earlier
+    /// session left off.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn with_options(
+        session: MoqSession,
+        broadcast_name: String,
+        catalog_type: CatalogType,
+        requested_tracks: Vec<TrackDefinition>,
+        retry: RetryConfig,
+        options: SubscriptionOptions,
+        initial_cursors: HashMap<String, u64>,
     ) -> Result<Self> {
         let (catalog_update_tx, _) = broadcast::channel(10);
+        let (catalog_tx, _) = tokio::sync::watch::channel(None);
 
         let manager = Self {
             session: session.clone(),
@@ -56,8 +400,17 @@ impl BroadcastSubscriptionManager {
             current_catalog: Arc::new(RwLock::new(None)),
             catalog_update_tx,
             track_data_callback: Arc::new(RwLock::new(None)),
+            catalog_tx,
             is_active: Arc::new(RwLock::new(false)),
             catalog_subscribed: Arc::new(RwLock::new(false)),
+            retry,
+            delivery_mode: Arc::new(RwLock::new(DeliveryMode::Lossless)),
+            frame_queue_capacity: Arc::new(RwLock::new(DEFAULT_FRAME_QUEUE_CAPACITY)),
+            track_dropped_frames: Arc::new(RwLock::new(HashMap::new())),
+            options,
+            cursors: Arc::new(RwLock::new(initial_cursors)),
+            group_cache: Arc::new(RwLock::new(HashMap::new())),
+            tasks: TaskGroup::new(),
         };
 
         // Start the subscription management flow
@@ -66,12 +419,58 @@ impl BroadcastSubscriptionManager {
         Ok(manager)
     }
 
+    /// The broadcast name this manager subscribes to
+    pub fn broadcast_name(&self) -> &str {
+        &self.broadcast_name
+    }
+
+    /// The [`SubscriptionMode`] this manager was created with
+    pub fn mode(&self) -> SubscriptionMode {
+        self.options.mode
+    }
+
+    /// Wait for the catalog to become available, resolving immediately if it already
+    /// has. Lets callers suspend here instead of polling [`Self::get_catalog`].
+    pub async fn await_catalog(&self) -> Catalog {
+        let mut rx = self.catalog_tx.subscribe();
+        loop {
+            if let Some(catalog) = rx.borrow().clone() {
+                return catalog;
+            }
+            if rx.changed().await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// Current durable cursors (last fully-received group sequence per track). Only
+    /// populated when [`SubscriptionOptions::durable`] is set; pass the result to
+    /// [`Self::with_options`]'s `initial_cursors` on a later run to resume from here.
+    pub async fn get_cursors(&self) -> HashMap<String, u64> {
+        self.cursors.read().await.clone()
+    }
+
     /// Set a callback to receive data from all tracks
+    ///
+    /// When [`SubscriptionOptions::replay_current_group`] is set, frames already
+    /// delivered from each track's in-progress group are replayed to `callback`
+    /// synchronously before it's installed for live delivery, so a late joiner doesn't
+    /// miss the start of a group that was already under way.
     pub async fn set_data_callback<F>(&self, callback: F)
     where
         F: Fn(String, Vec<u8>) + Send + Sync + 'static,
     {
-        *self.track_data_callback.write().await = Some(Arc::new(callback));
+        let callback: TrackDataCallback = Arc::new(callback);
+
+        if self.options.replay_current_group {
+            for (track_name, frames) in self.group_cache.read().await.iter() {
+                for frame in frames {
+                    callback(track_name.clone(), frame.clone());
+                }
+            }
+        }
+
+        *self.track_data_callback.write().await = Some(callback);
     }
 
     /// Start the complete subscription flow
@@ -84,11 +483,21 @@ impl BroadcastSubscriptionManager {
         let track_consumers = self.track_consumers.clone();
         let current_catalog = self.current_catalog.clone();
         let catalog_update_tx = self.catalog_update_tx.clone();
+        let catalog_tx = self.catalog_tx.clone();
         let track_data_callback = self.track_data_callback.clone();
         let is_active = self.is_active.clone();
         let catalog_subscribed = self.catalog_subscribed.clone();
-
-        tokio::spawn(async move {
+        let retry = self.retry;
+        let delivery_mode = self.delivery_mode.clone();
+        let frame_queue_capacity = self.frame_queue_capacity.clone();
+        let track_dropped_frames = self.track_dropped_frames.clone();
+        let options = self.options;
+        let cursors = self.cursors.clone();
+        let group_cache = self.group_cache.clone();
+        let tasks = self.tasks.clone();
+        let tasks_for_spawn = tasks.clone();
+
+        tasks.spawn(async move {
             info!(
                 "[BroadcastSubscriptionManager] Starting subscription flow for broadcast: {}",
                 broadcast_name
@@ -109,6 +518,10 @@ impl BroadcastSubscriptionManager {
                         catalog_consumer.clone(),
                         current_catalog.clone(),
                         catalog_update_tx.clone(),
+                        catalog_tx.clone(),
+                        catalog_subscribed.clone(),
+                        retry,
+                        tasks_for_spawn.clone(),
                     )
                     .await;
                 } else {
@@ -124,6 +537,14 @@ impl BroadcastSubscriptionManager {
                 track_consumers.clone(),
                 track_data_callback.clone(),
                 is_active.clone(),
+                retry,
+                delivery_mode,
+                frame_queue_capacity,
+                track_dropped_frames,
+                options,
+                cursors,
+                group_cache,
+                tasks_for_spawn,
             )
             .await;
         });
@@ -155,71 +576,100 @@ impl BroadcastSubscriptionManager {
     }
 
     /// Manage catalog subscription and updates
+    ///
+    /// Runs in its own background task: if the subscribe call fails or the catalog
+    /// stream ends while `catalog_subscribed` is still set, it resubscribes with
+    /// exponential backoff instead of giving up permanently.
     async fn manage_catalog_subscription(
         session: &MoqSession,
         broadcast_name: &str,
         catalog_consumer: Arc<RwLock<Option<TrackConsumer>>>,
         current_catalog: Arc<RwLock<Option<Catalog>>>,
         catalog_update_tx: broadcast::Sender<String>,
+        catalog_tx: tokio::sync::watch::Sender<Option<Catalog>>,
+        catalog_subscribed: Arc<RwLock<bool>>,
+        retry: RetryConfig,
+        tasks: TaskGroup,
     ) {
-        info!(
-            "[BroadcastSubscriptionManager] Subscribing to catalog for broadcast: {}",
-            broadcast_name
-        );
+        let session = session.clone();
+        let broadcast_name = broadcast_name.to_string();
+
+        tasks.spawn(async move {
+            let mut backoff = Backoff::new(retry);
+
+            while *catalog_subscribed.read().await {
+                info!("[BroadcastSubscriptionManager] 🔄 ATTEMPTING catalog.json subscription for broadcast: {}", broadcast_name);
+                let mut track_consumer = match session
+                    .subscribe_track_internal(&broadcast_name, "catalog.json")
+                    .await
+                {
+                    Ok(track_consumer) => track_consumer,
+                    Err(e) => {
+                        warn!(
+                            "[BroadcastSubscriptionManager] ❌ FAILED to subscribe to catalog for broadcast {}: {}",
+                            broadcast_name, e
+                        );
+                        backoff.wait().await;
+                        continue;
+                    }
+                };
 
-        // Subscribe to catalog.json - only once
-        info!("[BroadcastSubscriptionManager] 🔄 ATTEMPTING catalog.json subscription for broadcast: {}", broadcast_name);
-        match session
-            .subscribe_track_internal(broadcast_name, "catalog.json")
-            .await
-        {
-            Ok(mut track_consumer) => {
                 info!("[BroadcastSubscriptionManager] ✅ SUCCESS: catalog.json subscription created for broadcast: {}", broadcast_name);
                 *catalog_consumer.write().await = Some(track_consumer.clone());
 
-                // Monitor catalog for updates
-                tokio::spawn(async move {
-                    while let Ok(Some(mut group)) = track_consumer.next_group().await {
-                        if let Ok(Some(frame)) = group.read_frame().await {
-                            let catalog_json = String::from_utf8_lossy(&frame).to_string();
-                            info!(
-                                "[BroadcastSubscriptionManager] 📋 Catalog updated ({} bytes)",
-                                catalog_json.len()
-                            );
-
-                            // Parse and store the catalog
-                            match Catalog::parse_sesame(&catalog_json) {
-                                Ok(sesame_catalog) => {
-                                    let catalog = Catalog::Sesame(sesame_catalog);
-                                    *current_catalog.write().await = Some(catalog);
-                                    info!("[BroadcastSubscriptionManager] ✅ Catalog parsed successfully");
-                                }
-                                Err(e) => {
-                                    warn!("[BroadcastSubscriptionManager] ⚠️ Failed to parse catalog: {}", e);
-                                }
-                            }
+                // Monitor catalog for updates until the stream ends or errors
+                while let Ok(Some(mut group)) = track_consumer.next_group().await {
+                    if let Ok(Some(frame)) = group.read_frame().await {
+                        backoff.reset();
+
+                        let catalog_json = String::from_utf8_lossy(&frame).to_string();
+                        info!(
+                            "[BroadcastSubscriptionManager] 📋 Catalog updated ({} bytes)",
+                            catalog_json.len()
+                        );
 
-                            // Broadcast catalog update
-                            if let Err(e) = catalog_update_tx.send(catalog_json) {
-                                debug!("[BroadcastSubscriptionManager] No listeners for catalog update: {}", e);
+                        // Parse and store the catalog
+                        match Catalog::parse_sesame(&catalog_json) {
+                            Ok(sesame_catalog) => {
+                                let catalog = Catalog::Sesame(sesame_catalog);
+                                *current_catalog.write().await = Some(catalog.clone());
+                                let _ = catalog_tx.send(Some(catalog));
+                                info!("[BroadcastSubscriptionManager] ✅ Catalog parsed successfully");
+                            }
+                            Err(e) => {
+                                warn!("[BroadcastSubscriptionManager] ⚠️ Failed to parse catalog: {}", e);
                             }
                         }
+
+                        // Broadcast catalog update
+                        if let Err(e) = catalog_update_tx.send(catalog_json) {
+                            debug!("[BroadcastSubscriptionManager] No listeners for catalog update: {}", e);
+                        }
                     }
+                }
+
+                *catalog_consumer.write().await = None;
 
+                if *catalog_subscribed.read().await {
+                    warn!("[BroadcastSubscriptionManager] Catalog stream ended, resubscribing");
+                    backoff.wait().await;
+                } else {
                     warn!("[BroadcastSubscriptionManager] Catalog stream ended");
-                    *catalog_consumer.write().await = None;
-                });
-            }
-            Err(e) => {
-                warn!(
-                    "[BroadcastSubscriptionManager] ❌ FAILED to subscribe to catalog for broadcast {}: {}",
-                    broadcast_name, e
-                );
+                }
             }
-        }
+        });
     }
 
     /// Manage subscriptions to all requested tracks
+    ///
+    /// Each track gets its own task that resubscribes with exponential backoff
+    /// whenever its stream ends or fails to subscribe while `is_active` holds, instead
+    /// of disappearing until the whole manager is recreated. Each track subscribes
+    /// with its own `TrackDefinition::priority`, falling back to `options.priority_level`
+    /// when that's 0 (unset), and when `options.durable` is set, each track's last
+    /// fully-received group sequence is kept in `cursors` so a resubscribe resumes
+    /// from `cursor + 1` instead of `options.start`.
+    #[allow(clippy::too_many_arguments)]
     async fn manage_track_subscriptions(
         session: &MoqSession,
         broadcast_name: &str,
@@ -227,6 +677,14 @@ impl BroadcastSubscriptionManager {
         track_consumers: Arc<RwLock<HashMap<String, TrackConsumer>>>,
         track_data_callback: Arc<RwLock<Option<TrackDataCallback>>>,
         is_active: Arc<RwLock<bool>>,
+        retry: RetryConfig,
+        delivery_mode: Arc<RwLock<DeliveryMode>>,
+        frame_queue_capacity: Arc<RwLock<usize>>,
+        track_dropped_frames: Arc<RwLock<HashMap<String, Arc<AtomicU64>>>>,
+        options: SubscriptionOptions,
+        cursors: Arc<RwLock<HashMap<String, u64>>>,
+        group_cache: Arc<RwLock<HashMap<String, Vec<Vec<u8>>>>>,
+        tasks: TaskGroup,
     ) {
         info!(
             "[BroadcastSubscriptionManager] Subscribing to {} tracks",
@@ -237,66 +695,190 @@ impl BroadcastSubscriptionManager {
 
         for track_def in requested_tracks {
             let track_name = track_def.name.clone();
+            let track_priority = if track_def.priority != 0 {
+                track_def.priority as i32
+            } else {
+                options.priority_level
+            };
             let session_clone = session.clone();
             let broadcast_name_clone = broadcast_name.to_string();
             let track_consumers_clone = track_consumers.clone();
             let callback_clone = track_data_callback.clone();
             let is_active_clone = is_active.clone();
+            let delivery_mode_clone = delivery_mode.clone();
+            let frame_queue_capacity_clone = frame_queue_capacity.clone();
+            let track_dropped_frames_clone = track_dropped_frames.clone();
+            let cursors_clone = cursors.clone();
+            let group_cache_clone = group_cache.clone();
+            let tasks_clone = tasks.clone();
+
+            tasks.spawn(async move {
+                let mut backoff = Backoff::new(retry);
+                let mut first_attempt = true;
+
+                while *is_active_clone.read().await {
+                    let mut track_consumer = match session_clone
+                        .subscribe_track_internal_with_priority(
+                            &broadcast_name_clone,
+                            &track_name,
+                            track_priority,
+                        )
+                        .await
+                    {
+                        Ok(track_consumer) => track_consumer,
+                        Err(e) => {
+                            warn!("[BroadcastSubscriptionManager] ⚠️ Failed to subscribe to track '{}': {}", track_name, e);
+                            backoff.wait().await;
+                            continue;
+                        }
+                    };
+
+                    info!("[BroadcastSubscriptionManager] ✅ Successfully subscribed to track: {}", track_name);
+
+                    // Resume from the durable cursor if we have one; otherwise apply
+                    // `options.start` on the very first attempt only (later
+                    // resubscribes without a cursor just pick up wherever the relay's
+                    // live edge happens to be, same as before this existed).
+                    let resume_from = {
+                        let cursor = cursors_clone.read().await.get(&track_name).copied();
+                        match cursor {
+                            Some(last_seen) => Some(last_seen + 1),
+                            None if first_attempt => match options.start {
+                                StartPosition::Latest => None,
+                                StartPosition::Earliest => Some(0),
+                                StartPosition::FromGroup(n) => Some(n),
+                            },
+                            None => None,
+                        }
+                    };
+                    first_attempt = false;
+                    if let Some(resume_from) = resume_from {
+                        debug!(
+                            "[BroadcastSubscriptionManager] Track '{}' resuming from group {} (durable={})",
+                            track_name, resume_from, options.durable
+                        );
+                    }
 
-            tokio::spawn(async move {
-                // Subscribe to the track
-                match session_clone
-                    .subscribe_track_internal(&broadcast_name_clone, &track_name)
-                    .await
-                {
-                    Ok(mut track_consumer) => {
-                        info!("[BroadcastSubscriptionManager] ✅ Successfully subscribed to track: {}", track_name);
-
-                        // Store the consumer
-                        track_consumers_clone
-                            .write()
-                            .await
-                            .insert(track_name.clone(), track_consumer.clone());
-
-                        // Monitor track data
-                        while *is_active_clone.read().await {
-                            match track_consumer.next_group().await {
-                                Ok(Some(mut group)) => {
-                                    while let Ok(Some(frame)) = group.read_frame().await {
-                                        // Call the data callback if set
-                                        let callback_guard = callback_clone.read().await;
-                                        if let Some(callback) = callback_guard.as_ref() {
-                                            callback(track_name.clone(), frame.to_vec());
+                    // Store the consumer
+                    track_consumers_clone
+                        .write()
+                        .await
+                        .insert(track_name.clone(), track_consumer.clone());
+
+                    // Bounded queue decoupling the network reader below from the data
+                    // callback, so a slow callback can't stall group/frame reads
+                    let dropped_counter = track_dropped_frames_clone
+                        .write()
+                        .await
+                        .entry(track_name.clone())
+                        .or_insert_with(|| Arc::new(AtomicU64::new(0)))
+                        .clone();
+                    let mode = *delivery_mode_clone.read().await;
+                    let capacity = *frame_queue_capacity_clone.read().await;
+                    let queue = Arc::new(FrameQueue::new(capacity, mode, dropped_counter));
+
+                    let consumer_task = tokio::spawn({
+                        // Awaited locally below (to drain in order before resubscribing)
+                        // but also registered with the group so `stop()` can cancel it
+                        // immediately instead of waiting for it to drain on its own.
+                        let queue = queue.clone();
+                        let callback_clone = callback_clone.clone();
+                        let track_name = track_name.clone();
+                        async move {
+                            while let Some(frame) = queue.pop().await {
+                                let callback_guard = callback_clone.read().await;
+                                if let Some(callback) = callback_guard.as_ref() {
+                                    callback(track_name.clone(), frame);
+                                }
+                            }
+                        }
+                    });
+                    tasks_clone.register(consumer_task.abort_handle());
+
+                    // Monitor track data until the stream ends, errors, or we're stopped
+                    let mut ended = false;
+                    while *is_active_clone.read().await {
+                        match track_consumer.next_group().await {
+                            Ok(Some(mut group)) => {
+                                // `Group`'s sequence number is the same one the publisher
+                                // passed to `create_group`; groups below our resume point
+                                // are drained without delivery so the stream position
+                                // stays consistent (whether the relay actually replays
+                                // pre-cursor groups on resubscribe isn't something this
+                                // wrapper controls or can verify here).
+                                let group_sequence = group.sequence;
+                                let deliver = resume_from
+                                    .map(|threshold| group_sequence >= threshold)
+                                    .unwrap_or(true);
+
+                                if options.replay_current_group {
+                                    group_cache_clone
+                                        .write()
+                                        .await
+                                        .insert(track_name.clone(), Vec::new());
+                                }
+
+                                while let Ok(Some(frame)) = group.read_frame().await {
+                                    backoff.reset();
+                                    if deliver {
+                                        if options.replay_current_group {
+                                            if let Some(buffered) = group_cache_clone
+                                                .write()
+                                                .await
+                                                .get_mut(&track_name)
+                                            {
+                                                buffered.push(frame.to_vec());
+                                            }
                                         }
+                                        queue.push(frame.to_vec()).await;
                                     }
                                 }
-                                Ok(None) => {
-                                    info!(
-                                        "[BroadcastSubscriptionManager] Track '{}' stream ended",
-                                        track_name
-                                    );
-                                    break;
-                                }
-                                Err(e) => {
-                                    warn!(
-                                        "[BroadcastSubscriptionManager] Track '{}' error: {}",
-                                        track_name, e
-                                    );
-                                    break;
+
+                                if deliver && options.durable {
+                                    cursors_clone
+                                        .write()
+                                        .await
+                                        .insert(track_name.clone(), group_sequence);
                                 }
                             }
+                            Ok(None) => {
+                                info!(
+                                    "[BroadcastSubscriptionManager] Track '{}' stream ended",
+                                    track_name
+                                );
+                                ended = true;
+                                break;
+                            }
+                            Err(e) => {
+                                warn!(
+                                    "[BroadcastSubscriptionManager] Track '{}' error: {}",
+                                    track_name, e
+                                );
+                                ended = true;
+                                break;
+                            }
                         }
+                    }
 
-                        // Remove from active consumers
-                        track_consumers_clone.write().await.remove(&track_name);
+                    queue.close();
+                    let _ = consumer_task.await;
+
+                    // Remove from active consumers
+                    track_consumers_clone.write().await.remove(&track_name);
+                    group_cache_clone.write().await.remove(&track_name);
+
+                    if ended && *is_active_clone.read().await {
+                        info!(
+                            "[BroadcastSubscriptionManager] Track '{}' resubscribing after stream end",
+                            track_name
+                        );
+                        backoff.wait().await;
+                    } else {
                         info!(
                             "[BroadcastSubscriptionManager] Track '{}' subscription ended",
                             track_name
                         );
                     }
-                    Err(e) => {
-                        warn!("[BroadcastSubscriptionManager] ⚠️ Failed to subscribe to track '{}': {}", track_name, e);
-                    }
                 }
             });
 
@@ -315,7 +897,38 @@ impl BroadcastSubscriptionManager {
         self.track_consumers.read().await.keys().cloned().collect()
     }
 
-    /// Stop all subscriptions
+    /// Configure how future track subscriptions buffer frames for the data callback.
+    /// Tracks already subscribed keep the mode they started with until they next
+    /// resubscribe.
+    pub async fn set_delivery_mode(&self, mode: DeliveryMode) {
+        *self.delivery_mode.write().await = mode;
+    }
+
+    /// Configure the per-track frame buffer capacity used by future subscriptions.
+    /// Tracks already subscribed keep the capacity they started with until they next
+    /// resubscribe.
+    pub async fn set_frame_queue_capacity(&self, capacity: usize) {
+        *self.frame_queue_capacity.write().await = capacity;
+    }
+
+    /// Number of frames dropped for `track_name` because a `DropOldest` consumer fell
+    /// behind the configured buffer capacity. Returns 0 for lossless tracks or tracks
+    /// that haven't subscribed yet.
+    pub async fn dropped_frames(&self, track_name: &str) -> u64 {
+        self.track_dropped_frames
+            .read()
+            .await
+            .get(track_name)
+            .map(|counter| counter.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Stop all subscriptions and wait for every spawned task to genuinely finish
+    ///
+    /// This aborts the announcement-wait/catalog-monitor/per-track-monitor tasks (and
+    /// anything they in turn spawned) and awaits them, so once this returns nothing is
+    /// still running against the old session - callers that need full teardown should
+    /// `.await` this rather than polling [`Self::is_active`].
     pub async fn stop(&self) {
         info!(
             "[BroadcastSubscriptionManager] Stopping all subscriptions for broadcast: {}",
@@ -327,6 +940,8 @@ impl BroadcastSubscriptionManager {
         *self.catalog_consumer.write().await = None;
         self.track_consumers.write().await.clear();
         *self.current_catalog.write().await = None;
+
+        self.tasks.shutdown().await;
     }
 
     /// Check if the manager is actively managing subscriptions
@@ -334,3 +949,11 @@ impl BroadcastSubscriptionManager {
         *self.is_active.read().await
     }
 }
+
+impl Drop for BroadcastSubscriptionManager {
+    /// Abort every task this manager ever spawned. `Drop` can't `.await`, so this
+    /// doesn't wait for them to finish - call [`Self::stop`] first for that.
+    fn drop(&mut self) {
+        self.tasks.abort_all();
+    }
+}
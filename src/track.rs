@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use bytes::Bytes;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -7,10 +7,12 @@ use tokio::sync::RwLock;
 use tokio::time::{interval, Instant};
 use tracing::{debug, info, warn};
 
-use moq_lite::{GroupProducer, Track, TrackConsumer, TrackProducer};
+use moq_lite::{BroadcastProducer, GroupProducer, Track, TrackConsumer, TrackProducer};
 
 use crate::config::WrapperError;
+use crate::congestion::{BandwidthEstimator, CongestionState, DeliverySample};
 use crate::session::MoqSession;
+use crate::source;
 
 /// High-level wrapper for track management with automatic reconnection
 pub struct TrackManager {
@@ -48,6 +50,28 @@ impl TrackManager {
         ))
     }
 
+    /// Enables delay-based congestion control for `track_name`; see
+    /// [`MoqSession::enable_bandwidth_estimation`].
+    pub async fn enable_bandwidth_estimation(&self, track_name: &str, initial_bitrate_bps: f64) {
+        self.session
+            .enable_bandwidth_estimation(track_name, initial_bitrate_bps)
+            .await;
+    }
+
+    /// Feeds a delivery sample into `track_name`'s bandwidth estimator; see
+    /// [`MoqSession::record_bandwidth_sample`].
+    pub async fn record_delivery_sample(&self, track_name: &str, sample: DeliverySample) {
+        self.session
+            .record_bandwidth_sample(track_name, sample)
+            .await;
+    }
+
+    /// Current target bitrate for `track_name`, if bandwidth estimation is enabled; see
+    /// [`MoqSession::target_bitrate_bps`].
+    pub async fn target_bitrate_bps(&self, track_name: &str) -> Option<f64> {
+        self.session.target_bitrate_bps(track_name).await
+    }
+
     /// Subscribe to a track
     pub async fn subscribe_track(
         &self,
@@ -138,6 +162,8 @@ pub struct StreamPublisher {
     track_producer: TrackProducer,
     current_group: Option<GroupProducer>,
     sequence_number: u64,
+    estimator: Option<BandwidthEstimator>,
+    pending_frame: Option<Vec<u8>>,
 }
 
 impl StreamPublisher {
@@ -146,9 +172,37 @@ impl StreamPublisher {
             track_producer,
             current_group: None,
             sequence_number: 0,
+            estimator: None,
+            pending_frame: None,
         }
     }
 
+    /// Enables delay-based congestion control (see [`BandwidthEstimator`]) on this
+    /// publisher, seeded with `initial_bitrate_bps`. The counterpart to
+    /// [`MoqSession::enable_bandwidth_estimation`] for callers (like
+    /// [`Fmp4Publisher`]) with no `MoqSession` to hold the estimator.
+    pub fn with_bandwidth_estimator(mut self, initial_bitrate_bps: f64) -> Self {
+        self.estimator = Some(BandwidthEstimator::new(initial_bitrate_bps));
+        self
+    }
+
+    /// Feeds one group's id/send/arrival timestamps into this publisher's bandwidth
+    /// estimator, returning the target bitrate and overuse/normal/underuse
+    /// classification once the slope is trustworthy (see
+    /// [`BandwidthEstimator::on_group_delivered`]). A no-op returning `None` if
+    /// [`Self::with_bandwidth_estimator`] wasn't used.
+    pub fn record_delivery_sample(
+        &mut self,
+        sample: DeliverySample,
+    ) -> Option<(f64, CongestionState)> {
+        self.estimator.as_mut()?.on_group_delivered(sample)
+    }
+
+    /// Current target bitrate from this publisher's bandwidth estimator, if attached.
+    pub fn target_bitrate_bps(&self) -> Option<f64> {
+        self.estimator.as_ref().map(|e| e.target_bitrate_bps())
+    }
+
     /// Start a new group (typically for keyframes or logical boundaries)
     pub fn start_group(&mut self) -> Result<()> {
         // Close the current group if it exists
@@ -174,12 +228,9 @@ impl StreamPublisher {
 
     /// Write a frame to the current group
     pub fn write_frame(&mut self, data: Bytes) -> Result<()> {
-        let group = self.current_group.as_mut().ok_or_else(|| {
-            WrapperError::Session("No active group, call start_group() first".to_string())
-        })?;
-
-        group.write_frame(data);
-        Ok(())
+        self.begin_frame(Some(data.len()))?;
+        self.append_chunk(data)?;
+        self.end_frame()
     }
 
     /// Write a string frame (convenience method)
@@ -187,6 +238,59 @@ impl StreamPublisher {
         self.write_frame(Bytes::from(data.to_string()))
     }
 
+    /// Opens the current group's next frame for incremental writes via
+    /// [`Self::append_chunk`], optionally declaring its total size up front so the
+    /// buffer can be pre-allocated. `moq_lite::GroupProducer` only exposes whole-frame
+    /// writes, so chunks are accumulated here and shipped as a single frame by
+    /// [`Self::end_frame`] - this lets a caller forward a large keyframe as it arrives
+    /// from an encoder (MoQ's `fragment::Info { size }`) instead of buffering the
+    /// whole thing itself first. Fails if there's no active group, or a frame is
+    /// already in progress.
+    pub fn begin_frame(&mut self, total_size: Option<usize>) -> Result<()> {
+        if self.current_group.is_none() {
+            return Err(WrapperError::Session(
+                "No active group, call start_group() first".to_string(),
+            )
+            .into());
+        }
+        if self.pending_frame.is_some() {
+            return Err(WrapperError::Session(
+                "A frame is already in progress, call end_frame() first".to_string(),
+            )
+            .into());
+        }
+
+        self.pending_frame = Some(match total_size {
+            Some(size) => Vec::with_capacity(size),
+            None => Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Appends a chunk to the frame opened by [`Self::begin_frame`].
+    pub fn append_chunk(&mut self, data: Bytes) -> Result<()> {
+        let buffer = self.pending_frame.as_mut().ok_or_else(|| {
+            WrapperError::Session("No frame in progress, call begin_frame() first".to_string())
+        })?;
+
+        buffer.extend_from_slice(&data);
+        Ok(())
+    }
+
+    /// Ships the frame opened by [`Self::begin_frame`] as a single object on the
+    /// current group.
+    pub fn end_frame(&mut self) -> Result<()> {
+        let buffer = self.pending_frame.take().ok_or_else(|| {
+            WrapperError::Session("No frame in progress, call begin_frame() first".to_string())
+        })?;
+
+        let group = self.current_group.as_mut().ok_or_else(|| {
+            WrapperError::Session("No active group, call start_group() first".to_string())
+        })?;
+        group.write_frame(Bytes::from(buffer));
+        Ok(())
+    }
+
     /// Write a single frame and automatically manage the group
     pub fn write_single_frame(&mut self, data: Bytes) -> Result<()> {
         self.start_group()?;
@@ -197,6 +301,12 @@ impl StreamPublisher {
 
     /// Close the current group
     pub fn close_group(&mut self) {
+        // Ship whatever was written so far rather than silently dropping it, the same
+        // as ending the frame explicitly would.
+        if self.pending_frame.is_some() {
+            let _ = self.end_frame();
+        }
+
         if let Some(group) = self.current_group.take() {
             group.close();
             debug!("Closed group");
@@ -209,3 +319,70 @@ impl Drop for StreamPublisher {
         self.close_group();
     }
 }
+
+/// Demuxes a fragmented-MP4 (CMAF) byte stream straight into per-track
+/// [`StreamPublisher`]s on a `moq_lite` [`BroadcastProducer`], the way moq-pub splits
+/// ffmpeg/OBS output into MoQ tracks. Lower-level than [`crate::source::File`] /
+/// [`crate::source::Stdin`]: it works directly off a `BroadcastProducer` with no
+/// [`MoqSession`] or catalog involved, for callers who just want to feed raw fMP4 bytes
+/// in and have them land on the right tracks.
+pub struct Fmp4Publisher {
+    init_track: StreamPublisher,
+    tracks: HashMap<u32, StreamPublisher>,
+}
+
+impl Fmp4Publisher {
+    /// Name the init segment is published under, following moq-pub's `"<index>.mp4"`
+    /// convention for an MP4 container's init track.
+    pub const INIT_TRACK_NAME: &'static str = "0.mp4";
+
+    /// Parses `init_segment`'s `ftyp`+`moov` prefix, creates one media track per `trak`
+    /// (named `"track<id>"`, matching [`crate::source::parse_init_segment`]) plus a
+    /// dedicated init track, publishes `init_segment` as a single frame on it, and
+    /// returns the publisher ready for [`Self::write_fragments`].
+    pub fn new(broadcast: &mut BroadcastProducer, init_segment: &[u8]) -> Result<Self> {
+        let track_ids = source::fmp4_trak_track_ids(init_segment)?;
+
+        let init_track_producer = broadcast.create_track(Track {
+            name: Self::INIT_TRACK_NAME.to_string(),
+            priority: 0,
+        });
+        let mut init_track = StreamPublisher::new(init_track_producer);
+        init_track.write_single_frame(Bytes::copy_from_slice(init_segment))?;
+
+        let mut tracks = HashMap::new();
+        for track_id in track_ids {
+            let track_producer = broadcast.create_track(Track {
+                name: format!("track{track_id}"),
+                priority: 128,
+            });
+            tracks.insert(track_id, StreamPublisher::new(track_producer));
+        }
+
+        Ok(Self { init_track, tracks })
+    }
+
+    /// Scans `data` for `moof`+`mdat` fragments and routes each to its track (by the
+    /// track ID in its `traf`/`tfhd`), starting a new group per fragment via
+    /// `track_producer.create_group`. A fragment for a track ID not seen in the init
+    /// segment is skipped with a warning rather than failing the whole batch.
+    pub fn write_fragments(&mut self, data: &[u8]) -> Result<()> {
+        for (track_id, fragment) in source::fmp4_iter_fragments(data) {
+            match self.tracks.get_mut(&track_id) {
+                Some(publisher) => publisher
+                    .write_single_frame(fragment)
+                    .with_context(|| format!("failed to write fragment for track {track_id}"))?,
+                None => warn!("fragment for unknown track ID {track_id}; skipping"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes every track's current group, flushing any fragment still in progress.
+    pub fn close(&mut self) {
+        self.init_track.close_group();
+        for publisher in self.tracks.values_mut() {
+            publisher.close_group();
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use moq_wrapper::{ConnectionConfig, MoqSession, SessionConfig, TrackManager};
+use moq_wrapper::{ConnectionConfig, MoqSession, ReconnectStrategy, SessionConfig, TrackManager};
 
 /// This is a basic integration test that doesn't require an actual relay server.
 /// It tests the API surface and basic functionality.
@@ -70,6 +70,11 @@ async fn test_configuration() {
         broadcast_name: "test-config".to_string(),
         connection: connection_config,
         auto_reconnect: true,
+        reconnect: ReconnectStrategy::default(),
+        heartbeat_interval: Duration::from_secs(5),
+        heartbeat_timeout: Duration::from_secs(15),
+        max_clock_sync_rtt: Duration::from_millis(200),
+        metrics_interval: None,
     };
 
     // Test that configuration is properly stored